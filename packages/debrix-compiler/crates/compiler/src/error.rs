@@ -8,11 +8,37 @@ pub enum Error {
 
 impl Error {
 	pub fn compiler(start: usize, end: usize, message: &str) -> Error {
-		Error::CompilerError(CompilerError {
-			start,
-			end,
-			message: message.to_owned(),
-		})
+		Error::CompilerError(CompilerError::new(start, end, message))
+	}
+
+	/// Converts the offsets of the error from chars to `encoding` and resolves its location, where
+	/// columns are also counted in `encoding`.
+	pub fn encode(self, input: &str, encoding: Encoding) -> Error {
+		let offsets = OffsetIndex::new(input, encoding);
+		let lines = LineIndex::with_encoding(input, encoding);
+
+		match self {
+			Error::ParserError(err) => {
+				let position = offsets.offset(err.position);
+
+				Error::ParserError(ParserError {
+					position,
+					location: lines.location(position, position),
+					..err
+				})
+			}
+			Error::CompilerError(err) => {
+				let start = offsets.offset(err.start);
+				let end = offsets.offset(err.end);
+
+				Error::CompilerError(CompilerError {
+					start,
+					end,
+					location: lines.location(start, end),
+					..err
+				})
+			}
+		}
 	}
 }
 
@@ -20,6 +46,7 @@ impl Error {
 pub struct CompilerError {
 	pub start: usize,
 	pub end: usize,
+	pub location: Location,
 	pub message: String,
 }
 
@@ -28,6 +55,7 @@ impl CompilerError {
 		Self {
 			start,
 			end,
+			location: Location::default(),
 			message: message.to_owned(),
 		}
 	}
@@ -7,11 +7,14 @@ pub(crate) use {
 	utils::*,
 };
 
-use render::render_dom;
+pub use debrix_parser::{Encoding, LineIndex, Location, OffsetIndex, Position};
+
+use render::{render_dom, render_hydration, render_ssr};
 
 pub use error::Error;
 pub use render::Chunk;
 
+#[derive(Clone, Copy, PartialEq)]
 pub enum Target {
 	Client,
 	Hydration,
@@ -19,12 +22,69 @@ pub enum Target {
 }
 
 pub fn build(input: String, target: Target) -> Result<Chunk, Error> {
-	match debrix_parser::parse_document(input) {
+	build_with_encoding(input, target, Encoding::Char)
+}
+
+/// Builds the input, where offsets in mappings and errors are counted in `encoding`.
+pub fn build_with_encoding(
+	input: String,
+	target: Target,
+	encoding: Encoding,
+) -> Result<Chunk, Error> {
+	build_document(input, target, encoding, false)
+}
+
+/// Builds the input like [`build_with_encoding`], allowing TypeScript in expressions. Types are
+/// erased from the output.
+pub fn build_typescript(input: String, target: Target, encoding: Encoding) -> Result<Chunk, Error> {
+	build_document(input, target, encoding, true)
+}
+
+fn build_document(
+	input: String,
+	target: Target,
+	encoding: Encoding,
+	typescript: bool,
+) -> Result<Chunk, Error> {
+	let result = match parse_document(&input, typescript) {
 		Ok(document) => match target {
 			Target::Client => render_dom(document),
-			Target::Hydration => unimplemented!(),
-			Target::Server => unimplemented!(),
+			Target::Hydration => render_hydration(document),
+			Target::Server => render_ssr(document),
 		},
 		Err(err) => Err(Error::ParserError(err)),
+	};
+
+	match result {
+		Ok(chunk) => Ok(chunk.encode(&input, encoding)),
+		Err(err) => Err(err.encode(&input, encoding)),
+	}
+}
+
+/// Parses the input into the versioned JSON syntax tree, see [`debrix_parser::json`].
+#[cfg(feature = "serde")]
+pub fn parse(input: String) -> Result<String, Error> {
+	parse_json(input, false)
+}
+
+/// Parses the input like [`parse`], allowing TypeScript in expressions.
+#[cfg(feature = "serde")]
+pub fn parse_typescript(input: String) -> Result<String, Error> {
+	parse_json(input, true)
+}
+
+#[cfg(feature = "serde")]
+fn parse_json(input: String, typescript: bool) -> Result<String, Error> {
+	match parse_document(&input, typescript) {
+		Ok(document) => Ok(debrix_parser::json::to_string(&document)),
+		Err(err) => Err(Error::ParserError(err)),
+	}
+}
+
+fn parse_document(input: &str, typescript: bool) -> Result<ast::Document<'_>, ParserError> {
+	if typescript {
+		debrix_parser::parse_document_typescript(input)
+	} else {
+		debrix_parser::parse_document(input)
 	}
 }
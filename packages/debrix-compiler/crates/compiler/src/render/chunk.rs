@@ -1,6 +1,9 @@
+use crate::*;
+
 pub struct Chunk {
 	pub source: String,
-	/// From-to mappings of the source.
+	/// From-to mappings of the source. Offsets are counted in chars, unless converted by
+	/// [Chunk::encode].
 	pub mappings: Vec<(usize, usize)>,
 	pos: usize,
 }
@@ -21,7 +24,7 @@ impl Chunk {
 
 	pub fn write(&mut self, s: &str) -> &mut Self {
 		self.source.push_str(s);
-		self.pos += s.len();
+		self.pos += s.chars().count();
 		self
 	}
 
@@ -39,4 +42,67 @@ impl Chunk {
 	pub fn is_empty(&self) -> bool {
 		self.source.is_empty()
 	}
+
+	/// Removes leading and trailing whitespace.
+	pub fn trim(self) -> Self {
+		let leading = self.source.chars().count() - self.source.trim_start().chars().count();
+		let source = self.source.trim();
+		let length = source.chars().count();
+
+		let mut chunk = Self::new();
+		chunk.write(source);
+
+		for (from, to) in self.mappings {
+			chunk
+				.mappings
+				.push((from, to.saturating_sub(leading).min(length)));
+		}
+
+		chunk
+	}
+
+	/// Sets indentation and removes trailing whitespace for each line.
+	pub fn indent(self, indent: usize) -> Self {
+		let indent = "\t".repeat(indent);
+		let mut chunk = Self::new();
+		let mut mappings = self.mappings.into_iter().peekable();
+		let mut line_start = 0;
+
+		for (i, line) in self.source.split('\n').enumerate() {
+			if i > 0 {
+				chunk.write("\n");
+				line_start += 1;
+			}
+
+			chunk.write(&indent);
+
+			let trimmed = line.trim_end();
+			let line_end = line_start + line.chars().count();
+			let trimmed_length = trimmed.chars().count();
+
+			// Mappings are ordered, as chunks are only written to the end.
+			while let Some((from, to)) = mappings.next_if(|(_, to)| *to <= line_end) {
+				let column = (to - line_start).min(trimmed_length);
+				chunk.mappings.push((from, chunk.pos + column));
+			}
+
+			chunk.write(trimmed);
+			line_start = line_end;
+		}
+
+		chunk
+	}
+
+	/// Converts the offsets of the mappings from chars to `encoding`, where `input` is the source
+	/// which the chunk was rendered from.
+	pub fn encode(mut self, input: &str, encoding: Encoding) -> Self {
+		let from = OffsetIndex::new(input, encoding);
+		let to = OffsetIndex::new(&self.source, encoding);
+
+		for mapping in &mut self.mappings {
+			*mapping = (from.offset(mapping.0), to.offset(mapping.1));
+		}
+
+		self
+	}
 }
@@ -50,6 +50,7 @@ struct Declaration {
 }
 
 pub struct Document {
+	pub target: Target,
 	pub c_imports: Chunk,
 	pub c_exports: Chunk,
 	pub c_fragments: Chunk,
@@ -61,8 +62,9 @@ pub struct Document {
 }
 
 impl Document {
-	pub fn new() -> Self {
+	pub fn new(target: Target) -> Self {
 		Self {
+			target,
 			c_imports: Chunk::new(),
 			c_exports: Chunk::new(),
 			c_fragments: Chunk::new(),
@@ -243,13 +245,29 @@ impl Document {
 				None
 			};
 
-			Component::new().render(
-				&mut self,
-				component_name.clone(),
-				node,
-				family_symbol.clone(),
-				model_constructor,
-			)?;
+			match self.target {
+				Target::Server => ssr::Component::new().render(
+					&mut self,
+					component_name.clone(),
+					node,
+					family_symbol.clone(),
+					model_constructor,
+				)?,
+				Target::Hydration => hydration::Component::new().render(
+					&mut self,
+					component_name.clone(),
+					node,
+					family_symbol.clone(),
+					model_constructor,
+				)?,
+				Target::Client => dom::Component::new().render(
+					&mut self,
+					component_name.clone(),
+					node,
+					family_symbol.clone(),
+					model_constructor,
+				)?,
+			}
 			self.export(&component_name, Some(&name), None);
 		}
 
@@ -285,7 +303,7 @@ impl Document {
 				.map(range.end);
 
 			let kind = DeclarationKind::from(&specifier.usage.name);
-			let local = specifier.local.map(|i| i.name);
+			let local = specifier.local.map(|i| i.name.into_owned());
 
 			if !self.declare(
 				kind.clone(),
@@ -339,7 +357,7 @@ impl Document {
 
 				if !self.declare(
 					DeclarationKind::from(&specifier.usage.name),
-					Some(name_owner.name.clone()),
+					Some(name_owner.name.to_string()),
 					unique,
 				) {
 					return Err(Error::compiler(
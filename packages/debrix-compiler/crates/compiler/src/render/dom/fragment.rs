@@ -2,7 +2,7 @@ use super::*;
 
 pub struct Fragment {
 	pub js: JavascriptSerializer,
-	c_attr: Chunk,
+	pub(crate) c_attr: Chunk,
 	c_decl: Chunk,
 	c_init: Chunk,
 	c_bind: Chunk,
@@ -21,6 +21,14 @@ impl Fragment {
 		}
 	}
 
+	/// Creates a fragment nested in the one of `parent`, which is passed its local variables.
+	/// Returns it with the arguments to call it with.
+	pub fn nested(parent: &JavascriptSerializer) -> (Fragment, String) {
+		let mut fragment = Fragment::new();
+		let args = inherit_locals(parent, &mut fragment.js, &mut fragment.c_attr);
+		(fragment, args)
+	}
+
 	pub fn render_single(
 		self,
 		doc: &mut Document,
@@ -50,31 +58,31 @@ impl Fragment {
 			.write("(")
 			.write("$self");
 
-		if self.c_attr.source.len() > 0 {
+		if !self.c_attr.source.is_empty() {
 			doc.c_fragments.write(", ").append(&self.c_attr);
 		}
 
 		doc.c_fragments.write(") {\n");
 
-		if self.c_decl.source.len() > 0 {
+		if !self.c_decl.source.is_empty() {
 			doc.c_fragments
 				.write("\t/* declarations */\n")
 				.append(&format_chunk(self.c_decl, 1));
 		}
 
-		if self.c_init.source.len() > 0 {
+		if !self.c_init.source.is_empty() {
 			doc.c_fragments
 				.write("\n\n\t/* initialization */\n")
 				.append(&format_chunk(self.c_init, 1));
 		}
 
-		if self.c_bind.source.len() > 0 {
+		if !self.c_bind.source.is_empty() {
 			doc.c_fragments
 				.write("\n\n\t/* binding */\n")
 				.append(&format_chunk(self.c_bind, 1));
 		}
 
-		if self.c_insert.source.len() > 0 {
+		if !self.c_insert.source.is_empty() {
 			doc.c_fragments
 				.write("\n\n\t/* appending */\n")
 				.append(&format_chunk(self.c_insert, 1));
@@ -112,9 +120,9 @@ impl Fragment {
 			.write("(")
 			.write(target)
 			.write(", ")
-			.write(&previous)
+			.write(previous)
 			.write(", ")
-			.write(&nodes)
+			.write(nodes)
 			.write(");\n");
 	}
 
@@ -129,7 +137,7 @@ impl Fragment {
 			.map(node.start)
 			.write(&helper)
 			.write("(")
-			.write(&in_string(&node.comment))
+			.write(&in_string(&comment_data(&node.comment)))
 			.write(");\n")
 			.map(node.end);
 
@@ -137,261 +145,51 @@ impl Fragment {
 	}
 
 	fn render_element(&mut self, doc: &mut Document, node: ast::Element) -> Result<String, Error> {
-		let mut first_spread_attribute: Option<ast::Range> = None;
-		for attr in &node.attributes {
-			match attr {
-				ast::Attribute::Spread(attr) => {
-					if let Some(first) = first_spread_attribute {
-						return Err(Error::compiler(
-							attr.start,
-							attr.end,
-							&format!(
-								"Attributes can only be expanded once. Occured at {:?}.",
-								first
-							),
-						));
-					} else {
-						first_spread_attribute = Some(attr.range());
-					}
-				}
-				_ => continue,
-			}
-		}
+		check_spread_attributes(&node.attributes)?;
 
 		if let Some(constructor) =
 			doc.declaration(DeclarationKind::Component, Some(&node.tag_name.name))
 		{
 			let constructor = constructor.to_owned();
-
 			let name = doc.unique.from(&to_valid_identifier(&node.tag_name.name));
-			let mut slots: Map<String, Vec<ast::Node>> = Map::new();
-			let mut c_attrs = Chunk::new();
-
-			for node in node.children {
-				let mut slot_name = None;
-				match &node {
-					ast::Node::Element(node) => {
-						for attr in node.attributes.iter() {
-							match attr {
-								ast::Attribute::Static(attr) => {
-									if attr.name.name == "slot" {
-										if slot_name.is_some() {
-											return Err(Error::compiler(
-												attr.start,
-												attr.end,
-												"Special attribute cannot be defined twice.",
-											));
-										}
-
-										if let Some(literal) = &attr.value {
-											slot_name = Some(literal.value.to_owned());
-										} else {
-											return Err(Error::compiler(
-												attr.start,
-												attr.end,
-												"Special attribute must have value.",
-											));
-										}
-									}
-								}
-								ast::Attribute::Binding(attr) => {
-									if attr.name.name == "slot" {
-										return Err(Error::compiler(
-											attr.start,
-											attr.end,
-											"Special attribute must be static.",
-										));
-									}
-								}
-								ast::Attribute::ShortBinding(attr) => {
-									if attr.name.name == "slot" {
-										return Err(Error::compiler(
-											attr.start,
-											attr.end,
-											"Special attribute must be static.",
-										));
-									}
-								}
-								ast::Attribute::Spread(_) => continue,
-							}
-						}
-					}
-					_ => {}
-				}
-
-				let slot_name = slot_name.unwrap_or(DEFAULT_SLOT_NAME.to_owned());
-				if let Some(vec) = slots.get_mut(&slot_name) {
-					vec.push(node);
-				} else {
-					slots.set(slot_name, vec![node]);
-				}
-			}
-
-			for attribute in node.attributes {
-				match attribute {
-					ast::Attribute::Static(attribute) => {
-						if &attribute.name.name == "slot" {
-							// TODO:
-							continue;
-						}
-
-						c_attrs
-							.write(&to_valid_property(&attribute.name.name))
-							.write(": ")
-							.write(&in_string(
-								&attribute.value.map(|v| v.value).unwrap_or("".to_owned()),
-							))
-							.write(",\n");
-					}
-
-					ast::Attribute::Binding(attribute) => {
-						c_attrs
-							.write(&to_valid_property(&attribute.name.name))
-							.write(": this.$computed(() => ")
-							.append(&self.js.serialize(&attribute.value))
-							.write("),\n");
-					}
-
-					ast::Attribute::ShortBinding(attribute) => {
-						c_attrs
-							.write(&to_valid_property(&attribute.name.name))
-							.write(": this.$computed(() => ")
-							.append(&self.js.serialize(&attribute.name.into()))
-							.write("),\n");
-					}
-
-					ast::Attribute::Spread(attribute) => {
-						c_attrs
-							.write("_: ")
-							.append(&self.js.serialize(&attribute.value))
-							.write(",\n");
-					}
-				}
-			}
+			let (start, end) = (node.start, node.end);
+			let c_new = self.render_component(doc, &constructor, node)?;
 
 			self.c_decl
 				.write("let ")
 				.write(&name)
 				.write(" = ")
-				.map(node.start)
-				.write("new ")
-				.write(&constructor);
-
-			self.c_decl
-				.write("({\n")
-				.write("\t__family: ")
-				.write(&in_string("__family"))
-				.write(" in ")
-				.write(&constructor)
-				.write(" && $self[")
-				.write(&constructor)
-				.write(".__family")
-				.write("],\n");
-
-			if !slots.is_empty() {
-				self.c_decl.write("\tslots: {\n");
-
-				let mut slots = slots.into_entries().peekable();
-				while let Some((name, nodes)) = slots.next() {
-					let fragment_name = doc.unique.from("render_fragment");
-					let fragment = Fragment::new();
-					fragment.render(doc, fragment_name.clone(), nodes)?;
-
-					self.c_decl
-						.write("\t\t")
-						.write(&to_valid_property(&name))
-						.write(": ")
-						.write(&fragment_name)
-						.write(".bind(this)");
-
-					if slots.peek().is_some() {
-						self.c_decl.write(",\n");
-					} else {
-						self.c_decl.write("\n");
-					}
-				}
-
-				self.c_decl.write("\t},\n");
-			}
-
-			if !c_attrs.is_empty() {
-				self.c_decl
-					.write("\tattrs: {\n")
-					.append(&format_chunk(c_attrs, 2))
-					.write("\n\t},\n");
-			}
-
-			self.c_decl.write("});\n");
-
-			self.c_decl.map(node.end);
+				.map(start)
+				.append(&c_new)
+				.write(";\n")
+				.map(end);
 
 			return Ok(name);
 		}
 
-		if &node.tag_name.name == "slot" {
-			let slot_name =
-				if let Some(attr) = node.attributes.iter().find(find_static_attr("name")) {
-					match attr {
-						ast::Attribute::Static(attr) => {
-							if let Some(literal) = &attr.value {
-								literal.value.clone()
-							} else {
-								return Err(Error::compiler(
-									attr.start,
-									attr.end,
-									"Attribute must have value.",
-								));
-							}
-						}
-						_ => {
-							return Err(Error::compiler(
-								attr.start(),
-								attr.end(),
-								"Attribute must be static.",
-							))
-						}
-					}
-				} else {
-					DEFAULT_SLOT_NAME.to_owned()
-				};
+		if &node.tag_name.name == "component" {
+			return self.render_dynamic_component(doc, node);
+		}
 
+		if &node.tag_name.name == "slot" {
+			let slot_name = slot_name(&node)?;
+			let accessor = slot_accessor(&slot_name);
 			let instance_name = doc.unique.from("fragment");
 
 			self.c_decl
 				.write("let ")
 				.write(&instance_name)
-				.write(" = $self.slots");
-
-			if is_valid_identifier(&slot_name) {
-				self.c_decl.write(".").write(&slot_name);
-			} else {
-				self.c_decl
-					.write("[")
-					.write(&in_string(&slot_name))
-					.write("]");
-			}
-
-			self.c_decl.write(" && $self.slots");
-
-			if is_valid_identifier(&slot_name) {
-				self.c_decl.write(".").write(&slot_name);
-			} else {
-				self.c_decl
-					.write("[")
-					.write(&in_string(&slot_name))
-					.write("]");
-			}
+				.write(" = ")
+				.write(&accessor)
+				.write(" && ")
+				.write(&accessor);
 
 			self.c_decl.write("($self);\n");
 
 			return Ok(instance_name);
 		}
 
-		let tag_name = if node.tag_name.name.starts_with("html:") {
-			&node.tag_name.name[5..]
-		} else {
-			&node.tag_name.name
-		};
+		let tag_name = tag_name(&node);
 
 		let helper = doc.import("element", None, INTERNAL_MODULE);
 		let name = doc.unique.from(&to_valid_identifier(tag_name));
@@ -426,7 +224,7 @@ impl Fragment {
 			self.render_attribute(doc, &name, attribute)?;
 		}
 
-		if node.children.len() > 0 {
+		if !node.children.is_empty() {
 			let mut args = Vec::new();
 
 			for child in node.children {
@@ -439,6 +237,62 @@ impl Fragment {
 		Ok(name)
 	}
 
+	fn render_component(
+		&mut self,
+		doc: &mut Document,
+		constructor: &str,
+		node: ast::Element,
+	) -> Result<Chunk, Error> {
+		let c_attrs = component_attrs(&self.js, node.attributes);
+
+		render_component(
+			doc,
+			constructor,
+			node.children,
+			c_attrs,
+			None,
+			|doc, nodes| {
+				let fragment_name = doc.unique.from("render_fragment");
+				let (fragment, args) = Fragment::nested(&self.js);
+				fragment.render(doc, fragment_name.clone(), nodes)?;
+
+				let mut c_slot = Chunk::new();
+				c_slot.write(&slot_function(&fragment_name, &args));
+				Ok(c_slot)
+			},
+		)
+	}
+
+	fn render_dynamic_component(
+		&mut self,
+		doc: &mut Document,
+		mut node: ast::Element,
+	) -> Result<String, Error> {
+		let is = take_component_constructor(&mut node)?;
+		let helper = doc.import("bind_component", None, INTERNAL_MODULE);
+		let name = doc.unique.from("component");
+		let constructor = doc.unique.from("constructor");
+		let (start, end) = (node.start, node.end);
+		let c_new = self.render_component(doc, &constructor, node)?;
+
+		self.c_decl
+			.write("let ")
+			.write(&name)
+			.write(" = ")
+			.map(start)
+			.write(&helper)
+			.write("(this.$computed(() => ")
+			.append(&self.js.serialize(&is))
+			.write("), (")
+			.write(&constructor)
+			.write(") => ")
+			.append(&c_new)
+			.write(");\n")
+			.map(end);
+
+		Ok(name)
+	}
+
 	fn render_attribute(
 		&mut self,
 		doc: &mut Document,
@@ -469,73 +323,7 @@ impl Fragment {
 
 				self.c_init.write(");\n").map(attr.end);
 			}
-			ast::Attribute::Binding(attr) => {
-				if attr.name.name.starts_with("bind:") {
-					let name = &attr.name.name[5..];
-					let helper = doc.import("bind", None, INTERNAL_MODULE);
-
-					let decl = doc.declaration(DeclarationKind::Binder, Some(name));
-
-					if decl.is_none() {
-						return Err(Error::compiler(
-							attr.name.start,
-							attr.name.end,
-							&format!("Undefined binder '{}'.", name),
-						));
-					}
-
-					let decl = decl.unwrap();
-
-					self.c_bind
-						.write(&helper)
-						.write("(")
-						.write(parent)
-						.write(", ")
-						.write(&decl)
-						.write(", this.$computed(() => ")
-						.append(&self.js.serialize(&attr.value))
-						.write("));\n");
-				} else {
-					let helper = doc.import("bind_attr", None, INTERNAL_MODULE);
-
-					self.c_bind
-						.map(attr.start)
-						.write(&helper)
-						.write("(")
-						.write(parent)
-						.write(", ")
-						.write(&in_string(&attr.name.name))
-						.write(", this.$computed(() => ")
-						.append(&self.js.serialize(&attr.value))
-						.write("));\n");
-				}
-			}
-			ast::Attribute::Spread(attr) => {
-				let helper = doc.import("bind_attr_spread", None, INTERNAL_MODULE);
-
-				self.c_bind
-					.map(attr.start)
-					.write(&helper)
-					.write("(")
-					.write(parent)
-					.write(", this.$computed(() => ")
-					.append(&self.js.serialize(&attr.value))
-					.write("));\n");
-			}
-			ast::Attribute::ShortBinding(attr) => {
-				let helper = doc.import("bind_attr", None, INTERNAL_MODULE);
-
-				self.c_bind
-					.map(attr.start)
-					.write(&helper)
-					.write("(")
-					.write(parent)
-					.write(", ")
-					.write(&in_string(&attr.name.name))
-					.write(", this.$computed(() => ")
-					.append(&self.js.serialize(&attr.name.into()))
-					.write("));\n");
-			}
+			attribute => bind_attribute(doc, &self.js, &mut self.c_bind, parent, attribute)?,
 		}
 
 		Ok(())
@@ -607,6 +395,20 @@ impl Fragment {
 		Ok(name)
 	}
 
+	/// Renders the fragment of a branch. Returns its render function bound to the component,
+	/// so that the branch is only created when selected.
+	fn bind_branch(
+		&mut self,
+		doc: &mut Document,
+		children: Vec<ast::Node>,
+	) -> Result<String, Error> {
+		let fragment_name = doc.unique.from("render_fragment");
+		let (fragment, args) = Fragment::nested(&self.js);
+		fragment.render(doc, fragment_name.clone(), children)?;
+
+		Ok(format!("{fragment_name}.bind(this, {args})"))
+	}
+
 	fn render_flow_control(
 		&mut self,
 		doc: &mut Document,
@@ -614,128 +416,448 @@ impl Fragment {
 	) -> Result<Vec<String>, Error> {
 		match node {
 			ast::FlowControl::When(node) => {
-				let mut names = Vec::new();
+				let name = doc.unique.from("flow");
+
+				if node.chain.is_empty() {
+					let bind_when = doc.import("bind_when", None, INTERNAL_MODULE);
+					let render = self.bind_branch(doc, node.children)?;
+
+					self.c_bind
+						.write("let ")
+						.write(&name)
+						.write(" = ")
+						.write(&bind_when)
+						.write("(")
+						.write(&render)
+						.write(", this.$computed(() => ")
+						.append(&self.js.serialize(&node.condition))
+						.write("));\n");
+
+					return Ok(vec![name]);
+				}
+
+				let bind_when_chain = doc.import("bind_when_chain", None, INTERNAL_MODULE);
+
+				let mut branches = vec![(Some(*node.condition), node.children)];
+				for node in node.chain {
+					branches.push((node.condition, node.children));
+				}
+
+				self.c_bind
+					.write("let ")
+					.write(&name)
+					.write(" = ")
+					.write(&bind_when_chain)
+					.write("([\n");
+
+				for (condition, children) in branches {
+					let render = self.bind_branch(doc, children)?;
+
+					self.c_bind.write("\t[");
+
+					if let Some(condition) = condition {
+						self.c_bind
+							.write("this.$computed(() => ")
+							.append(&self.js.serialize(&condition))
+							.write(")");
+					} else {
+						self.c_bind.write("null");
+					}
+
+					self.c_bind.write(", ").write(&render).write("],\n");
+				}
+
+				self.c_bind.write("]);\n");
+
+				Ok(vec![name])
+			}
+
+			ast::FlowControl::Each(node) => {
+				let name = doc.unique.from("flow");
 
-				let instance_name = doc.unique.from("fragment");
 				let fragment_name = doc.unique.from("render_fragment");
-				let fragment = Fragment::new();
+				let (mut fragment, args) = Fragment::nested(&self.js);
+
+				let index = node
+					.index
+					.map(|index| ast::javascript::Expression::Identifier(*index));
+				let mut params = vec![&*node.iterator];
+				params.extend(&index);
+
+				let aliases = doc.unique.params(&params);
+				let params =
+					declare_params(&mut fragment.js, &mut fragment.c_attr, &params, &aliases);
+				let key =
+					(node.key.as_ref()).map(|key| fragment.js.serialize_closure(&params, key));
+
 				fragment.render(doc, fragment_name.clone(), node.children)?;
 
-				self.c_decl
+				let fallback_name = match node.fallback {
+					Some(fallback) => {
+						let fallback_name = doc.unique.from("render_fragment");
+						let (fallback_fragment, _) = Fragment::nested(&self.js);
+						fallback_fragment.render(doc, fallback_name.clone(), fallback.children)?;
+						Some(fallback_name)
+					}
+					None => None,
+				};
+
+				let helper = if node.key.is_some() {
+					doc.import("bind_each_keyed", None, INTERNAL_MODULE)
+				} else {
+					doc.import("bind_each", None, INTERNAL_MODULE)
+				};
+
+				self.c_bind
 					.write("let ")
-					.write(&instance_name)
+					.write(&name)
 					.write(" = ")
+					.write(&helper)
+					.write("(")
 					.write(&fragment_name)
-					.write(".call(this);\n");
+					.write(".bind(this, ")
+					.write(&args)
+					.write("), this.$computed(() => ")
+					.append(&self.js.serialize(&node.iterable))
+					.write(")");
+
+				if let Some(key) = &key {
+					self.c_bind.write(", ").append(key);
+				}
 
-				let bind_when = doc.import("bind_when", None, INTERNAL_MODULE);
-				let binding_name = doc.unique.from("flow");
-				names.push(binding_name.clone());
+				if let Some(fallback_name) = &fallback_name {
+					self.c_bind
+						.write(", ")
+						.write(fallback_name)
+						.write(".bind(this, ")
+						.write(&args)
+						.write(")");
+				}
 
-				let mut accessor = Chunk::new();
-				accessor
-					.write("this.$computed(() => ")
-					.append(&self.js.serialize(&node.condition))
-					.write("))");
+				self.c_bind.write(");\n");
 
-				if node.chain.len() > 0 {
-					let accessor_name = doc.unique.from("accessor");
+				Ok(vec![name])
+			}
 
-					self.c_bind
-						.write("\nlet ")
-						.write(&accessor_name)
-						.write(" = ")
-						.append(&accessor)
-						.write(";\n");
+			ast::FlowControl::Let(node) => {
+				let name = doc.unique.from("flow");
+				let helper = doc.import("bind_let", None, INTERNAL_MODULE);
 
-					self.c_bind
-						.write("let ")
-						.write(&binding_name)
-						.write(" = ")
-						.write(&bind_when)
-						.write("(")
-						.write(&instance_name)
-						.write(", ")
-						.write(&accessor_name)
-						.write("\n");
-
-					for node in node.chain {
-						let instance_name = doc.unique.from("fragment");
-						let fragment_name = doc.unique.from("render_fragment");
-						let fragment = Fragment::new();
-						fragment.render(doc, fragment_name.clone(), node.children)?;
-
-						self.c_decl
-							.write("let ")
-							.write(&instance_name)
-							.write(" = ")
-							.write(&fragment_name)
-							.write(".call(this);\n");
-
-						let computed_not = doc.import("computed_not", None, INTERNAL_MODULE);
-						let binding_name = doc.unique.from("flow");
-						names.push(binding_name.clone());
+				let fragment_name = doc.unique.from("render_fragment");
+				let (mut fragment, args) = Fragment::nested(&self.js);
+
+				// The value is passed to the fragment, like the items of a list.
+				let binding = ast::javascript::Expression::Identifier(*node.name);
+				let aliases = doc.unique.params(&[&binding]);
+				declare_params(
+					&mut fragment.js,
+					&mut fragment.c_attr,
+					&[&binding],
+					&aliases,
+				);
+				fragment.render(doc, fragment_name.clone(), node.children)?;
 
-						self.c_bind
-							.write("let ")
-							.write(&binding_name)
-							.write(" = ")
-							.write(&bind_when)
-							.write("(")
-							.write(&instance_name)
-							.write(", ")
-							.write(&computed_not)
-							.write("(")
-							.write(&accessor_name)
-							.write(");\n");
+				self.c_bind
+					.write("let ")
+					.write(&name)
+					.write(" = ")
+					.write(&helper)
+					.write("(")
+					.write(&fragment_name)
+					.write(".bind(this, ")
+					.write(&args)
+					.write("), this.$computed(() => ")
+					.append(&self.js.serialize(&node.value))
+					.write("));\n");
+
+				Ok(vec![name])
+			}
+
+			ast::FlowControl::Await(node) => {
+				let name = doc.unique.from("flow");
+				let helper = doc.import("bind_await", None, INTERNAL_MODULE);
+
+				let pending_name = doc.unique.from("render_fragment");
+				let (pending, args) = Fragment::nested(&self.js);
+				pending.render(doc, pending_name.clone(), node.children)?;
+
+				self.c_bind
+					.write("let ")
+					.write(&name)
+					.write(" = ")
+					.write(&helper)
+					.write("(this.$computed(() => ")
+					.append(&self.js.serialize(&node.promise))
+					.write("), ")
+					.write(&pending_name)
+					.write(".bind(this, ")
+					.write(&args)
+					.write(")");
+
+				// The settled value is passed to the fragment of the branch, like the items of a list.
+				let branches = [node.then, node.catch];
+				let last = branches.iter().rposition(Option::is_some);
+
+				for branch in branches.into_iter().take(last.map_or(0, |last| last + 1)) {
+					let Some(branch) = branch else {
+						self.c_bind.write(", undefined");
+						continue;
+					};
+
+					let fragment_name = doc.unique.from("render_fragment");
+					let (mut fragment, _) = Fragment::nested(&self.js);
+
+					if let Some(binding) = &branch.binding {
+						let aliases = doc.unique.params(&[&**binding]);
+						declare_params(
+							&mut fragment.js,
+							&mut fragment.c_attr,
+							&[&**binding],
+							&aliases,
+						);
 					}
-				} else {
+
+					fragment.render(doc, fragment_name.clone(), branch.children)?;
 					self.c_bind
-						.write("let ")
-						.write(&binding_name)
-						.write(" = ")
-						.write(&bind_when)
-						.write("(")
-						.write(&instance_name)
 						.write(", ")
-						.append(&accessor)
-						.write(";\n");
+						.write(&fragment_name)
+						.write(".bind(this, ")
+						.write(&args)
+						.write(")");
 				}
 
-				Ok(names)
+				self.c_bind.write(");\n");
+
+				Ok(vec![name])
 			}
 
-			ast::FlowControl::Each(node) => {
-				let bind_each = doc.import("bind_each", None, INTERNAL_MODULE);
+			ast::FlowControl::Try(node) => {
 				let name = doc.unique.from("flow");
+				let helper = doc.import("bind_try", None, INTERNAL_MODULE);
 
 				let fragment_name = doc.unique.from("render_fragment");
-				let mut fragment = Fragment::new();
+				let (fragment, args) = Fragment::nested(&self.js);
+				fragment.render(doc, fragment_name.clone(), node.children)?;
 
-				let iterator = doc.unique.ensure(&node.iterator.name);
-				fragment.js.local_vars.push(iterator.clone());
+				let catch_name = doc.unique.from("render_fragment");
+				let (mut catch, _) = Fragment::nested(&self.js);
 
-				let mut attr = Chunk::new();
-				attr.map(node.iterator.start)
-					.write(&iterator)
-					.map(node.iterator.end);
+				if let Some(binding) = &node.catch.binding {
+					let aliases = doc.unique.params(&[&**binding]);
+					declare_params(&mut catch.js, &mut catch.c_attr, &[&**binding], &aliases);
+				}
 
-				fragment.c_attr.append(&attr);
-				fragment.render(doc, fragment_name.clone(), node.children)?;
+				catch.render(doc, catch_name.clone(), node.catch.children)?;
 
 				self.c_bind
 					.write("let ")
 					.write(&name)
 					.write(" = ")
-					.write(&bind_each)
+					.write(&helper)
 					.write("(")
 					.write(&fragment_name)
-					.write(".bind(this), this.$computed(() => ")
-					.append(&self.js.serialize(&node.iterable))
+					.write(".bind(this, ")
+					.write(&args)
+					.write("), ")
+					.write(&catch_name)
+					.write(".bind(this, ")
+					.write(&args)
 					.write("));\n");
 
 				Ok(vec![name])
 			}
+
+			ast::FlowControl::Match(node) => {
+				let name = doc.unique.from("flow");
+				let helper = doc.import("bind_match", None, INTERNAL_MODULE);
+				let accessor = match_accessor(&self.js, &node);
+
+				self.c_bind
+					.write("let ")
+					.write(&name)
+					.write(" = ")
+					.write(&helper)
+					.write("(")
+					.append(&accessor)
+					.write(", [\n");
+
+				for case in node.cases {
+					let render = self.bind_branch(doc, case.children)?;
+					self.c_bind.write("\t").write(&render).write(",\n");
+				}
+
+				self.c_bind.write("]);\n");
+
+				Ok(vec![name])
+			}
+		}
+	}
+}
+
+/// Serializes a computed of the index of the matching case, or -1.
+pub(crate) fn match_accessor(js: &JavascriptSerializer, node: &ast::FlowControlMatch) -> Chunk {
+	let mut c_accessor = Chunk::new();
+
+	c_accessor
+		.write("this.$computed(() => {\n")
+		.write("\tswitch (")
+		.append(&js.serialize(&node.discriminant))
+		.write(") {\n");
+
+	for (index, case) in node.cases.iter().enumerate() {
+		if case.tests.is_empty() {
+			c_accessor.write("\t\tdefault:\n");
+		}
+
+		for test in &case.tests {
+			c_accessor
+				.write("\t\tcase ")
+				.append(&js.serialize(test))
+				.write(":\n");
 		}
+
+		c_accessor
+			.write("\t\t\treturn ")
+			.write(&index.to_string())
+			.write(";\n");
+	}
+
+	c_accessor.write("\t}\n");
+
+	if !node.cases.last().is_some_and(|case| case.tests.is_empty()) {
+		c_accessor.write("\n\treturn -1;\n");
 	}
+
+	c_accessor.write("})");
+	c_accessor
+}
+
+pub(crate) fn component_attrs(js: &JavascriptSerializer, attributes: Vec<ast::Attribute>) -> Chunk {
+	let mut c_attrs = Chunk::new();
+
+	for attribute in attributes {
+		match attribute {
+			ast::Attribute::Static(attribute) => {
+				if &attribute.name.name == "slot" {
+					// TODO:
+					continue;
+				}
+
+				c_attrs
+					.write(&to_valid_property(&attribute.name.name))
+					.write(": ")
+					.write(&in_string(
+						&attribute.value.map(|v| v.value).unwrap_or_default(),
+					))
+					.write(",\n");
+			}
+
+			ast::Attribute::Binding(attribute) => {
+				c_attrs
+					.write(&to_valid_property(&attribute.name.name))
+					.write(": this.$computed(() => ")
+					.append(&js.serialize(&attribute.value))
+					.write("),\n");
+			}
+
+			ast::Attribute::ShortBinding(attribute) => {
+				c_attrs
+					.write(&to_valid_property(&attribute.name.name))
+					.write(": this.$computed(() => ")
+					.append(&js.serialize(&attribute.name.into()))
+					.write("),\n");
+			}
+
+			ast::Attribute::Spread(attribute) => {
+				c_attrs
+					.write("_: ")
+					.append(&js.serialize(&attribute.value))
+					.write(",\n");
+			}
+		}
+	}
+
+	c_attrs
+}
+
+pub(crate) fn bind_attribute(
+	doc: &mut Document,
+	js: &JavascriptSerializer,
+	c_bind: &mut Chunk,
+	parent: &str,
+	attribute: ast::Attribute,
+) -> Result<(), Error> {
+	match attribute {
+		// Static attributes are set when the element is created, not bound.
+		ast::Attribute::Static(_) => {}
+		ast::Attribute::Binding(attr) => {
+			if attr.name.name.starts_with("bind:") {
+				let name = &attr.name.name[5..];
+				let helper = doc.import("bind", None, INTERNAL_MODULE);
+
+				let decl = doc.declaration(DeclarationKind::Binder, Some(name));
+
+				if decl.is_none() {
+					return Err(Error::compiler(
+						attr.name.start,
+						attr.name.end,
+						&format!("Undefined binder '{}'.", name),
+					));
+				}
+
+				let decl = decl.unwrap();
+
+				c_bind
+					.write(&helper)
+					.write("(")
+					.write(parent)
+					.write(", ")
+					.write(decl)
+					.write(", this.$computed(() => ")
+					.append(&js.serialize(&attr.value))
+					.write("));\n");
+			} else {
+				let helper = doc.import("bind_attr", None, INTERNAL_MODULE);
+
+				c_bind
+					.map(attr.start)
+					.write(&helper)
+					.write("(")
+					.write(parent)
+					.write(", ")
+					.write(&in_string(&attr.name.name))
+					.write(", this.$computed(() => ")
+					.append(&js.serialize(&attr.value))
+					.write("));\n");
+			}
+		}
+		ast::Attribute::Spread(attr) => {
+			let helper = doc.import("bind_attr_spread", None, INTERNAL_MODULE);
+
+			c_bind
+				.map(attr.start)
+				.write(&helper)
+				.write("(")
+				.write(parent)
+				.write(", this.$computed(() => ")
+				.append(&js.serialize(&attr.value))
+				.write("));\n");
+		}
+		ast::Attribute::ShortBinding(attr) => {
+			let helper = doc.import("bind_attr", None, INTERNAL_MODULE);
+
+			c_bind
+				.map(attr.start)
+				.write(&helper)
+				.write("(")
+				.write(parent)
+				.write(", ")
+				.write(&in_string(&attr.name.name))
+				.write(", this.$computed(() => ")
+				.append(&js.serialize(&attr.name.into()))
+				.write("));\n");
+		}
+	}
+
+	Ok(())
 }
@@ -0,0 +1,763 @@
+use super::*;
+
+enum TextLength {
+	// In UTF-16 code units, like javascript strings.
+	Static(usize),
+	Binding(String),
+}
+
+struct Cursor {
+	parent: String,
+	// Index returned by the last claimed block, whose length is only known at runtime.
+	base: Option<String>,
+	offset: usize,
+	// The server renders adjacent text as one node, which is split when claimed.
+	text: Option<(String, TextLength)>,
+}
+
+impl Cursor {
+	fn new(parent: &str, base: Option<&str>) -> Self {
+		Self {
+			parent: parent.to_owned(),
+			base: base.map(|base| base.to_owned()),
+			offset: 0,
+			text: None,
+		}
+	}
+
+	fn index(&self) -> String {
+		match &self.base {
+			Some(base) if self.offset == 0 => base.clone(),
+			Some(base) => format!("{} + {}", base, self.offset),
+			None => self.offset.to_string(),
+		}
+	}
+
+	fn next(&mut self) -> String {
+		let index = self.index();
+		self.offset += 1;
+		self.text = None;
+		index
+	}
+
+	fn reset(&mut self, base: String) {
+		self.base = Some(base);
+		self.offset = 0;
+		self.text = None;
+	}
+}
+
+pub struct Fragment {
+	pub js: JavascriptSerializer,
+	// Local variables of the enclosing fragment, passed before the claimed position.
+	c_locals: Chunk,
+	c_attr: Chunk,
+	c_claim: Chunk,
+	c_bind: Chunk,
+}
+
+impl Fragment {
+	pub fn new() -> Self {
+		Self {
+			js: JavascriptSerializer::new(),
+			c_locals: Chunk::new(),
+			c_attr: Chunk::new(),
+			c_claim: Chunk::new(),
+			c_bind: Chunk::new(),
+		}
+	}
+
+	pub fn render_single(
+		self,
+		doc: &mut Document,
+		name: String,
+		node: ast::Node,
+	) -> Result<(), Error> {
+		self.render(doc, name, vec![node])
+	}
+
+	pub fn render(
+		mut self,
+		doc: &mut Document,
+		name: String,
+		nodes: Vec<ast::Node>,
+	) -> Result<(), Error> {
+		self.js.local_vars.push("$self".to_owned());
+
+		let mut cursor = Cursor::new("$parent", Some("$index"));
+		let mut elements = Vec::new();
+
+		for node in nodes {
+			elements.append(&mut self.render_node(doc, node, &mut cursor)?);
+		}
+
+		doc.c_fragments
+			.write("function ")
+			.write(&name)
+			.write("($self");
+
+		if !self.c_locals.is_empty() {
+			doc.c_fragments.write(", ").append(&self.c_locals);
+		}
+
+		doc.c_fragments.write(", $parent, $index");
+
+		if !self.c_attr.is_empty() {
+			doc.c_fragments.write(", ").append(&self.c_attr);
+		}
+
+		doc.c_fragments.write(") {\n");
+
+		if !self.c_claim.is_empty() {
+			doc.c_fragments
+				.write("\t/* claiming */\n")
+				.append(&format_chunk(self.c_claim, 1));
+		}
+
+		if !self.c_bind.is_empty() {
+			doc.c_fragments
+				.write("\n\n\t/* binding */\n")
+				.append(&format_chunk(self.c_bind, 1));
+		}
+
+		doc.c_fragments
+			.write("\n\n\treturn [[")
+			.write(&elements.join(", "))
+			.write("], ")
+			.write(&cursor.index())
+			.write("];\n")
+			.write("}\n\n");
+
+		Ok(())
+	}
+
+	fn render_node(
+		&mut self,
+		doc: &mut Document,
+		node: ast::Node,
+		cursor: &mut Cursor,
+	) -> Result<Vec<String>, Error> {
+		match node {
+			ast::Node::Comment(node) => Ok(vec![self.render_comment(doc, node, cursor)]),
+			ast::Node::Element(node) => Ok(vec![self.render_element(doc, node, cursor)?]),
+			ast::Node::Text(node) => Ok(vec![self.render_text(doc, node, cursor)]),
+			ast::Node::TextBinding(node) => Ok(vec![self.render_text_binding(doc, node, cursor)]),
+			ast::Node::FlowControl(node) => Ok(vec![self.render_flow_control(doc, node, cursor)?]),
+
+			_ => Err(Error::compiler(
+				node.start(),
+				node.end(),
+				"Node is not allowed here.",
+			)),
+		}
+	}
+
+	fn claim(
+		&mut self,
+		doc: &mut Document,
+		name: &str,
+		start: usize,
+		end: usize,
+		cursor: &mut Cursor,
+	) {
+		let helper = doc.import("claim", None, INTERNAL_MODULE);
+
+		self.c_claim
+			.write("let ")
+			.write(name)
+			.write(" = ")
+			.map(start)
+			.write(&helper)
+			.write("(")
+			.write(&cursor.parent)
+			.write(", ")
+			.write(&cursor.next())
+			.write(");\n")
+			.map(end);
+	}
+
+	fn claim_text(
+		&mut self,
+		doc: &mut Document,
+		start: usize,
+		end: usize,
+		length: TextLength,
+		cursor: &mut Cursor,
+	) -> String {
+		let name = doc.unique.from("text");
+
+		self.c_claim
+			.write("let ")
+			.write(&name)
+			.write(" = ")
+			.map(start);
+
+		if let Some((previous, previous_length)) = cursor.text.take() {
+			let helper = doc.import("split_text", None, INTERNAL_MODULE);
+
+			let offset = match previous_length {
+				TextLength::Static(length) => length.to_string(),
+				TextLength::Binding(accessor) => {
+					let text_length = doc.import("text_length", None, INTERNAL_MODULE);
+					format!("{}({}.get())", text_length, accessor)
+				}
+			};
+
+			self.c_claim
+				.write(&helper)
+				.write("(")
+				.write(&previous)
+				.write(", ")
+				.write(&offset)
+				.write(");\n");
+
+			cursor.next();
+		} else {
+			let helper = doc.import("claim_text", None, INTERNAL_MODULE);
+
+			self.c_claim
+				.write(&helper)
+				.write("(")
+				.write(&cursor.parent)
+				.write(", ")
+				.write(&cursor.next())
+				.write(");\n");
+		}
+
+		self.c_claim.map(end);
+		cursor.text = Some((name.clone(), length));
+
+		name
+	}
+
+	fn render_comment(
+		&mut self,
+		doc: &mut Document,
+		node: ast::Comment,
+		cursor: &mut Cursor,
+	) -> String {
+		let name = doc.unique.from("comment");
+		self.claim(doc, &name, node.start, node.end, cursor);
+		name
+	}
+
+	fn render_element(
+		&mut self,
+		doc: &mut Document,
+		node: ast::Element,
+		cursor: &mut Cursor,
+	) -> Result<String, Error> {
+		check_spread_attributes(&node.attributes)?;
+
+		if let Some(constructor) =
+			doc.declaration(DeclarationKind::Component, Some(&node.tag_name.name))
+		{
+			let constructor = constructor.to_owned();
+			let name = doc.unique.from(&to_valid_identifier(&node.tag_name.name));
+			let (start, end) = (node.start, node.end);
+			let index = cursor.next();
+			let hydrate = format!("[{}, {}]", cursor.parent, index);
+			let c_new = self.render_component(doc, &constructor, node, &hydrate)?;
+
+			self.c_claim
+				.write("let ")
+				.write(&name)
+				.write(" = ")
+				.map(start)
+				.append(&c_new)
+				.write(";\n")
+				.map(end);
+
+			return Ok(name);
+		}
+
+		if &node.tag_name.name == "component" {
+			return self.render_dynamic_component(doc, node, cursor);
+		}
+
+		if &node.tag_name.name == "slot" {
+			let accessor = slot_accessor(&slot_name(&node)?);
+			let helper = doc.import("claim_slot", None, INTERNAL_MODULE);
+			let name = doc.unique.from("fragment");
+			let index = doc.unique.from("$index");
+
+			self.c_claim
+				.write("let [")
+				.write(&name)
+				.write(", ")
+				.write(&index)
+				.write("] = ")
+				.map(node.start)
+				.write(&helper)
+				.write("(")
+				.write(&cursor.parent)
+				.write(", ")
+				.write(&cursor.index())
+				.write(", $self, ")
+				.write(&accessor)
+				.write(");\n")
+				.map(node.end);
+
+			cursor.reset(index);
+			return Ok(name);
+		}
+
+		let name = doc.unique.from(&to_valid_identifier(tag_name(&node)));
+		self.claim(doc, &name, node.start, node.end, cursor);
+
+		// Static attributes are already rendered by the server.
+		for attribute in node.attributes {
+			dom::bind_attribute(doc, &self.js, &mut self.c_bind, &name, attribute)?;
+		}
+
+		let mut children = Cursor::new(&name, None);
+
+		for child in node.children {
+			self.render_node(doc, child, &mut children)?;
+		}
+
+		Ok(name)
+	}
+
+	fn render_component(
+		&mut self,
+		doc: &mut Document,
+		constructor: &str,
+		node: ast::Element,
+		hydrate: &str,
+	) -> Result<Chunk, Error> {
+		let c_attrs = dom::component_attrs(&self.js, node.attributes);
+
+		render_component(
+			doc,
+			constructor,
+			node.children,
+			c_attrs,
+			Some(hydrate),
+			|doc, nodes| {
+				let hydratable = doc.import("hydratable", None, INTERNAL_MODULE);
+				let (render_name, hydrate_name, args) =
+					self.render_fragments(doc, nodes, &[], &[])?;
+
+				let mut c_slot = Chunk::new();
+				c_slot
+					.write(&hydratable)
+					.write("(")
+					.write(&slot_function(&render_name, &args))
+					.write(", ")
+					.write(&slot_function(&hydrate_name, &args))
+					.write(")");
+				Ok(c_slot)
+			},
+		)
+	}
+
+	fn render_dynamic_component(
+		&mut self,
+		doc: &mut Document,
+		mut node: ast::Element,
+		cursor: &mut Cursor,
+	) -> Result<String, Error> {
+		let is = take_component_constructor(&mut node)?;
+		let helper = doc.import("claim_component", None, INTERNAL_MODULE);
+		let name = doc.unique.from("component");
+		let index = doc.unique.from("$index");
+		let constructor = doc.unique.from("constructor");
+		let hydrate = doc.unique.from("hydrate");
+		let (start, end) = (node.start, node.end);
+		let c_new = self.render_component(doc, &constructor, node, &hydrate)?;
+
+		self.c_claim
+			.write("let [")
+			.write(&name)
+			.write(", ")
+			.write(&index)
+			.write("] = ")
+			.map(start)
+			.write(&helper)
+			.write("(")
+			.write(&cursor.parent)
+			.write(", ")
+			.write(&cursor.index())
+			.write(", this.$computed(() => ")
+			.append(&self.js.serialize(&is))
+			.write("), (")
+			.write(&constructor)
+			.write(", ")
+			.write(&hydrate)
+			.write(") => ")
+			.append(&c_new)
+			.write(");\n")
+			.map(end);
+
+		cursor.reset(index);
+		Ok(name)
+	}
+
+	fn render_text(&mut self, doc: &mut Document, node: ast::Text, cursor: &mut Cursor) -> String {
+		let text = join_spaces(&node.content);
+		let length = TextLength::Static(text.encode_utf16().count());
+
+		self.claim_text(doc, node.start, node.end, length, cursor)
+	}
+
+	fn render_text_binding(
+		&mut self,
+		doc: &mut Document,
+		node: ast::TextBinding,
+		cursor: &mut Cursor,
+	) -> String {
+		let accessor = doc.unique.from("accessor");
+
+		self.c_claim
+			.write("let ")
+			.write(&accessor)
+			.write(" = this.$computed(() => ")
+			.append(&self.js.serialize(&node.expression))
+			.write(");\n");
+
+		let name = self.claim_text(
+			doc,
+			node.start,
+			node.end,
+			TextLength::Binding(accessor.clone()),
+			cursor,
+		);
+
+		let helper = doc.import("bind_text", None, INTERNAL_MODULE);
+
+		self.c_bind
+			.write(&helper)
+			.write("(")
+			.write(&name)
+			.write(", ")
+			.write(&accessor)
+			.write(");\n");
+
+		name
+	}
+
+	fn render_flow_control(
+		&mut self,
+		doc: &mut Document,
+		node: ast::FlowControl,
+		cursor: &mut Cursor,
+	) -> Result<String, Error> {
+		let name = doc.unique.from("flow");
+		let index = doc.unique.from("$index");
+
+		self.c_claim
+			.write("let [")
+			.write(&name)
+			.write(", ")
+			.write(&index)
+			.write("] = ")
+			.map(node.start());
+
+		match node {
+			ast::FlowControl::When(node) => {
+				let helper = doc.import("claim_when", None, INTERNAL_MODULE);
+
+				let mut branches = vec![(Some(*node.condition), node.children)];
+				for node in node.chain {
+					branches.push((node.condition, node.children));
+				}
+
+				self.c_claim
+					.write(&helper)
+					.write("(")
+					.write(&cursor.parent)
+					.write(", ")
+					.write(&cursor.index())
+					.write(", [\n");
+
+				for (condition, children) in branches {
+					let (render_name, hydrate_name, args) =
+						self.render_fragments(doc, children, &[], &[])?;
+
+					self.c_claim.write("\t[");
+
+					if let Some(condition) = condition {
+						self.c_claim
+							.write("this.$computed(() => ")
+							.append(&self.js.serialize(&condition))
+							.write(")");
+					} else {
+						self.c_claim.write("null");
+					}
+
+					self.c_claim
+						.write(", ")
+						.write(&render_name)
+						.write(".bind(this, ")
+						.write(&args)
+						.write("), ")
+						.write(&hydrate_name)
+						.write(".bind(this, ")
+						.write(&args)
+						.write(")],\n");
+				}
+
+				self.c_claim.write("]);\n");
+			}
+
+			ast::FlowControl::Each(node) => {
+				let helper = doc.import("claim_each", None, INTERNAL_MODULE);
+
+				let index = node
+					.index
+					.map(|index| ast::javascript::Expression::Identifier(*index));
+				let mut params = vec![&*node.iterator];
+				params.extend(&index);
+
+				let aliases = doc.unique.params(&params);
+				let (render_name, hydrate_name, args) =
+					self.render_fragments(doc, node.children, &params, &aliases)?;
+				let fallback = match node.fallback {
+					Some(fallback) => {
+						Some(self.render_fragments(doc, fallback.children, &[], &[])?)
+					}
+					None => None,
+				};
+
+				self.c_claim
+					.write(&helper)
+					.write("(")
+					.write(&cursor.parent)
+					.write(", ")
+					.write(&cursor.index())
+					.write(", this.$computed(() => ")
+					.append(&self.js.serialize(&node.iterable))
+					.write("), ")
+					.write(&render_name)
+					.write(".bind(this, ")
+					.write(&args)
+					.write("), ")
+					.write(&hydrate_name)
+					.write(".bind(this, ")
+					.write(&args)
+					.write(")");
+
+				if let Some(key) = &node.key {
+					// The key is evaluated in the scope of the items, like the fragments.
+					let js = self.js.with_params(&aliases);
+					let params: Vec<_> = params
+						.iter()
+						.map(|param| js.serialize_param(param))
+						.collect();
+					self.c_claim
+						.write(", ")
+						.append(&js.serialize_closure(&params, key));
+				} else if fallback.is_some() {
+					self.c_claim.write(", undefined");
+				}
+
+				if let Some((render_name, hydrate_name, _)) = &fallback {
+					self.c_claim
+						.write(", [")
+						.write(render_name)
+						.write(".bind(this, ")
+						.write(&args)
+						.write("), ")
+						.write(hydrate_name)
+						.write(".bind(this, ")
+						.write(&args)
+						.write(")]");
+				}
+
+				self.c_claim.write(");\n");
+			}
+
+			ast::FlowControl::Let(node) => {
+				let helper = doc.import("claim_let", None, INTERNAL_MODULE);
+
+				let binding = ast::javascript::Expression::Identifier(*node.name);
+				let aliases = doc.unique.params(&[&binding]);
+				let (render_name, hydrate_name, args) =
+					self.render_fragments(doc, node.children, &[&binding], &aliases)?;
+
+				self.c_claim
+					.write(&helper)
+					.write("(")
+					.write(&cursor.parent)
+					.write(", ")
+					.write(&cursor.index())
+					.write(", this.$computed(() => ")
+					.append(&self.js.serialize(&node.value))
+					.write("), ")
+					.write(&render_name)
+					.write(".bind(this, ")
+					.write(&args)
+					.write("), ")
+					.write(&hydrate_name)
+					.write(".bind(this, ")
+					.write(&args)
+					.write("));\n");
+			}
+
+			ast::FlowControl::Await(node) => {
+				let helper = doc.import("claim_await", None, INTERNAL_MODULE);
+				let (render_name, hydrate_name, args) =
+					self.render_fragments(doc, node.children, &[], &[])?;
+
+				self.c_claim
+					.write(&helper)
+					.write("(")
+					.write(&cursor.parent)
+					.write(", ")
+					.write(&cursor.index())
+					.write(", this.$computed(() => ")
+					.append(&self.js.serialize(&node.promise))
+					.write("), [")
+					.write(&render_name)
+					.write(".bind(this, ")
+					.write(&args)
+					.write("), ")
+					.write(&hydrate_name)
+					.write(".bind(this, ")
+					.write(&args)
+					.write(")]");
+
+				// The server renders the then branch for values which are not promises, so it is
+				// hydrated too. The catch branch is only rendered on the client.
+				if let Some(then) = node.then {
+					let params: Vec<_> = then.binding.iter().map(|binding| &**binding).collect();
+					let aliases = doc.unique.params(&params);
+					let (render_name, hydrate_name, args) =
+						self.render_fragments(doc, then.children, &params, &aliases)?;
+
+					self.c_claim
+						.write(", [")
+						.write(&render_name)
+						.write(".bind(this, ")
+						.write(&args)
+						.write("), ")
+						.write(&hydrate_name)
+						.write(".bind(this, ")
+						.write(&args)
+						.write(")]");
+				} else if node.catch.is_some() {
+					self.c_claim.write(", undefined");
+				}
+
+				if let Some(catch) = node.catch {
+					let render_name = doc.unique.from("render_fragment");
+					let (mut render, _) = dom::Fragment::nested(&self.js);
+
+					if let Some(binding) = &catch.binding {
+						let aliases = doc.unique.params(&[&**binding]);
+						declare_params(&mut render.js, &mut render.c_attr, &[&**binding], &aliases);
+					}
+
+					render.render(doc, render_name.clone(), catch.children)?;
+					self.c_claim
+						.write(", ")
+						.write(&render_name)
+						.write(".bind(this, ")
+						.write(&args)
+						.write(")");
+				}
+
+				self.c_claim.write(");\n");
+			}
+
+			ast::FlowControl::Try(node) => {
+				let helper = doc.import("claim_try", None, INTERNAL_MODULE);
+				let (render_name, hydrate_name, args) =
+					self.render_fragments(doc, node.children, &[], &[])?;
+
+				// The catch branch is never claimed, the client renders the children again instead.
+				let catch_name = doc.unique.from("render_fragment");
+				let (mut catch, _) = dom::Fragment::nested(&self.js);
+
+				if let Some(binding) = &node.catch.binding {
+					let aliases = doc.unique.params(&[&**binding]);
+					declare_params(&mut catch.js, &mut catch.c_attr, &[&**binding], &aliases);
+				}
+
+				catch.render(doc, catch_name.clone(), node.catch.children)?;
+
+				self.c_claim
+					.write(&helper)
+					.write("(")
+					.write(&cursor.parent)
+					.write(", ")
+					.write(&cursor.index())
+					.write(", [")
+					.write(&render_name)
+					.write(".bind(this, ")
+					.write(&args)
+					.write("), ")
+					.write(&hydrate_name)
+					.write(".bind(this, ")
+					.write(&args)
+					.write(")], ")
+					.write(&catch_name)
+					.write(".bind(this, ")
+					.write(&args)
+					.write("));\n");
+			}
+
+			ast::FlowControl::Match(node) => {
+				let helper = doc.import("claim_match", None, INTERNAL_MODULE);
+				let accessor = dom::match_accessor(&self.js, &node);
+
+				self.c_claim
+					.write(&helper)
+					.write("(")
+					.write(&cursor.parent)
+					.write(", ")
+					.write(&cursor.index())
+					.write(", ")
+					.append(&accessor)
+					.write(", [\n");
+
+				for case in node.cases {
+					let (render_name, hydrate_name, args) =
+						self.render_fragments(doc, case.children, &[], &[])?;
+
+					self.c_claim
+						.write("\t[")
+						.write(&render_name)
+						.write(".bind(this, ")
+						.write(&args)
+						.write("), ")
+						.write(&hydrate_name)
+						.write(".bind(this, ")
+						.write(&args)
+						.write(")],\n");
+				}
+
+				self.c_claim.write("]);\n");
+			}
+		}
+
+		cursor.reset(index);
+		Ok(name)
+	}
+
+	/// Renders the fragments which create and claim nodes, nested in this one. Returns their names
+	/// with the arguments to call them with.
+	fn render_fragments(
+		&self,
+		doc: &mut Document,
+		nodes: Vec<ast::Node>,
+		params: &[&ast::javascript::Expression],
+		aliases: &[(&str, String)],
+	) -> Result<(String, String, String), Error> {
+		let render_name = doc.unique.from("render_fragment");
+		let hydrate_name = doc.unique.from("hydrate_fragment");
+
+		let (mut render, args) = dom::Fragment::nested(&self.js);
+		let mut hydrate = Fragment::new();
+		inherit_locals(&self.js, &mut hydrate.js, &mut hydrate.c_locals);
+
+		declare_params(&mut render.js, &mut render.c_attr, params, aliases);
+		declare_params(&mut hydrate.js, &mut hydrate.c_attr, params, aliases);
+
+		render.render(doc, render_name.clone(), nodes.clone())?;
+		hydrate.render(doc, hydrate_name.clone(), nodes)?;
+
+		Ok((render_name, hydrate_name, args))
+	}
+}
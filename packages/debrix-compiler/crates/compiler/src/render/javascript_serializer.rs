@@ -1,15 +1,19 @@
 use super::*;
+use std::collections::HashMap;
 
 const OVERRIDES_PROPERTY: [&str; 6] = ["null", "undefined", "NaN", "Infinity", "false", "true"];
 
 pub struct JavascriptSerializer {
 	pub local_vars: Vec<String>,
+	/// Local variables which are renamed in the output, to avoid conflicts with other names.
+	pub aliases: HashMap<String, String>,
 }
 
 impl JavascriptSerializer {
 	pub fn new() -> JavascriptSerializer {
 		Self {
 			local_vars: Vec::new(),
+			aliases: HashMap::new(),
 		}
 	}
 
@@ -27,6 +31,15 @@ impl JavascriptSerializer {
 			ast::javascript::Expression::Literal(expr) => {
 				chunk.map(expr.start()).write(&expr.raw()).map(expr.end());
 			}
+			ast::javascript::Expression::This(expr) => {
+				chunk.map(expr.start).write("this").map(expr.end);
+			}
+			ast::javascript::Expression::Unary(expr) if !expr.prefix => {
+				chunk
+					.append(&self._serialize(&expr.operand, thisify))
+					.write(&expr.operator.to_string())
+					.map(expr.end);
+			}
 			ast::javascript::Expression::Unary(expr) => {
 				let operator = expr.operator.to_string();
 
@@ -56,7 +69,7 @@ impl JavascriptSerializer {
 			ast::javascript::Expression::Call(expr) => {
 				chunk
 					.append(&self._serialize(&expr.callee, thisify))
-					.write("(");
+					.write(if expr.optional { "?.(" } else { "(" });
 
 				let mut arguments = expr.arguments.iter().peekable();
 				while let Some(arg) = arguments.next() {
@@ -109,21 +122,69 @@ impl JavascriptSerializer {
 					chunk.write("]");
 				}
 			}
+			ast::javascript::Expression::Sequence(expr) => {
+				// Parenthesized, so the commas do not separate arguments where the expression is
+				// written.
+				chunk.write("(");
+
+				let mut expressions = expr.expressions.iter().peekable();
+				while let Some(expr) = expressions.next() {
+					chunk.append(&self._serialize(expr, thisify));
+
+					if expressions.peek().is_some() {
+						chunk.write(", ");
+					}
+				}
+
+				chunk.write(")");
+			}
+			ast::javascript::Expression::Await(expr) => {
+				chunk
+					.map(expr.start)
+					.write("await ")
+					.append(&self._serialize(&expr.argument, thisify));
+			}
 			ast::javascript::Expression::Function(expr) => {
-				chunk.map(expr.start).write("(");
+				// Parameters and `var` declarations are local to the whole function.
+				let mut names = Vec::new();
+
+				for param in &expr.parameters {
+					bound_names(param, &mut names);
+				}
+
+				if let ast::javascript::FunctionBody::Block(body) = &expr.body {
+					var_names(&body.body, &mut names);
+				}
+
+				let js = self.scope(&names);
+
+				chunk.map(expr.start);
+
+				if expr.is_async {
+					chunk.write("async ");
+				}
+
+				chunk.write("(");
 
 				let mut params = expr.parameters.iter().peekable();
 				while let Some(param) = params.next() {
-					chunk.append(&self._serialize(param, false));
+					chunk.append(&js.serialize_pattern(param, thisify));
 
 					if params.peek().is_some() {
 						chunk.write(", ");
 					}
 				}
 
-				chunk
-					.write(") => ")
-					.append(&self._serialize(&expr.body, thisify));
+				chunk.write(") => ");
+
+				match &expr.body {
+					ast::javascript::FunctionBody::Expression(body) => {
+						chunk.append(&js._serialize(body, thisify));
+					}
+					ast::javascript::FunctionBody::Block(body) => {
+						chunk.append(&js.serialize_block(body, thisify));
+					}
+				}
 			}
 			ast::javascript::Expression::Assignment(expr) => {
 				chunk
@@ -140,12 +201,12 @@ impl JavascriptSerializer {
 					.append(&self._serialize(&expr.argument, thisify));
 			}
 			ast::javascript::Expression::Template(expr) => {
-				return self.serialize_template(expr);
+				return self.serialize_template(expr, thisify);
 			}
 			ast::javascript::Expression::TaggedTemplate(expr) => {
 				chunk
 					.append(&self._serialize(&expr.tag, thisify))
-					.append(&self.serialize_template(&expr.quasi));
+					.append(&self.serialize_template(&expr.quasi, thisify));
 			}
 			ast::javascript::Expression::Object(expr) => {
 				chunk.map(expr.start).write("{");
@@ -208,6 +269,324 @@ impl JavascriptSerializer {
 			ast::javascript::Expression::Empty(expr) => {
 				chunk.map(expr.start).write(";").map(expr.end);
 			}
+			// Types are erased, leaving the expression. The assertions bind like relational
+			// operators, so the expression is grouped the same without them.
+			ast::javascript::Expression::TsAs(expr) => {
+				return self._serialize(&expr.expression, thisify);
+			}
+			ast::javascript::Expression::TsSatisfies(expr) => {
+				return self._serialize(&expr.expression, thisify);
+			}
+			ast::javascript::Expression::TsNonNull(expr) => {
+				return self._serialize(&expr.expression, thisify);
+			}
+			ast::javascript::Expression::TsAnnotated(expr) => {
+				return self.serialize_pattern(&expr.expression, thisify);
+			}
+		}
+
+		chunk
+	}
+
+	/// Returns a serializer for the scope of a nested fragment, in which the names bound by its
+	/// parameters are declared with their aliases from [`Unique::params`].
+	pub fn with_params(&self, aliases: &[(&str, String)]) -> JavascriptSerializer {
+		let mut js = self.scope(&[]);
+
+		for (name, alias) in aliases {
+			js.declare(name, alias);
+		}
+
+		js
+	}
+
+	/// Declares a local variable, which is named `alias` in the output.
+	pub fn declare(&mut self, name: &str, alias: &str) {
+		self.local_vars.push(name.to_owned());
+
+		if name != alias {
+			self.aliases.insert(name.to_owned(), alias.to_owned());
+		} else {
+			self.aliases.remove(name);
+		}
+	}
+
+	/// Serializes a function parameter, which is an identifier or a pattern. The names it binds
+	/// are declared beforehand.
+	pub fn serialize_param(&self, pattern: &ast::javascript::Expression) -> Chunk {
+		self.serialize_pattern(pattern, true)
+	}
+
+	/// Serializes an arrow function taking the serialized `params` and returning `body`.
+	pub fn serialize_closure(&self, params: &[Chunk], body: &ast::javascript::Expression) -> Chunk {
+		let mut chunk = Chunk::new();
+		chunk.write("(");
+
+		for (i, param) in params.iter().enumerate() {
+			if i > 0 {
+				chunk.write(", ");
+			}
+
+			chunk.append(param);
+		}
+
+		chunk.write(") => ").append(&self.serialize(body));
+		chunk
+	}
+
+	/// Returns a serializer for a nested scope, in which `names` are local and not aliased.
+	fn scope(&self, names: &[&str]) -> JavascriptSerializer {
+		let mut local_vars = self.local_vars.clone();
+		let mut aliases = self.aliases.clone();
+
+		for name in names {
+			local_vars.push((*name).to_owned());
+			aliases.remove(*name);
+		}
+
+		JavascriptSerializer {
+			local_vars,
+			aliases,
+		}
+	}
+
+	/// Serializes the target of a parameter or declaration. Only default values and computed
+	/// keys are expressions, the names are bound as they are.
+	fn serialize_pattern(&self, expr: &ast::javascript::Expression, thisify: bool) -> Chunk {
+		let mut chunk = Chunk::new();
+
+		match expr {
+			ast::javascript::Expression::Identifier(expr) => {
+				return self.serialize_identifier(expr, false);
+			}
+			ast::javascript::Expression::Assignment(expr) => {
+				chunk
+					.append(&self.serialize_pattern(&expr.left, thisify))
+					.write(" = ")
+					.append(&self._serialize(&expr.right, thisify));
+			}
+			ast::javascript::Expression::Spread(expr) => {
+				chunk
+					.map(expr.start)
+					.write("...")
+					.append(&self.serialize_pattern(&expr.argument, thisify));
+			}
+			ast::javascript::Expression::Object(expr) => {
+				chunk.map(expr.start).write("{");
+
+				let mut props = expr.properties.iter().peekable();
+				while let Some(prop) = props.next() {
+					match prop {
+						ast::javascript::ObjectProperty::Keyed(prop) => {
+							// The key is the name of the property, and is never renamed.
+							chunk
+								.map(prop.key.start)
+								.write(&prop.key.name)
+								.map(prop.key.end);
+
+							match &prop.value {
+								// A shorthand property with a default value, `{ a = 1 }`.
+								Some(ast::javascript::Expression::Assignment(value))
+									if matches!(
+										&*value.left,
+										ast::javascript::Expression::Identifier(left)
+											if left.name == prop.key.name
+												&& !self.aliases.contains_key(left.name.as_ref())
+									) =>
+								{
+									chunk
+										.write(" = ")
+										.append(&self._serialize(&value.right, thisify));
+								}
+								Some(value) => {
+									chunk
+										.write(": ")
+										.append(&self.serialize_pattern(value, thisify));
+								}
+								None => {
+									// A shorthand property binding a renamed name, `{ a: a_1 }`.
+									if let Some(alias) = self.aliases.get(prop.key.name.as_ref()) {
+										chunk.write(": ").write(alias);
+									}
+								}
+							}
+						}
+						ast::javascript::ObjectProperty::Computed(prop) => {
+							chunk
+								.map(prop.start)
+								.write("[")
+								.append(&self._serialize(&prop.key, thisify))
+								.write("]: ")
+								.append(&self.serialize_pattern(&prop.value, thisify));
+						}
+						ast::javascript::ObjectProperty::Spread(prop) => {
+							chunk
+								.map(prop.start)
+								.write("...")
+								.append(&self.serialize_pattern(&prop.argument, thisify));
+						}
+					}
+
+					if props.peek().is_some() {
+						chunk.write(", ");
+					}
+				}
+
+				chunk.write("}").map(expr.end);
+			}
+			ast::javascript::Expression::Array(expr) => {
+				chunk.map(expr.start).write("[");
+
+				let mut elements = expr.elements.iter().peekable();
+				while let Some(element) = elements.next() {
+					// Holes are left empty, `[a, , b]`.
+					if !matches!(element, ast::javascript::Expression::Empty(_)) {
+						chunk.append(&self.serialize_pattern(element, thisify));
+					}
+
+					if elements.peek().is_some() {
+						chunk.write(", ");
+					}
+				}
+
+				chunk.write("]").map(expr.end);
+			}
+			_ => return self._serialize(expr, thisify),
+		}
+
+		chunk
+	}
+
+	/// Serializes a block on one line, where `let` and `const` declarations are local.
+	fn serialize_block(&self, stmt: &ast::javascript::BlockStatement, thisify: bool) -> Chunk {
+		let mut names = Vec::new();
+
+		for child in &stmt.body {
+			if let ast::javascript::Statement::Variable(child) = child {
+				if child.kind != ast::javascript::VariableKind::Var {
+					for declarator in &child.declarations {
+						bound_names(&declarator.id, &mut names);
+					}
+				}
+			}
+		}
+
+		let js = self.scope(&names);
+		let mut chunk = Chunk::new();
+		chunk.map(stmt.start).write("{");
+
+		for child in &stmt.body {
+			chunk
+				.write(" ")
+				.append(&js.serialize_statement(child, thisify));
+		}
+
+		if !stmt.body.is_empty() {
+			chunk.write(" ");
+		}
+
+		chunk.write("}").map(stmt.end);
+		chunk
+	}
+
+	fn serialize_statement(&self, stmt: &ast::javascript::Statement, thisify: bool) -> Chunk {
+		let mut chunk = Chunk::new();
+
+		match stmt {
+			ast::javascript::Statement::Block(stmt) => {
+				return self.serialize_block(stmt, thisify);
+			}
+			ast::javascript::Statement::Expression(stmt) => {
+				chunk
+					.append(&self._serialize(&stmt.expression, thisify))
+					.write(";");
+			}
+			ast::javascript::Statement::Variable(stmt) => {
+				chunk
+					.map(stmt.start)
+					.write(&stmt.kind.to_string())
+					.write(" ");
+
+				let mut declarations = stmt.declarations.iter().peekable();
+				while let Some(declarator) = declarations.next() {
+					chunk.append(&self.serialize_pattern(&declarator.id, thisify));
+
+					if let Some(init) = &declarator.init {
+						chunk.write(" = ").append(&self._serialize(init, thisify));
+					}
+
+					if declarations.peek().is_some() {
+						chunk.write(", ");
+					}
+				}
+
+				chunk.write(";");
+			}
+			ast::javascript::Statement::Return(stmt) => {
+				chunk.map(stmt.start).write("return");
+
+				if let Some(argument) = &stmt.argument {
+					chunk.write(" ").append(&self._serialize(argument, thisify));
+				}
+
+				chunk.write(";");
+			}
+			ast::javascript::Statement::If(stmt) => {
+				chunk
+					.map(stmt.start)
+					.write("if (")
+					.append(&self._serialize(&stmt.condition, thisify))
+					.write(") ")
+					.append(&self.serialize_statement(&stmt.consequent, thisify));
+
+				if let Some(alternate) = &stmt.alternate {
+					chunk
+						.write(" else ")
+						.append(&self.serialize_statement(alternate, thisify));
+				}
+			}
+			ast::javascript::Statement::Throw(stmt) => {
+				chunk
+					.map(stmt.start)
+					.write("throw ")
+					.append(&self._serialize(&stmt.argument, thisify))
+					.write(";");
+			}
+			ast::javascript::Statement::Try(stmt) => {
+				chunk
+					.map(stmt.start)
+					.write("try ")
+					.append(&self.serialize_block(&stmt.block, thisify));
+
+				if let Some(handler) = &stmt.handler {
+					chunk.map(handler.start).write(" catch ");
+
+					let mut names = Vec::new();
+					if let Some(parameter) = &handler.parameter {
+						bound_names(parameter, &mut names);
+					}
+
+					let js = self.scope(&names);
+
+					if let Some(parameter) = &handler.parameter {
+						chunk
+							.write("(")
+							.append(&js.serialize_pattern(parameter, thisify))
+							.write(") ");
+					}
+
+					chunk.append(&js.serialize_block(&handler.body, thisify));
+				}
+
+				if let Some(finalizer) = &stmt.finalizer {
+					chunk
+						.write(" finally ")
+						.append(&self.serialize_block(finalizer, thisify));
+				}
+			}
+			ast::javascript::Statement::Empty(stmt) => {
+				chunk.map(stmt.start).write(";").map(stmt.end);
+			}
 		}
 
 		chunk
@@ -220,7 +599,12 @@ impl JavascriptSerializer {
 	) -> Chunk {
 		let mut chunk = Chunk::new();
 
-		let is_local = self.local_vars.contains(&expr.name);
+		if let Some(alias) = self.aliases.get(expr.name.as_ref()) {
+			chunk.map(expr.start).write(alias).map(expr.end);
+			return chunk;
+		}
+
+		let is_local = self.local_vars.iter().any(|var| *var == expr.name);
 		let force_local = is_local || OVERRIDES_PROPERTY.contains(&expr.name.as_ref());
 		let is_reserved = RESERVED_JAVASCRIPT_KEYWORDS.contains(&expr.name.as_ref());
 
@@ -259,10 +643,92 @@ impl JavascriptSerializer {
 		chunk
 	}
 
-	fn serialize_template(&self, expr: &ast::javascript::TemplateLiteral) -> Chunk {
-		// TODO: serialize all inline expressions in the template
+	fn serialize_template(&self, expr: &ast::javascript::TemplateLiteral, thisify: bool) -> Chunk {
 		let mut chunk = Chunk::new();
-		chunk.map(expr.start).write(&expr.raw).map(expr.end);
+		chunk.map(expr.start).write("`");
+
+		for (i, quasi) in expr.quasis.iter().enumerate() {
+			chunk.write(&quasi.raw);
+
+			if let Some(expression) = expr.expressions.get(i) {
+				chunk
+					.write("${")
+					.append(&self._serialize(expression, thisify))
+					.write("}");
+			}
+		}
+
+		chunk.write("`").map(expr.end);
 		chunk
 	}
 }
+
+/// Collects the names bound by a parameter or declaration, which may destructure its value.
+pub fn bound_names<'a>(pattern: &'a ast::javascript::Expression, names: &mut Vec<&'a str>) {
+	match pattern {
+		ast::javascript::Expression::Identifier(pattern) => names.push(&pattern.name),
+		ast::javascript::Expression::Assignment(pattern) => bound_names(&pattern.left, names),
+		ast::javascript::Expression::Spread(pattern) => bound_names(&pattern.argument, names),
+		ast::javascript::Expression::TsAnnotated(pattern) => {
+			bound_names(&pattern.expression, names)
+		}
+		ast::javascript::Expression::Array(pattern) => {
+			for element in &pattern.elements {
+				bound_names(element, names);
+			}
+		}
+		ast::javascript::Expression::Object(pattern) => {
+			for property in &pattern.properties {
+				match property {
+					ast::javascript::ObjectProperty::Keyed(property) => match &property.value {
+						Some(value) => bound_names(value, names),
+						None => names.push(&property.key.name),
+					},
+					ast::javascript::ObjectProperty::Computed(property) => {
+						bound_names(&property.value, names)
+					}
+					ast::javascript::ObjectProperty::Spread(property) => {
+						bound_names(&property.argument, names)
+					}
+				}
+			}
+		}
+		_ => {}
+	}
+}
+
+/// Collects the names of `var` declarations in a function body, which are hoisted out of nested
+/// blocks but not out of nested functions.
+fn var_names<'a>(statements: &'a [ast::javascript::Statement], names: &mut Vec<&'a str>) {
+	for stmt in statements {
+		match stmt {
+			ast::javascript::Statement::Variable(stmt)
+				if stmt.kind == ast::javascript::VariableKind::Var =>
+			{
+				for declarator in &stmt.declarations {
+					bound_names(&declarator.id, names);
+				}
+			}
+			ast::javascript::Statement::Block(stmt) => var_names(&stmt.body, names),
+			ast::javascript::Statement::If(stmt) => {
+				var_names(std::slice::from_ref(&*stmt.consequent), names);
+
+				if let Some(alternate) = &stmt.alternate {
+					var_names(std::slice::from_ref(&**alternate), names);
+				}
+			}
+			ast::javascript::Statement::Try(stmt) => {
+				var_names(&stmt.block.body, names);
+
+				if let Some(handler) = &stmt.handler {
+					var_names(&handler.body.body, names);
+				}
+
+				if let Some(finalizer) = &stmt.finalizer {
+					var_names(&finalizer.body, names);
+				}
+			}
+			_ => {}
+		}
+	}
+}
@@ -2,16 +2,22 @@ use crate::*;
 
 // Targets
 mod dom;
+mod hydration;
+mod ssr;
 pub use dom::render as render_dom;
+pub use hydration::render as render_hydration;
+pub use ssr::render as render_ssr;
 
 // Exports
 pub use chunk::Chunk;
 
 // Utils
 mod chunk;
+mod document;
 mod javascript_serializer;
 mod utils;
 
+pub(crate) use document::*;
 pub(crate) use javascript_serializer::*;
 pub(crate) use utils::*;
 
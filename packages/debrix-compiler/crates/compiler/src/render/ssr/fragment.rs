@@ -0,0 +1,709 @@
+use super::*;
+
+pub struct Fragment {
+	pub js: JavascriptSerializer,
+	c_attr: Chunk,
+	c_render: Chunk,
+	// Static html not yet written to `c_render`.
+	html: String,
+	html_start: Option<usize>,
+}
+
+impl Fragment {
+	pub fn new() -> Self {
+		Self {
+			js: JavascriptSerializer::new(),
+			c_attr: Chunk::new(),
+			c_render: Chunk::new(),
+			html: String::new(),
+			html_start: None,
+		}
+	}
+
+	pub fn render_single(
+		self,
+		doc: &mut Document,
+		name: String,
+		node: ast::Node,
+	) -> Result<(), Error> {
+		self.render(doc, name, vec![node])
+	}
+
+	pub fn render(
+		mut self,
+		doc: &mut Document,
+		name: String,
+		nodes: Vec<ast::Node>,
+	) -> Result<(), Error> {
+		self.js.local_vars.push("$self".to_owned());
+
+		for node in nodes {
+			self.render_node(doc, node)?;
+		}
+
+		self.flush();
+
+		doc.c_fragments
+			.write("function ")
+			.write(&name)
+			.write("(")
+			.write("$self");
+
+		if !self.c_attr.is_empty() {
+			doc.c_fragments.write(", ").append(&self.c_attr);
+		}
+
+		doc.c_fragments
+			.write(") {\n")
+			.write("\tlet $html = \"\";\n");
+
+		if !self.c_render.is_empty() {
+			doc.c_fragments
+				.write("\n")
+				.append(&format_chunk(self.c_render, 1));
+		}
+
+		doc.c_fragments
+			.write("\n\n\treturn $html;\n")
+			.write("}\n\n");
+
+		Ok(())
+	}
+
+	fn write_html(&mut self, start: usize, html: &str) {
+		if self.html_start.is_none() {
+			self.html_start = Some(start);
+		}
+
+		self.html.push_str(html);
+	}
+
+	fn flush(&mut self) {
+		if let Some(start) = self.html_start.take() {
+			self.c_render
+				.map(start)
+				.write("$html += ")
+				.write(&in_string(&self.html))
+				.write(";\n");
+
+			self.html.clear();
+		}
+	}
+
+	fn write_expression(&mut self, start: usize, expression: &Chunk) {
+		self.flush();
+		self.c_render
+			.map(start)
+			.write("$html += ")
+			.append(expression)
+			.write(";\n");
+	}
+
+	fn render_node(&mut self, doc: &mut Document, node: ast::Node) -> Result<(), Error> {
+		match node {
+			ast::Node::Comment(node) => self.render_comment(node),
+			ast::Node::Element(node) => self.render_element(doc, node),
+			ast::Node::Text(node) => self.render_text(node),
+			ast::Node::TextBinding(node) => self.render_text_binding(doc, node),
+			ast::Node::FlowControl(node) => self.render_flow_control(doc, node),
+
+			_ => Err(Error::compiler(
+				node.start(),
+				node.end(),
+				"Node is not allowed here.",
+			)),
+		}
+	}
+
+	fn render_comment(&mut self, node: ast::Comment) -> Result<(), Error> {
+		let html = format!("<!--{}-->", comment_data(&node.comment));
+		self.write_html(node.start, &html);
+		Ok(())
+	}
+
+	fn render_element(&mut self, doc: &mut Document, node: ast::Element) -> Result<(), Error> {
+		check_spread_attributes(&node.attributes)?;
+
+		if let Some(constructor) =
+			doc.declaration(DeclarationKind::Component, Some(&node.tag_name.name))
+		{
+			let constructor = constructor.to_owned();
+			let start = node.start;
+			let mut expression = self.render_component(doc, &constructor, node)?;
+
+			expression.write(".toString()");
+			self.write_expression(start, &expression);
+			return Ok(());
+		}
+
+		if &node.tag_name.name == "component" {
+			return self.render_dynamic_component(doc, node);
+		}
+
+		if &node.tag_name.name == "slot" {
+			let accessor = slot_accessor(&slot_name(&node)?);
+
+			let mut expression = Chunk::new();
+			expression
+				.write("(")
+				.write(&accessor)
+				.write(" ? ")
+				.write(&accessor)
+				.write("($self) : \"\")");
+
+			self.write_html(node.start, BLOCK_START);
+			self.write_expression(node.start, &expression);
+			self.write_html(node.end, BLOCK_END);
+			return Ok(());
+		}
+
+		let tag_name = tag_name(&node).to_owned();
+
+		self.write_html(node.start, &("<".to_owned() + &tag_name));
+
+		for attribute in node.attributes.into_iter() {
+			if let ast::Attribute::Static(attribute) = &attribute {
+				if attribute.name.name == "slot" || attribute.name.name == "as" {
+					continue;
+				}
+			}
+
+			self.render_attribute(doc, attribute)?;
+		}
+
+		self.write_html(node.start_tag.end, ">");
+
+		if VOID_ELEMENTS.contains(&tag_name.as_ref()) {
+			if !node.children.is_empty() {
+				return Err(Error::compiler(
+					node.start,
+					node.end,
+					"Void elements cannot have children.",
+				));
+			}
+
+			return Ok(());
+		}
+
+		for child in node.children {
+			self.render_node(doc, child)?;
+		}
+
+		self.write_html(
+			node.end_tag.map(|range| range.start).unwrap_or(node.end),
+			&("</".to_owned() + &tag_name + ">"),
+		);
+
+		Ok(())
+	}
+
+	fn render_component(
+		&mut self,
+		doc: &mut Document,
+		constructor: &str,
+		node: ast::Element,
+	) -> Result<Chunk, Error> {
+		let mut c_attrs = Chunk::new();
+
+		for attribute in node.attributes {
+			match attribute {
+				ast::Attribute::Static(attribute) => {
+					if &attribute.name.name == "slot" {
+						continue;
+					}
+
+					c_attrs
+						.write(&to_valid_property(&attribute.name.name))
+						.write(": ")
+						.write(&in_string(
+							&attribute.value.map(|v| v.value).unwrap_or_default(),
+						))
+						.write(",\n");
+				}
+
+				ast::Attribute::Binding(attribute) => {
+					c_attrs
+						.write(&to_valid_property(&attribute.name.name))
+						.write(": ")
+						.append(&self.js.serialize(&attribute.value))
+						.write(",\n");
+				}
+
+				ast::Attribute::ShortBinding(attribute) => {
+					c_attrs
+						.write(&to_valid_property(&attribute.name.name))
+						.write(": ")
+						.append(&self.js.serialize(&attribute.name.into()))
+						.write(",\n");
+				}
+
+				ast::Attribute::Spread(attribute) => {
+					c_attrs
+						.write("_: ")
+						.append(&self.js.serialize(&attribute.value))
+						.write(",\n");
+				}
+			}
+		}
+
+		render_component(
+			doc,
+			constructor,
+			node.children,
+			c_attrs,
+			None,
+			|doc, nodes| {
+				let (fragment_name, args) = self.declare_fragment(doc, nodes, &[])?;
+
+				let mut c_slot = Chunk::new();
+				c_slot.write(&slot_function(&fragment_name, &args));
+				Ok(c_slot)
+			},
+		)
+	}
+
+	fn render_dynamic_component(
+		&mut self,
+		doc: &mut Document,
+		mut node: ast::Element,
+	) -> Result<(), Error> {
+		let is = take_component_constructor(&mut node)?;
+		let constructor = doc.unique.from("constructor");
+		let (start, end) = (node.start, node.end);
+		let mut expression = Chunk::new();
+
+		expression
+			.write("$html += ")
+			.append(&self.render_component(doc, &constructor, node)?)
+			.write(".toString();");
+
+		self.write_html(start, BLOCK_START);
+		self.flush();
+
+		self.c_render
+			.map(start)
+			.write("const ")
+			.write(&constructor)
+			.write(" = ")
+			.append(&self.js.serialize(&is))
+			.write(";\n")
+			.write("if (")
+			.write(&constructor)
+			.write(") {\n")
+			.append(&format_chunk(expression, 1))
+			.write("\n}\n");
+
+		self.write_html(end, BLOCK_END);
+		Ok(())
+	}
+
+	fn render_attribute(
+		&mut self,
+		doc: &mut Document,
+		attribute: ast::Attribute,
+	) -> Result<(), Error> {
+		match attribute {
+			ast::Attribute::Static(attr) => {
+				let mut html = " ".to_owned() + &attr.name.name;
+
+				if let Some(value) = attr.value {
+					html.push_str("=\"");
+					html.push_str(&escape_html(&value.value));
+					html.push('"');
+				}
+
+				self.write_html(attr.start, &html);
+			}
+			ast::Attribute::Binding(attr) => {
+				if attr.name.name.starts_with("bind:") {
+					let name = &attr.name.name[5..];
+
+					// Binders only apply to the client, but should be validated on all targets.
+					if doc
+						.declaration(DeclarationKind::Binder, Some(name))
+						.is_none()
+					{
+						return Err(Error::compiler(
+							attr.name.start,
+							attr.name.end,
+							&format!("Undefined binder '{}'.", name),
+						));
+					}
+				} else {
+					let helper = doc.import("render_attr", None, INTERNAL_MODULE);
+
+					let mut expression = Chunk::new();
+					expression
+						.write(&helper)
+						.write("(")
+						.write(&in_string(&attr.name.name))
+						.write(", ")
+						.append(&self.js.serialize(&attr.value))
+						.write(")");
+
+					self.write_expression(attr.start, &expression);
+				}
+			}
+			ast::Attribute::Spread(attr) => {
+				let helper = doc.import("render_attr_spread", None, INTERNAL_MODULE);
+
+				let mut expression = Chunk::new();
+				expression
+					.write(&helper)
+					.write("(")
+					.append(&self.js.serialize(&attr.value))
+					.write(")");
+
+				self.write_expression(attr.start, &expression);
+			}
+			ast::Attribute::ShortBinding(attr) => {
+				let helper = doc.import("render_attr", None, INTERNAL_MODULE);
+
+				let mut expression = Chunk::new();
+				expression
+					.write(&helper)
+					.write("(")
+					.write(&in_string(&attr.name.name))
+					.write(", ")
+					.append(&self.js.serialize(&attr.name.into()))
+					.write(")");
+
+				self.write_expression(attr.start, &expression);
+			}
+		}
+
+		Ok(())
+	}
+
+	fn render_text(&mut self, node: ast::Text) -> Result<(), Error> {
+		let text = join_spaces(&node.content);
+		self.write_html(node.start, &escape_html(&text));
+		Ok(())
+	}
+
+	fn render_text_binding(
+		&mut self,
+		doc: &mut Document,
+		node: ast::TextBinding,
+	) -> Result<(), Error> {
+		let helper = doc.import("escape", None, INTERNAL_MODULE);
+
+		let mut expression = Chunk::new();
+		expression
+			.write(&helper)
+			.write("(")
+			.append(&self.js.serialize(&node.expression))
+			.write(")");
+
+		self.write_expression(node.start, &expression);
+
+		Ok(())
+	}
+
+	fn render_fragment(
+		&mut self,
+		doc: &mut Document,
+		nodes: Vec<ast::Node>,
+		params: &[(&str, String)],
+	) -> Result<Chunk, Error> {
+		let (fragment_name, args) = self.declare_fragment(doc, nodes, params)?;
+
+		let mut call = Chunk::new();
+		call.write(&fragment_name)
+			.write(".call(this, ")
+			.write(&args);
+
+		for (_, alias) in params {
+			call.write(", ").write(alias);
+		}
+
+		call.write(")");
+		Ok(call)
+	}
+
+	/// Declares a fragment which is passed the local variables of this one, followed by `params`.
+	/// Returns its name with the arguments to call it with, without the params.
+	fn declare_fragment(
+		&self,
+		doc: &mut Document,
+		nodes: Vec<ast::Node>,
+		params: &[(&str, String)],
+	) -> Result<(String, String), Error> {
+		let fragment_name = doc.unique.from("render_fragment");
+		let mut fragment = Fragment::new();
+		let args = inherit_locals(&self.js, &mut fragment.js, &mut fragment.c_attr);
+
+		for (name, alias) in params {
+			fragment.js.declare(name, alias);
+
+			if !fragment.c_attr.is_empty() {
+				fragment.c_attr.write(", ");
+			}
+
+			fragment.c_attr.write(alias);
+		}
+
+		fragment.render(doc, fragment_name.clone(), nodes)?;
+		Ok((fragment_name, args))
+	}
+
+	fn render_flow_control(
+		&mut self,
+		doc: &mut Document,
+		node: ast::FlowControl,
+	) -> Result<(), Error> {
+		let (start, end) = (node.start(), node.end());
+
+		self.write_html(start, BLOCK_START);
+		self.flush();
+
+		match node {
+			ast::FlowControl::When(node) => {
+				let call = self.render_fragment(doc, node.children, &[])?;
+
+				self.c_render
+					.map(node.start)
+					.write("if (")
+					.append(&self.js.serialize(&node.condition))
+					.write(") {\n")
+					.write("\t$html += ")
+					.append(&call)
+					.write(";\n");
+
+				for node in node.chain {
+					let call = self.render_fragment(doc, node.children, &[])?;
+
+					self.c_render.map(node.start).write("} else ");
+
+					if let Some(condition) = &node.condition {
+						self.c_render
+							.write("if (")
+							.append(&self.js.serialize(condition))
+							.write(") ");
+					}
+
+					self.c_render
+						.write("{\n")
+						.write("\t$html += ")
+						.append(&call)
+						.write(";\n");
+				}
+
+				self.c_render.write("}\n");
+			}
+
+			ast::FlowControl::Each(node) => {
+				let index = node
+					.index
+					.map(|index| ast::javascript::Expression::Identifier(*index));
+				let mut params = vec![&*node.iterator];
+				params.extend(&index);
+
+				// The loop destructures the items, and passes the names they bind to the fragment.
+				let aliases = doc.unique.params(&params);
+				let js = self.js.with_params(&aliases);
+				let call = self.render_fragment(doc, node.children, &aliases)?;
+
+				// The fallback is rendered when the loop does not run once.
+				let fallback = match node.fallback {
+					Some(fallback) => {
+						let call = self.render_fragment(doc, fallback.children, &[])?;
+						Some((doc.unique.from("empty"), call))
+					}
+					None => None,
+				};
+
+				if let Some((empty, _)) = &fallback {
+					self.c_render
+						.map(node.start)
+						.write("let ")
+						.write(empty)
+						.write(" = true;\n");
+				}
+
+				self.c_render.map(node.start).write("for (const ");
+
+				if let Some(index) = &index {
+					self.c_render
+						.write("[")
+						.append(&js.serialize_param(index))
+						.write(", ");
+				}
+
+				self.c_render.append(&js.serialize_param(&node.iterator));
+
+				if index.is_some() {
+					self.c_render
+						.write("] of Array.from(")
+						.append(&self.js.serialize(&node.iterable))
+						.write(").entries()");
+				} else {
+					self.c_render
+						.write(" of ")
+						.append(&self.js.serialize(&node.iterable));
+				}
+
+				self.c_render.write(") {\n");
+
+				if let Some((empty, _)) = &fallback {
+					self.c_render.write("\t").write(empty).write(" = false;\n");
+				}
+
+				self.c_render
+					.write("\t$html += ")
+					.append(&call)
+					.write(";\n")
+					.write("}\n");
+
+				if let Some((empty, call)) = &fallback {
+					self.c_render
+						.write("if (")
+						.write(empty)
+						.write(") {\n")
+						.write("\t$html += ")
+						.append(call)
+						.write(";\n")
+						.write("}\n");
+				}
+			}
+
+			ast::FlowControl::Let(node) => {
+				let binding = ast::javascript::Expression::Identifier(*node.name);
+				let aliases = doc.unique.params(&[&binding]);
+				let (fragment_name, args) = self.declare_fragment(doc, node.children, &aliases)?;
+
+				// The value is evaluated once, and passed to the fragment.
+				self.c_render
+					.map(node.start)
+					.write("$html += ")
+					.write(&fragment_name)
+					.write(".call(this, ")
+					.write(&args)
+					.write(", ")
+					.append(&self.js.serialize(&node.value))
+					.write(");\n");
+			}
+
+			ast::FlowControl::Await(node) => {
+				// Promises are rendered as pending, and other values as resolved, which the
+				// client hydrates.
+				let awaited = doc.unique.from("awaited");
+				let call = self.render_fragment(doc, node.children, &[])?;
+
+				self.c_render
+					.map(node.start)
+					.write("let ")
+					.write(&awaited)
+					.write(" = ")
+					.append(&self.js.serialize(&node.promise))
+					.write(";\n")
+					.write("if (typeof ")
+					.write(&awaited)
+					.write("?.then === \"function\") {\n")
+					.write("\t$html += ")
+					.append(&call)
+					.write(";\n");
+
+				if let Some(then) = node.then {
+					let params: Vec<_> = then.binding.iter().map(|binding| &**binding).collect();
+					let aliases = doc.unique.params(&params);
+					let js = self.js.with_params(&aliases);
+					let call = self.render_fragment(doc, then.children, &aliases)?;
+
+					self.c_render.write("} else {\n");
+
+					if let Some(binding) = &then.binding {
+						self.c_render
+							.write("\tconst ")
+							.append(&js.serialize_param(binding))
+							.write(" = ")
+							.write(&awaited)
+							.write(";\n");
+					}
+
+					self.c_render
+						.write("\t$html += ")
+						.append(&call)
+						.write(";\n");
+				}
+
+				self.c_render.write("}\n");
+			}
+
+			ast::FlowControl::Try(node) => {
+				let call = self.render_fragment(doc, node.children, &[])?;
+
+				self.c_render
+					.map(node.start)
+					.write("try {\n")
+					.write("\t$html += ")
+					.append(&call)
+					.write(";\n")
+					.write("} catch ");
+
+				let params: Vec<_> = node
+					.catch
+					.binding
+					.iter()
+					.map(|binding| &**binding)
+					.collect();
+				let aliases = doc.unique.params(&params);
+				let js = self.js.with_params(&aliases);
+				let call = self.render_fragment(doc, node.catch.children, &aliases)?;
+
+				if let Some(binding) = &node.catch.binding {
+					self.c_render
+						.write("(")
+						.append(&js.serialize_param(binding))
+						.write(") ");
+				}
+
+				// The output of the children is discarded, as it is only added once they return.
+				self.c_render
+					.write("{\n")
+					.write("\t$html += ")
+					.write(&in_string(BLOCK_CAUGHT))
+					.write(";\n")
+					.write("\t$html += ")
+					.append(&call)
+					.write(";\n")
+					.write("}\n");
+			}
+
+			ast::FlowControl::Match(node) => {
+				self.c_render
+					.map(node.start)
+					.write("switch (")
+					.append(&self.js.serialize(&node.discriminant))
+					.write(") {\n");
+
+				for case in node.cases {
+					if case.tests.is_empty() {
+						self.c_render.write("\tdefault:\n");
+					}
+
+					for test in &case.tests {
+						self.c_render
+							.write("\tcase ")
+							.append(&self.js.serialize(test))
+							.write(":\n");
+					}
+
+					let call = self.render_fragment(doc, case.children, &[])?;
+
+					self.c_render
+						.write("\t\t$html += ")
+						.append(&call)
+						.write(";\n")
+						.write("\t\tbreak;\n");
+				}
+
+				self.c_render.write("}\n");
+			}
+		}
+
+		self.write_html(end, BLOCK_END);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,11 @@
+use super::*;
+
+mod component;
+mod fragment;
+
+pub(crate) use component::*;
+pub(crate) use fragment::*;
+
+pub fn render(document: ast::Document) -> Result<Chunk, Error> {
+	Document::new(Target::Server).render(document)
+}
@@ -4,6 +4,14 @@ fn compile(s: &str) -> Result<Chunk, Error> {
 	build(s.to_owned(), Target::Client)
 }
 
+fn compile_server(s: &str) -> Result<Chunk, Error> {
+	build(s.to_owned(), Target::Server)
+}
+
+fn compile_hydration(s: &str) -> Result<Chunk, Error> {
+	build(s.to_owned(), Target::Hydration)
+}
+
 #[test]
 fn test_impl_nested_components() {
 	compile(
@@ -36,7 +44,742 @@ fn test_use_nested_components() {
 	.unwrap();
 }
 
+#[test]
+fn test_dynamic_component() {
+	let chunk =
+		compile(r#"<div><component is={view} title="a"><p>b</p></component></div>"#).unwrap();
+
+	assert!(chunk
+		.source
+		.contains("(constructor_1) => new constructor_1({\n"));
+	assert!(chunk.source.contains(
+		"\t\t__family: \"__family\" in constructor_1 && $self[constructor_1.__family],\n"
+	));
+	assert!(chunk
+		.source
+		.contains("\t\t\tmain: render_fragment_1.bind(this)\n"));
+	assert!(!chunk.source.contains("is: "));
+
+	assert!(compile(r#"<div><component title="a"></component></div>"#).is_err());
+	assert!(compile(r#"<div><component is="a"></component></div>"#).is_err());
+}
+
 #[test]
 fn test_illegal_as_attribute() {
 	assert!(compile(r#"<div as></div>"#).is_err());
 }
+
+#[test]
+fn test_flow_control_when() {
+	let chunk = compile(r#"<div><#when {a}><p>a</p></when></div>"#).unwrap();
+
+	// The branch is only rendered when the condition holds.
+	assert!(chunk
+		.source
+		.contains("bind_when(render_fragment_1.bind(this, $self), "));
+	assert!(chunk
+		.source
+		.contains(r#", this.$computed(() => ("a" in this ? this["a"] : a)));"#));
+	assert!(!chunk.source.contains("render_fragment_1.call("));
+	assert!(!chunk.source.contains("bind_when_chain"));
+}
+
+#[test]
+fn test_flow_control_when_chain() {
+	let chunk = compile(
+		r#"
+		<div>
+			<#when {a}>
+				<p>a</p>
+			<#else when {b}>
+				<p>b</p>
+			<#else when {c}>
+				<p>c</p>
+			<#else>
+				<p>none</p>
+			</when>
+		</div>
+		"#,
+	)
+	.unwrap();
+
+	assert!(chunk.source.contains("bind_when_chain(["));
+	assert!(chunk.source.contains(
+		r#"[this.$computed(() => ("a" in this ? this["a"] : a)), render_fragment_1.bind(this, $self)]"#
+	));
+	assert!(chunk.source.contains(
+		r#"[this.$computed(() => ("b" in this ? this["b"] : b)), render_fragment_2.bind(this, $self)]"#
+	));
+	assert!(chunk.source.contains(
+		r#"[this.$computed(() => ("c" in this ? this["c"] : c)), render_fragment_3.bind(this, $self)]"#
+	));
+	assert!(chunk
+		.source
+		.contains("[null, render_fragment_4.bind(this, $self)]"));
+	assert_eq!(chunk.source.matches("bind_when_chain(").count(), 1);
+	assert!(!chunk.source.contains("computed_not"));
+}
+
+#[test]
+fn test_flow_control_when_chain_without_else() {
+	let chunk = compile(
+		r#"
+		<div>
+			<#when {a}>
+				<p>a</p>
+			<#else when {b}>
+				<p>b</p>
+			</when>
+		</div>
+		"#,
+	)
+	.unwrap();
+
+	assert!(chunk.source.contains(
+		r#"[this.$computed(() => ("a" in this ? this["a"] : a)), render_fragment_1.bind(this, $self)]"#
+	));
+	assert!(chunk.source.contains(
+		r#"[this.$computed(() => ("b" in this ? this["b"] : b)), render_fragment_2.bind(this, $self)]"#
+	));
+	assert!(!chunk.source.contains("[null, "));
+}
+
+#[test]
+fn test_flow_control_each_keyed() {
+	let chunk =
+		compile(r#"<ul><#each {item, i in items; key item.id}><li>{i}</li></each></ul>"#).unwrap();
+
+	assert!(chunk
+		.source
+		.contains("function render_fragment_1($self, item, i) {"));
+	assert!(chunk
+		.source
+		.contains("bind_each_keyed(render_fragment_1.bind(this, $self), "));
+	assert!(chunk.source.contains(", (item, i) => item.id);"));
+}
+
+#[test]
+fn test_flow_control_each_index() {
+	let chunk = compile(r#"<ul><#each {item, i in items}><li>{i}</li></each></ul>"#).unwrap();
+
+	assert!(chunk
+		.source
+		.contains("function render_fragment_1($self, item, i) {"));
+	assert!(chunk
+		.source
+		.contains("bind_each(render_fragment_1.bind(this, $self), "));
+	assert!(!chunk.source.contains("bind_each_keyed"));
+}
+
+#[test]
+fn test_flow_control_each_destructuring() {
+	let chunk = compile(
+		r#"
+		<ul>
+			<#each {{name, price = 0, tags: [tag]}, i in products; key name}>
+				<li>{i}: {name} {price} {tag}</li>
+			</each>
+		</ul>
+		"#,
+	)
+	.unwrap();
+
+	assert!(chunk
+		.source
+		.contains("function render_fragment_1($self, {name, price = 0, tags: [tag]}, i) {"));
+	assert!(chunk
+		.source
+		.contains(", ({name, price = 0, tags: [tag]}, i) => name);"));
+	assert!(!chunk.source.contains(r#"this["price"]"#));
+}
+
+#[test]
+fn test_flow_control_each_destructuring_renamed() {
+	let chunk = compile(
+		r#"<ul><#each {{a} in b}><li>{a}</li></each><#each {{a = 1} in c}><li>{a}</li></each></ul>"#,
+	)
+	.unwrap();
+
+	assert!(chunk
+		.source
+		.contains("function render_fragment_1($self, {a}) {"));
+	assert!(chunk
+		.source
+		.contains("function render_fragment_2($self, {a: a_1 = 1}) {"));
+	assert!(chunk.source.contains("this.$computed(() => a_1)"));
+}
+
+#[test]
+fn test_flow_control_each_else() {
+	let chunk = compile(
+		r#"<ul><#each {item in items}><li>{item}</li><#else><li>No items</li></each></ul>"#,
+	)
+	.unwrap();
+
+	assert!(chunk.source.contains("function render_fragment_2($self) {"));
+	assert!(chunk
+		.source
+		.contains(", render_fragment_2.bind(this, $self));"));
+}
+
+#[test]
+fn test_flow_control_let() {
+	let chunk =
+		compile(r#"<p><#let {city = user.address.city}><b>{city}</b> {city}</let></p>"#).unwrap();
+
+	assert!(chunk
+		.source
+		.contains("function render_fragment_1($self, city) {"));
+	assert!(chunk
+		.source
+		.contains("bind_let(render_fragment_1.bind(this, $self), "));
+	assert!(chunk.source.contains("this.$computed(() => city)"));
+	assert_eq!(chunk.source.matches(".address.city").count(), 1);
+}
+
+#[test]
+fn test_flow_control_match() {
+	let chunk = compile(
+		r#"<p><#match {status}><#case {'loading'}>Loading<#case {'error', 'failed'}>Error<#default>Ready</match></p>"#,
+	)
+	.unwrap();
+
+	assert_eq!(chunk.source.matches(r#"this["status"]"#).count(), 1);
+	assert!(chunk
+		.source
+		.contains("\t\t\tcase 'error':\n\t\t\tcase 'failed':\n\t\t\t\treturn 1;\n"));
+	assert!(chunk.source.contains("\t\t\tdefault:\n\t\t\t\treturn 2;\n"));
+	assert!(!chunk.source.contains("return -1;"));
+	// The cases are only rendered when selected.
+	assert!(chunk.source.contains(
+		"}), [\n\t\trender_fragment_1.bind(this, $self),\n\t\trender_fragment_2.bind(this, $self),\n\t\trender_fragment_3.bind(this, $self),\n\t]);"
+	));
+	assert!(!chunk.source.contains(".call(this, $self)"));
+}
+
+#[test]
+fn test_flow_control_slot() {
+	let chunk = compile(
+		r#"<div><#when {a}><slot /></when><#match {b}><#case {1}><slot name="x" /></match></div>"#,
+	)
+	.unwrap();
+
+	// Slots are read from `$self`, which is passed to the fragments of the branches.
+	assert!(chunk
+		.source
+		.contains("bind_when(render_fragment_1.bind(this, $self), "));
+	assert!(chunk
+		.source
+		.contains("\trender_fragment_2.bind(this, $self),\n"));
+	assert!(chunk
+		.source
+		.contains("$self.slots.x && $self.slots.x($self)"));
+}
+
+#[test]
+fn test_flow_control_nested_scope() {
+	let source = r#"
+		using component foo from "nowhere"
+		<div>
+			<#let {x = a.b}><#when {ok}>{x}<foo>{x}</foo></when></let>
+			<#each {item, i in items}><#try>{item}<#catch {e}>{e}{i}</try></each>
+		</div>
+	"#;
+
+	// The local variables of a fragment are passed to the fragments nested in it.
+	let chunk = compile(source).unwrap();
+
+	assert!(chunk
+		.source
+		.contains("bind_when(render_fragment_2.bind(this, $self, x), "));
+	assert!(chunk
+		.source
+		.contains("main: ($self, ...$args) => render_fragment_3.call(this, $self, x, ...$args)"));
+	assert!(chunk
+		.source
+		.contains("function render_fragment_6($self, item, i, e) {"));
+	assert!(chunk.source.contains(
+		"bind_try(render_fragment_5.bind(this, $self, item, i), render_fragment_6.bind(this, $self, item, i));"
+	));
+	assert!(!chunk.source.contains(r#"this["x"]"#));
+
+	let chunk = compile_hydration(source).unwrap();
+
+	assert!(chunk
+		.source
+		.contains("function hydrate_fragment_2($self, x_1, $parent, $index) {"));
+	assert!(chunk.source.contains(
+		"render_fragment_10.bind(this, $self, x_1), hydrate_fragment_2.bind(this, $self, x_1)]"
+	));
+	assert!(!chunk.source.contains(r#"this["x"]"#));
+
+	let chunk = compile_server(source).unwrap();
+
+	assert!(chunk
+		.source
+		.contains("$html += render_fragment_2.call(this, $self, x);"));
+	assert!(chunk
+		.source
+		.contains("$html += render_fragment_6.call(this, $self, item, i, e);"));
+	assert!(!chunk.source.contains(r#"this["x"]"#));
+}
+
+#[test]
+fn test_flow_control_await() {
+	let chunk = compile(
+		r#"<p><#await {user}>Loading<#then {{name}}>{name}<#catch {error}>{error.message}</await></p>"#,
+	)
+	.unwrap();
+
+	assert!(chunk
+		.source
+		.contains("function render_fragment_2($self, {name}) {"));
+	assert!(chunk
+		.source
+		.contains("function render_fragment_3($self, error) {"));
+	assert!(chunk.source.contains(
+		"render_fragment_1.bind(this, $self), render_fragment_2.bind(this, $self), render_fragment_3.bind(this, $self));"
+	));
+
+	let chunk = compile(r#"<p><#await {user}>Loading<#catch>Failed</await></p>"#).unwrap();
+
+	assert!(chunk.source.contains(
+		"render_fragment_1.bind(this, $self), undefined, render_fragment_2.bind(this, $self));"
+	));
+}
+
+#[test]
+fn test_flow_control_try() {
+	let chunk = compile(r#"<p><#try><b>{a.b}</b><#catch {{message}}>{message}</try></p>"#).unwrap();
+
+	assert!(chunk
+		.source
+		.contains("function render_fragment_2($self, {message}) {"));
+	assert!(chunk.source.contains(
+		"bind_try(render_fragment_1.bind(this, $self), render_fragment_2.bind(this, $self));"
+	));
+}
+
+#[test]
+fn test_server_escapes_static_html() {
+	let chunk = compile_server(r#"<p title="a&b">Tom & Jerry</p>"#).unwrap();
+
+	assert!(chunk
+		.source
+		.contains(r#"$html += "<p title=\"a&amp;b\">Tom &amp; Jerry</p>";"#));
+}
+
+#[test]
+fn test_server_flow_control() {
+	let chunk = compile_server(
+		r#"
+		<ul>
+			<#when {a}>
+				<li>a</li>
+			<#else when {b}>
+				<#each {item in items}>
+					<li>{item}</li>
+				</each>
+			<#else>
+				<li>none</li>
+			</when>
+		</ul>
+		"#,
+	)
+	.unwrap();
+
+	assert!(chunk.source.contains("} else if ("));
+	assert!(chunk.source.contains("for (const item of "));
+	assert!(chunk.source.contains(r#"$html += "<ul> <!--[-->";"#));
+}
+
+#[test]
+fn test_server_each_index() {
+	let chunk =
+		compile_server(r#"<ul><#each {item, i in items}><li>{i}</li></each></ul>"#).unwrap();
+
+	assert!(chunk.source.contains("for (const [i, item] of Array.from("));
+	assert!(chunk.source.contains(".call(this, $self, item, i);"));
+}
+
+#[test]
+fn test_server_each_destructuring() {
+	let chunk = compile_server(
+		r#"<ul><#each {{name, tags: [tag]} in items}><li>{name}{tag}</li></each></ul>"#,
+	)
+	.unwrap();
+
+	assert!(chunk.source.contains("for (const {name, tags: [tag]} of "));
+	assert!(chunk.source.contains(".call(this, $self, name, tag);"));
+}
+
+#[test]
+fn test_server_each_else() {
+	let chunk = compile_server(
+		r#"<ul><#each {item in items}><li>{item}</li><#else><li>No items</li></each></ul>"#,
+	)
+	.unwrap();
+
+	assert!(chunk.source.contains("let empty_1 = true;\n"));
+	assert!(chunk.source.contains("\tempty_1 = false;\n"));
+	assert!(chunk
+		.source
+		.contains("if (empty_1) {\n\t\t$html += render_fragment_2.call(this, $self);"));
+}
+
+#[test]
+fn test_server_let() {
+	let chunk = compile_server(r#"<p><#let {city = user.city}>{city}</let></p>"#).unwrap();
+
+	assert!(chunk
+		.source
+		.contains("function render_fragment_1($self, city) {"));
+	assert!(chunk
+		.source
+		.contains("$html += render_fragment_1.call(this, $self, "));
+}
+
+#[test]
+fn test_server_match() {
+	let chunk =
+		compile_server(r#"<p><#match {status}><#case {'a'}>A<#default>B</match></p>"#).unwrap();
+
+	assert!(chunk.source.contains(
+		"\tcase 'a':\n\t\t\t$html += render_fragment_1.call(this, $self);\n\t\t\tbreak;\n"
+	));
+	assert!(chunk
+		.source
+		.contains("\tdefault:\n\t\t\t$html += render_fragment_2.call(this, $self);\n"));
+}
+
+#[test]
+fn test_server_await() {
+	let chunk =
+		compile_server(r#"<p><#await {user}>Loading<#then {{name}}>{name}</await></p>"#).unwrap();
+
+	assert!(chunk
+		.source
+		.contains("if (typeof awaited_1?.then === \"function\") {\n"));
+	assert!(chunk.source.contains(
+		"\t\tconst {name} = awaited_1;\n\t\t$html += render_fragment_2.call(this, $self, name);\n"
+	));
+}
+
+#[test]
+fn test_server_try() {
+	let chunk = compile_server(r#"<p><#try>{a.b}<#catch {error}>{error}</try></p>"#).unwrap();
+
+	assert!(chunk.source.contains(
+		"try {\n\t\t$html += render_fragment_1.call(this, $self);\n\t} catch (error) {\n"
+	));
+	assert!(chunk.source.contains(
+		"\t\t$html += \"<!--!-->\";\n\t\t$html += render_fragment_2.call(this, $self, error);\n"
+	));
+
+	let chunk = compile_server(r#"<p><#try>{a.b}<#catch>Failed</try></p>"#).unwrap();
+	assert!(chunk.source.contains("\t} catch {\n"));
+}
+
+#[test]
+fn test_comments_are_not_block_markers() {
+	let source = r#"<p><!--[--><!--]--><!--!--><!-- [ --></p>"#;
+
+	// Comments reading as markers are padded, so they are not claimed as blocks when hydrating.
+	let chunk = compile_server(source).unwrap();
+	assert!(chunk
+		.source
+		.contains(r#"$html += "<p><!-- [ --><!-- ] --><!-- ! --><!-- [ --></p>";"#));
+
+	let chunk = compile(source).unwrap();
+	assert!(chunk.source.contains(r#"comment(" [ ");"#));
+	assert!(chunk.source.contains(r#"comment(" ] ");"#));
+	assert!(chunk.source.contains(r#"comment(" ! ");"#));
+}
+
+#[test]
+fn test_server_use_nested_components() {
+	let chunk = compile_server(
+		r#"
+		using component foo, { component bar } from "nowhere"
+
+		<foo>
+			<bar>baz</bar>
+		</foo>
+		"#,
+	)
+	.unwrap();
+
+	assert!(chunk.source.contains("extends ServerComponent"));
+	assert!(chunk.source.contains("}).toString();"));
+}
+
+#[test]
+fn test_server_dynamic_component() {
+	let chunk = compile_server(r#"<div><component {is} /></div>"#).unwrap();
+
+	assert!(chunk.source.contains("$html += \"<div><!--[-->\";\n"));
+	assert!(chunk
+		.source
+		.contains("\tif (constructor_1) {\n\t\t$html += new constructor_1({\n"));
+	assert!(chunk.source.contains("\t\t}).toString();\n\t}\n"));
+}
+
+#[test]
+fn test_server_illegal_void_children() {
+	assert!(compile_server(r#"<br>foo</br>"#).is_err());
+}
+
+#[test]
+fn test_hydration_splits_text() {
+	let chunk = compile_hydration(r#"<p>Hello {name}!</p>"#).unwrap();
+
+	assert!(chunk.source.contains("static render = render_default;"));
+	assert!(chunk.source.contains("static hydrate = hydrate_default;"));
+	assert!(chunk.source.contains("let text_4 = claim_text(p_2, 0);"));
+	assert!(chunk.source.contains("let text_5 = split_text(text_4, 6);"));
+	assert!(chunk
+		.source
+		.contains("let text_6 = split_text(text_5, text_length(accessor_1.get()));"));
+	assert!(chunk.source.contains("bind_text(text_5, accessor_1);"));
+}
+
+#[test]
+fn test_hydration_flow_control() {
+	let chunk = compile_hydration(
+		r#"
+		<ul>
+			<#when {a}>
+				<li>a</li>
+			<#else>
+				<li>none</li>
+			</when>
+			<#each {item in items}>
+				<li>{item}</li>
+			</each>
+			<li>last</li>
+		</ul>
+		"#,
+	)
+	.unwrap();
+
+	assert!(chunk.source.contains("claim_when(ul_2, 1, ["));
+	assert!(chunk.source.contains("[null, "));
+	assert!(chunk.source.contains("claim_each(ul_2, $index_1 + 1, "));
+	assert!(chunk.source.contains("claim(ul_2, $index_2 + 1);"));
+}
+
+#[test]
+fn test_hydration_each_keyed() {
+	let chunk =
+		compile_hydration(r#"<ul><#each {item, i in items; key item.id}><li>{i}</li></each></ul>"#)
+			.unwrap();
+
+	assert!(chunk
+		.source
+		.contains("hydrate_fragment_1($self, $parent, $index, item_1, i_1)"));
+	assert!(chunk.source.contains(", (item_1, i_1) => item_1.id);"));
+}
+
+#[test]
+fn test_hydration_each_else() {
+	let chunk = compile_hydration(
+		r#"<ul><#each {item in items}><li>{item}</li><#else><li>No items</li></each></ul>"#,
+	)
+	.unwrap();
+
+	assert!(chunk.source.contains(
+		", undefined, [render_fragment_4.bind(this, $self), hydrate_fragment_2.bind(this, $self)]);"
+	));
+}
+
+#[test]
+fn test_hydration_let() {
+	let chunk = compile_hydration(r#"<p><#let {city = user.city}>{city}</let></p>"#).unwrap();
+
+	assert!(chunk
+		.source
+		.contains("function hydrate_fragment_1($self, $parent, $index, city_1) {"));
+	assert!(chunk
+		.source
+		.contains("claim_let(p_2, 0, this.$computed(() => "));
+}
+
+#[test]
+fn test_hydration_match() {
+	let chunk = compile_hydration(r#"<p><#match {status}><#case {'a'}>A</match></p>"#).unwrap();
+
+	assert!(chunk
+		.source
+		.contains("claim_match(p_2, 0, this.$computed(() => {"));
+	assert!(chunk.source.contains("\n\t\treturn -1;\n\t}), [\n"));
+	assert!(chunk
+		.source
+		.contains("[render_fragment_2.bind(this, $self), hydrate_fragment_1.bind(this, $self)],"));
+}
+
+#[test]
+fn test_hydration_await() {
+	let chunk =
+		compile_hydration(r#"<p><#await {user}>Loading<#then {user}>{user.name}</await></p>"#)
+			.unwrap();
+
+	assert!(chunk
+		.source
+		.contains("claim_await(p_2, 0, this.$computed(() => "));
+	assert!(chunk
+		.source
+		.contains("function hydrate_fragment_2($self, $parent, $index, user_1) {"));
+	assert!(chunk.source.contains(
+		"[render_fragment_3.bind(this, $self), hydrate_fragment_1.bind(this, $self)], [render_fragment_4.bind(this, $self), hydrate_fragment_2.bind(this, $self)]);"
+	));
+}
+
+#[test]
+fn test_hydration_try() {
+	let chunk = compile_hydration(r#"<p><#try>{a.b}<#catch>Failed</try></p>"#).unwrap();
+
+	assert!(chunk.source.contains(
+		"claim_try(p_2, 0, [render_fragment_3.bind(this, $self), hydrate_fragment_1.bind(this, $self)], render_fragment_4.bind(this, $self));"
+	));
+}
+
+#[test]
+fn test_hydration_use_nested_components() {
+	let chunk = compile_hydration(
+		r#"
+		using component foo, { component bar } from "nowhere"
+
+		<foo>
+			<bar>baz</bar>
+		</foo>
+		"#,
+	)
+	.unwrap();
+
+	assert!(chunk.source.contains("main: hydratable(render_fragment_"));
+	assert!(chunk.source.contains("hydrate: [$parent, $index],"));
+}
+
+#[test]
+fn test_hydration_dynamic_component() {
+	let chunk = compile_hydration(r#"<div><component is={view} /><p>{a}</p></div>"#).unwrap();
+
+	assert!(chunk
+		.source
+		.contains("let [component_2, $index_1] = claim_component(div_2, 0, this.$computed(() => "));
+	assert!(chunk
+		.source
+		.contains("(constructor_2, hydrate_1) => new constructor_2({\n"));
+	assert!(chunk.source.contains("\t\thydrate: hydrate_1,\n"));
+	assert!(chunk.source.contains("claim(div_2, $index_1)"));
+}
+
+#[test]
+fn test_template_substitutions() {
+	let chunk = compile("<p>{`Hello ${name}!`}{tag`a${b}`}</p>").unwrap();
+
+	assert!(chunk
+		.source
+		.contains(r#"`Hello ${("name" in this ? this["name"] : name)}!`"#));
+	assert!(chunk
+		.source
+		.contains(r#"("tag" in this ? this["tag"] : tag)`a${("b" in this ? this["b"] : b)}`"#));
+}
+
+#[test]
+fn test_operators() {
+	let chunk = compile("<p>{this.a ?? b, c?.()}</p>").unwrap();
+
+	assert!(chunk.source.contains(
+		r#"(this.a ?? ("b" in this ? this["b"] : b), ("c" in this ? this["c"] : c)?.())"#
+	));
+}
+
+#[test]
+fn test_literals() {
+	let chunk = compile("<p title='a\\nb'>{/\\d+/.test(x) ? 10n : 0x1F}</p>").unwrap();
+
+	assert!(chunk.source.contains(r#"attr(p_1, "title", 'a\nb');"#));
+	assert!(chunk
+		.source
+		.contains(r#"/\d+/.test(("x" in this ? this["x"] : x)) ? 10n : 0x1F"#));
+}
+
+#[test]
+fn test_functions() {
+	let chunk = compile(
+		"<button on:click={async (e, {id, ...rest} = x) => { const y = await save(e, id); if (y) { var z = rest; } close(z); }}></button>",
+	)
+	.unwrap();
+
+	// Parameters and declarations are local, the other names are looked up on the component.
+	assert!(chunk.source.contains(
+		r#"async (e, {id, ...rest} = ("x" in this ? this["x"] : x)) => { const y = await ("save" in this ? this["save"] : save)(e, id); if (y) { var z = rest; } ("close" in this ? this["close"] : close)(z); }"#
+	));
+
+	let chunk =
+		compile("<p>{([a, , b = c]) => { try { a(b); } catch (c) { return c; } }}</p>").unwrap();
+
+	assert!(chunk.source.contains(
+		r#"([a, , b = ("c" in this ? this["c"] : c)]) => { try { a(b); } catch (c) { return c; } }"#
+	));
+}
+
+#[test]
+fn test_typescript() {
+	let input = "<p on:click={<T,>(e?: T, n: number = 1): void => { let x: T = e!; f(x as unknown as U, n satisfies number); }}>{a!.b}</p>";
+	let chunk = build_typescript(input.to_owned(), Target::Client, Encoding::Char).unwrap();
+
+	// Types are erased, and typed parameters are still local.
+	assert!(chunk
+		.source
+		.contains(r#"(e, n = 1) => { let x = e; ("f" in this ? this["f"] : f)(x, n); }"#));
+	assert!(chunk.source.contains(r#"("a" in this ? this["a"] : a).b"#));
+
+	// Without TypeScript, types are syntax errors.
+	assert!(matches!(compile(input), Err(Error::ParserError(_))));
+}
+
+#[test]
+fn test_error_location() {
+	match compile("<div\n\tbind:foo={x}></div>") {
+		Err(Error::CompilerError(err)) => {
+			assert_eq!(err.location.start, Position::new(2, 2));
+		}
+		_ => panic!("expected compiler error"),
+	}
+}
+
+#[test]
+fn test_utf16_mappings() {
+	let input = "<p>Café 👋 {name}</p>";
+	let chunk = build_with_encoding(input.to_owned(), Target::Client, Encoding::Utf16).unwrap();
+
+	let input: Vec<u16> = input.encode_utf16().collect();
+	let output: Vec<u16> = chunk.source.encode_utf16().collect();
+	let name: Vec<u16> = "name".encode_utf16().collect();
+	let expected: Vec<u16> = "(\"name\" in this".encode_utf16().collect();
+
+	let start = input.windows(4).position(|window| window == name).unwrap();
+	let (_, to) = chunk
+		.mappings
+		.iter()
+		.find(|(from, _)| *from == start)
+		.unwrap();
+
+	assert_eq!(&output[*to..*to + expected.len()], &expected[..]);
+}
+
+#[test]
+fn test_utf16_error_location() {
+	let input = "<p>👋 {a +}</p>";
+
+	match build_with_encoding(input.to_owned(), Target::Client, Encoding::Utf16) {
+		Err(Error::ParserError(err)) => {
+			assert_eq!(err.position, 10);
+			assert_eq!(err.location.start, Position::new(1, 11));
+		}
+		_ => panic!("expected parser error"),
+	}
+}
+
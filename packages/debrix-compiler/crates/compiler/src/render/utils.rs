@@ -1,4 +1,5 @@
 use super::*;
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 #[rustfmt::skip]
@@ -15,6 +16,21 @@ pub const RESERVED_JAVASCRIPT_KEYWORDS: [&str; 64] = [
 pub(crate) const INTERNAL_MODULE: &str = "@debrix/internal";
 pub(crate) const DEFAULT_SLOT_NAME: &str = "main";
 
+#[rustfmt::skip]
+pub(crate) const VOID_ELEMENTS: [&str; 14] = [
+	"area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+	"track", "wbr"
+];
+
+/// Comments marking the boundaries of dynamic blocks in server rendered html, so the blocks
+/// can be claimed when hydrating.
+pub(crate) const BLOCK_START: &str = "<!--[-->";
+pub(crate) const BLOCK_END: &str = "<!--]-->";
+
+/// Comment at the start of a try block whose children threw on the server, so the client
+/// renders the block again instead of claiming the catch branch.
+pub(crate) const BLOCK_CAUGHT: &str = "<!--!-->";
+
 pub struct Unique {
 	map: HashMap<String, usize>,
 }
@@ -68,6 +84,24 @@ impl Unique {
 		self.set(name, index);
 		Unique::join(name, index)
 	}
+
+	/// Names the variables bound by the parameters of a fragment. Returns each name with its
+	/// alias, which differs from the name when it is already taken.
+	pub fn params<'a>(
+		&mut self,
+		params: &[&'a ast::javascript::Expression],
+	) -> Vec<(&'a str, String)> {
+		let mut names = Vec::new();
+
+		for param in params {
+			bound_names(param, &mut names);
+		}
+
+		names
+			.into_iter()
+			.map(|name| (name, self.ensure(name)))
+			.collect()
+	}
 }
 
 pub fn find_static_attr(name: &str) -> impl Fn(&&ast::Attribute) -> bool {
@@ -80,8 +114,61 @@ pub fn find_static_attr(name: &str) -> impl Fn(&&ast::Attribute) -> bool {
 	}
 }
 
+/// Escapes characters with special meaning in html text and attribute values.
+pub fn escape_html(value: &str) -> String {
+	let mut escaped = String::new();
+
+	for char in value.chars() {
+		match char {
+			'&' => escaped.push_str("&amp;"),
+			'<' => escaped.push_str("&lt;"),
+			'>' => escaped.push_str("&gt;"),
+			'"' => escaped.push_str("&quot;"),
+			'\'' => escaped.push_str("&#39;"),
+			_ => escaped.push(char),
+		}
+	}
+
+	escaped
+}
+
+/// Returns the data of a comment in the template. Comments which would read as a marker of a
+/// block, like `<!--[-->`, are padded with spaces, so they are not claimed as one when hydrating.
+pub fn comment_data(comment: &str) -> Cow<'_, str> {
+	let html = format!("<!--{}-->", comment);
+
+	if [BLOCK_START, BLOCK_END, BLOCK_CAUGHT].contains(&html.as_str()) {
+		Cow::Owned(format!(" {} ", comment))
+	} else {
+		Cow::Borrowed(comment)
+	}
+}
+
 pub fn in_string(value: &str) -> String {
-	"\"".to_owned() + &value.replace('"', "\\\"") + "\""
+	in_quotes(value, '"')
+}
+
+/// Writes the value as a JavaScript string in the quotes, escaping what cannot be written as is.
+pub fn in_quotes(value: &str, quote: char) -> String {
+	let mut out = String::from(quote);
+
+	for char in value.chars() {
+		match char {
+			'\\' => out.push_str("\\\\"),
+			'\n' => out.push_str("\\n"),
+			'\r' => out.push_str("\\r"),
+			'\u{2028}' => out.push_str("\\u2028"),
+			'\u{2029}' => out.push_str("\\u2029"),
+			char if char == quote => {
+				out.push('\\');
+				out.push(char);
+			}
+			char => out.push(char),
+		}
+	}
+
+	out.push(quote);
+	out
 }
 
 fn is_ident_char(char: &char) -> bool {
@@ -174,7 +261,301 @@ pub fn title_case(s: &str) -> String {
 }
 
 pub fn serialize_string_literal(lit: &ast::StringLiteral) -> String {
-	lit.quote.to_string() + &lit.value + &lit.quote.to_string()
+	in_quotes(&lit.value, lit.quote)
+}
+
+/// Returns the tag name of the element without the `html:` namespace prefix.
+pub fn tag_name<'a>(node: &'a ast::Element) -> &'a str {
+	if node.tag_name.name.starts_with("html:") {
+		&node.tag_name.name[5..]
+	} else {
+		&node.tag_name.name
+	}
+}
+
+/// Ensures that attributes are expanded at most once on the element.
+pub fn check_spread_attributes(attributes: &[ast::Attribute]) -> Result<(), Error> {
+	let mut first_spread_attribute: Option<ast::Range> = None;
+	for attr in attributes {
+		match attr {
+			ast::Attribute::Spread(attr) => {
+				if let Some(first) = first_spread_attribute {
+					return Err(Error::compiler(
+						attr.start,
+						attr.end,
+						&format!(
+							"Attributes can only be expanded once. Occured at {:?}.",
+							first
+						),
+					));
+				} else {
+					first_spread_attribute = Some(attr.range());
+				}
+			}
+			_ => continue,
+		}
+	}
+
+	Ok(())
+}
+
+/// Groups the children of a component element by the slot they are passed to.
+pub fn collect_slots(children: Vec<ast::Node>) -> Result<Map<String, Vec<ast::Node>>, Error> {
+	let mut slots: Map<String, Vec<ast::Node>> = Map::new();
+
+	for node in children {
+		let mut slot_name = None;
+		if let ast::Node::Element(node) = &node {
+			for attr in node.attributes.iter() {
+				match attr {
+					ast::Attribute::Static(attr) => {
+						if attr.name.name == "slot" {
+							if slot_name.is_some() {
+								return Err(Error::compiler(
+									attr.start,
+									attr.end,
+									"Special attribute cannot be defined twice.",
+								));
+							}
+
+							if let Some(literal) = &attr.value {
+								slot_name = Some(literal.value.to_string());
+							} else {
+								return Err(Error::compiler(
+									attr.start,
+									attr.end,
+									"Special attribute must have value.",
+								));
+							}
+						}
+					}
+					ast::Attribute::Binding(attr) => {
+						if attr.name.name == "slot" {
+							return Err(Error::compiler(
+								attr.start,
+								attr.end,
+								"Special attribute must be static.",
+							));
+						}
+					}
+					ast::Attribute::ShortBinding(attr) => {
+						if attr.name.name == "slot" {
+							return Err(Error::compiler(
+								attr.start,
+								attr.end,
+								"Special attribute must be static.",
+							));
+						}
+					}
+					ast::Attribute::Spread(_) => continue,
+				}
+			}
+		}
+
+		let slot_name = slot_name.unwrap_or(DEFAULT_SLOT_NAME.to_owned());
+		if let Some(vec) = slots.get_mut(&slot_name) {
+			vec.push(node);
+		} else {
+			slots.set(slot_name, vec![node]);
+		}
+	}
+
+	Ok(slots)
+}
+
+/// Resolves the name of the slot rendered by a `<slot>` element.
+pub fn slot_name(node: &ast::Element) -> Result<String, Error> {
+	if let Some(attr) = node.attributes.iter().find(find_static_attr("name")) {
+		match attr {
+			ast::Attribute::Static(attr) => {
+				if let Some(literal) = &attr.value {
+					Ok(literal.value.to_string())
+				} else {
+					Err(Error::compiler(
+						attr.start,
+						attr.end,
+						"Attribute must have value.",
+					))
+				}
+			}
+			_ => Err(Error::compiler(
+				attr.start(),
+				attr.end(),
+				"Attribute must be static.",
+			)),
+		}
+	} else {
+		Ok(DEFAULT_SLOT_NAME.to_owned())
+	}
+}
+
+/// Takes the `is` binding of a `<component>` element, evaluating to its constructor.
+pub fn take_component_constructor<'src>(
+	node: &mut ast::Element<'src>,
+) -> Result<ast::javascript::Expression<'src>, Error> {
+	let position = node
+		.attributes
+		.iter()
+		.position(|attribute| match attribute {
+			ast::Attribute::Static(attribute) => attribute.name.name == "is",
+			ast::Attribute::Binding(attribute) => attribute.name.name == "is",
+			ast::Attribute::ShortBinding(attribute) => attribute.name.name == "is",
+			ast::Attribute::Spread(_) => false,
+		});
+
+	match position.map(|position| node.attributes.remove(position)) {
+		Some(ast::Attribute::Binding(attribute)) => Ok(attribute.value),
+		Some(ast::Attribute::ShortBinding(attribute)) => Ok(attribute.name.into()),
+		Some(attribute) => Err(Error::compiler(
+			attribute.start(),
+			attribute.end(),
+			"Attribute must be a binding.",
+		)),
+		None => Err(Error::compiler(
+			node.start,
+			node.end,
+			"Dynamic components must have an 'is' binding.",
+		)),
+	}
+}
+
+/// Declares the parameters of a fragment function, with their aliases from [`Unique::params`].
+pub fn declare_params(
+	js: &mut JavascriptSerializer,
+	c_attr: &mut Chunk,
+	params: &[&ast::javascript::Expression],
+	aliases: &[(&str, String)],
+) -> Vec<Chunk> {
+	for (name, alias) in aliases {
+		js.declare(name, alias);
+	}
+
+	params
+		.iter()
+		.map(|param| {
+			let param = js.serialize_param(param);
+
+			if !c_attr.is_empty() {
+				c_attr.write(", ");
+			}
+
+			c_attr.append(&param);
+			param
+		})
+		.collect()
+}
+
+/// Passes the local variables of a fragment to a nested fragment, as parameters after `$self`.
+/// Returns the arguments of the nested fragment, starting with `$self`.
+pub fn inherit_locals(
+	parent: &JavascriptSerializer,
+	js: &mut JavascriptSerializer,
+	c_attr: &mut Chunk,
+) -> String {
+	let mut args = vec!["$self".to_owned()];
+
+	for name in &parent.local_vars {
+		// Shadowed variables have the alias of the innermost one.
+		let alias = parent.aliases.get(name).unwrap_or(name);
+
+		if args.contains(alias) {
+			continue;
+		}
+
+		js.declare(name, alias);
+
+		if !c_attr.is_empty() {
+			c_attr.write(", ");
+		}
+
+		c_attr.write(alias);
+		args.push(alias.clone());
+	}
+
+	args.join(", ")
+}
+
+/// Renders the function of a slot rendered by a fragment, which is called with the `$self` of
+/// the component and is passed the `args` of the fragment.
+pub fn slot_function(fragment_name: &str, args: &str) -> String {
+	if args == "$self" {
+		format!("{}.bind(this)", fragment_name)
+	} else {
+		format!(
+			"($self, ...$args) => {}.call(this, {}, ...$args)",
+			fragment_name, args
+		)
+	}
+}
+
+/// Renders the creation of a component instance, with the slots rendered by `render_slot`.
+pub fn render_component<'src>(
+	doc: &mut Document,
+	constructor: &str,
+	children: Vec<ast::Node<'src>>,
+	c_attrs: Chunk,
+	hydrate: Option<&str>,
+	mut render_slot: impl FnMut(&mut Document, Vec<ast::Node<'src>>) -> Result<Chunk, Error>,
+) -> Result<Chunk, Error> {
+	let slots = collect_slots(children)?;
+	let mut c_new = Chunk::new();
+
+	c_new
+		.write("new ")
+		.write(constructor)
+		.write("({\n")
+		.write("\t__family: ")
+		.write(&in_string("__family"))
+		.write(" in ")
+		.write(constructor)
+		.write(" && $self[")
+		.write(constructor)
+		.write(".__family")
+		.write("],\n");
+
+	if !slots.is_empty() {
+		c_new.write("\tslots: {\n");
+
+		let mut slots = slots.into_entries().peekable();
+		while let Some((name, nodes)) = slots.next() {
+			c_new
+				.write("\t\t")
+				.write(&to_valid_property(&name))
+				.write(": ")
+				.append(&render_slot(doc, nodes)?);
+
+			if slots.peek().is_some() {
+				c_new.write(",\n");
+			} else {
+				c_new.write("\n");
+			}
+		}
+
+		c_new.write("\t},\n");
+	}
+
+	if !c_attrs.is_empty() {
+		c_new
+			.write("\tattrs: {\n")
+			.append(&format_chunk(c_attrs, 2))
+			.write("\n\t},\n");
+	}
+
+	if let Some(hydrate) = hydrate {
+		c_new.write("\thydrate: ").write(hydrate).write(",\n");
+	}
+
+	c_new.write("})");
+	Ok(c_new)
+}
+
+/// Renders the accessor of a slot on `$self.slots`, e.g. `$self.slots.main`.
+pub fn slot_accessor(name: &str) -> String {
+	if is_valid_identifier(name) {
+		"$self.slots.".to_owned() + name
+	} else {
+		"$self.slots[".to_owned() + &in_string(name) + "]"
+	}
 }
 
 pub fn join_spaces(value: &str) -> String {
@@ -221,11 +602,11 @@ impl<K, V> Map<K, V> {
 		self.values.push(value);
 	}
 
-	pub fn keys(&mut self) -> std::slice::Iter<K> {
+	pub fn keys(&mut self) -> std::slice::Iter<'_, K> {
 		self.keys.iter()
 	}
 
-	pub fn values(&mut self) -> std::slice::Iter<V> {
+	pub fn values(&mut self) -> std::slice::Iter<'_, V> {
 		self.values.iter()
 	}
 
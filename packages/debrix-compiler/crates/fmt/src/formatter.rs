@@ -0,0 +1,921 @@
+use crate::*;
+use debrix_parser::ast;
+
+/// Elements laid out like text. Other elements are put on their own line when possible.
+const INLINE_ELEMENTS: &[&str] = &[
+	"a", "abbr", "b", "bdi", "bdo", "br", "button", "cite", "code", "data", "dfn", "em", "i",
+	"img", "input", "kbd", "label", "mark", "q", "s", "samp", "select", "small", "span", "strong",
+	"sub", "sup", "time", "u", "var", "wbr",
+];
+
+/// Elements whose content is whitespace sensitive, printed as in the input on their own line.
+const VERBATIM_ELEMENTS: &[&str] = &["pre", "textarea"];
+
+/// Whitespace before an item in the content of an element or flow control.
+#[derive(Clone, Copy)]
+enum Sep {
+	/// No whitespace, the items must be printed together.
+	None,
+	/// Whitespace, printed as a space or a line break. Holds the number of line breaks in the
+	/// input, to keep blank lines.
+	Space(usize),
+	/// Whitespace skipped by the parser, printed as nothing or a line break.
+	Free(usize),
+}
+
+impl Sep {
+	fn newlines(&self) -> usize {
+		match self {
+			Sep::None => 0,
+			Sep::Space(newlines) | Sep::Free(newlines) => *newlines,
+		}
+	}
+
+	fn is_none(&self) -> bool {
+		matches!(self, Sep::None)
+	}
+
+	fn space(self, newlines: usize) -> Sep {
+		Sep::Space(self.newlines() + newlines)
+	}
+}
+
+struct Item {
+	sep: Sep,
+	text: String,
+	/// Whether the item is put on its own line, unless there is no whitespace around it.
+	block: bool,
+}
+
+struct Content {
+	items: Vec<Item>,
+	trailing: Sep,
+}
+
+impl Content {
+	fn leading(&self) -> Sep {
+		self.items.first().map_or(self.trailing, |item| item.sep)
+	}
+
+	/// Whether the content started or ended on a new line in the input.
+	fn is_broken(&self) -> bool {
+		self.leading().newlines() > 0 || self.trailing.newlines() > 0
+	}
+
+	fn flat(&self) -> Option<String> {
+		let mut out = String::new();
+
+		for item in &self.items {
+			if item.block || item.text.contains('\n') {
+				return None;
+			}
+
+			if let Sep::Space(_) = item.sep {
+				out.push(' ');
+			}
+
+			out.push_str(&item.text);
+		}
+
+		if let Sep::Space(_) = self.trailing {
+			out.push(' ');
+		}
+
+		Some(out)
+	}
+}
+
+pub struct Formatter<'a> {
+	chars: Vec<char>,
+	comments: Vec<&'a cst::SyntaxToken>,
+	indent: usize,
+}
+
+impl<'a> Formatter<'a> {
+	pub fn new(input: &str, syntax: &'a cst::SyntaxNode) -> Self {
+		Self {
+			chars: input.chars().collect(),
+			comments: syntax
+				.tokens()
+				.into_iter()
+				.filter(|token| {
+					matches!(
+						token.kind,
+						cst::TokenKind::LineComment | cst::TokenKind::BlockComment
+					)
+				})
+				.collect(),
+			indent: 0,
+		}
+	}
+
+	/// Returns the comments starting between `start` and `end`.
+	fn comments(&self, start: usize, end: usize) -> Vec<&'a cst::SyntaxToken> {
+		let from = self.comments.partition_point(|token| token.start < start);
+		let to = self.comments.partition_point(|token| token.start < end);
+		self.comments[from..to.max(from)].to_vec()
+	}
+
+	/// Prints the comments between `start` and `end`, before a token.
+	pub(crate) fn leading(&self, start: usize, end: usize) -> String {
+		let mut out = String::new();
+
+		for comment in self.comments(start, end) {
+			out.push_str(&comment.text);
+
+			if comment.kind == cst::TokenKind::LineComment {
+				newline(&mut out, self.indent + 1, false);
+			} else {
+				out.push(' ');
+			}
+		}
+
+		out
+	}
+
+	/// Prints the comments between `start` and `end`, before a closing token.
+	pub(crate) fn trailing(&self, start: usize, end: usize) -> String {
+		let mut out = String::new();
+
+		for comment in self.comments(start, end) {
+			out.push(' ');
+			out.push_str(&comment.text);
+
+			if comment.kind == cst::TokenKind::LineComment {
+				newline(&mut out, self.indent, false);
+			}
+		}
+
+		out
+	}
+
+	fn slice(&self, start: usize, end: usize) -> String {
+		self.chars[start..end].iter().collect()
+	}
+
+	fn newlines(&self, start: usize, end: usize) -> usize {
+		self.chars[start..end.max(start)]
+			.iter()
+			.filter(|char| **char == '\n')
+			.count()
+	}
+
+	/// Returns the position after the whitespace and comments at `position`.
+	fn skip_trivia(&self, mut position: usize) -> usize {
+		loop {
+			if self
+				.chars
+				.get(position)
+				.is_some_and(|char| char.is_whitespace())
+			{
+				position += 1;
+			} else if let Some(comment) = self.comments(position, position + 1).first() {
+				position = comment.end;
+			} else {
+				return position;
+			}
+		}
+	}
+
+	/// Whether `text` fits on a line at the current indentation.
+	fn fits(&self, text: &str) -> bool {
+		self.indent * TAB_WIDTH + width(text) <= WIDTH
+	}
+
+	pub fn document(&mut self, document: &ast::Document) -> String {
+		let mut out = String::new();
+		let mut cursor = 0;
+
+		for node in &document.children {
+			for comment in self.comments(cursor, node.start()) {
+				self.line(&mut out, cursor, comment.start, &comment.text, true);
+				cursor = comment.end;
+			}
+
+			let (text, _) = self.node(node);
+			self.line(&mut out, cursor, node.start(), &text, false);
+			cursor = node.end();
+		}
+
+		for comment in self.comments(cursor, self.chars.len()) {
+			self.line(&mut out, cursor, comment.start, &comment.text, true);
+			cursor = comment.end;
+		}
+
+		out
+	}
+
+	/// Appends a line to the document, keeping a blank line before it from the input. Comments
+	/// on the same line as the previous node stay there.
+	fn line(&self, out: &mut String, previous: usize, start: usize, text: &str, comment: bool) {
+		let newlines = self.newlines(previous, start);
+
+		if !out.is_empty() && newlines == 0 && comment {
+			out.pop();
+			out.push(' ');
+		} else if !out.is_empty() && newlines > 1 {
+			out.push('\n');
+		}
+
+		out.push_str(text);
+		out.push('\n');
+	}
+
+	/// Prints a node at the current indentation. Also returns whether it is a block.
+	fn node(&mut self, node: &ast::Node) -> (String, bool) {
+		match node {
+			ast::Node::DependencyStatement(node) => (self.dependency_statement(node), true),
+			ast::Node::Comment(node) => (self.slice(node.start, node.end), true),
+			ast::Node::Element(node) => self.element(node),
+			ast::Node::Text(node) => (self.slice(node.start, node.end), false),
+			ast::Node::TextBinding(node) => {
+				let text = format!(
+					"{{{}{}{}}}",
+					self.leading(node.start, node.expression.start()),
+					self.expression(&node.expression),
+					self.trailing(node.expression.end(), node.end)
+				);
+				let block = text.contains('\n');
+				(text, block)
+			}
+			ast::Node::FlowControl(node) => (self.flow_control(node), true),
+			ast::Node::Invalid(node) => (self.slice(node.start, node.end), false),
+		}
+	}
+
+	fn dependency_statement(&self, node: &ast::DependencyStatement) -> String {
+		let mut out = String::from("using");
+		let mut cursor = node.start + "using".len();
+
+		let mut identifier = |out: &mut String, prefix: &str, identifier: &ast::Identifier| {
+			out.push_str(prefix);
+			out.push_str(&self.leading(cursor, identifier.start));
+			out.push_str(&identifier.name);
+			cursor = identifier.end;
+		};
+
+		if let Some(default) = &node.default {
+			identifier(&mut out, " ", &default.usage);
+
+			if let Some(local) = &default.local {
+				identifier(&mut out, " ", local);
+			}
+		}
+
+		if let Some(named) = &node.named {
+			if node.default.is_some() {
+				out.push(',');
+			}
+
+			out.push_str(" {");
+
+			for specifier in &named.nodes {
+				identifier(&mut out, " ", &specifier.usage);
+				identifier(&mut out, " ", &specifier.imported);
+
+				if let Some(local) = &specifier.local {
+					identifier(&mut out, " as ", local);
+				}
+			}
+
+			out.push_str(&self.trailing(cursor, node.source.start));
+			out.push_str(if named.nodes.is_empty() { "}" } else { " }" });
+			cursor = node.source.start;
+		}
+
+		out.push_str(" from ");
+		out.push_str(&self.leading(cursor, node.source.start));
+		out.push_str(&string(&node.source.value));
+		out.push_str(&self.trailing(node.source.end, node.end));
+		out
+	}
+
+	fn element(&mut self, node: &ast::Element) -> (String, bool) {
+		let inline = INLINE_ELEMENTS.contains(&&*node.tag_name.name);
+		let open = self.start_tag(node);
+
+		let text = match &node.end_tag {
+			Some(end_tag) if VERBATIM_ELEMENTS.contains(&&*node.tag_name.name) => format!(
+				"{}{}</{}>",
+				open,
+				self.slice(node.start_tag.end, end_tag.start),
+				node.tag_name.name
+			),
+			Some(end_tag) => {
+				self.indent += 1;
+				let content =
+					self.content(&node.children, node.start_tag.end, end_tag.start, false);
+				self.indent -= 1;
+
+				self.layout(vec![(open, content)], format!("</{}>", node.tag_name.name))
+			}
+			None => open,
+		};
+
+		let block = !inline || text.contains('\n');
+		(text, block)
+	}
+
+	fn start_tag(&mut self, node: &ast::Element) -> String {
+		let close = if node.end_tag.is_none() { "/>" } else { ">" };
+		let mut items = Vec::new();
+		let mut cursor = node.tag_name.end;
+		// Line comments end at the line break, so they are wrapped like multiline attributes.
+		let mut wrap = false;
+
+		self.indent += 1;
+
+		for attribute in &node.attributes {
+			for comment in self.comments(cursor, attribute.start()) {
+				wrap |= comment.kind == cst::TokenKind::LineComment;
+				items.push(comment.text.clone());
+			}
+
+			items.push(self.attribute(attribute));
+			cursor = attribute.end();
+		}
+
+		self.indent -= 1;
+
+		for comment in self.comments(cursor, node.start_tag.end) {
+			wrap |= comment.kind == cst::TokenKind::LineComment;
+			items.push(comment.text.clone());
+		}
+
+		let mut flat = format!("<{}", node.tag_name.name);
+		for item in &items {
+			flat.push(' ');
+			flat.push_str(item);
+		}
+		if node.end_tag.is_none() {
+			flat.push(' ');
+		}
+		flat.push_str(close);
+
+		if !wrap && !flat.contains('\n') && self.fits(&flat) {
+			return flat;
+		}
+
+		let mut out = format!("<{}", node.tag_name.name);
+		for item in &items {
+			newline(&mut out, self.indent + 1, false);
+			out.push_str(item);
+		}
+		newline(&mut out, self.indent, false);
+		out.push_str(close);
+		out
+	}
+
+	fn attribute(&self, attribute: &ast::Attribute) -> String {
+		match attribute {
+			ast::Attribute::Static(attribute) => match &attribute.value {
+				Some(value) => format!(
+					"{}={}{}",
+					attribute.name.name,
+					self.leading(attribute.name.end, value.start),
+					string(&value.value)
+				),
+				None => attribute.name.name.to_string(),
+			},
+			ast::Attribute::Binding(attribute) => format!(
+				"{}={{{}{}{}}}",
+				attribute.name.name,
+				self.leading(attribute.name.end, attribute.value.start()),
+				self.expression(&attribute.value),
+				self.trailing(attribute.value.end(), attribute.end)
+			),
+			ast::Attribute::Spread(attribute) => format!(
+				"{{...{}{}{}}}",
+				self.leading(attribute.start, attribute.value.start()),
+				self.expression(&attribute.value),
+				self.trailing(attribute.value.end(), attribute.end)
+			),
+			ast::Attribute::ShortBinding(attribute) => format!(
+				"{{{}{}{}}}",
+				self.leading(attribute.start, attribute.name.start),
+				attribute.name.name,
+				self.trailing(attribute.name.end, attribute.end)
+			),
+		}
+	}
+
+	fn flow_control(&mut self, node: &ast::FlowControl) -> String {
+		match node {
+			ast::FlowControl::When(node) => {
+				let close = self.skip_trivia(node.condition.end());
+				let open = format!(
+					"<#when {{{}{}{}}}>",
+					self.leading(node.start, node.condition.start()),
+					self.expression(&node.condition),
+					self.trailing(node.condition.end(), close)
+				);
+
+				// The end of the content is the next `<#else` or `</when>`.
+				let ends: Vec<usize> = node
+					.chain
+					.iter()
+					.map(|chained| chained.start)
+					.chain([node.end - "</when>".len()])
+					.collect();
+
+				self.indent += 1;
+				let content = self.content(&node.children, close + 2, ends[0], true);
+				self.indent -= 1;
+
+				let mut segments = vec![(open, content)];
+
+				for (chained, end) in node.chain.iter().zip(&ends[1..]) {
+					let cursor = chained.start + "<#else".len();
+
+					let (open, start) = match &chained.condition {
+						Some(condition) => {
+							let close = self.skip_trivia(condition.end());
+							let open = format!(
+								"<#else when {{{}{}{}}}>",
+								self.leading(cursor, condition.start()),
+								self.expression(condition),
+								self.trailing(condition.end(), close)
+							);
+							(open, close + 2)
+						}
+						None => {
+							let close = self.skip_trivia(cursor);
+							(
+								format!("<#else{}>", self.trailing(cursor, close)),
+								close + 1,
+							)
+						}
+					};
+
+					self.indent += 1;
+					let content = self.content(&chained.children, start, *end, false);
+					self.indent -= 1;
+
+					segments.push((open, content));
+				}
+
+				self.layout(segments, "</when>".to_owned())
+			}
+			ast::FlowControl::Each(node) => {
+				let mut bindings = format!(
+					"{}{}",
+					self.leading(node.start, node.iterator.start()),
+					self.expression(&node.iterator)
+				);
+				let mut bindings_end = node.iterator.end();
+
+				if let Some(index) = &node.index {
+					bindings += &format!(
+						", {}{}",
+						self.leading(bindings_end, index.start),
+						index.name
+					);
+					bindings_end = index.end;
+				}
+
+				let mut iterable = self.expression(&node.iterable);
+				let mut iterable_end = node.iterable.end();
+
+				if let Some(key) = &node.key {
+					iterable += &format!(
+						"; key {}{}",
+						self.leading(iterable_end, key.start()),
+						self.expression(key)
+					);
+					iterable_end = key.end();
+				}
+
+				let close = self.skip_trivia(iterable_end);
+				let open = format!(
+					"<#each {{{} in {}{}{}}}>",
+					bindings,
+					self.leading(bindings_end, node.iterable.start()),
+					iterable,
+					self.trailing(iterable_end, close)
+				);
+
+				let end = node.end - "</each>".len();
+				let children_end = node
+					.fallback
+					.as_ref()
+					.map_or(end, |fallback| fallback.start);
+
+				self.indent += 1;
+				let content = self.content(&node.children, close + 2, children_end, true);
+				self.indent -= 1;
+
+				let mut segments = vec![(open, content)];
+
+				if let Some(fallback) = &node.fallback {
+					let cursor = fallback.start + "<#else".len();
+					let close = self.skip_trivia(cursor);
+					let open = format!("<#else{}>", self.trailing(cursor, close));
+
+					self.indent += 1;
+					let content = self.content(&fallback.children, close + 1, end, false);
+					self.indent -= 1;
+
+					segments.push((open, content));
+				}
+
+				self.layout(segments, "</each>".to_owned())
+			}
+			ast::FlowControl::Let(node) => {
+				let close = self.skip_trivia(node.value.end());
+				let open = format!(
+					"<#let {{{}{} = {}{}{}}}>",
+					self.leading(node.start, node.name.start),
+					node.name.name,
+					self.leading(node.name.end, node.value.start()),
+					self.expression(&node.value),
+					self.trailing(node.value.end(), close)
+				);
+
+				self.indent += 1;
+				let content =
+					self.content(&node.children, close + 2, node.end - "</let>".len(), true);
+				self.indent -= 1;
+
+				self.layout(vec![(open, content)], "</let>".to_owned())
+			}
+			ast::FlowControl::Await(node) => {
+				let close = self.skip_trivia(node.promise.end());
+				let open = format!(
+					"<#await {{{}{}{}}}>",
+					self.leading(node.start, node.promise.start()),
+					self.expression(&node.promise),
+					self.trailing(node.promise.end(), close)
+				);
+
+				let branches: Vec<_> = [("then", &node.then), ("catch", &node.catch)]
+					.into_iter()
+					.filter_map(|(keyword, branch)| Some((keyword, branch.as_deref()?)))
+					.collect();
+
+				// The end of the content is the next `<#then`, `<#catch` or `</await>`.
+				let ends: Vec<usize> = branches
+					.iter()
+					.map(|(_, branch)| branch.start)
+					.chain([node.end - "</await>".len()])
+					.collect();
+
+				self.indent += 1;
+				let content = self.content(&node.children, close + 2, ends[0], true);
+				self.indent -= 1;
+
+				let mut segments = vec![(open, content)];
+
+				for ((keyword, branch), end) in branches.into_iter().zip(&ends[1..]) {
+					segments.push(self.branch(keyword, branch, *end));
+				}
+
+				self.layout(segments, "</await>".to_owned())
+			}
+			ast::FlowControl::Try(node) => {
+				let end = node.catch.start;
+
+				self.indent += 1;
+				let content = self.content(&node.children, node.start + "<#try>".len(), end, true);
+				self.indent -= 1;
+
+				let catch = self.branch("catch", &node.catch, node.end - "</try>".len());
+				self.layout(
+					vec![("<#try>".to_owned(), content), catch],
+					"</try>".to_owned(),
+				)
+			}
+			ast::FlowControl::Match(node) => {
+				let close = self.skip_trivia(node.discriminant.end());
+				let open = format!(
+					"<#match {{{}{}{}}}>",
+					self.leading(node.start, node.discriminant.start()),
+					self.expression(&node.discriminant),
+					self.trailing(node.discriminant.end(), close)
+				);
+
+				// The end of a case is the next `<#case`, `<#default` or `</match>`.
+				let ends: Vec<usize> = node
+					.cases
+					.iter()
+					.skip(1)
+					.map(|case| case.start)
+					.chain([node.end - "</match>".len()])
+					.collect();
+
+				// Cases are indented within the match, and their content within the case.
+				self.indent += 1;
+				let mut segments = Vec::new();
+
+				for (case, end) in node.cases.iter().zip(ends) {
+					let (open, start) = if case.tests.is_empty() {
+						let cursor = case.start + "<#default".len();
+						let close = self.skip_trivia(cursor);
+						(
+							format!("<#default{}>", self.trailing(cursor, close)),
+							close + 1,
+						)
+					} else {
+						let mut cursor = case.start + "<#case".len();
+						let mut tests = Vec::new();
+
+						for test in &case.tests {
+							tests.push(format!(
+								"{}{}",
+								self.leading(cursor, test.start()),
+								self.expression(test)
+							));
+							cursor = test.end();
+						}
+
+						let close = self.skip_trivia(cursor);
+						(
+							format!(
+								"<#case {{{}{}}}>",
+								tests.join(", "),
+								self.trailing(cursor, close)
+							),
+							close + 2,
+						)
+					};
+
+					self.indent += 1;
+					let content = self.content(&case.children, start, end, false);
+					self.indent -= 1;
+
+					segments.push((open, content));
+				}
+
+				self.indent -= 1;
+
+				let flat = segments
+					.iter()
+					.try_fold(open.clone(), |mut out, (open, content)| {
+						if open.contains('\n') || content.is_broken() {
+							return None;
+						}
+
+						out.push_str(open);
+						out.push_str(&content.flat()?);
+						Some(out)
+					})
+					.map(|flat| flat + "</match>");
+
+				if let Some(flat) = flat.filter(|flat| self.fits(flat)) {
+					return flat;
+				}
+
+				let mut out = open;
+				self.indent += 1;
+
+				for (open, content) in segments {
+					out.truncate(out.trim_end().len());
+					newline(&mut out, self.indent, false);
+					out.push_str(&open);
+					self.block(&mut out, &content);
+				}
+
+				self.indent -= 1;
+				out.truncate(out.trim_end().len());
+				newline(&mut out, self.indent, false);
+				out.push_str("</match>");
+				out
+			}
+		}
+	}
+
+	/// Formats the opening of a `<#then>` or `<#catch>` branch, with its content up to `end`.
+	fn branch(
+		&mut self,
+		keyword: &str,
+		branch: &ast::FlowControlAwaitBranch,
+		end: usize,
+	) -> (String, Content) {
+		let cursor = branch.start + 2 + keyword.len();
+
+		let (open, start) = match &branch.binding {
+			Some(binding) => {
+				let close = self.skip_trivia(binding.end());
+				let open = format!(
+					"<#{} {{{}{}{}}}>",
+					keyword,
+					self.leading(cursor, binding.start()),
+					self.expression(binding),
+					self.trailing(binding.end(), close)
+				);
+				(open, close + 2)
+			}
+			None => {
+				let close = self.skip_trivia(cursor);
+				(
+					format!("<#{}{}>", keyword, self.trailing(cursor, close)),
+					close + 1,
+				)
+			}
+		};
+
+		self.indent += 1;
+		let content = self.content(&branch.children, start, end, false);
+		self.indent -= 1;
+
+		(open, content)
+	}
+
+	/// Splits the children between `start` and `end` into items. Text is split into words. If
+	/// `free`, whitespace and comments at the start were skipped by the parser.
+	fn content(&mut self, children: &[ast::Node], start: usize, end: usize, free: bool) -> Content {
+		let mut items = Vec::new();
+		let mut sep = Sep::None;
+
+		if free {
+			let mut cursor = start;
+			let until = children.first().map_or(end, |child| child.start());
+
+			for comment in self.comments(start, until) {
+				items.push(Item {
+					sep: Sep::Free(self.newlines(cursor, comment.start)),
+					text: comment.text.clone(),
+					block: true,
+				});
+				cursor = comment.end;
+			}
+
+			sep = Sep::Free(self.newlines(cursor, until));
+		}
+
+		for child in children {
+			match child {
+				ast::Node::Text(text) => {
+					let mut word = String::new();
+					let mut chars = text.content.chars();
+
+					while let Some(char) = chars.next() {
+						if char.is_whitespace() {
+							if !word.is_empty() {
+								items.push(Item {
+									sep,
+									text: std::mem::take(&mut word),
+									block: false,
+								});
+								sep = Sep::None;
+							}
+
+							sep = sep.space(if char == '\n' { 1 } else { 0 });
+							continue;
+						}
+
+						word.push(char);
+
+						if char == '\\' {
+							word.extend(chars.next());
+						}
+					}
+
+					if !word.is_empty() {
+						items.push(Item {
+							sep,
+							text: word,
+							block: false,
+						});
+						sep = Sep::None;
+					}
+				}
+				child => {
+					let (text, block) = self.node(child);
+					items.push(Item { sep, text, block });
+					sep = Sep::None;
+				}
+			}
+		}
+
+		Content {
+			items,
+			trailing: sep,
+		}
+	}
+
+	/// Prints contents between their opening and closing tags. The contents are printed on one
+	/// line if possible, otherwise blocks are put on their own lines and text is filled.
+	fn layout(&self, segments: Vec<(String, Content)>, close: String) -> String {
+		let flat = segments
+			.iter()
+			.try_fold(String::new(), |mut out, (open, content)| {
+				if open.contains('\n') || content.is_broken() {
+					return None;
+				}
+
+				out.push_str(open);
+				out.push_str(&content.flat()?);
+				Some(out)
+			});
+
+		if let Some(mut flat) = flat {
+			flat.push_str(&close);
+
+			if self.fits(&flat) {
+				return flat;
+			}
+		}
+
+		let mut out = String::new();
+		// Branches of a flow control and its closing tag are put on their own lines.
+		let branches = segments.len() > 1;
+
+		for (i, (open, content)) in segments.into_iter().enumerate() {
+			if i > 0 {
+				out.truncate(out.trim_end().len());
+				newline(&mut out, self.indent, false);
+			}
+
+			out.push_str(&open);
+			self.block(&mut out, &content);
+		}
+
+		if branches {
+			out.truncate(out.trim_end().len());
+			newline(&mut out, self.indent, false);
+		}
+
+		out.push_str(&close);
+		out
+	}
+
+	fn block(&self, out: &mut String, content: &Content) {
+		if content.items.is_empty() {
+			if !content.trailing.is_none() {
+				newline(out, self.indent, false);
+			}
+
+			return;
+		}
+
+		let mut previous_block = false;
+
+		for (i, item) in content.items.iter().enumerate() {
+			if !item.sep.is_none() {
+				// Items without whitespace between them are printed together, and measured as one.
+				let group: usize = content.items[i..]
+					.iter()
+					.enumerate()
+					.take_while(|(j, item)| *j == 0 || item.sep.is_none())
+					.map(|(_, item)| width(item.text.split('\n').next().unwrap()))
+					.sum();
+
+				if i == 0 || previous_block || item.block || line_width(out) + 1 + group > WIDTH {
+					newline(out, self.indent + 1, i > 0 && item.sep.newlines() > 1);
+				} else {
+					out.push(' ');
+				}
+			}
+
+			out.push_str(&item.text);
+			previous_block = item.block;
+		}
+
+		if !content.trailing.is_none() {
+			newline(out, self.indent, false);
+		}
+	}
+}
+
+fn newline(out: &mut String, indent: usize, blank: bool) {
+	if blank {
+		out.push('\n');
+	}
+
+	out.push('\n');
+	out.push_str(&"\t".repeat(indent));
+}
+
+fn width(text: &str) -> usize {
+	text.chars()
+		.map(|char| if char == '\t' { TAB_WIDTH } else { 1 })
+		.sum()
+}
+
+fn line_width(out: &str) -> usize {
+	width(out.rsplit('\n').next().unwrap())
+}
+
+/// Prints a template string, preferring double quotes. Template strings have no escapes, so
+/// single quotes are used if the value contains a double quote.
+/// Quotes the value of a string, with the escapes it needs.
+fn string(value: &str) -> String {
+	let quote = if value.contains('"') { '\'' } else { '"' };
+	let mut out = String::from(quote);
+
+	for char in value.chars() {
+		match char {
+			'\\' => out.push_str("\\\\"),
+			'\n' => out.push_str("\\n"),
+			'\r' => out.push_str("\\r"),
+			char if char == quote => {
+				out.push('\\');
+				out.push(char);
+			}
+			char => out.push(char),
+		}
+	}
+
+	out.push(quote);
+	out
+}
@@ -0,0 +1,441 @@
+use crate::*;
+use debrix_parser::ast::javascript as js;
+
+impl Formatter<'_> {
+	pub(crate) fn expression(&self, expr: &js::Expression) -> String {
+		let mut printer = Printer {
+			formatter: self,
+			out: String::new(),
+			cursor: expr.start(),
+		};
+
+		printer.expression(expr);
+		printer.out
+	}
+}
+
+/// Prints an expression on one line. Comments are printed before the next child expression or
+/// closing token, so the cursor is the end of the input printed so far.
+struct Printer<'f, 'a> {
+	formatter: &'f Formatter<'a>,
+	out: String,
+	cursor: usize,
+}
+
+impl Printer<'_, '_> {
+	fn write(&mut self, text: &str) {
+		self.out.push_str(text);
+	}
+
+	fn leading(&mut self, start: usize) {
+		let comments = self.formatter.leading(self.cursor, start);
+		self.write(&comments);
+		self.cursor = self.cursor.max(start);
+	}
+
+	fn trailing(&mut self, end: usize) {
+		let comments = self.formatter.trailing(self.cursor, end);
+		self.write(&comments);
+		self.cursor = self.cursor.max(end);
+	}
+
+	fn child(&mut self, expr: &js::Expression) {
+		self.leading(expr.start());
+		self.expression(expr);
+		self.cursor = self.cursor.max(expr.end());
+	}
+
+	fn identifier(&mut self, expr: &js::IdentifierExpression) {
+		self.leading(expr.start);
+		self.write(&expr.name);
+		self.cursor = self.cursor.max(expr.end);
+	}
+
+	fn list(&mut self, exprs: &[js::Expression]) {
+		for (i, expr) in exprs.iter().enumerate() {
+			if i > 0 {
+				self.write(", ");
+			}
+
+			self.child(expr);
+		}
+	}
+
+	fn expression(&mut self, expr: &js::Expression) {
+		match expr {
+			js::Expression::Identifier(expr) => self.identifier(expr),
+			js::Expression::Literal(js::Literal::String(literal)) => {
+				self.write(&string(&literal.raw[1..literal.raw.len() - 1]));
+			}
+			js::Expression::Literal(literal) => self.write(literal.raw()),
+			js::Expression::This(_) => self.write("this"),
+			js::Expression::Unary(expr) if !expr.prefix => {
+				self.child(&expr.operand);
+				self.write(&expr.operator.to_string());
+			}
+			js::Expression::Unary(expr) => {
+				let operator = expr.operator.to_string();
+				self.write(&operator);
+
+				// Keep `- -a` from becoming `--a`, and words apart.
+				let separate = match &*expr.operand {
+					js::Expression::Unary(operand) => operand
+						.operator
+						.to_string()
+						.starts_with(operator.chars().last().unwrap()),
+					_ => false,
+				};
+
+				if separate || operator.chars().all(char::is_alphabetic) {
+					self.write(" ");
+				}
+
+				self.child(&expr.operand);
+			}
+			js::Expression::Binary(expr) => {
+				self.child(&expr.left);
+				self.write(&format!(" {} ", expr.operator.to_string()));
+				self.child(&expr.right);
+			}
+			js::Expression::Conditional(expr) => {
+				self.child(&expr.condition);
+				self.write(" ? ");
+				self.child(&expr.consequent);
+				self.write(" : ");
+				self.child(&expr.alternate);
+			}
+			js::Expression::Call(expr) => {
+				self.child(&expr.callee);
+				self.write(if expr.optional { "?.(" } else { "(" });
+				self.list(&expr.arguments);
+				self.trailing(expr.end);
+				self.write(")");
+			}
+			js::Expression::New(expr) => {
+				self.write("new ");
+				self.child(&expr.callee);
+				self.write("(");
+				self.list(&expr.arguments);
+				self.trailing(expr.end);
+				self.write(")");
+			}
+			js::Expression::Member(expr) => {
+				self.child(&expr.object);
+
+				if expr.optional {
+					self.write("?.");
+				}
+
+				if expr.computed {
+					self.write("[");
+					self.child(&expr.property);
+					self.trailing(expr.end);
+					self.write("]");
+				} else {
+					if !expr.optional {
+						self.write(".");
+					}
+
+					self.child(&expr.property);
+				}
+			}
+			js::Expression::Sequence(expr) => self.list(&expr.expressions),
+			js::Expression::Await(expr) => {
+				self.write("await ");
+				self.child(&expr.argument);
+			}
+			js::Expression::Function(expr) => {
+				if expr.is_async {
+					self.write("async ");
+				}
+
+				if let Some(type_parameters) = &expr.type_parameters {
+					self.ts_type(type_parameters);
+				}
+
+				self.write("(");
+				self.list(&expr.parameters);
+				self.write(")");
+
+				if let Some(return_type) = &expr.return_type {
+					self.write(": ");
+					self.ts_type(return_type);
+				}
+
+				self.write(" => ");
+
+				match &expr.body {
+					js::FunctionBody::Expression(body) => self.child(body),
+					js::FunctionBody::Block(body) => self.block(body),
+				}
+			}
+			js::Expression::Assignment(expr) => {
+				self.child(&expr.left);
+				self.write(&format!(" {} ", expr.operator.to_string()));
+				self.child(&expr.right);
+			}
+			js::Expression::Spread(expr) => self.spread(expr),
+			js::Expression::Template(expr) => self.template(expr),
+			js::Expression::TaggedTemplate(expr) => {
+				self.child(&expr.tag);
+				self.leading(expr.quasi.start);
+				self.template(&expr.quasi);
+			}
+			js::Expression::Object(expr) => {
+				if expr.properties.is_empty() {
+					self.write("{");
+					self.trailing(expr.end);
+					self.write("}");
+					return;
+				}
+
+				self.write("{ ");
+
+				for (i, property) in expr.properties.iter().enumerate() {
+					if i > 0 {
+						self.write(", ");
+					}
+
+					match property {
+						// A shorthand property with a default in a pattern, like `{ a = 1 }`.
+						js::ObjectProperty::Keyed(js::ObjectKeyedProperty {
+							key,
+							value: Some(value @ js::Expression::Assignment(assignment)),
+							..
+						}) if assignment.left.start() == key.start => self.child(value),
+						js::ObjectProperty::Keyed(property) => {
+							self.identifier(&property.key);
+
+							if let Some(value) = &property.value {
+								self.write(": ");
+								self.child(value);
+							}
+						}
+						js::ObjectProperty::Computed(property) => {
+							self.write("[");
+							self.child(&property.key);
+							self.write("]: ");
+							self.child(&property.value);
+						}
+						js::ObjectProperty::Spread(property) => self.spread(property),
+					}
+				}
+
+				self.trailing(expr.end);
+				self.write(" }");
+			}
+			js::Expression::Array(expr) => {
+				self.write("[");
+				self.list(&expr.elements);
+				self.trailing(expr.end);
+				self.write("]");
+			}
+			js::Expression::Parenthesized(expr) => {
+				self.write("(");
+				self.child(&expr.expression);
+				self.trailing(expr.end);
+				self.write(")");
+			}
+			js::Expression::Empty(_) => {}
+			js::Expression::TsAs(expr) => {
+				self.child(&expr.expression);
+				self.write(" as ");
+				self.ts_type(&expr.type_annotation);
+			}
+			js::Expression::TsSatisfies(expr) => {
+				self.child(&expr.expression);
+				self.write(" satisfies ");
+				self.ts_type(&expr.type_annotation);
+			}
+			js::Expression::TsNonNull(expr) => {
+				self.child(&expr.expression);
+				self.write("!");
+			}
+			js::Expression::TsAnnotated(expr) => {
+				self.child(&expr.expression);
+
+				if expr.optional {
+					self.write("?");
+				}
+
+				if let Some(type_annotation) = &expr.type_annotation {
+					self.write(": ");
+					self.ts_type(type_annotation);
+				}
+			}
+		}
+	}
+
+	/// Types are printed as written.
+	fn ts_type(&mut self, ty: &js::TsType) {
+		self.leading(ty.start);
+		self.write(&ty.raw);
+		self.cursor = self.cursor.max(ty.end);
+	}
+
+	fn block(&mut self, stmt: &js::BlockStatement) {
+		self.leading(stmt.start);
+
+		if stmt.body.is_empty() {
+			self.write("{");
+			self.trailing(stmt.end);
+			self.write("}");
+			return;
+		}
+
+		self.write("{ ");
+
+		for (i, child) in stmt.body.iter().enumerate() {
+			if i > 0 {
+				self.write(" ");
+			}
+
+			self.statement(child);
+		}
+
+		self.trailing(stmt.end);
+		self.write(" }");
+	}
+
+	/// Prints a statement, always ending it with a semicolon since the block is on one line.
+	fn statement(&mut self, stmt: &js::Statement) {
+		self.leading(stmt.start());
+
+		match stmt {
+			js::Statement::Block(stmt) => self.block(stmt),
+			js::Statement::Expression(stmt) => {
+				self.child(&stmt.expression);
+				self.write(";");
+			}
+			js::Statement::Variable(stmt) => {
+				self.write(&stmt.kind.to_string());
+				self.write(" ");
+
+				for (i, declarator) in stmt.declarations.iter().enumerate() {
+					if i > 0 {
+						self.write(", ");
+					}
+
+					self.child(&declarator.id);
+
+					if let Some(init) = &declarator.init {
+						self.write(" = ");
+						self.child(init);
+					}
+				}
+
+				self.write(";");
+			}
+			js::Statement::Return(stmt) => {
+				self.write("return");
+
+				if let Some(argument) = &stmt.argument {
+					self.write(" ");
+					self.child(argument);
+				}
+
+				self.write(";");
+			}
+			js::Statement::If(stmt) => {
+				self.write("if (");
+				self.child(&stmt.condition);
+				self.write(") ");
+				self.statement(&stmt.consequent);
+
+				if let Some(alternate) = &stmt.alternate {
+					self.write(" else ");
+					self.statement(alternate);
+				}
+			}
+			js::Statement::Throw(stmt) => {
+				self.write("throw ");
+				self.child(&stmt.argument);
+				self.write(";");
+			}
+			js::Statement::Try(stmt) => {
+				self.write("try ");
+				self.block(&stmt.block);
+
+				if let Some(handler) = &stmt.handler {
+					self.write(" catch ");
+
+					if let Some(parameter) = &handler.parameter {
+						self.write("(");
+						self.child(parameter);
+						self.write(") ");
+					}
+
+					self.block(&handler.body);
+				}
+
+				if let Some(finalizer) = &stmt.finalizer {
+					self.write(" finally ");
+					self.block(finalizer);
+				}
+			}
+			js::Statement::Empty(_) => self.write(";"),
+		}
+
+		self.cursor = self.cursor.max(stmt.end());
+	}
+
+	fn spread(&mut self, expr: &js::SpreadExpression) {
+		self.write("...");
+		self.child(&expr.argument);
+	}
+
+	/// Prints the text of a template as is, and formats the expressions in its substitutions.
+	fn template(&mut self, expr: &js::TemplateLiteral) {
+		self.write("`");
+
+		for (i, quasi) in expr.quasis.iter().enumerate() {
+			self.write(&quasi.raw);
+			self.cursor = self.cursor.max(quasi.end);
+
+			if let Some(expression) = expr.expressions.get(i) {
+				self.write("${");
+				self.child(expression);
+				// The closing brace is right before the next quasi.
+				self.trailing(expr.quasis[i + 1].start - 1);
+				self.write("}");
+			}
+		}
+
+		self.write("`");
+		self.cursor = self.cursor.max(expr.end);
+	}
+}
+
+/// Prints a string literal, preferring double quotes unless the value contains more double
+/// than single quotes.
+fn string(value: &str) -> String {
+	let doubles = value.matches('"').count();
+	let singles = value.matches('\'').count();
+	let quote = if doubles > singles { '\'' } else { '"' };
+
+	let mut out = String::from(quote);
+	let mut chars = value.chars();
+
+	while let Some(char) = chars.next() {
+		if char == '\\' {
+			match chars.next() {
+				// Escaping the other quote is unnecessary.
+				Some(char) if (char == '"' || char == '\'') && char != quote => out.push(char),
+				Some(char) => {
+					out.push('\\');
+					out.push(char);
+				}
+				None => out.push('\\'),
+			}
+		} else {
+			if char == quote {
+				out.push('\\');
+			}
+
+			out.push(char);
+		}
+	}
+
+	out.push(quote);
+	out
+}
@@ -0,0 +1,32 @@
+//! Formatter for debrix documents.
+//!
+//! Documents are printed in one canonical style, keeping comments. Whitespace in text is
+//! significant to the compiler, so it is never added or removed between nodes, only changed
+//! between spaces and line breaks. Formatting the output again returns it unchanged.
+
+mod formatter;
+mod javascript;
+
+pub(crate) use self::formatter::*;
+
+use debrix_parser::{cst, ParserError};
+
+#[cfg(test)]
+mod tests;
+
+/// Max line width, where tabs count as `TAB_WIDTH` columns.
+pub const WIDTH: usize = 80;
+pub const TAB_WIDTH: usize = 4;
+
+pub fn format(input: String) -> Result<String, ParserError> {
+	let document = debrix_parser::parse_document(&input)?;
+	let syntax = cst::build(&input, &document);
+	Ok(Formatter::new(&input, &syntax).document(&document))
+}
+
+/// Formats a document with TypeScript in its expressions. Types are kept as written.
+pub fn format_typescript(input: String) -> Result<String, ParserError> {
+	let document = debrix_parser::parse_document_typescript(&input)?;
+	let syntax = cst::build(&input, &document);
+	Ok(Formatter::new(&input, &syntax).document(&document))
+}
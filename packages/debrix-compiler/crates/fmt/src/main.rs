@@ -0,0 +1,79 @@
+use std::{env, fs, io, io::Read, process};
+
+const USAGE: &str = "usage: debrix_fmt [--check] [files...]
+
+Formats the files in place. Without files, formats stdin to stdout.
+With --check, only reports the files which are not formatted.";
+
+fn main() {
+	let mut check = false;
+	let mut files = Vec::new();
+
+	for arg in env::args().skip(1) {
+		match arg.as_str() {
+			"--check" => check = true,
+			"-h" | "--help" => {
+				println!("{}", USAGE);
+				return;
+			}
+			_ => files.push(arg),
+		}
+	}
+
+	if files.is_empty() {
+		let mut input = String::new();
+
+		if let Err(err) = io::stdin().read_to_string(&mut input) {
+			eprintln!("stdin: {}", err);
+			process::exit(2);
+		}
+
+		match debrix_fmt::format(input.clone()) {
+			Ok(output) if check && output != input => process::exit(1),
+			Ok(_) if check => {}
+			Ok(output) => print!("{}", output),
+			Err(err) => {
+				eprintln!("stdin: {:?}", err);
+				process::exit(2);
+			}
+		}
+
+		return;
+	}
+
+	let mut code = 0;
+
+	for file in files {
+		let input = match fs::read_to_string(&file) {
+			Ok(input) => input,
+			Err(err) => {
+				eprintln!("{}: {}", file, err);
+				code = 2;
+				continue;
+			}
+		};
+
+		let output = match debrix_fmt::format(input.clone()) {
+			Ok(output) => output,
+			Err(err) => {
+				eprintln!("{}: {:?}", file, err);
+				code = 2;
+				continue;
+			}
+		};
+
+		if output == input {
+			continue;
+		}
+
+		if check {
+			println!("{}", file);
+			code = code.max(1);
+		} else if let Err(err) = fs::write(&file, output) {
+			eprintln!("{}: {}", file, err);
+			code = 2;
+		}
+	}
+
+	process::exit(code);
+}
@@ -0,0 +1,247 @@
+use super::*;
+
+/// Formats the input, and asserts that formatting the output again does not change it.
+fn fmt(input: &str) -> String {
+	let output = format(input.to_owned()).unwrap();
+	assert_eq!(format(output.clone()).unwrap(), output, "not idempotent");
+	output
+}
+
+#[test]
+fn test_format_empty() {
+	assert_eq!(fmt(""), "");
+	assert_eq!(fmt("\n\t\n"), "");
+}
+
+#[test]
+fn test_format_dependency_statement() {
+	assert_eq!(
+		fmt("using   model   from 'self.model.js'\nusing foo bar ,{ baz qux   as  quux } from 'quuz'"),
+		"using model from \"self.model.js\"\nusing foo bar, { baz qux as quux } from \"quuz\"\n"
+	);
+}
+
+#[test]
+fn test_format_blank_lines() {
+	assert_eq!(
+		fmt("using a from 'a'\n\n\n\nusing b from 'b'\n<p></p>"),
+		"using a from \"a\"\n\nusing b from \"b\"\n<p></p>\n"
+	);
+}
+
+#[test]
+fn test_format_attributes() {
+	assert_eq!(
+		fmt("<input   type='text'  value={ value }   {...props} {disabled}/>"),
+		"<input type=\"text\" value={value} {...props} {disabled} />\n"
+	);
+	assert_eq!(
+		fmt("<p title='say \"hi\"'></p>"),
+		"<p title='say \"hi\"'></p>\n"
+	);
+}
+
+#[test]
+fn test_format_attribute_wrapping() {
+	assert_eq!(
+		fmt("<div class='a very long class name' title='a very long title' on:click={() => count += 1}>foo</div>"),
+		"<div\n\tclass=\"a very long class name\"\n\ttitle=\"a very long title\"\n\ton:click={() => count += 1}\n>foo</div>\n"
+	);
+}
+
+#[test]
+fn test_format_children() {
+	assert_eq!(fmt("<p>Hello   {name}!</p>"), "<p>Hello {name}!</p>\n");
+	assert_eq!(
+		fmt("<div>\n<p>foo</p>\n\n\n<p>bar <b>baz</b></p></div>"),
+		"<div>\n\t<p>foo</p>\n\n\t<p>bar <b>baz</b></p></div>\n"
+	);
+	assert_eq!(
+		fmt("<ul><li>foo</li><li>bar</li></ul>"),
+		"<ul><li>foo</li><li>bar</li></ul>\n"
+	);
+}
+
+#[test]
+fn test_format_keeps_whitespace() {
+	// Whitespace is never added between nodes without whitespace between them.
+	assert_eq!(
+		fmt("<div><p>foo</p>\n<p>bar</p></div>"),
+		"<div><p>foo</p>\n\t<p>bar</p></div>\n"
+	);
+	assert_eq!(fmt("<p> foo </p>"), "<p> foo </p>\n");
+}
+
+#[test]
+fn test_format_fill() {
+	let words = "lorem ipsum dolor sit amet ".repeat(4);
+	assert_eq!(
+		fmt(&format!("<p>\n{}\n</p>", words)),
+		"<p>\n\tlorem ipsum dolor sit amet lorem ipsum dolor sit amet lorem ipsum dolor sit\n\tamet lorem ipsum dolor sit amet\n</p>\n"
+	);
+}
+
+#[test]
+fn test_format_flow_control() {
+	assert_eq!(
+		fmt("<p><#when {a}>foo<#else when {b}>bar<#else>baz</when></p>"),
+		"<p><#when {a}>foo<#else when {b}>bar<#else>baz</when></p>\n"
+	);
+	assert_eq!(
+		fmt("<ul>\n<#each {item   in  items}>\n<li>{item}</li>\n</each>\n</ul>"),
+		"<ul>\n\t<#each {item in items}>\n\t\t<li>{item}</li>\n\t</each>\n</ul>\n"
+	);
+	assert_eq!(
+		fmt("<ul>\n<#each { item ,i in items ;key  item.id }>\n<li>{i}</li>\n</each>\n</ul>"),
+		"<ul>\n\t<#each {item, i in items; key item.id}>\n\t\t<li>{i}</li>\n\t</each>\n</ul>\n"
+	);
+	assert_eq!(
+		fmt("<ul>\n<#each {{ name,price=0 },i in items}>\n<li>{name}</li>\n</each>\n</ul>"),
+		"<ul>\n\t<#each {{ name, price = 0 }, i in items}>\n\t\t<li>{name}</li>\n\t</each>\n</ul>\n"
+	);
+	assert_eq!(
+		fmt("<ul>\n<#each {item in items}>\n<li>{item}</li>\n<#else  >\n<li>none</li>\n</each>\n</ul>"),
+		"<ul>\n\t<#each {item in items}>\n\t\t<li>{item}</li>\n\t<#else>\n\t\t<li>none</li>\n\t</each>\n</ul>\n"
+	);
+	assert_eq!(
+		fmt("<p>\n<#let {  city=user.address.city }>\n<b>{city}</b>\n</let>\n</p>"),
+		"<p>\n\t<#let {city = user.address.city}>\n\t\t<b>{city}</b>\n\t</let>\n</p>\n"
+	);
+	assert_eq!(
+		fmt("<p><#await {  user }>Loading<#then {{ name }  }>{name}<#catch  >Failed</await></p>"),
+		"<p><#await {user}>Loading<#then {{ name }}>{name}<#catch>Failed</await></p>\n"
+	);
+	assert_eq!(
+		fmt("<p>\n<#try>\n{a.b}\n<#catch {  error }>\n{error.message}\n</try>\n</p>"),
+		"<p>\n\t<#try>\n\t\t{a.b}\n\t<#catch {error}>\n\t\t{error.message}\n\t</try>\n</p>\n"
+	);
+	assert_eq!(
+		fmt("<p><#match {  status }><#case {'a'}>A<#case {'b' ,'c'}>B<#default >C</match></p>"),
+		"<p><#match {status}><#case {\"a\"}>A<#case {\"b\", \"c\"}>B<#default>C</match></p>\n"
+	);
+	assert_eq!(
+		fmt("<p>\n<#match {status}>\n<#case {'loading'}>\n<b>Loading</b>\n<#default>\n<b>Ready</b>\n</match>\n</p>"),
+		"<p>\n\t<#match {status}>\n\t\t<#case {\"loading\"}>\n\t\t\t<b>Loading</b>\n\t\t<#default>\n\t\t\t<b>Ready</b>\n\t</match>\n</p>\n"
+	);
+	assert_eq!(
+		fmt("<div>\n<#when {a}>\n<p>foo</p>\n<#else>\n<p>bar</p>\n</when>\n</div>"),
+		"<div>\n\t<#when {a}>\n\t\t<p>foo</p>\n\t<#else>\n\t\t<p>bar</p>\n\t</when>\n</div>\n"
+	);
+	// Branches are put on their own lines when a flow control is broken.
+	assert_eq!(
+		fmt("<div><#when {a}><p>foo</p><#else><p>bar</p></when></div>"),
+		"<div><#when {a}>\n\t\t<p>foo</p>\n\t<#else><p>bar</p>\n\t</when></div>\n"
+	);
+	assert_eq!(
+		fmt("<div>\n<#try>\n<Foo /><#catch {err}>{err}</try>\n</div>"),
+		"<div>\n\t<#try>\n\t\t<Foo />\n\t<#catch {err}>{err}\n\t</try>\n</div>\n"
+	);
+	assert_eq!(
+		fmt("<div><#await {user}><p>Loading</p><#then {user}><p>{user.name}</p><#catch>Failed</await></div>"),
+		"<div><#await {user}>\n\t\t<p>Loading</p>\n\t<#then {user}><p>{user.name}</p>\n\t<#catch>Failed\n\t</await></div>\n"
+	);
+}
+
+#[test]
+fn test_format_verbatim() {
+	assert_eq!(
+		fmt("<div>\n<textarea>  a\n  b </textarea>\n<input />\n</div>"),
+		"<div>\n\t<textarea>  a\n  b </textarea>\n\t<input />\n</div>\n"
+	);
+	assert_eq!(
+		fmt("<div><textarea> a b </textarea> <input /></div>"),
+		"<div><textarea> a b </textarea>\n\t<input /></div>\n"
+	);
+	assert_eq!(
+		fmt("<pre>  foo\n    bar</pre>"),
+		"<pre>  foo\n    bar</pre>\n"
+	);
+}
+
+#[test]
+fn test_format_expressions() {
+	assert_eq!(
+		fmt("<p>{foo( 1,'two',[3,4],{ five: 5, [six]: 6 } )}</p>"),
+		"<p>{foo(1, \"two\", [3, 4], { five: 5, [six]: 6 })}</p>\n"
+	);
+	assert_eq!(
+		fmt("<p>{a?b:c}{new Date().getTime()}{x[ 'y' ]}{(a+b)*c}{tag`x`}</p>"),
+		"<p>{a ? b : c}{new Date().getTime()}{x[\"y\"]}{(a + b) * c}{tag`x`}</p>\n"
+	);
+	assert_eq!(fmt("<p>{'it\"s'}</p>"), "<p>{'it\"s'}</p>\n");
+	assert_eq!(
+		fmt("<p title='a\\\\b\\u0041'>{/[}]/g.test(0xF_F/2n)}</p>"),
+		"<p title=\"a\\\\bA\">{/[}]/g.test(0xF_F / 2n)}</p>\n"
+	);
+	assert_eq!(
+		fmt("<p>{a++??this.b?.(c),d||=-e}</p>"),
+		"<p>{a++ ?? this.b?.(c), d ||= -e}</p>\n"
+	);
+	assert_eq!(
+		fmt("<p>{`a  ${ b+c /* d */ }  ${tag`e${ f }`}`}</p>"),
+		"<p>{`a  ${b + c /* d */}  ${tag`e${f}`}`}</p>\n"
+	);
+}
+
+#[test]
+fn test_format_functions() {
+	assert_eq!(
+		fmt("<p on:click={async(e)=>{await save(e)\nclose()}}></p>"),
+		"<p on:click={async (e) => { await save(e); close(); }}></p>\n"
+	);
+	assert_eq!(
+		fmt("<p>{({id,a=1,...rest}={},[b,,c])=>{}}</p>"),
+		"<p>{({ id, a = 1, ...rest } = {}, [b, , c]) => {}}</p>\n"
+	);
+	assert_eq!(
+		fmt("<p>{x=>{const {a}=x;if(a)return a\nelse{throw x}try{f()}catch{}}}</p>"),
+		"<p>{(x) => { const { a } = x; if (a) return a; else { throw x; } try { f(); } catch {} }}</p>\n"
+	);
+}
+
+#[test]
+fn test_format_typescript() {
+	let fmt = |input: &str| {
+		let output = format_typescript(input.to_owned()).unwrap();
+		assert_eq!(format_typescript(output.clone()).unwrap(), output);
+		output
+	};
+
+	assert_eq!(
+		fmt("<p>{a!.b as Array<T>}{c   satisfies  T}</p>"),
+		"<p>{a!.b as Array<T>}{c satisfies T}</p>\n"
+	);
+	assert_eq!(
+		fmt("<p on:click={<T,>(e?:T,n:number=1):void=>{let x:T=e!}}></p>"),
+		"<p on:click={<T,>(e?: T, n: number = 1): void => { let x: T = e!; }}></p>\n"
+	);
+}
+
+#[test]
+fn test_format_comments() {
+	assert_eq!(
+		fmt("// foo\nusing a from 'a' /* bar */\n\n/* baz */\n<p>{/* qux */ a}</p>"),
+		"// foo\nusing a from \"a\" /* bar */\n\n/* baz */\n<p>{/* qux */ a}</p>\n"
+	);
+	assert_eq!(
+		fmt("<div /* foo */ class='a'><!-- bar --></div>"),
+		"<div /* foo */ class=\"a\"><!-- bar --></div>\n"
+	);
+	assert_eq!(
+		fmt("<div // foo\n class='a'></div>"),
+		"<div\n\t// foo\n\tclass=\"a\"\n></div>\n"
+	);
+	assert_eq!(
+		fmt("<p title={a // foo\n}></p>"),
+		"<p\n\ttitle={a // foo\n\t}\n></p>\n"
+	);
+	assert_eq!(
+		fmt("<div><#when {a}>\n// foo\n<p>bar</p>\n</when></div>"),
+		"<div><#when {a}>\n\t\t// foo\n\t\t<p>bar</p>\n\t</when></div>\n"
+	);
+}
+
+#[test]
+fn test_format_error() {
+	assert!(format("<p>{a +}</p>".to_owned()).is_err());
+}
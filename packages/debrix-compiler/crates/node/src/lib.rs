@@ -9,51 +9,112 @@ pub fn int_to_target(int: usize) -> debrix_compiler::Target {
 	}
 }
 
+pub fn int_to_encoding(int: usize) -> debrix_compiler::Encoding {
+	match int {
+		0 => debrix_compiler::Encoding::Char,
+		1 => debrix_compiler::Encoding::Utf8,
+		2 => debrix_compiler::Encoding::Utf16,
+		_ => unreachable!(),
+	}
+}
+
+fn serialize_position<'a>(
+	cx: &mut FunctionContext<'a>,
+	position: &debrix_compiler::Position,
+) -> JsResult<'a, JsObject> {
+	let js_position = cx.empty_object();
+	let js_line = cx.number(position.line as f64);
+	let js_column = cx.number(position.column as f64);
+	js_position.set(cx, "line", js_line)?;
+	js_position.set(cx, "column", js_column)?;
+	Ok(js_position)
+}
+
+fn serialize_location<'a>(
+	cx: &mut FunctionContext<'a>,
+	location: &debrix_compiler::Location,
+) -> JsResult<'a, JsObject> {
+	let js_location = cx.empty_object();
+	let js_start = serialize_position(cx, &location.start)?;
+	let js_end = serialize_position(cx, &location.end)?;
+	js_location.set(cx, "start", js_start)?;
+	js_location.set(cx, "end", js_end)?;
+	Ok(js_location)
+}
+
+fn throw_error<'a, T: Value>(
+	cx: &mut FunctionContext<'a>,
+	err: debrix_compiler::Error,
+) -> JsResult<'a, T> {
+	match err {
+		debrix_compiler::Error::ParserError(err) => {
+			let js_err = cx.empty_object();
+
+			let js_type = cx.number(1);
+			let js_message = cx.string(format!("{:?}", err));
+			let js_start = cx.number(err.position as f64);
+			let js_location = serialize_location(cx, &err.location)?;
+
+			let js_positives = cx.empty_array();
+			for (i, s) in err.positives.iter().enumerate() {
+				let js_str = cx.string(s);
+				js_positives.set(cx, i as u32, js_str)?;
+			}
+
+			js_err.set(cx, "type", js_type)?;
+			js_err.set(cx, "message", js_message)?;
+			js_err.set(cx, "start", js_start)?;
+			js_err.set(cx, "location", js_location)?;
+			js_err.set(cx, "positives", js_positives)?;
+			cx.throw(js_err)
+		}
+		debrix_compiler::Error::CompilerError(err) => {
+			let js_err = cx.empty_object();
+
+			let js_type = cx.number(0);
+			let js_message = cx.string(format!("{:?}", err));
+			let js_start = cx.number(err.start as f64);
+			let js_end = cx.number(err.end as f64);
+			let js_location = serialize_location(cx, &err.location)?;
+			let js_message2 = cx.string(err.message);
+
+			js_err.set(cx, "type", js_type)?;
+			js_err.set(cx, "message", js_message)?;
+			js_err.set(cx, "start", js_start)?;
+			js_err.set(cx, "end", js_end)?;
+			js_err.set(cx, "location", js_location)?;
+			js_err.set(cx, "_message", js_message2)?;
+			cx.throw(js_err)
+		}
+	}
+}
+
 fn build(mut cx: FunctionContext) -> JsResult<JsObject> {
 	let input = cx.argument::<JsString>(0)?.value(&mut cx);
 	let target = int_to_target(cx.argument::<JsNumber>(1)?.value(&mut cx) as usize);
+	let encoding = match cx.argument_opt(2) {
+		Some(value) => value
+			.downcast_or_throw::<JsNumber, _>(&mut cx)?
+			.value(&mut cx) as usize,
+		None => 0,
+	};
+	let encoding = int_to_encoding(encoding);
+	let typescript = match cx.argument_opt(3) {
+		Some(value) => value
+			.downcast_or_throw::<JsBoolean, _>(&mut cx)?
+			.value(&mut cx),
+		None => false,
+	};
+
+	let result = if typescript {
+		debrix_compiler::build_typescript(input, target, encoding)
+	} else {
+		debrix_compiler::build_with_encoding(input, target, encoding)
+	};
 
-	let result = match debrix_compiler::build(input, target) {
+	let result = match result {
 		Ok(result) => result,
-		Err(err) => {
-			return match err {
-				debrix_compiler::Error::ParserError(err) => {
-					let js_err = cx.empty_object();
-
-					let js_type = cx.number(1);
-					let js_message = cx.string(format!("{:?}", err));
-					let js_start = cx.number(err.position as f64);
-
-					let js_positives = cx.empty_array();
-					for (i, s) in err.positives.iter().enumerate() {
-						let js_str = cx.string(s);
-						js_positives.set(&mut cx, i as u32, js_str)?;
-					}
-
-					js_err.set(&mut cx, "type", js_type)?;
-					js_err.set(&mut cx, "message", js_message)?;
-					js_err.set(&mut cx, "start", js_start)?;
-					js_err.set(&mut cx, "positives", js_positives)?;
-					cx.throw(js_err)
-				}
-				debrix_compiler::Error::CompilerError(err) => {
-					let js_err = cx.empty_object();
-
-					let js_type = cx.number(0);
-					let js_message = cx.string(format!("{:?}", err));
-					let js_start = cx.number(err.start as f64);
-					let js_end = cx.number(err.end as f64);
-					let js_message2 = cx.string(err.message);
-
-					js_err.set(&mut cx, "type", js_type)?;
-					js_err.set(&mut cx, "message", js_message)?;
-					js_err.set(&mut cx, "start", js_start)?;
-					js_err.set(&mut cx, "end", js_end)?;
-					js_err.set(&mut cx, "_message", js_message2)?;
-					cx.throw(js_err)
-				}
-			};
-		}
+		Err(err) => return throw_error(&mut cx, err),
 	};
 
 	let result_js = cx.empty_object();
@@ -73,8 +134,30 @@ fn build(mut cx: FunctionContext) -> JsResult<JsObject> {
 	Ok(result_js)
 }
 
+fn parse(mut cx: FunctionContext) -> JsResult<JsString> {
+	let input = cx.argument::<JsString>(0)?.value(&mut cx);
+	let typescript = match cx.argument_opt(1) {
+		Some(value) => value
+			.downcast_or_throw::<JsBoolean, _>(&mut cx)?
+			.value(&mut cx),
+		None => false,
+	};
+
+	let result = if typescript {
+		debrix_compiler::parse_typescript(input)
+	} else {
+		debrix_compiler::parse(input)
+	};
+
+	match result {
+		Ok(json) => Ok(cx.string(json)),
+		Err(err) => throw_error(&mut cx, err),
+	}
+}
+
 #[neon::main]
 fn main(mut cx: ModuleContext) -> NeonResult<()> {
 	cx.export_function("build", build)?;
+	cx.export_function("parse", parse)?;
 	Ok(())
 }
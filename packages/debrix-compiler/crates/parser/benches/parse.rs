@@ -0,0 +1,94 @@
+//! Parsing benchmarks over realistic documents, run with `cargo bench -p debrix_parser`.
+//!
+//! Prints the mean time and the number of heap allocations of parsing every document. Without
+//! `--bench`, like under `cargo test --benches`, every document is only parsed once.
+//!
+//! Scanning by byte and borrowing strings from the input, instead of collecting the input into
+//! characters and owning every string, gave on one machine:
+//!
+//! ```text
+//!               before  allocations       after  allocations
+//! todo         51.59µs          455     39.58µs          158
+//! form         56.92µs          492     43.16µs          152
+//! article      50.80µs          454     46.13µs          153
+//! all x 50      9.78ms        67582      8.63ms        22761
+//! ```
+
+use std::{
+	alloc::{GlobalAlloc, Layout, System},
+	env,
+	hint::black_box,
+	sync::atomic::{AtomicUsize, Ordering},
+	time::{Duration, Instant},
+};
+
+/// Counts allocations, including reallocations, on top of the system allocator.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		System.alloc(layout)
+	}
+
+	unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		System.realloc(ptr, layout, new_size)
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		System.dealloc(ptr, layout)
+	}
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const DOCUMENTS: &[(&str, &str)] = &[
+	("todo", include_str!("fixtures/todo.debrix")),
+	("form", include_str!("fixtures/form.debrix")),
+	("article", include_str!("fixtures/article.debrix")),
+];
+
+const DURATION: Duration = Duration::from_secs(2);
+
+fn bench(name: &str, input: &str, iterate: bool) {
+	let before = ALLOCATIONS.load(Ordering::Relaxed);
+	let document = debrix_parser::parse_document(input).unwrap();
+	let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+	drop(document);
+
+	if !iterate {
+		return;
+	}
+
+	let start = Instant::now();
+	let mut iterations = 0;
+
+	while start.elapsed() < DURATION {
+		black_box(debrix_parser::parse_document(black_box(input)).unwrap());
+		iterations += 1;
+	}
+
+	println!(
+		"{:<10} {:>8} bytes {:>12.2?} {:>8} allocations",
+		name,
+		input.len(),
+		start.elapsed() / iterations,
+		allocations
+	);
+}
+
+fn main() {
+	let iterate = env::args().any(|arg| arg == "--bench");
+
+	for (name, input) in DOCUMENTS {
+		bench(name, input, iterate);
+	}
+
+	// All documents, as many times as in a large app.
+	let all: Vec<&str> = DOCUMENTS.iter().map(|(_, input)| *input).collect();
+	bench("all x 50", &all.join("\n").repeat(50), iterate);
+}
@@ -3,6 +3,7 @@ mod dependency;
 mod element;
 mod flow_control;
 mod identifier;
+mod invalid;
 pub mod javascript;
 mod literal;
 mod text;
@@ -12,20 +13,24 @@ pub use dependency::*;
 pub use element::*;
 pub use flow_control::*;
 pub use identifier::*;
+pub use invalid::*;
 pub use literal::*;
 pub use text::*;
 
-#[derive(Debug)]
-pub enum Node {
-	DependencyStatement(DependencyStatement),
-	Comment(Comment),
-	Element(Element),
-	Text(Text),
-	TextBinding(TextBinding),
-	FlowControl(FlowControl),
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum Node<'src> {
+	DependencyStatement(DependencyStatement<'src>),
+	Comment(Comment<'src>),
+	Element(Element<'src>),
+	Text(Text<'src>),
+	TextBinding(TextBinding<'src>),
+	FlowControl(FlowControl<'src>),
+	Invalid(Invalid),
 }
 
-impl Node {
+impl Node<'_> {
 	pub fn start(&self) -> usize {
 		match self {
 			Node::DependencyStatement(node) => node.start,
@@ -34,6 +39,7 @@ impl Node {
 			Node::Text(node) => node.start,
 			Node::TextBinding(node) => node.start,
 			Node::FlowControl(node) => node.start(),
+			Node::Invalid(node) => node.start,
 		}
 	}
 
@@ -45,11 +51,13 @@ impl Node {
 			Node::Text(node) => node.end,
 			Node::TextBinding(node) => node.end,
 			Node::FlowControl(node) => node.end(),
+			Node::Invalid(node) => node.end,
 		}
 	}
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Range {
 	pub start: usize,
 	pub end: usize,
@@ -67,12 +75,15 @@ impl Default for Range {
 	}
 }
 
-#[derive(Debug)]
-pub struct Document {
-	pub children: Vec<Node>,
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub struct Document<'src> {
+	pub children: Vec<Node<'src>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Nodes<N> {
 	pub start: usize,
 	pub end: usize,
@@ -1,26 +1,28 @@
 use crate::ast::*;
+use std::borrow::Cow;
 
-#[derive(Debug)]
-pub struct Comment {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Comment<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub comment: String,
+	pub comment: Cow<'src, str>,
 }
 
-impl Comment {
+impl Comment<'_> {
 	pub fn range(&self) -> Range {
 		Range::new(self.start, self.end)
 	}
 }
 
-impl From<Comment> for Range {
-	fn from(node: Comment) -> Self {
+impl<'src> From<Comment<'src>> for Range {
+	fn from(node: Comment<'src>) -> Self {
 		node.range()
 	}
 }
 
-impl From<Comment> for Node {
-	fn from(node: Comment) -> Node {
+impl<'src> From<Comment<'src>> for Node<'src> {
+	fn from(node: Comment<'src>) -> Node<'src> {
 		Node::Comment(node)
 	}
 }
@@ -1,69 +1,72 @@
 use crate::ast::*;
 
-#[derive(Debug)]
-pub struct DependencyStatement {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DependencyStatement<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub default: Option<DependencyDefaultSpecifier>,
-	pub named: Option<Nodes<DependencyNamedSpecifier>>,
-	pub source: StringLiteral,
+	pub default: Option<DependencyDefaultSpecifier<'src>>,
+	pub named: Option<Nodes<DependencyNamedSpecifier<'src>>>,
+	pub source: StringLiteral<'src>,
 }
 
-impl DependencyStatement {
+impl DependencyStatement<'_> {
 	pub fn range(&self) -> Range {
 		Range::new(self.start, self.end)
 	}
 }
 
-impl From<DependencyStatement> for Range {
-	fn from(node: DependencyStatement) -> Self {
+impl<'src> From<DependencyStatement<'src>> for Range {
+	fn from(node: DependencyStatement<'src>) -> Self {
 		node.range()
 	}
 }
 
-impl From<DependencyStatement> for Node {
-	fn from(node: DependencyStatement) -> Node {
+impl<'src> From<DependencyStatement<'src>> for Node<'src> {
+	fn from(node: DependencyStatement<'src>) -> Node<'src> {
 		Node::DependencyStatement(node)
 	}
 }
 
-#[derive(Debug)]
-pub struct DependencyDefaultSpecifier {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DependencyDefaultSpecifier<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub local: Option<Identifier>,
-	pub usage: Identifier,
+	pub local: Option<Identifier<'src>>,
+	pub usage: Identifier<'src>,
 }
 
-impl DependencyDefaultSpecifier {
+impl DependencyDefaultSpecifier<'_> {
 	pub fn range(&self) -> Range {
 		Range::new(self.start, self.end)
 	}
 }
 
-impl From<DependencyDefaultSpecifier> for Range {
-    fn from(node: DependencyDefaultSpecifier) -> Self {
+impl<'src> From<DependencyDefaultSpecifier<'src>> for Range {
+    fn from(node: DependencyDefaultSpecifier<'src>) -> Self {
         node.range()
     }
 }
 
-#[derive(Debug)]
-pub struct DependencyNamedSpecifier {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DependencyNamedSpecifier<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub imported: Identifier,
-	pub local: Option<Identifier>,
-	pub usage: Identifier,
+	pub imported: Identifier<'src>,
+	pub local: Option<Identifier<'src>>,
+	pub usage: Identifier<'src>,
 }
 
-impl DependencyNamedSpecifier {
+impl DependencyNamedSpecifier<'_> {
 	pub fn range(&self) -> Range {
 		Range::new(self.start, self.end)
 	}
 }
 
-impl From<DependencyNamedSpecifier> for Range {
-    fn from(node: DependencyNamedSpecifier) -> Self {
+impl<'src> From<DependencyNamedSpecifier<'src>> for Range {
+    fn from(node: DependencyNamedSpecifier<'src>) -> Self {
         node.range()
     }
 }
@@ -1,43 +1,51 @@
 use crate::ast::*;
 
-#[derive(Debug)]
-pub struct Element {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Element<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub tag_name: Identifier,
+	pub tag_name: Identifier<'src>,
 	pub start_tag: Range,
 	pub end_tag: Option<Range>,
-	pub attributes: Vec<Attribute>,
-	pub children: Vec<Node>,
+	pub attributes: Vec<Attribute<'src>>,
+	pub children: Vec<Node<'src>>,
 }
 
-impl Element {
+impl Element<'_> {
 	pub fn range(&self) -> Range {
 		Range::new(self.start, self.end)
 	}
 }
 
-impl From<Element> for Range {
-	fn from(node: Element) -> Self {
+impl<'src> From<Element<'src>> for Range {
+	fn from(node: Element<'src>) -> Self {
 		node.range()
 	}
 }
 
-impl From<Element> for Node {
-	fn from(node: Element) -> Node {
+impl<'src> From<Element<'src>> for Node<'src> {
+	fn from(node: Element<'src>) -> Node<'src> {
 		Node::Element(node)
 	}
 }
 
-#[derive(Debug)]
-pub enum Attribute {
-	Static(StaticAttribute),
-	Binding(BindingAttribute),
-	Spread(SpreadAttribute),
-	ShortBinding(ShortBindingAttribute),
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum Attribute<'src> {
+	#[cfg_attr(feature = "serde", serde(rename = "StaticAttribute"))]
+	Static(StaticAttribute<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "BindingAttribute"))]
+	Binding(BindingAttribute<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "SpreadAttribute"))]
+	Spread(SpreadAttribute<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "ShortBindingAttribute"))]
+	ShortBinding(ShortBindingAttribute<'src>),
 }
 
-impl Attribute {
+impl Attribute<'_> {
 	pub fn start(&self) -> usize {
 		match self {
 			Attribute::Static(range) => range.start,
@@ -61,86 +69,90 @@ impl Attribute {
 	}
 }
 
-impl From<Attribute> for Range {
-	fn from(node: Attribute) -> Self {
+impl<'src> From<Attribute<'src>> for Range {
+	fn from(node: Attribute<'src>) -> Self {
 		node.range()
 	}
 }
 
-#[derive(Debug)]
-pub struct StaticAttribute {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StaticAttribute<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub name: Identifier,
-	pub value: Option<StringLiteral>,
+	pub name: Identifier<'src>,
+	pub value: Option<StringLiteral<'src>>,
 }
 
-impl StaticAttribute {
+impl StaticAttribute<'_> {
 	pub fn range(&self) -> Range {
 		Range::new(self.start, self.end)
 	}
 }
 
-impl From<StaticAttribute> for Range {
-	fn from(node: StaticAttribute) -> Self {
+impl<'src> From<StaticAttribute<'src>> for Range {
+	fn from(node: StaticAttribute<'src>) -> Self {
 		node.range()
 	}
 }
 
-#[derive(Debug)]
-pub struct BindingAttribute {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BindingAttribute<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub name: Identifier,
-	pub value: javascript::Expression,
+	pub name: Identifier<'src>,
+	pub value: javascript::Expression<'src>,
 }
 
-impl BindingAttribute {
+impl BindingAttribute<'_> {
 	pub fn range(&self) -> Range {
 		Range::new(self.start, self.end)
 	}
 }
 
-impl From<BindingAttribute> for Range {
-	fn from(node: BindingAttribute) -> Self {
+impl<'src> From<BindingAttribute<'src>> for Range {
+	fn from(node: BindingAttribute<'src>) -> Self {
 		node.range()
 	}
 }
 
-#[derive(Debug)]
-pub struct SpreadAttribute {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpreadAttribute<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub value: javascript::Expression,
+	pub value: javascript::Expression<'src>,
 }
 
-impl SpreadAttribute {
+impl SpreadAttribute<'_> {
 	pub fn range(&self) -> Range {
 		Range::new(self.start, self.end)
 	}
 }
 
-impl From<SpreadAttribute> for Range {
-	fn from(node: SpreadAttribute) -> Self {
+impl<'src> From<SpreadAttribute<'src>> for Range {
+	fn from(node: SpreadAttribute<'src>) -> Self {
 		node.range()
 	}
 }
 
-#[derive(Debug)]
-pub struct ShortBindingAttribute {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShortBindingAttribute<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub name: javascript::IdentifierExpression,
+	pub name: javascript::IdentifierExpression<'src>,
 }
 
-impl ShortBindingAttribute {
+impl ShortBindingAttribute<'_> {
 	pub fn range(&self) -> Range {
 		Range::new(self.start, self.end)
 	}
 }
 
-impl From<ShortBindingAttribute> for Range {
-	fn from(node: ShortBindingAttribute) -> Self {
+impl<'src> From<ShortBindingAttribute<'src>> for Range {
+	fn from(node: ShortBindingAttribute<'src>) -> Self {
 		node.range()
 	}
 }
@@ -1,108 +1,315 @@
 use crate::ast::*;
 
-#[derive(Debug)]
-pub struct FlowControlWhen {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowControlWhen<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub condition: Box<javascript::Expression>,
-	pub children: Vec<Node>,
-	pub chain: Vec<FlowControlElse>,
+	pub condition: Box<javascript::Expression<'src>>,
+	pub children: Vec<Node<'src>>,
+	pub chain: Vec<FlowControlElse<'src>>,
 }
 
-impl FlowControlWhen {
+impl FlowControlWhen<'_> {
 	pub fn range(&self) -> Range {
 		Range::new(self.start, self.end)
 	}
 }
 
-impl From<FlowControlWhen> for Range {
-	fn from(node: FlowControlWhen) -> Self {
+impl<'src> From<FlowControlWhen<'src>> for Range {
+	fn from(node: FlowControlWhen<'src>) -> Self {
 		node.range()
 	}
 }
 
-impl From<FlowControlWhen> for Node {
-	fn from(node: FlowControlWhen) -> Node {
+impl<'src> From<FlowControlWhen<'src>> for Node<'src> {
+	fn from(node: FlowControlWhen<'src>) -> Node<'src> {
 		(FlowControl::from(node)).into()
 	}
 }
 
-impl From<FlowControlWhen> for FlowControl {
-	fn from(node: FlowControlWhen) -> FlowControl {
+impl<'src> From<FlowControlWhen<'src>> for FlowControl<'src> {
+	fn from(node: FlowControlWhen<'src>) -> FlowControl<'src> {
 		FlowControl::When(node)
 	}
 }
 
-#[derive(Debug)]
-pub struct FlowControlElse {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowControlElse<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub condition: Option<javascript::Expression>,
-	pub children: Vec<Node>,
+	pub condition: Option<javascript::Expression<'src>>,
+	pub children: Vec<Node<'src>>,
 }
 
-impl FlowControlElse {
+impl FlowControlElse<'_> {
 	pub fn range(&self) -> Range {
 		Range::new(self.start, self.end)
 	}
 }
 
-impl From<FlowControlElse> for Range {
-	fn from(node: FlowControlElse) -> Self {
+impl<'src> From<FlowControlElse<'src>> for Range {
+	fn from(node: FlowControlElse<'src>) -> Self {
 		node.range()
 	}
 }
 
-#[derive(Debug)]
-pub struct FlowControlEach {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowControlEach<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub iterator: Box<javascript::IdentifierExpression>,
-	pub iterable: Box<javascript::Expression>,
-	pub children: Vec<Node>,
+	/// The identifier, or object or array pattern, bound to each item.
+	pub iterator: Box<javascript::Expression<'src>>,
+	/// The optional binding for the index of the item, as in `{item, i in items}`.
+	pub index: Option<Box<javascript::IdentifierExpression<'src>>>,
+	pub iterable: Box<javascript::Expression<'src>>,
+	/// The optional key identifying each item, as in `{item in items; key item.id}`.
+	pub key: Option<Box<javascript::Expression<'src>>>,
+	pub children: Vec<Node<'src>>,
+	/// The `<#else>` branch, rendered while the iterable is empty. It never has a condition.
+	pub fallback: Option<Box<FlowControlElse<'src>>>,
 }
 
-impl FlowControlEach {
+impl FlowControlEach<'_> {
 	pub fn range(&self) -> Range {
 		Range::new(self.start, self.end)
 	}
 }
 
-impl From<FlowControlEach> for Range {
-	fn from(node: FlowControlEach) -> Self {
+impl<'src> From<FlowControlEach<'src>> for Range {
+	fn from(node: FlowControlEach<'src>) -> Self {
 		node.range()
 	}
 }
 
-impl From<FlowControlEach> for Node {
-	fn from(node: FlowControlEach) -> Node {
+impl<'src> From<FlowControlEach<'src>> for Node<'src> {
+	fn from(node: FlowControlEach<'src>) -> Node<'src> {
 		(FlowControl::from(node)).into()
 	}
 }
 
-impl From<FlowControlEach> for FlowControl {
-	fn from(node: FlowControlEach) -> FlowControl {
+impl<'src> From<FlowControlEach<'src>> for FlowControl<'src> {
+	fn from(node: FlowControlEach<'src>) -> FlowControl<'src> {
 		FlowControl::Each(node)
 	}
 }
 
-#[derive(Debug)]
-pub enum FlowControl {
-	When(FlowControlWhen),
-	Each(FlowControlEach),
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowControlLet<'src> {
+	pub start: usize,
+	pub end: usize,
+	/// The name the value is bound to within the children, as in `{city = user.address.city}`.
+	pub name: Box<javascript::IdentifierExpression<'src>>,
+	pub value: Box<javascript::Expression<'src>>,
+	pub children: Vec<Node<'src>>,
+}
+
+impl FlowControlLet<'_> {
+	pub fn range(&self) -> Range {
+		Range::new(self.start, self.end)
+	}
+}
+
+impl<'src> From<FlowControlLet<'src>> for Range {
+	fn from(node: FlowControlLet<'src>) -> Self {
+		node.range()
+	}
+}
+
+impl<'src> From<FlowControlLet<'src>> for Node<'src> {
+	fn from(node: FlowControlLet<'src>) -> Node<'src> {
+		(FlowControl::from(node)).into()
+	}
+}
+
+impl<'src> From<FlowControlLet<'src>> for FlowControl<'src> {
+	fn from(node: FlowControlLet<'src>) -> FlowControl<'src> {
+		FlowControl::Let(node)
+	}
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowControlMatch<'src> {
+	pub start: usize,
+	pub end: usize,
+	pub discriminant: Box<javascript::Expression<'src>>,
+	/// The cases, where a `<#default>` case can only be the last.
+	pub cases: Vec<FlowControlCase<'src>>,
+}
+
+impl FlowControlMatch<'_> {
+	pub fn range(&self) -> Range {
+		Range::new(self.start, self.end)
+	}
+}
+
+impl<'src> From<FlowControlMatch<'src>> for Range {
+	fn from(node: FlowControlMatch<'src>) -> Self {
+		node.range()
+	}
+}
+
+impl<'src> From<FlowControlMatch<'src>> for Node<'src> {
+	fn from(node: FlowControlMatch<'src>) -> Node<'src> {
+		(FlowControl::from(node)).into()
+	}
+}
+
+impl<'src> From<FlowControlMatch<'src>> for FlowControl<'src> {
+	fn from(node: FlowControlMatch<'src>) -> FlowControl<'src> {
+		FlowControl::Match(node)
+	}
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowControlCase<'src> {
+	pub start: usize,
+	pub end: usize,
+	/// The values matched by the case, as in `<#case {'a', 'b'}>`. Empty for `<#default>`.
+	pub tests: Vec<javascript::Expression<'src>>,
+	pub children: Vec<Node<'src>>,
+}
+
+impl FlowControlCase<'_> {
+	pub fn range(&self) -> Range {
+		Range::new(self.start, self.end)
+	}
+}
+
+impl<'src> From<FlowControlCase<'src>> for Range {
+	fn from(node: FlowControlCase<'src>) -> Self {
+		node.range()
+	}
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowControlAwait<'src> {
+	pub start: usize,
+	pub end: usize,
+	pub promise: Box<javascript::Expression<'src>>,
+	/// The children rendered while the promise is pending.
+	pub children: Vec<Node<'src>>,
+	/// The `<#then>` branch, rendered with the resolved value.
+	pub then: Option<Box<FlowControlAwaitBranch<'src>>>,
+	/// The `<#catch>` branch, rendered with the reason of the rejection.
+	pub catch: Option<Box<FlowControlAwaitBranch<'src>>>,
+}
+
+impl FlowControlAwait<'_> {
+	pub fn range(&self) -> Range {
+		Range::new(self.start, self.end)
+	}
+}
+
+impl<'src> From<FlowControlAwait<'src>> for Range {
+	fn from(node: FlowControlAwait<'src>) -> Self {
+		node.range()
+	}
+}
+
+impl<'src> From<FlowControlAwait<'src>> for Node<'src> {
+	fn from(node: FlowControlAwait<'src>) -> Node<'src> {
+		(FlowControl::from(node)).into()
+	}
+}
+
+impl<'src> From<FlowControlAwait<'src>> for FlowControl<'src> {
+	fn from(node: FlowControlAwait<'src>) -> FlowControl<'src> {
+		FlowControl::Await(node)
+	}
+}
+
+/// A `<#then>` or `<#catch>` branch of an await block, or the `<#catch>` branch of a try block.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowControlAwaitBranch<'src> {
+	pub start: usize,
+	pub end: usize,
+	/// The optional identifier, or object or array pattern, bound to the settled value, as in
+	/// `<#then {user}>`.
+	pub binding: Option<Box<javascript::Expression<'src>>>,
+	pub children: Vec<Node<'src>>,
+}
+
+impl FlowControlAwaitBranch<'_> {
+	pub fn range(&self) -> Range {
+		Range::new(self.start, self.end)
+	}
+}
+
+impl<'src> From<FlowControlAwaitBranch<'src>> for Range {
+	fn from(node: FlowControlAwaitBranch<'src>) -> Self {
+		node.range()
+	}
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowControlTry<'src> {
+	pub start: usize,
+	pub end: usize,
+	pub children: Vec<Node<'src>>,
+	/// The `<#catch>` branch, rendered with the error when the children throw.
+	pub catch: Box<FlowControlAwaitBranch<'src>>,
+}
+
+impl FlowControlTry<'_> {
+	pub fn range(&self) -> Range {
+		Range::new(self.start, self.end)
+	}
+}
+
+impl<'src> From<FlowControlTry<'src>> for Range {
+	fn from(node: FlowControlTry<'src>) -> Self {
+		node.range()
+	}
+}
+
+impl<'src> From<FlowControlTry<'src>> for Node<'src> {
+	fn from(node: FlowControlTry<'src>) -> Node<'src> {
+		(FlowControl::from(node)).into()
+	}
+}
+
+impl<'src> From<FlowControlTry<'src>> for FlowControl<'src> {
+	fn from(node: FlowControlTry<'src>) -> FlowControl<'src> {
+		FlowControl::Try(node)
+	}
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
+pub enum FlowControl<'src> {
+	When(FlowControlWhen<'src>),
+	Each(FlowControlEach<'src>),
+	Let(FlowControlLet<'src>),
+	Match(FlowControlMatch<'src>),
+	Await(FlowControlAwait<'src>),
+	Try(FlowControlTry<'src>),
 }
 
-impl From<FlowControl> for Node {
-	fn from(node: FlowControl) -> Node {
+impl<'src> From<FlowControl<'src>> for Node<'src> {
+	fn from(node: FlowControl<'src>) -> Node<'src> {
 		Node::FlowControl(node)
 	}
 }
 
-impl FlowControl {
+impl FlowControl<'_> {
 	pub fn start(&self) -> usize {
 		match self {
 			FlowControl::When(node) => node.start,
 			FlowControl::Each(node) => node.start,
+			FlowControl::Let(node) => node.start,
+			FlowControl::Match(node) => node.start,
+			FlowControl::Await(node) => node.start,
+			FlowControl::Try(node) => node.start,
 		}
 	}
 
@@ -110,6 +317,10 @@ impl FlowControl {
 		match self {
 			FlowControl::When(node) => node.end,
 			FlowControl::Each(node) => node.end,
+			FlowControl::Let(node) => node.end,
+			FlowControl::Match(node) => node.end,
+			FlowControl::Await(node) => node.end,
+			FlowControl::Try(node) => node.end,
 		}
 	}
 	
@@ -118,8 +329,8 @@ impl FlowControl {
 	}
 }
 
-impl From<FlowControl> for Range {
-	fn from(node: FlowControl) -> Self {
+impl<'src> From<FlowControl<'src>> for Range {
+	fn from(node: FlowControl<'src>) -> Self {
 		node.range()
 	}
 }
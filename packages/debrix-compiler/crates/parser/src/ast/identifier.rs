@@ -1,20 +1,22 @@
 use crate::ast::*;
+use std::borrow::Cow;
 
-#[derive(Debug)]
-pub struct Identifier {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Identifier<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub name: String,
+	pub name: Cow<'src, str>,
 }
 
-impl Identifier {
+impl Identifier<'_> {
 	pub fn range(&self) -> Range {
 		Range::new(self.start, self.end)
 	}
 }
 
-impl From<Identifier> for Range {
-	fn from(node: Identifier) -> Self {
+impl<'src> From<Identifier<'src>> for Range {
+	fn from(node: Identifier<'src>) -> Self {
 		node.range()
 	}
 }
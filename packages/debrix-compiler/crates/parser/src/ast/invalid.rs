@@ -0,0 +1,27 @@
+use crate::ast::*;
+
+/// Placeholder for input which could not be parsed, when recovering from errors.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Invalid {
+	pub start: usize,
+	pub end: usize,
+}
+
+impl Invalid {
+	pub fn range(&self) -> Range {
+		Range::new(self.start, self.end)
+	}
+}
+
+impl From<Invalid> for Range {
+	fn from(node: Invalid) -> Self {
+		node.range()
+	}
+}
+
+impl<'src> From<Invalid> for Node<'src> {
+	fn from(node: Invalid) -> Node<'src> {
+		Node::Invalid(node)
+	}
+}
@@ -1,41 +1,79 @@
-#[derive(Debug)]
-pub enum Expression {
-	Identifier(IdentifierExpression),
-	Literal(Literal),
-	Unary(UnaryExpression),
-	Binary(BinaryExpression),
-	Conditional(ConditionalExpression),
-	Call(CallExpression),
-	New(NewExpression),
-	Member(MemberExpression),
-	Function(FunctionExpression),
-	Assignment(AssignmentExpression),
-	Spread(SpreadExpression),
-	Template(TemplateLiteral),
-	TaggedTemplate(TaggedTemplateExpression),
-	Object(ObjectExpression),
-	Array(ArrayExpression),
-	Parenthesized(ParenthesizedExpression),
+use std::borrow::Cow;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum Expression<'src> {
+	Identifier(IdentifierExpression<'src>),
+	Literal(Literal<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "ThisExpression"))]
+	This(ThisExpression),
+	#[cfg_attr(feature = "serde", serde(rename = "UnaryExpression"))]
+	Unary(UnaryExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "BinaryExpression"))]
+	Binary(BinaryExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "ConditionalExpression"))]
+	Conditional(ConditionalExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "CallExpression"))]
+	Call(CallExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "NewExpression"))]
+	New(NewExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "MemberExpression"))]
+	Member(MemberExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "SequenceExpression"))]
+	Sequence(SequenceExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "AwaitExpression"))]
+	Await(AwaitExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "ArrowFunctionExpression"))]
+	Function(FunctionExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "AssignmentExpression"))]
+	Assignment(AssignmentExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "SpreadElement"))]
+	Spread(SpreadExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "TemplateLiteral"))]
+	Template(TemplateLiteral<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "TaggedTemplateExpression"))]
+	TaggedTemplate(TaggedTemplateExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "ObjectExpression"))]
+	Object(ObjectExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "ArrayExpression"))]
+	Array(ArrayExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "ParenthesizedExpression"))]
+	Parenthesized(ParenthesizedExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "EmptyExpression"))]
 	Empty(EmptyExpression),
+	#[cfg_attr(feature = "serde", serde(rename = "TSAsExpression"))]
+	TsAs(TsAsExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "TSSatisfiesExpression"))]
+	TsSatisfies(TsSatisfiesExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "TSNonNullExpression"))]
+	TsNonNull(TsNonNullExpression<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "TSAnnotatedExpression"))]
+	TsAnnotated(TsAnnotatedExpression<'src>),
 }
 
-impl Expression {
+impl Expression<'_> {
 	pub fn start(&self) -> usize {
 		match self {
 			Expression::Literal(literal) => match literal {
 				Literal::String(literal) => literal.start,
 				Literal::Number(literal) => literal.start,
+				Literal::BigInt(literal) => literal.start,
 				Literal::Boolean(literal) => literal.start,
 				Literal::Null(literal) => literal.start,
+				Literal::RegExp(literal) => literal.start,
 			},
 
 			Expression::Identifier(expr) => expr.start,
+			Expression::This(expr) => expr.start,
 			Expression::Unary(expr) => expr.start,
 			Expression::Binary(expr) => expr.start,
 			Expression::Conditional(expr) => expr.start,
 			Expression::Call(expr) => expr.start,
 			Expression::New(expr) => expr.start,
 			Expression::Member(expr) => expr.start,
+			Expression::Sequence(expr) => expr.start,
+			Expression::Await(expr) => expr.start,
 			Expression::Function(expr) => expr.start,
 			Expression::Assignment(expr) => expr.start,
 			Expression::Spread(expr) => expr.start,
@@ -45,6 +83,10 @@ impl Expression {
 			Expression::Array(expr) => expr.start,
 			Expression::Parenthesized(expr) => expr.start,
 			Expression::Empty(expr) => expr.start,
+			Expression::TsAs(expr) => expr.start,
+			Expression::TsSatisfies(expr) => expr.start,
+			Expression::TsNonNull(expr) => expr.start,
+			Expression::TsAnnotated(expr) => expr.start,
 		}
 	}
 
@@ -53,17 +95,22 @@ impl Expression {
 			Expression::Literal(literal) => match literal {
 				Literal::String(literal) => literal.end,
 				Literal::Number(literal) => literal.end,
+				Literal::BigInt(literal) => literal.end,
 				Literal::Boolean(literal) => literal.end,
 				Literal::Null(literal) => literal.end,
+				Literal::RegExp(literal) => literal.end,
 			},
 
 			Expression::Identifier(expr) => expr.end,
+			Expression::This(expr) => expr.end,
 			Expression::Unary(expr) => expr.end,
 			Expression::Binary(expr) => expr.end,
 			Expression::Conditional(expr) => expr.end,
 			Expression::Call(expr) => expr.end,
 			Expression::New(expr) => expr.end,
 			Expression::Member(expr) => expr.end,
+			Expression::Sequence(expr) => expr.end,
+			Expression::Await(expr) => expr.end,
 			Expression::Function(expr) => expr.end,
 			Expression::Assignment(expr) => expr.end,
 			Expression::Spread(expr) => expr.end,
@@ -73,169 +120,245 @@ impl Expression {
 			Expression::Array(expr) => expr.end,
 			Expression::Parenthesized(expr) => expr.end,
 			Expression::Empty(expr) => expr.end,
+			Expression::TsAs(expr) => expr.end,
+			Expression::TsSatisfies(expr) => expr.end,
+			Expression::TsNonNull(expr) => expr.end,
+			Expression::TsAnnotated(expr) => expr.end,
 		}
 	}
 }
 
-impl From<Literal> for Expression {
-	fn from(expression: Literal) -> Self {
+impl<'src> From<Literal<'src>> for Expression<'src> {
+	fn from(expression: Literal<'src>) -> Self {
 		Expression::Literal(expression)
 	}
 }
 
-impl From<StringLiteral> for Literal {
-	fn from(literal: StringLiteral) -> Self {
+impl<'src> From<StringLiteral<'src>> for Literal<'src> {
+	fn from(literal: StringLiteral<'src>) -> Self {
 		Literal::String(literal)
 	}
 }
 
-impl From<NumberLiteral> for Literal {
-	fn from(literal: NumberLiteral) -> Self {
+impl<'src> From<NumberLiteral<'src>> for Literal<'src> {
+	fn from(literal: NumberLiteral<'src>) -> Self {
 		Literal::Number(literal)
 	}
 }
 
-impl From<BooleanLiteral> for Literal {
-	fn from(literal: BooleanLiteral) -> Self {
+impl<'src> From<BigIntLiteral<'src>> for Literal<'src> {
+	fn from(literal: BigIntLiteral<'src>) -> Self {
+		Literal::BigInt(literal)
+	}
+}
+
+impl<'src> From<BooleanLiteral<'src>> for Literal<'src> {
+	fn from(literal: BooleanLiteral<'src>) -> Self {
 		Literal::Boolean(literal)
 	}
 }
 
-impl From<NullLiteral> for Literal {
-	fn from(literal: NullLiteral) -> Self {
+impl<'src> From<NullLiteral<'src>> for Literal<'src> {
+	fn from(literal: NullLiteral<'src>) -> Self {
 		Literal::Null(literal)
 	}
 }
 
-impl From<StringLiteral> for Expression {
-	fn from(literal: StringLiteral) -> Self {
+impl<'src> From<RegExpLiteral<'src>> for Literal<'src> {
+	fn from(literal: RegExpLiteral<'src>) -> Self {
+		Literal::RegExp(literal)
+	}
+}
+
+impl<'src> From<StringLiteral<'src>> for Expression<'src> {
+	fn from(literal: StringLiteral<'src>) -> Self {
+		Literal::from(literal).into()
+	}
+}
+
+impl<'src> From<NumberLiteral<'src>> for Expression<'src> {
+	fn from(literal: NumberLiteral<'src>) -> Self {
 		Literal::from(literal).into()
 	}
 }
 
-impl From<NumberLiteral> for Expression {
-	fn from(literal: NumberLiteral) -> Self {
+impl<'src> From<BigIntLiteral<'src>> for Expression<'src> {
+	fn from(literal: BigIntLiteral<'src>) -> Self {
 		Literal::from(literal).into()
 	}
 }
 
-impl From<BooleanLiteral> for Expression {
-	fn from(literal: BooleanLiteral) -> Self {
+impl<'src> From<BooleanLiteral<'src>> for Expression<'src> {
+	fn from(literal: BooleanLiteral<'src>) -> Self {
 		Literal::from(literal).into()
 	}
 }
 
-impl From<NullLiteral> for Expression {
-	fn from(literal: NullLiteral) -> Self {
+impl<'src> From<NullLiteral<'src>> for Expression<'src> {
+	fn from(literal: NullLiteral<'src>) -> Self {
 		Literal::from(literal).into()
 	}
 }
 
-impl From<TemplateLiteral> for Expression {
-	fn from(literal: TemplateLiteral) -> Expression {
+impl<'src> From<RegExpLiteral<'src>> for Expression<'src> {
+	fn from(literal: RegExpLiteral<'src>) -> Self {
+		Literal::from(literal).into()
+	}
+}
+
+impl<'src> From<TemplateLiteral<'src>> for Expression<'src> {
+	fn from(literal: TemplateLiteral<'src>) -> Expression<'src> {
 		Expression::Template(literal)
 	}
 }
 
-impl From<IdentifierExpression> for Expression {
-	fn from(expression: IdentifierExpression) -> Expression {
+impl<'src> From<IdentifierExpression<'src>> for Expression<'src> {
+	fn from(expression: IdentifierExpression<'src>) -> Expression<'src> {
 		Expression::Identifier(expression)
 	}
 }
 
-impl From<UnaryExpression> for Expression {
-	fn from(expression: UnaryExpression) -> Expression {
+impl From<ThisExpression> for Expression<'_> {
+	fn from(expression: ThisExpression) -> Self {
+		Expression::This(expression)
+	}
+}
+
+impl<'src> From<UnaryExpression<'src>> for Expression<'src> {
+	fn from(expression: UnaryExpression<'src>) -> Expression<'src> {
 		Expression::Unary(expression)
 	}
 }
 
-impl From<BinaryExpression> for Expression {
-	fn from(expression: BinaryExpression) -> Expression {
+impl<'src> From<BinaryExpression<'src>> for Expression<'src> {
+	fn from(expression: BinaryExpression<'src>) -> Expression<'src> {
 		Expression::Binary(expression)
 	}
 }
 
-impl From<ConditionalExpression> for Expression {
-	fn from(expression: ConditionalExpression) -> Expression {
+impl<'src> From<ConditionalExpression<'src>> for Expression<'src> {
+	fn from(expression: ConditionalExpression<'src>) -> Expression<'src> {
 		Expression::Conditional(expression)
 	}
 }
 
-impl From<CallExpression> for Expression {
-	fn from(expression: CallExpression) -> Expression {
+impl<'src> From<CallExpression<'src>> for Expression<'src> {
+	fn from(expression: CallExpression<'src>) -> Expression<'src> {
 		Expression::Call(expression)
 	}
 }
 
-impl From<NewExpression> for Expression {
-	fn from(expression: NewExpression) -> Expression {
+impl<'src> From<NewExpression<'src>> for Expression<'src> {
+	fn from(expression: NewExpression<'src>) -> Expression<'src> {
 		Expression::New(expression)
 	}
 }
 
-impl From<MemberExpression> for Expression {
-	fn from(expression: MemberExpression) -> Expression {
+impl<'src> From<MemberExpression<'src>> for Expression<'src> {
+	fn from(expression: MemberExpression<'src>) -> Expression<'src> {
 		Expression::Member(expression)
 	}
 }
 
-impl From<FunctionExpression> for Expression {
-	fn from(expression: FunctionExpression) -> Expression {
+impl<'src> From<SequenceExpression<'src>> for Expression<'src> {
+	fn from(expression: SequenceExpression<'src>) -> Expression<'src> {
+		Expression::Sequence(expression)
+	}
+}
+
+impl<'src> From<AwaitExpression<'src>> for Expression<'src> {
+	fn from(expression: AwaitExpression<'src>) -> Expression<'src> {
+		Expression::Await(expression)
+	}
+}
+
+impl<'src> From<FunctionExpression<'src>> for Expression<'src> {
+	fn from(expression: FunctionExpression<'src>) -> Expression<'src> {
 		Expression::Function(expression)
 	}
 }
 
-impl From<AssignmentExpression> for Expression {
-	fn from(expression: AssignmentExpression) -> Expression {
+impl<'src> From<AssignmentExpression<'src>> for Expression<'src> {
+	fn from(expression: AssignmentExpression<'src>) -> Expression<'src> {
 		Expression::Assignment(expression)
 	}
 }
 
-impl From<SpreadExpression> for Expression {
-	fn from(expression: SpreadExpression) -> Expression {
+impl<'src> From<SpreadExpression<'src>> for Expression<'src> {
+	fn from(expression: SpreadExpression<'src>) -> Expression<'src> {
 		Expression::Spread(expression)
 	}
 }
 
-impl From<TaggedTemplateExpression> for Expression {
-	fn from(expression: TaggedTemplateExpression) -> Expression {
+impl<'src> From<TaggedTemplateExpression<'src>> for Expression<'src> {
+	fn from(expression: TaggedTemplateExpression<'src>) -> Expression<'src> {
 		Expression::TaggedTemplate(expression)
 	}
 }
 
-impl From<ObjectExpression> for Expression {
-	fn from(expression: ObjectExpression) -> Expression {
+impl<'src> From<ObjectExpression<'src>> for Expression<'src> {
+	fn from(expression: ObjectExpression<'src>) -> Expression<'src> {
 		Expression::Object(expression)
 	}
 }
 
-impl From<ArrayExpression> for Expression {
-	fn from(expression: ArrayExpression) -> Expression {
+impl<'src> From<ArrayExpression<'src>> for Expression<'src> {
+	fn from(expression: ArrayExpression<'src>) -> Expression<'src> {
 		Expression::Array(expression)
 	}
 }
 
-impl From<ParenthesizedExpression> for Expression {
-	fn from(expression: ParenthesizedExpression) -> Expression {
+impl<'src> From<ParenthesizedExpression<'src>> for Expression<'src> {
+	fn from(expression: ParenthesizedExpression<'src>) -> Expression<'src> {
 		Expression::Parenthesized(expression)
 	}
 }
 
-#[derive(Debug)]
-pub enum Literal {
-	String(StringLiteral),
-	Number(NumberLiteral),
-	Boolean(BooleanLiteral),
-	Null(NullLiteral),
+impl<'src> From<TsAsExpression<'src>> for Expression<'src> {
+	fn from(expression: TsAsExpression<'src>) -> Self {
+		Expression::TsAs(expression)
+	}
+}
+
+impl<'src> From<TsSatisfiesExpression<'src>> for Expression<'src> {
+	fn from(expression: TsSatisfiesExpression<'src>) -> Self {
+		Expression::TsSatisfies(expression)
+	}
+}
+
+impl<'src> From<TsNonNullExpression<'src>> for Expression<'src> {
+	fn from(expression: TsNonNullExpression<'src>) -> Self {
+		Expression::TsNonNull(expression)
+	}
+}
+
+impl<'src> From<TsAnnotatedExpression<'src>> for Expression<'src> {
+	fn from(expression: TsAnnotatedExpression<'src>) -> Self {
+		Expression::TsAnnotated(expression)
+	}
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
+pub enum Literal<'src> {
+	String(StringLiteral<'src>),
+	Number(NumberLiteral<'src>),
+	BigInt(BigIntLiteral<'src>),
+	Boolean(BooleanLiteral<'src>),
+	Null(NullLiteral<'src>),
+	RegExp(RegExpLiteral<'src>),
 }
 
-impl Literal {
+impl Literal<'_> {
 	pub fn start(&self) -> usize {
 		match self {
 			Literal::String(literal) => literal.start,
 			Literal::Number(literal) => literal.start,
+			Literal::BigInt(literal) => literal.start,
 			Literal::Boolean(literal) => literal.start,
 			Literal::Null(literal) => literal.start,
+			Literal::RegExp(literal) => literal.start,
 		}
 	}
 
@@ -243,8 +366,10 @@ impl Literal {
 		match self {
 			Literal::String(literal) => literal.end,
 			Literal::Number(literal) => literal.end,
+			Literal::BigInt(literal) => literal.end,
 			Literal::Boolean(literal) => literal.end,
 			Literal::Null(literal) => literal.end,
+			Literal::RegExp(literal) => literal.end,
 		}
 	}
 
@@ -252,54 +377,90 @@ impl Literal {
 		match self {
 			Literal::String(literal) => &literal.raw,
 			Literal::Number(literal) => &literal.raw,
+			Literal::BigInt(literal) => &literal.raw,
 			Literal::Boolean(literal) => &literal.raw,
 			Literal::Null(literal) => &literal.raw,
+			Literal::RegExp(literal) => &literal.raw,
 		}
 	}
 }
 
-#[derive(Debug)]
-pub struct StringLiteral {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StringLiteral<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub value: String,
+	pub value: Cow<'src, str>,
 	pub quote: char,
-	pub raw: String,
+	pub raw: Cow<'src, str>,
 }
 
-#[derive(Debug)]
-pub struct NumberLiteral {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NumberLiteral<'src> {
 	pub start: usize,
 	pub end: usize,
 	pub value: f64,
-	pub raw: String,
+	pub raw: Cow<'src, str>,
 }
 
-#[derive(Debug)]
-pub struct BooleanLiteral {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BigIntLiteral<'src> {
+	pub start: usize,
+	pub end: usize,
+	/// The digits without separators and the `n` suffix, like `0xff` for `0xf_fn`.
+	pub value: Cow<'src, str>,
+	pub raw: Cow<'src, str>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BooleanLiteral<'src> {
 	pub start: usize,
 	pub end: usize,
 	pub value: bool,
-	pub raw: String,
+	pub raw: Cow<'src, str>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NullLiteral<'src> {
+	pub start: usize,
+	pub end: usize,
+	pub raw: Cow<'src, str>,
 }
 
-#[derive(Debug)]
-pub struct NullLiteral {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegExpLiteral<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub raw: String,
+	pub pattern: Cow<'src, str>,
+	pub flags: Cow<'src, str>,
+	pub raw: Cow<'src, str>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UnaryOperator {
+	#[cfg_attr(feature = "serde", serde(rename = "-"))]
 	Minus,
+	#[cfg_attr(feature = "serde", serde(rename = "+"))]
 	Plus,
+	#[cfg_attr(feature = "serde", serde(rename = "++"))]
 	Increment,
+	#[cfg_attr(feature = "serde", serde(rename = "--"))]
 	Decrement,
+	#[cfg_attr(feature = "serde", serde(rename = "!"))]
 	Not,
+	#[cfg_attr(feature = "serde", serde(rename = "~"))]
 	BitwiseNot,
+	#[cfg_attr(feature = "serde", serde(rename = "typeof"))]
 	Typeof,
+	#[cfg_attr(feature = "serde", serde(rename = "void"))]
 	Void,
+	#[cfg_attr(feature = "serde", serde(rename = "delete"))]
 	Delete,
 }
 
@@ -320,31 +481,58 @@ impl ToString for UnaryOperator {
 	}
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BinaryOperator {
+	#[cfg_attr(feature = "serde", serde(rename = "+"))]
 	Plus,
+	#[cfg_attr(feature = "serde", serde(rename = "-"))]
 	Minus,
+	#[cfg_attr(feature = "serde", serde(rename = "*"))]
 	Multiply,
+	#[cfg_attr(feature = "serde", serde(rename = "/"))]
 	Divide,
+	#[cfg_attr(feature = "serde", serde(rename = "%"))]
 	Modulo,
+	#[cfg_attr(feature = "serde", serde(rename = "**"))]
 	Exponent,
+	#[cfg_attr(feature = "serde", serde(rename = "<<"))]
 	LeftShift,
+	#[cfg_attr(feature = "serde", serde(rename = ">>"))]
 	RightShift,
+	#[cfg_attr(feature = "serde", serde(rename = ">>>"))]
 	UnsignedRightShift,
+	#[cfg_attr(feature = "serde", serde(rename = "<"))]
 	LessThan,
+	#[cfg_attr(feature = "serde", serde(rename = ">"))]
 	GreaterThan,
+	#[cfg_attr(feature = "serde", serde(rename = "<="))]
 	LessThanOrEqual,
+	#[cfg_attr(feature = "serde", serde(rename = ">="))]
 	GreaterThanOrEqual,
+	#[cfg_attr(feature = "serde", serde(rename = "=="))]
 	Equal,
+	#[cfg_attr(feature = "serde", serde(rename = "!="))]
 	NotEqual,
+	#[cfg_attr(feature = "serde", serde(rename = "==="))]
 	StrictEqual,
+	#[cfg_attr(feature = "serde", serde(rename = "!=="))]
 	StrictNotEqual,
+	#[cfg_attr(feature = "serde", serde(rename = "&"))]
 	BitwiseAnd,
+	#[cfg_attr(feature = "serde", serde(rename = "|"))]
 	BitwiseOr,
+	#[cfg_attr(feature = "serde", serde(rename = "^"))]
 	BitwiseXor,
+	#[cfg_attr(feature = "serde", serde(rename = "&&"))]
 	LogicalAnd,
+	#[cfg_attr(feature = "serde", serde(rename = "||"))]
 	LogicalOr,
+	#[cfg_attr(feature = "serde", serde(rename = "??"))]
+	NullishCoalescing,
+	#[cfg_attr(feature = "serde", serde(rename = "instanceof"))]
 	InstanceOf,
+	#[cfg_attr(feature = "serde", serde(rename = "in"))]
 	In,
 }
 
@@ -373,6 +561,7 @@ impl ToString for BinaryOperator {
 			BinaryOperator::BitwiseXor => "^",
 			BinaryOperator::LogicalAnd => "&&",
 			BinaryOperator::LogicalOr => "||",
+			BinaryOperator::NullishCoalescing => "??",
 			BinaryOperator::InstanceOf => "instanceof",
 			BinaryOperator::In => "in",
 		}
@@ -380,21 +569,41 @@ impl ToString for BinaryOperator {
 	}
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AssignmentOperator {
+	#[cfg_attr(feature = "serde", serde(rename = "="))]
 	Equal,
+	#[cfg_attr(feature = "serde", serde(rename = "+="))]
 	PlusEqual,
+	#[cfg_attr(feature = "serde", serde(rename = "-="))]
 	MinusEqual,
+	#[cfg_attr(feature = "serde", serde(rename = "*="))]
 	MultiplyEqual,
+	#[cfg_attr(feature = "serde", serde(rename = "/="))]
 	DivideEqual,
+	#[cfg_attr(feature = "serde", serde(rename = "%="))]
 	ModuloEqual,
+	#[cfg_attr(feature = "serde", serde(rename = "**="))]
 	ExponentEqual,
+	#[cfg_attr(feature = "serde", serde(rename = "<<="))]
 	LeftShiftEqual,
+	#[cfg_attr(feature = "serde", serde(rename = ">>="))]
 	RightShiftEqual,
+	#[cfg_attr(feature = "serde", serde(rename = ">>>="))]
 	UnsignedRightShiftEqual,
+	#[cfg_attr(feature = "serde", serde(rename = "&="))]
 	BitwiseAndEqual,
+	#[cfg_attr(feature = "serde", serde(rename = "|="))]
 	BitwiseOrEqual,
+	#[cfg_attr(feature = "serde", serde(rename = "^="))]
 	BitwiseXorEqual,
+	#[cfg_attr(feature = "serde", serde(rename = "&&="))]
+	LogicalAndEqual,
+	#[cfg_attr(feature = "serde", serde(rename = "||="))]
+	LogicalOrEqual,
+	#[cfg_attr(feature = "serde", serde(rename = "??="))]
+	NullishEqual,
 }
 
 impl ToString for AssignmentOperator {
@@ -402,7 +611,7 @@ impl ToString for AssignmentOperator {
 		match self {
 			AssignmentOperator::Equal => "=",
 			AssignmentOperator::PlusEqual => "+=",
-			AssignmentOperator::MinusEqual => "-+",
+			AssignmentOperator::MinusEqual => "-=",
 			AssignmentOperator::MultiplyEqual => "*=",
 			AssignmentOperator::DivideEqual => "/=",
 			AssignmentOperator::ModuloEqual => "%=",
@@ -413,162 +622,517 @@ impl ToString for AssignmentOperator {
 			AssignmentOperator::BitwiseAndEqual => "&=",
 			AssignmentOperator::BitwiseOrEqual => "|=",
 			AssignmentOperator::BitwiseXorEqual => "^=",
+			AssignmentOperator::LogicalAndEqual => "&&=",
+			AssignmentOperator::LogicalOrEqual => "||=",
+			AssignmentOperator::NullishEqual => "??=",
 		}
 		.to_owned()
 	}
 }
 
-#[derive(Debug)]
-pub struct IdentifierExpression {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IdentifierExpression<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub name: String,
+	pub name: Cow<'src, str>,
 }
 
-#[derive(Debug)]
-pub struct UnaryExpression {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThisExpression {
+	pub start: usize,
+	pub end: usize,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnaryExpression<'src> {
 	pub start: usize,
 	pub end: usize,
 	pub operator: UnaryOperator,
-	pub operand: Box<Expression>,
+	#[cfg_attr(feature = "serde", serde(rename = "argument"))]
+	pub operand: Box<Expression<'src>>,
+	/// `false` for `a++` and `a--`.
+	pub prefix: bool,
 }
 
-#[derive(Debug)]
-pub struct BinaryExpression {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinaryExpression<'src> {
 	pub start: usize,
 	pub end: usize,
 	pub operator: BinaryOperator,
-	pub left: Box<Expression>,
-	pub right: Box<Expression>,
+	pub left: Box<Expression<'src>>,
+	pub right: Box<Expression<'src>>,
 }
 
-#[derive(Debug)]
-pub struct ConditionalExpression {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConditionalExpression<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub condition: Box<Expression>,
-	pub consequent: Box<Expression>,
-	pub alternate: Box<Expression>,
+	#[cfg_attr(feature = "serde", serde(rename = "test"))]
+	pub condition: Box<Expression<'src>>,
+	pub consequent: Box<Expression<'src>>,
+	pub alternate: Box<Expression<'src>>,
 }
 
-#[derive(Debug)]
-pub struct CallExpression {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallExpression<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub callee: Box<Expression>,
-	pub arguments: Vec<Expression>,
+	pub callee: Box<Expression<'src>>,
+	pub arguments: Vec<Expression<'src>>,
+	pub optional: bool,
 }
 
-#[derive(Debug)]
-pub struct NewExpression {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NewExpression<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub callee: Box<Expression>,
-	pub arguments: Vec<Expression>,
+	pub callee: Box<Expression<'src>>,
+	pub arguments: Vec<Expression<'src>>,
 }
 
-#[derive(Debug)]
-pub struct MemberExpression {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemberExpression<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub object: Box<Expression>,
-	pub property: Box<Expression>,
+	pub object: Box<Expression<'src>>,
+	pub property: Box<Expression<'src>>,
 	pub computed: bool,
 	pub optional: bool,
 }
 
-#[derive(Debug)]
-pub struct SequenceExpression {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SequenceExpression<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub expressions: Vec<Expression>,
+	pub expressions: Vec<Expression<'src>>,
 }
 
-#[derive(Debug)]
-pub struct FunctionExpression {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AwaitExpression<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub parameters: Vec<Expression>,
-	pub body: Box<Expression>,
+	pub argument: Box<Expression<'src>>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FunctionExpression<'src> {
+	pub start: usize,
+	pub end: usize,
+	#[cfg_attr(feature = "serde", serde(rename = "async"))]
+	pub is_async: bool,
+	/// Identifiers, or objects and arrays destructuring the argument as in an assignment. A
+	/// default value is an `AssignmentExpression`, and the rest parameter a `SpreadExpression`.
+	#[cfg_attr(feature = "serde", serde(rename = "params"))]
+	pub parameters: Vec<Expression<'src>>,
+	/// The `<T>` before the parameters of a generic function, including the brackets.
+	#[cfg_attr(feature = "serde", serde(rename = "typeParameters"))]
+	pub type_parameters: Option<TsType<'src>>,
+	#[cfg_attr(feature = "serde", serde(rename = "returnType"))]
+	pub return_type: Option<TsType<'src>>,
+	pub body: FunctionBody<'src>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum FunctionBody<'src> {
+	Expression(Box<Expression<'src>>),
+	Block(BlockStatement<'src>),
+}
+
+impl FunctionBody<'_> {
+	pub fn start(&self) -> usize {
+		match self {
+			FunctionBody::Expression(expr) => expr.start(),
+			FunctionBody::Block(block) => block.start,
+		}
+	}
+
+	pub fn end(&self) -> usize {
+		match self {
+			FunctionBody::Expression(expr) => expr.end(),
+			FunctionBody::Block(block) => block.end,
+		}
+	}
 }
 
-#[derive(Debug)]
-pub struct AssignmentExpression {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AssignmentExpression<'src> {
 	pub start: usize,
 	pub end: usize,
 	pub operator: AssignmentOperator,
-	pub left: Box<Expression>,
-	pub right: Box<Expression>,
+	pub left: Box<Expression<'src>>,
+	pub right: Box<Expression<'src>>,
 }
 
-#[derive(Debug)]
-pub struct SpreadExpression {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpreadExpression<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub argument: Box<Expression>,
+	pub argument: Box<Expression<'src>>,
 }
 
-#[derive(Debug)]
-pub struct TemplateLiteral {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TemplateLiteral<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub raw: String,
+	pub quasis: Vec<TemplateElement<'src>>,
+	pub expressions: Vec<Expression<'src>>,
 }
 
-#[derive(Debug)]
-pub struct TaggedTemplateExpression {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TemplateElement<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub tag: Box<Expression>,
-	pub quasi: Box<TemplateLiteral>,
+	pub raw: Cow<'src, str>,
+	/// `None` if the raw text has an invalid escape, which only tagged templates allow.
+	pub cooked: Option<Cow<'src, str>>,
 }
 
-#[derive(Debug)]
-pub struct ObjectExpression {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TaggedTemplateExpression<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub properties: Vec<ObjectProperty>,
+	pub tag: Box<Expression<'src>>,
+	pub quasi: Box<TemplateLiteral<'src>>,
 }
 
-#[derive(Debug)]
-pub enum ObjectProperty {
-	Keyed(ObjectKeyedProperty),
-	Computed(ObjectComputedProperty),
-	Spread(SpreadExpression),
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectExpression<'src> {
+	pub start: usize,
+	pub end: usize,
+	pub properties: Vec<ObjectProperty<'src>>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum ObjectProperty<'src> {
+	#[cfg_attr(feature = "serde", serde(rename = "Property"))]
+	Keyed(ObjectKeyedProperty<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "ComputedProperty"))]
+	Computed(ObjectComputedProperty<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "SpreadElement"))]
+	Spread(SpreadExpression<'src>),
 }
 
-#[derive(Debug)]
-pub struct ObjectKeyedProperty {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectKeyedProperty<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub key: Box<IdentifierExpression>,
-	pub value: Option<Expression>,
+	pub key: Box<IdentifierExpression<'src>>,
+	pub value: Option<Expression<'src>>,
 }
 
-#[derive(Debug)]
-pub struct ObjectComputedProperty {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectComputedProperty<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub key: Box<Expression>,
-	pub value: Box<Expression>,
+	pub key: Box<Expression<'src>>,
+	pub value: Box<Expression<'src>>,
 }
 
-#[derive(Debug)]
-pub struct ArrayExpression {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArrayExpression<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub elements: Vec<Expression>,
+	pub elements: Vec<Expression<'src>>,
 }
 
-#[derive(Debug)]
-pub struct ParenthesizedExpression {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParenthesizedExpression<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub expression: Box<Expression>,
+	pub expression: Box<Expression<'src>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EmptyExpression {
 	pub start: usize,
 	pub end: usize,
 }
+
+/// `a as T`, which the compiler erases to `a`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TsAsExpression<'src> {
+	pub start: usize,
+	pub end: usize,
+	pub expression: Box<Expression<'src>>,
+	#[cfg_attr(feature = "serde", serde(rename = "typeAnnotation"))]
+	pub type_annotation: TsType<'src>,
+}
+
+/// `a satisfies T`, which the compiler erases to `a`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TsSatisfiesExpression<'src> {
+	pub start: usize,
+	pub end: usize,
+	pub expression: Box<Expression<'src>>,
+	#[cfg_attr(feature = "serde", serde(rename = "typeAnnotation"))]
+	pub type_annotation: TsType<'src>,
+}
+
+/// `a!`, which the compiler erases to `a`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TsNonNullExpression<'src> {
+	pub start: usize,
+	pub end: usize,
+	pub expression: Box<Expression<'src>>,
+}
+
+/// A parameter or declared variable with a type, like `a?: string` or `{ a }: T`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TsAnnotatedExpression<'src> {
+	pub start: usize,
+	pub end: usize,
+	pub expression: Box<Expression<'src>>,
+	/// `true` for an optional parameter, marked with `?`.
+	pub optional: bool,
+	#[cfg_attr(feature = "serde", serde(rename = "typeAnnotation"))]
+	pub type_annotation: Option<TsType<'src>>,
+}
+
+/// The source of a TypeScript type. Types are checked to be well-formed but kept as written,
+/// since they are only erased.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TsType<'src> {
+	pub start: usize,
+	pub end: usize,
+	pub raw: Cow<'src, str>,
+}
+
+/// Statements, which can only appear in the block body of a function.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum Statement<'src> {
+	#[cfg_attr(feature = "serde", serde(rename = "BlockStatement"))]
+	Block(BlockStatement<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "ExpressionStatement"))]
+	Expression(ExpressionStatement<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "VariableDeclaration"))]
+	Variable(VariableDeclaration<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "ReturnStatement"))]
+	Return(ReturnStatement<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "IfStatement"))]
+	If(IfStatement<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "ThrowStatement"))]
+	Throw(ThrowStatement<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "TryStatement"))]
+	Try(TryStatement<'src>),
+	#[cfg_attr(feature = "serde", serde(rename = "EmptyStatement"))]
+	Empty(EmptyStatement),
+}
+
+impl Statement<'_> {
+	pub fn start(&self) -> usize {
+		match self {
+			Statement::Block(stmt) => stmt.start,
+			Statement::Expression(stmt) => stmt.start,
+			Statement::Variable(stmt) => stmt.start,
+			Statement::Return(stmt) => stmt.start,
+			Statement::If(stmt) => stmt.start,
+			Statement::Throw(stmt) => stmt.start,
+			Statement::Try(stmt) => stmt.start,
+			Statement::Empty(stmt) => stmt.start,
+		}
+	}
+
+	pub fn end(&self) -> usize {
+		match self {
+			Statement::Block(stmt) => stmt.end,
+			Statement::Expression(stmt) => stmt.end,
+			Statement::Variable(stmt) => stmt.end,
+			Statement::Return(stmt) => stmt.end,
+			Statement::If(stmt) => stmt.end,
+			Statement::Throw(stmt) => stmt.end,
+			Statement::Try(stmt) => stmt.end,
+			Statement::Empty(stmt) => stmt.end,
+		}
+	}
+}
+
+impl<'src> From<BlockStatement<'src>> for Statement<'src> {
+	fn from(statement: BlockStatement<'src>) -> Statement<'src> {
+		Statement::Block(statement)
+	}
+}
+
+impl<'src> From<ExpressionStatement<'src>> for Statement<'src> {
+	fn from(statement: ExpressionStatement<'src>) -> Statement<'src> {
+		Statement::Expression(statement)
+	}
+}
+
+impl<'src> From<VariableDeclaration<'src>> for Statement<'src> {
+	fn from(statement: VariableDeclaration<'src>) -> Statement<'src> {
+		Statement::Variable(statement)
+	}
+}
+
+impl<'src> From<ReturnStatement<'src>> for Statement<'src> {
+	fn from(statement: ReturnStatement<'src>) -> Statement<'src> {
+		Statement::Return(statement)
+	}
+}
+
+impl<'src> From<IfStatement<'src>> for Statement<'src> {
+	fn from(statement: IfStatement<'src>) -> Statement<'src> {
+		Statement::If(statement)
+	}
+}
+
+impl<'src> From<ThrowStatement<'src>> for Statement<'src> {
+	fn from(statement: ThrowStatement<'src>) -> Statement<'src> {
+		Statement::Throw(statement)
+	}
+}
+
+impl<'src> From<TryStatement<'src>> for Statement<'src> {
+	fn from(statement: TryStatement<'src>) -> Statement<'src> {
+		Statement::Try(statement)
+	}
+}
+
+impl<'src> From<EmptyStatement> for Statement<'src> {
+	fn from(statement: EmptyStatement) -> Statement<'src> {
+		Statement::Empty(statement)
+	}
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockStatement<'src> {
+	pub start: usize,
+	pub end: usize,
+	pub body: Vec<Statement<'src>>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExpressionStatement<'src> {
+	pub start: usize,
+	pub end: usize,
+	pub expression: Expression<'src>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VariableDeclaration<'src> {
+	pub start: usize,
+	pub end: usize,
+	pub kind: VariableKind,
+	pub declarations: Vec<VariableDeclarator<'src>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VariableKind {
+	#[cfg_attr(feature = "serde", serde(rename = "let"))]
+	Let,
+	#[cfg_attr(feature = "serde", serde(rename = "const"))]
+	Const,
+	#[cfg_attr(feature = "serde", serde(rename = "var"))]
+	Var,
+}
+
+impl std::fmt::Display for VariableKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(match self {
+			VariableKind::Let => "let",
+			VariableKind::Const => "const",
+			VariableKind::Var => "var",
+		})
+	}
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VariableDeclarator<'src> {
+	pub start: usize,
+	pub end: usize,
+	/// An identifier, or an object or array destructuring the value.
+	pub id: Expression<'src>,
+	pub init: Option<Expression<'src>>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReturnStatement<'src> {
+	pub start: usize,
+	pub end: usize,
+	pub argument: Option<Expression<'src>>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IfStatement<'src> {
+	pub start: usize,
+	pub end: usize,
+	#[cfg_attr(feature = "serde", serde(rename = "test"))]
+	pub condition: Expression<'src>,
+	pub consequent: Box<Statement<'src>>,
+	pub alternate: Option<Box<Statement<'src>>>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThrowStatement<'src> {
+	pub start: usize,
+	pub end: usize,
+	pub argument: Expression<'src>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TryStatement<'src> {
+	pub start: usize,
+	pub end: usize,
+	pub block: BlockStatement<'src>,
+	pub handler: Option<CatchClause<'src>>,
+	pub finalizer: Option<BlockStatement<'src>>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CatchClause<'src> {
+	pub start: usize,
+	pub end: usize,
+	#[cfg_attr(feature = "serde", serde(rename = "param"))]
+	pub parameter: Option<Expression<'src>>,
+	pub body: BlockStatement<'src>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EmptyStatement {
+	pub start: usize,
+	pub end: usize,
+}
@@ -1,14 +1,17 @@
 use crate::ast::*;
+use std::borrow::Cow;
 
-#[derive(Debug)]
-pub enum Literal {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
+pub enum Literal<'src> {
 	Number(NumberLiteral),
-	String(StringLiteral),
+	String(StringLiteral<'src>),
 	Boolean(BooleanLiteral),
 	Null(NullLiteral),
 }
 
-impl Literal {
+impl Literal<'_> {
 	pub fn start(&self) -> usize {
 		match self {
 			Literal::Number(literal) => literal.start,
@@ -32,33 +35,35 @@ impl Literal {
 	}
 }
 
-impl From<Literal> for Range {
-	fn from(node: Literal) -> Self {
+impl<'src> From<Literal<'src>> for Range {
+	fn from(node: Literal<'src>) -> Self {
 		node.range()
 	}
 }
 
-#[derive(Debug)]
-pub struct StringLiteral {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StringLiteral<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub value: String,
+	pub value: Cow<'src, str>,
 	pub quote: char,
 }
 
-impl StringLiteral {
+impl StringLiteral<'_> {
 	pub fn range(&self) -> Range {
 		Range::new(self.start, self.end)
 	}
 }
 
-impl From<StringLiteral> for Range {
-	fn from(node: StringLiteral) -> Self {
+impl<'src> From<StringLiteral<'src>> for Range {
+	fn from(node: StringLiteral<'src>) -> Self {
 		node.range()
 	}
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NumberLiteral {
 	pub start: usize,
 	pub end: usize,
@@ -77,7 +82,8 @@ impl From<NumberLiteral> for Range {
 	}
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BooleanLiteral {
 	pub start: usize,
 	pub end: usize,
@@ -96,7 +102,8 @@ impl From<BooleanLiteral> for Range {
 	}
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NullLiteral {
 	pub start: usize,
 	pub end: usize,
@@ -1,51 +1,54 @@
 use crate::ast::*;
+use std::borrow::Cow;
 
-#[derive(Debug)]
-pub struct Text {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Text<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub content: String,
+	pub content: Cow<'src, str>,
 }
 
-impl Text {
+impl Text<'_> {
 	pub fn range(&self) -> Range {
 		Range::new(self.start, self.end)
 	}
 }
 
-impl From<Text> for Range {
-	fn from(node: Text) -> Self {
+impl<'src> From<Text<'src>> for Range {
+	fn from(node: Text<'src>) -> Self {
 		node.range()
 	}
 }
 
-impl From<Text> for Node {
-	fn from(node: Text) -> Node {
+impl<'src> From<Text<'src>> for Node<'src> {
+	fn from(node: Text<'src>) -> Node<'src> {
 		Node::Text(node)
 	}
 }
 
-#[derive(Debug)]
-pub struct TextBinding {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextBinding<'src> {
 	pub start: usize,
 	pub end: usize,
-	pub expression: javascript::Expression,
+	pub expression: javascript::Expression<'src>,
 }
 
-impl TextBinding {
+impl TextBinding<'_> {
 	pub fn range(&self) -> Range {
 		Range::new(self.start, self.end)
 	}
 }
 
-impl From<TextBinding> for Range {
-	fn from(node: TextBinding) -> Self {
+impl<'src> From<TextBinding<'src>> for Range {
+	fn from(node: TextBinding<'src>) -> Self {
 		node.range()
 	}
 }
 
-impl From<TextBinding> for Node {
-	fn from(node: TextBinding) -> Node {
+impl<'src> From<TextBinding<'src>> for Node<'src> {
+	fn from(node: TextBinding<'src>) -> Node<'src> {
 		Node::TextBinding(node)
 	}
 }
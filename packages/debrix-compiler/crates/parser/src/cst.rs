@@ -0,0 +1,1067 @@
+//! Lossless concrete syntax tree.
+//!
+//! The tree is built on top of the AST. Every AST node becomes a [`SyntaxNode`] and the input
+//! between its children, like punctuation, keywords, whitespace and comments, is kept as
+//! [`SyntaxToken`]s. Writing every token in order reproduces the input exactly.
+//!
+//! As everywhere else in the parser, offsets are char offsets.
+
+use crate::{ast, ast::javascript as js};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+	Document,
+	DependencyStatement,
+	DependencyDefaultSpecifier,
+	DependencyNamedSpecifiers,
+	DependencyNamedSpecifier,
+	Comment,
+	Element,
+	StartTag,
+	EndTag,
+	StaticAttribute,
+	BindingAttribute,
+	SpreadAttribute,
+	ShortBindingAttribute,
+	Text,
+	TextBinding,
+	FlowControlWhen,
+	FlowControlElse,
+	FlowControlEach,
+	FlowControlLet,
+	FlowControlMatch,
+	FlowControlCase,
+	FlowControlAwait,
+	FlowControlAwaitBranch,
+	FlowControlTry,
+	Invalid,
+	Identifier,
+	StringLiteral,
+	NumberLiteral,
+	BigIntLiteral,
+	BooleanLiteral,
+	NullLiteral,
+	RegExpLiteral,
+	IdentifierExpression,
+	ThisExpression,
+	UnaryExpression,
+	BinaryExpression,
+	ConditionalExpression,
+	CallExpression,
+	NewExpression,
+	MemberExpression,
+	SequenceExpression,
+	AwaitExpression,
+	FunctionExpression,
+	AssignmentExpression,
+	SpreadExpression,
+	TemplateLiteral,
+	TemplateElement,
+	TaggedTemplateExpression,
+	ObjectExpression,
+	ObjectKeyedProperty,
+	ObjectComputedProperty,
+	ArrayExpression,
+	ParenthesizedExpression,
+	EmptyExpression,
+	TsAsExpression,
+	TsSatisfiesExpression,
+	TsNonNullExpression,
+	TsAnnotatedExpression,
+	TsType,
+	BlockStatement,
+	ExpressionStatement,
+	VariableDeclaration,
+	VariableDeclarator,
+	ReturnStatement,
+	IfStatement,
+	ThrowStatement,
+	TryStatement,
+	CatchClause,
+	EmptyStatement,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+	Whitespace,
+	/// `// ...`, excluding the line break.
+	LineComment,
+	/// `/* ... */`
+	BlockComment,
+	/// Identifiers, keywords and tag names.
+	Word,
+	Punctuation,
+	String,
+	Number,
+	/// `/.../flags`
+	RegExp,
+	/// The text of a template literal between `` ` ``, `${` and `}`.
+	Template,
+	Text,
+	/// `<!-- ... -->`, which unlike the other comments is part of the AST.
+	Comment,
+	/// Input which could not be parsed, when recovering from errors.
+	Invalid,
+}
+
+impl TokenKind {
+	pub fn is_trivia(&self) -> bool {
+		matches!(
+			self,
+			TokenKind::Whitespace | TokenKind::LineComment | TokenKind::BlockComment
+		)
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct SyntaxToken {
+	pub kind: TokenKind,
+	pub start: usize,
+	pub end: usize,
+	pub text: String,
+}
+
+impl SyntaxToken {
+	pub fn is_trivia(&self) -> bool {
+		self.kind.is_trivia()
+	}
+}
+
+impl fmt::Display for SyntaxToken {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.text)
+	}
+}
+
+#[derive(Debug, Clone)]
+pub enum SyntaxElement {
+	Node(SyntaxNode),
+	Token(SyntaxToken),
+}
+
+impl SyntaxElement {
+	pub fn start(&self) -> usize {
+		match self {
+			SyntaxElement::Node(node) => node.start,
+			SyntaxElement::Token(token) => token.start,
+		}
+	}
+
+	pub fn end(&self) -> usize {
+		match self {
+			SyntaxElement::Node(node) => node.end,
+			SyntaxElement::Token(token) => token.end,
+		}
+	}
+}
+
+impl fmt::Display for SyntaxElement {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			SyntaxElement::Node(node) => node.fmt(f),
+			SyntaxElement::Token(token) => token.fmt(f),
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct SyntaxNode {
+	pub kind: NodeKind,
+	pub start: usize,
+	pub end: usize,
+	pub children: Vec<SyntaxElement>,
+}
+
+impl SyntaxNode {
+	/// Returns the child nodes, without tokens.
+	pub fn nodes(&self) -> impl Iterator<Item = &SyntaxNode> {
+		self.children.iter().filter_map(|child| match child {
+			SyntaxElement::Node(node) => Some(node),
+			SyntaxElement::Token(_) => None,
+		})
+	}
+
+	/// Returns every token in the subtree, in source order.
+	pub fn tokens(&self) -> Vec<&SyntaxToken> {
+		let mut tokens = Vec::new();
+		self.collect_tokens(&mut tokens);
+		tokens
+	}
+
+	fn collect_tokens<'a>(&'a self, tokens: &mut Vec<&'a SyntaxToken>) {
+		for child in &self.children {
+			match child {
+				SyntaxElement::Node(node) => node.collect_tokens(tokens),
+				SyntaxElement::Token(token) => tokens.push(token),
+			}
+		}
+	}
+
+	/// Returns the token containing `offset`. At the boundary of two tokens, the latter is returned.
+	pub fn token_at(&self, offset: usize) -> Option<&SyntaxToken> {
+		self.tokens()
+			.into_iter()
+			.find(|token| token.start <= offset && offset < token.end)
+	}
+}
+
+impl fmt::Display for SyntaxNode {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for child in &self.children {
+			child.fmt(f)?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Punctuation, longest first. Other punctuation chars are single-char tokens.
+const PUNCTUATION: &[&str] = &[
+	">>>=", "<!--", "===", "!==", "**=", "<<=", ">>=", ">>>", "&&=", "||=", "??=", "...", "-->",
+	"</", "/>", "<#", "=>", "==", "!=", "<=", ">=", "&&", "||", "??", "?.", "++", "--", "**", "<<",
+	">>", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "${",
+];
+
+/// Builds the concrete syntax tree of `document`, which must have been parsed from `input`.
+pub fn build(input: &str, document: &ast::Document) -> SyntaxNode {
+	let mut bytes: Vec<usize> = input.char_indices().map(|(i, _)| i).collect();
+	bytes.push(input.len());
+
+	let builder = Builder {
+		input,
+		chars: input.chars().collect(),
+		bytes,
+	};
+
+	let children = document
+		.children
+		.iter()
+		.map(|node| builder.node(node))
+		.collect();
+
+	builder.branch(NodeKind::Document, 0, builder.chars.len(), children)
+}
+
+struct Builder<'a> {
+	input: &'a str,
+	chars: Vec<char>,
+	/// Byte offsets of every char, followed by the length of the input.
+	bytes: Vec<usize>,
+}
+
+impl Builder<'_> {
+	/// Creates a node from its child nodes. The input around them is split into tokens.
+	fn branch(
+		&self,
+		kind: NodeKind,
+		start: usize,
+		end: usize,
+		mut nodes: Vec<SyntaxNode>,
+	) -> SyntaxNode {
+		let mut children = Vec::new();
+		let mut cursor = start;
+
+		nodes.sort_by_key(|node| node.start);
+
+		for node in nodes {
+			// Nodes outside of the parent, or overlapping the previous one, are kept as tokens
+			// instead so that no input is lost or duplicated.
+			if node.start < cursor || node.end > end {
+				continue;
+			}
+
+			self.tokens(cursor, node.start, &mut children);
+			cursor = node.end;
+			children.push(SyntaxElement::Node(node));
+		}
+
+		self.tokens(cursor, end, &mut children);
+
+		SyntaxNode {
+			kind,
+			start,
+			end,
+			children,
+		}
+	}
+
+	/// Creates a node consisting of a single token.
+	fn leaf(&self, kind: NodeKind, token: TokenKind, start: usize, end: usize) -> SyntaxNode {
+		let mut children = Vec::new();
+
+		if start < end {
+			children.push(self.token(token, start, end));
+		}
+
+		SyntaxNode {
+			kind,
+			start,
+			end,
+			children,
+		}
+	}
+
+	fn token(&self, kind: TokenKind, start: usize, end: usize) -> SyntaxElement {
+		SyntaxElement::Token(SyntaxToken {
+			kind,
+			start,
+			end,
+			text: self.input[self.bytes[start]..self.bytes[end]].to_owned(),
+		})
+	}
+
+	/// Splits the input between `start` and `end` into whitespace, comments, words and
+	/// punctuation.
+	fn tokens(&self, start: usize, end: usize, children: &mut Vec<SyntaxElement>) {
+		let mut cursor = start;
+
+		while cursor < end {
+			let token_start = cursor;
+			let char = self.chars[cursor];
+
+			let kind = if char.is_whitespace() {
+				while cursor < end && self.chars[cursor].is_whitespace() {
+					cursor += 1;
+				}
+
+				TokenKind::Whitespace
+			} else if self.test(cursor, end, "//") {
+				while cursor < end && self.chars[cursor] != '\n' {
+					cursor += 1;
+				}
+
+				TokenKind::LineComment
+			} else if self.test(cursor, end, "/*") {
+				cursor += 2;
+
+				while cursor < end && !self.test(cursor, end, "*/") {
+					cursor += 1;
+				}
+
+				cursor = end.min(cursor + 2);
+				TokenKind::BlockComment
+			} else if (char.is_alphanumeric() || char == '_' || char == '$')
+				&& !self.test(cursor, end, "${")
+			{
+				while cursor < end && {
+					let char = self.chars[cursor];
+					char.is_alphanumeric() || char == '_' || char == '$'
+				} {
+					cursor += 1;
+				}
+
+				TokenKind::Word
+			} else {
+				cursor += PUNCTUATION
+					.iter()
+					.find(|punctuation| self.test(cursor, end, punctuation))
+					.map_or(1, |punctuation| punctuation.len());
+
+				TokenKind::Punctuation
+			};
+
+			children.push(self.token(kind, token_start, cursor));
+		}
+	}
+
+	/// Returns whether the input at `cursor`, before `end`, starts with `target`.
+	fn test(&self, cursor: usize, end: usize, target: &str) -> bool {
+		let mut chars = self.chars[cursor..end].iter();
+		target.chars().all(|char| chars.next() == Some(&char))
+	}
+
+	fn node(&self, node: &ast::Node) -> SyntaxNode {
+		match node {
+			ast::Node::DependencyStatement(node) => self.dependency_statement(node),
+			ast::Node::Comment(node) => {
+				self.leaf(NodeKind::Comment, TokenKind::Comment, node.start, node.end)
+			}
+			ast::Node::Element(node) => self.element(node),
+			ast::Node::Text(node) => {
+				self.leaf(NodeKind::Text, TokenKind::Text, node.start, node.end)
+			}
+			ast::Node::TextBinding(node) => self.branch(
+				NodeKind::TextBinding,
+				node.start,
+				node.end,
+				vec![self.expression(&node.expression)],
+			),
+			ast::Node::FlowControl(node) => self.flow_control(node),
+			ast::Node::Invalid(node) => {
+				self.leaf(NodeKind::Invalid, TokenKind::Invalid, node.start, node.end)
+			}
+		}
+	}
+
+	fn nodes(&self, nodes: &[ast::Node]) -> Vec<SyntaxNode> {
+		nodes.iter().map(|node| self.node(node)).collect()
+	}
+
+	fn identifier(&self, node: &ast::Identifier) -> SyntaxNode {
+		self.leaf(NodeKind::Identifier, TokenKind::Word, node.start, node.end)
+	}
+
+	fn string(&self, node: &ast::StringLiteral) -> SyntaxNode {
+		self.leaf(
+			NodeKind::StringLiteral,
+			TokenKind::String,
+			node.start,
+			node.end,
+		)
+	}
+
+	fn dependency_statement(&self, node: &ast::DependencyStatement) -> SyntaxNode {
+		let mut children = vec![self.string(&node.source)];
+
+		if let Some(default) = &node.default {
+			let mut specifier = vec![self.identifier(&default.usage)];
+			specifier.extend(default.local.iter().map(|local| self.identifier(local)));
+
+			children.push(self.branch(
+				NodeKind::DependencyDefaultSpecifier,
+				default.start,
+				default.end,
+				specifier,
+			));
+		}
+
+		if let Some(named) = &node.named {
+			let specifiers = named
+				.nodes
+				.iter()
+				.map(|named| {
+					let mut specifier = vec![
+						self.identifier(&named.imported),
+						self.identifier(&named.usage),
+					];
+					specifier.extend(named.local.iter().map(|local| self.identifier(local)));
+
+					self.branch(
+						NodeKind::DependencyNamedSpecifier,
+						named.start,
+						named.end,
+						specifier,
+					)
+				})
+				.collect();
+
+			children.push(self.branch(
+				NodeKind::DependencyNamedSpecifiers,
+				named.start,
+				named.end,
+				specifiers,
+			));
+		}
+
+		self.branch(
+			NodeKind::DependencyStatement,
+			node.start,
+			node.end,
+			children,
+		)
+	}
+
+	fn element(&self, node: &ast::Element) -> SyntaxNode {
+		let mut start_tag = vec![self.identifier(&node.tag_name)];
+		start_tag.extend(
+			node.attributes
+				.iter()
+				.map(|attribute| self.attribute(attribute)),
+		);
+
+		let mut children = self.nodes(&node.children);
+		children.push(self.branch(
+			NodeKind::StartTag,
+			node.start_tag.start,
+			node.start_tag.end,
+			start_tag,
+		));
+
+		if let Some(end_tag) = &node.end_tag {
+			children.push(self.branch(NodeKind::EndTag, end_tag.start, end_tag.end, Vec::new()));
+		}
+
+		self.branch(NodeKind::Element, node.start, node.end, children)
+	}
+
+	fn attribute(&self, attribute: &ast::Attribute) -> SyntaxNode {
+		match attribute {
+			ast::Attribute::Static(attribute) => {
+				let mut children = vec![self.identifier(&attribute.name)];
+				children.extend(attribute.value.iter().map(|value| self.string(value)));
+				self.branch(
+					NodeKind::StaticAttribute,
+					attribute.start,
+					attribute.end,
+					children,
+				)
+			}
+			ast::Attribute::Binding(attribute) => self.branch(
+				NodeKind::BindingAttribute,
+				attribute.start,
+				attribute.end,
+				vec![
+					self.identifier(&attribute.name),
+					self.expression(&attribute.value),
+				],
+			),
+			ast::Attribute::Spread(attribute) => self.branch(
+				NodeKind::SpreadAttribute,
+				attribute.start,
+				attribute.end,
+				vec![self.expression(&attribute.value)],
+			),
+			ast::Attribute::ShortBinding(attribute) => self.branch(
+				NodeKind::ShortBindingAttribute,
+				attribute.start,
+				attribute.end,
+				vec![self.identifier_expression(&attribute.name)],
+			),
+		}
+	}
+
+	fn flow_control(&self, node: &ast::FlowControl) -> SyntaxNode {
+		match node {
+			ast::FlowControl::When(node) => {
+				let mut children = self.nodes(&node.children);
+				children.push(self.expression(&node.condition));
+
+				for chained in &node.chain {
+					let mut chained_children = self.nodes(&chained.children);
+					chained_children.extend(
+						chained
+							.condition
+							.iter()
+							.map(|condition| self.expression(condition)),
+					);
+
+					children.push(self.branch(
+						NodeKind::FlowControlElse,
+						chained.start,
+						chained.end,
+						chained_children,
+					));
+				}
+
+				self.branch(NodeKind::FlowControlWhen, node.start, node.end, children)
+			}
+			ast::FlowControl::Each(node) => {
+				let mut children = self.nodes(&node.children);
+				children.push(self.expression(&node.iterator));
+
+				if let Some(index) = &node.index {
+					children.push(self.identifier_expression(index));
+				}
+
+				children.push(self.expression(&node.iterable));
+
+				if let Some(key) = &node.key {
+					children.push(self.expression(key));
+				}
+
+				if let Some(fallback) = &node.fallback {
+					children.push(self.branch(
+						NodeKind::FlowControlElse,
+						fallback.start,
+						fallback.end,
+						self.nodes(&fallback.children),
+					));
+				}
+
+				self.branch(NodeKind::FlowControlEach, node.start, node.end, children)
+			}
+			ast::FlowControl::Let(node) => {
+				let mut children = self.nodes(&node.children);
+				children.push(self.identifier_expression(&node.name));
+				children.push(self.expression(&node.value));
+
+				self.branch(NodeKind::FlowControlLet, node.start, node.end, children)
+			}
+			ast::FlowControl::Match(node) => {
+				let mut children = vec![self.expression(&node.discriminant)];
+
+				for case in &node.cases {
+					let mut case_children = self.nodes(&case.children);
+					case_children.extend(case.tests.iter().map(|test| self.expression(test)));
+
+					children.push(self.branch(
+						NodeKind::FlowControlCase,
+						case.start,
+						case.end,
+						case_children,
+					));
+				}
+
+				self.branch(NodeKind::FlowControlMatch, node.start, node.end, children)
+			}
+			ast::FlowControl::Await(node) => {
+				let mut children = self.nodes(&node.children);
+				children.push(self.expression(&node.promise));
+
+				for branch in node.then.iter().chain(&node.catch) {
+					let mut branch_children = self.nodes(&branch.children);
+					branch_children.extend(
+						branch
+							.binding
+							.iter()
+							.map(|binding| self.expression(binding)),
+					);
+
+					children.push(self.branch(
+						NodeKind::FlowControlAwaitBranch,
+						branch.start,
+						branch.end,
+						branch_children,
+					));
+				}
+
+				self.branch(NodeKind::FlowControlAwait, node.start, node.end, children)
+			}
+			ast::FlowControl::Try(node) => {
+				let mut children = self.nodes(&node.children);
+				let mut catch_children = self.nodes(&node.catch.children);
+				catch_children.extend(
+					node.catch
+						.binding
+						.iter()
+						.map(|binding| self.expression(binding)),
+				);
+
+				children.push(self.branch(
+					NodeKind::FlowControlAwaitBranch,
+					node.catch.start,
+					node.catch.end,
+					catch_children,
+				));
+
+				self.branch(NodeKind::FlowControlTry, node.start, node.end, children)
+			}
+		}
+	}
+
+	fn identifier_expression(&self, expr: &js::IdentifierExpression) -> SyntaxNode {
+		self.leaf(
+			NodeKind::IdentifierExpression,
+			TokenKind::Word,
+			expr.start,
+			expr.end,
+		)
+	}
+
+	fn template(&self, expr: &js::TemplateLiteral) -> SyntaxNode {
+		let mut children = self.expressions(&expr.expressions);
+		children.extend(expr.quasis.iter().map(|quasi| {
+			self.leaf(
+				NodeKind::TemplateElement,
+				TokenKind::Template,
+				quasi.start,
+				quasi.end,
+			)
+		}));
+
+		self.branch(NodeKind::TemplateLiteral, expr.start, expr.end, children)
+	}
+
+	fn expressions(&self, exprs: &[js::Expression]) -> Vec<SyntaxNode> {
+		exprs.iter().map(|expr| self.expression(expr)).collect()
+	}
+
+	fn expression(&self, expr: &js::Expression) -> SyntaxNode {
+		match expr {
+			js::Expression::Identifier(expr) => self.identifier_expression(expr),
+			js::Expression::Literal(literal) => {
+				let (kind, token) = match literal {
+					js::Literal::String(_) => (NodeKind::StringLiteral, TokenKind::String),
+					js::Literal::Number(_) => (NodeKind::NumberLiteral, TokenKind::Number),
+					js::Literal::BigInt(_) => (NodeKind::BigIntLiteral, TokenKind::Number),
+					js::Literal::Boolean(_) => (NodeKind::BooleanLiteral, TokenKind::Word),
+					js::Literal::Null(_) => (NodeKind::NullLiteral, TokenKind::Word),
+					js::Literal::RegExp(_) => (NodeKind::RegExpLiteral, TokenKind::RegExp),
+				};
+
+				self.leaf(kind, token, literal.start(), literal.end())
+			}
+			js::Expression::This(expr) => self.leaf(
+				NodeKind::ThisExpression,
+				TokenKind::Word,
+				expr.start,
+				expr.end,
+			),
+			js::Expression::Unary(expr) => self.branch(
+				NodeKind::UnaryExpression,
+				expr.start,
+				expr.end,
+				vec![self.expression(&expr.operand)],
+			),
+			js::Expression::Binary(expr) => self.branch(
+				NodeKind::BinaryExpression,
+				expr.start,
+				expr.end,
+				vec![self.expression(&expr.left), self.expression(&expr.right)],
+			),
+			js::Expression::Conditional(expr) => self.branch(
+				NodeKind::ConditionalExpression,
+				expr.start,
+				expr.end,
+				vec![
+					self.expression(&expr.condition),
+					self.expression(&expr.consequent),
+					self.expression(&expr.alternate),
+				],
+			),
+			js::Expression::Call(expr) => {
+				let mut children = vec![self.expression(&expr.callee)];
+				children.extend(self.expressions(&expr.arguments));
+				self.branch(NodeKind::CallExpression, expr.start, expr.end, children)
+			}
+			js::Expression::New(expr) => {
+				let mut children = vec![self.expression(&expr.callee)];
+				children.extend(self.expressions(&expr.arguments));
+				self.branch(NodeKind::NewExpression, expr.start, expr.end, children)
+			}
+			js::Expression::Member(expr) => self.branch(
+				NodeKind::MemberExpression,
+				expr.start,
+				expr.end,
+				vec![
+					self.expression(&expr.object),
+					self.expression(&expr.property),
+				],
+			),
+			js::Expression::Sequence(expr) => self.branch(
+				NodeKind::SequenceExpression,
+				expr.start,
+				expr.end,
+				self.expressions(&expr.expressions),
+			),
+			js::Expression::Await(expr) => self.branch(
+				NodeKind::AwaitExpression,
+				expr.start,
+				expr.end,
+				vec![self.expression(&expr.argument)],
+			),
+			js::Expression::Function(expr) => {
+				let mut children = self.expressions(&expr.parameters);
+				children.extend(expr.type_parameters.iter().map(|ty| self.ts_type(ty)));
+				children.extend(expr.return_type.iter().map(|ty| self.ts_type(ty)));
+				children.push(match &expr.body {
+					js::FunctionBody::Expression(body) => self.expression(body),
+					js::FunctionBody::Block(body) => self.block(body),
+				});
+				self.branch(NodeKind::FunctionExpression, expr.start, expr.end, children)
+			}
+			js::Expression::Assignment(expr) => self.branch(
+				NodeKind::AssignmentExpression,
+				expr.start,
+				expr.end,
+				vec![self.expression(&expr.left), self.expression(&expr.right)],
+			),
+			js::Expression::Spread(expr) => self.spread(expr),
+			js::Expression::Template(expr) => self.template(expr),
+			js::Expression::TaggedTemplate(expr) => self.branch(
+				NodeKind::TaggedTemplateExpression,
+				expr.start,
+				expr.end,
+				vec![self.expression(&expr.tag), self.template(&expr.quasi)],
+			),
+			js::Expression::Object(expr) => {
+				let children = expr
+					.properties
+					.iter()
+					.map(|property| match property {
+						js::ObjectProperty::Keyed(property) => {
+							let mut children = vec![self.identifier_expression(&property.key)];
+							children
+								.extend(property.value.iter().map(|value| self.expression(value)));
+							self.branch(
+								NodeKind::ObjectKeyedProperty,
+								property.start,
+								property.end,
+								children,
+							)
+						}
+						js::ObjectProperty::Computed(property) => self.branch(
+							NodeKind::ObjectComputedProperty,
+							property.start,
+							property.end,
+							vec![
+								self.expression(&property.key),
+								self.expression(&property.value),
+							],
+						),
+						js::ObjectProperty::Spread(property) => self.spread(property),
+					})
+					.collect();
+
+				self.branch(NodeKind::ObjectExpression, expr.start, expr.end, children)
+			}
+			js::Expression::Array(expr) => self.branch(
+				NodeKind::ArrayExpression,
+				expr.start,
+				expr.end,
+				self.expressions(&expr.elements),
+			),
+			js::Expression::Parenthesized(expr) => self.branch(
+				NodeKind::ParenthesizedExpression,
+				expr.start,
+				expr.end,
+				vec![self.expression(&expr.expression)],
+			),
+			js::Expression::Empty(expr) => {
+				self.branch(NodeKind::EmptyExpression, expr.start, expr.end, Vec::new())
+			}
+			js::Expression::TsAs(expr) => self.branch(
+				NodeKind::TsAsExpression,
+				expr.start,
+				expr.end,
+				vec![
+					self.expression(&expr.expression),
+					self.ts_type(&expr.type_annotation),
+				],
+			),
+			js::Expression::TsSatisfies(expr) => self.branch(
+				NodeKind::TsSatisfiesExpression,
+				expr.start,
+				expr.end,
+				vec![
+					self.expression(&expr.expression),
+					self.ts_type(&expr.type_annotation),
+				],
+			),
+			js::Expression::TsNonNull(expr) => self.branch(
+				NodeKind::TsNonNullExpression,
+				expr.start,
+				expr.end,
+				vec![self.expression(&expr.expression)],
+			),
+			js::Expression::TsAnnotated(expr) => {
+				let mut children = vec![self.expression(&expr.expression)];
+				children.extend(expr.type_annotation.iter().map(|ty| self.ts_type(ty)));
+				self.branch(
+					NodeKind::TsAnnotatedExpression,
+					expr.start,
+					expr.end,
+					children,
+				)
+			}
+		}
+	}
+
+	/// Types are kept as written, so their tokens are split from the input like punctuation.
+	fn ts_type(&self, ty: &js::TsType) -> SyntaxNode {
+		self.branch(NodeKind::TsType, ty.start, ty.end, Vec::new())
+	}
+
+	fn statement(&self, stmt: &js::Statement) -> SyntaxNode {
+		match stmt {
+			js::Statement::Block(stmt) => self.block(stmt),
+			js::Statement::Expression(stmt) => self.branch(
+				NodeKind::ExpressionStatement,
+				stmt.start,
+				stmt.end,
+				vec![self.expression(&stmt.expression)],
+			),
+			js::Statement::Variable(stmt) => {
+				let children = stmt
+					.declarations
+					.iter()
+					.map(|declarator| {
+						let mut children = vec![self.expression(&declarator.id)];
+						children.extend(declarator.init.iter().map(|init| self.expression(init)));
+						self.branch(
+							NodeKind::VariableDeclarator,
+							declarator.start,
+							declarator.end,
+							children,
+						)
+					})
+					.collect();
+
+				self.branch(
+					NodeKind::VariableDeclaration,
+					stmt.start,
+					stmt.end,
+					children,
+				)
+			}
+			js::Statement::Return(stmt) => self.branch(
+				NodeKind::ReturnStatement,
+				stmt.start,
+				stmt.end,
+				stmt.argument
+					.iter()
+					.map(|argument| self.expression(argument))
+					.collect(),
+			),
+			js::Statement::If(stmt) => {
+				let mut children = vec![
+					self.expression(&stmt.condition),
+					self.statement(&stmt.consequent),
+				];
+				children.extend(
+					stmt.alternate
+						.iter()
+						.map(|alternate| self.statement(alternate)),
+				);
+				self.branch(NodeKind::IfStatement, stmt.start, stmt.end, children)
+			}
+			js::Statement::Throw(stmt) => self.branch(
+				NodeKind::ThrowStatement,
+				stmt.start,
+				stmt.end,
+				vec![self.expression(&stmt.argument)],
+			),
+			js::Statement::Try(stmt) => {
+				let mut children = vec![self.block(&stmt.block)];
+
+				if let Some(handler) = &stmt.handler {
+					let mut clause = Vec::new();
+					clause.extend(handler.parameter.iter().map(|param| self.expression(param)));
+					clause.push(self.block(&handler.body));
+					children.push(self.branch(
+						NodeKind::CatchClause,
+						handler.start,
+						handler.end,
+						clause,
+					));
+				}
+
+				children.extend(stmt.finalizer.iter().map(|finalizer| self.block(finalizer)));
+				self.branch(NodeKind::TryStatement, stmt.start, stmt.end, children)
+			}
+			js::Statement::Empty(stmt) => {
+				self.branch(NodeKind::EmptyStatement, stmt.start, stmt.end, Vec::new())
+			}
+		}
+	}
+
+	fn block(&self, stmt: &js::BlockStatement) -> SyntaxNode {
+		let children = stmt.body.iter().map(|stmt| self.statement(stmt)).collect();
+		self.branch(NodeKind::BlockStatement, stmt.start, stmt.end, children)
+	}
+
+	fn spread(&self, expr: &js::SpreadExpression) -> SyntaxNode {
+		self.branch(
+			NodeKind::SpreadExpression,
+			expr.start,
+			expr.end,
+			vec![self.expression(&expr.argument)],
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parse(input: &str) -> SyntaxNode {
+		let document = crate::parse_document(input).unwrap();
+		build(input, &document)
+	}
+
+	#[test]
+	fn test_tokens() {
+		let node = parse("<p /* foo */ class='bar'>baz</p>");
+		let tokens: Vec<(TokenKind, &str)> = node
+			.tokens()
+			.into_iter()
+			.map(|token| (token.kind, token.text.as_str()))
+			.collect();
+
+		assert_eq!(
+			tokens,
+			[
+				(TokenKind::Punctuation, "<"),
+				(TokenKind::Word, "p"),
+				(TokenKind::Whitespace, " "),
+				(TokenKind::BlockComment, "/* foo */"),
+				(TokenKind::Whitespace, " "),
+				(TokenKind::Word, "class"),
+				(TokenKind::Punctuation, "="),
+				(TokenKind::String, "'bar'"),
+				(TokenKind::Punctuation, ">"),
+				(TokenKind::Text, "baz"),
+				(TokenKind::Punctuation, "</"),
+				(TokenKind::Word, "p"),
+				(TokenKind::Punctuation, ">"),
+			]
+		);
+	}
+
+	#[test]
+	fn test_structure() {
+		let node = parse("<p>{a + b}</p>");
+		let element = node.nodes().next().unwrap();
+		let kinds: Vec<NodeKind> = element.nodes().map(|node| node.kind).collect();
+
+		assert_eq!(element.kind, NodeKind::Element);
+		assert_eq!(
+			kinds,
+			[NodeKind::StartTag, NodeKind::TextBinding, NodeKind::EndTag]
+		);
+	}
+
+	#[test]
+	fn test_template_tokens() {
+		let node = parse("<p>{`a${ b }c`}</p>");
+		let tokens: Vec<(TokenKind, &str)> = node
+			.tokens()
+			.into_iter()
+			.map(|token| (token.kind, token.text.as_str()))
+			.skip(4)
+			.take(7)
+			.collect();
+
+		assert_eq!(
+			tokens,
+			[
+				(TokenKind::Punctuation, "`"),
+				(TokenKind::Template, "a"),
+				(TokenKind::Punctuation, "${"),
+				(TokenKind::Whitespace, " "),
+				(TokenKind::Word, "b"),
+				(TokenKind::Whitespace, " "),
+				(TokenKind::Punctuation, "}"),
+			]
+		);
+	}
+
+	#[test]
+	fn test_operator_tokens() {
+		let node = parse("<p>{a ??= this?.b, c}</p>");
+		let tokens: Vec<&str> = node
+			.tokens()
+			.into_iter()
+			.filter(|token| token.kind == TokenKind::Punctuation)
+			.map(|token| token.text.as_str())
+			.skip(3)
+			.take(3)
+			.collect();
+
+		assert_eq!(tokens, ["??=", "?.", ","]);
+
+		let binding = node.nodes().next().unwrap().nodes().nth(1).unwrap();
+		let sequence = binding.nodes().next().unwrap();
+		let kinds: Vec<NodeKind> = sequence.nodes().map(|node| node.kind).collect();
+
+		assert_eq!(sequence.kind, NodeKind::SequenceExpression);
+		assert_eq!(
+			kinds,
+			[
+				NodeKind::AssignmentExpression,
+				NodeKind::IdentifierExpression
+			]
+		);
+	}
+
+	#[test]
+	fn test_token_at() {
+		let node = parse("<p>{a + foo}</p>");
+		let token = node.token_at(9).unwrap();
+
+		assert_eq!(token.kind, TokenKind::Word);
+		assert_eq!(token.text, "foo");
+		assert_eq!((token.start, token.end), (8, 11));
+	}
+}
@@ -2,7 +2,10 @@ use crate::*;
 
 pub struct ParserError {
 	pub position: usize,
+	pub location: Location,
 	pub positives: Vec<String>,
+	/// Replaces the default message, like for JavaScript outside of the supported subset.
+	pub message: Option<String>,
 }
 
 impl fmt::Debug for ParserError {
@@ -37,7 +40,11 @@ impl fmt::Debug for ParserError {
 			}
 		}
 
-		write!(f, "Unexpected at {:?}", self.position)?;
+		if let Some(message) = &self.message {
+			return write!(f, "{} at {:?}.", message, self.location.start);
+		}
+
+		write!(f, "Unexpected at {:?}", self.location.start)?;
 
 		if !self.positives.is_empty() {
 			write!(f, ", expected {}.", fmt_list(&self.positives))?;
@@ -53,7 +60,9 @@ impl From<usize> for ParserError {
 	fn from(position: usize) -> ParserError {
 		ParserError {
 			position,
+			location: Location::default(),
 			positives: Vec::new(),
+			message: None,
 		}
 	}
 }
@@ -0,0 +1,393 @@
+//! Incremental reparsing, for editors which parse the document again on every change.
+
+use crate::{
+	ast::{self, javascript},
+	visit_mut::*,
+	Parser, ParserError,
+};
+
+/// A change to the input, which replaces `start..end` of the previous input with `text`.
+/// Offsets count characters, like the offsets in the syntax tree.
+#[derive(Debug, Clone)]
+pub struct Edit {
+	pub start: usize,
+	pub end: usize,
+	pub text: String,
+}
+
+impl Edit {
+	pub fn new(start: usize, end: usize, text: impl Into<String>) -> Self {
+		Self {
+			start,
+			end,
+			text: text.into(),
+		}
+	}
+
+	/// Returns the input after the edit.
+	pub fn apply(&self, input: &str) -> String {
+		let mut chars = input.chars();
+		let mut output: String = chars.by_ref().take(self.start).collect();
+		output.push_str(&self.text);
+		output.extend(chars.skip(self.end - self.start));
+		output
+	}
+
+	/// Returns how far the input after the edit moved.
+	fn delta(&self) -> isize {
+		self.text.chars().count() as isize - (self.end - self.start) as isize
+	}
+}
+
+/// Parses `input` again after `edit`, where `document` is the syntax tree of the input before
+/// the edit.
+///
+/// Only the smallest element or flow control enclosing the edit is parsed again, and the rest
+/// of the tree is reused. If the node no longer parses to the same end, its parent is tried
+/// instead, until the whole input is parsed again. The result is the same as for
+/// [`crate::parse_document`].
+///
+/// The reused nodes keep borrowing the previous input, which must live as long as the result.
+pub fn reparse_document<'src>(
+	mut document: ast::Document<'src>,
+	input: &'src str,
+	edit: &Edit,
+) -> Result<ast::Document<'src>, ParserError> {
+	let mut parser = Parser::new(input);
+
+	for path in enclosing(&document, edit).iter().rev() {
+		let node = get(&mut document, path);
+		let end = (node.end() as isize + edit.delta()) as usize;
+
+		if let Ok((reparsed, reparsed_end)) = parser.parse_node_at(node.start()) {
+			if reparsed_end == end {
+				Shift { edit }.visit_document(&mut document);
+				*get(&mut document, path) = reparsed;
+				return Ok(document);
+			}
+		}
+	}
+
+	crate::parse_document(input)
+}
+
+/// Returns the paths of the elements and flow controls which strictly enclose the edit, from
+/// the outermost to the innermost. A path is the index of a node in the list of nodes, for
+/// every level of nesting.
+fn enclosing(document: &ast::Document, edit: &Edit) -> Vec<Vec<(usize, usize)>> {
+	let mut paths = Vec::new();
+	let mut path = Vec::new();
+	let mut lists = vec![&document.children];
+
+	'outer: loop {
+		for (i, list) in lists.iter().enumerate() {
+			for (j, node) in list.iter().enumerate() {
+				let reparsable = matches!(node, ast::Node::Element(_) | ast::Node::FlowControl(_));
+
+				if reparsable && node.start() < edit.start && edit.end < node.end() {
+					path.push((i, j));
+					paths.push(path.clone());
+					lists = children(node);
+					continue 'outer;
+				}
+			}
+		}
+
+		return paths;
+	}
+}
+
+/// Returns the lists of child nodes of a node.
+fn children<'a, 'src>(node: &'a ast::Node<'src>) -> Vec<&'a Vec<ast::Node<'src>>> {
+	match node {
+		ast::Node::Element(node) => vec![&node.children],
+		ast::Node::FlowControl(ast::FlowControl::When(node)) => {
+			let mut lists = vec![&node.children];
+			lists.extend(node.chain.iter().map(|chained| &chained.children));
+			lists
+		}
+		ast::Node::FlowControl(ast::FlowControl::Each(node)) => {
+			let mut lists = vec![&node.children];
+			lists.extend(node.fallback.iter().map(|fallback| &fallback.children));
+			lists
+		}
+		ast::Node::FlowControl(ast::FlowControl::Let(node)) => vec![&node.children],
+		ast::Node::FlowControl(ast::FlowControl::Match(node)) => {
+			node.cases.iter().map(|case| &case.children).collect()
+		}
+		ast::Node::FlowControl(ast::FlowControl::Await(node)) => {
+			let mut lists = vec![&node.children];
+			lists.extend(node.then.iter().map(|then| &then.children));
+			lists.extend(node.catch.iter().map(|catch| &catch.children));
+			lists
+		}
+		ast::Node::FlowControl(ast::FlowControl::Try(node)) => {
+			vec![&node.children, &node.catch.children]
+		}
+		_ => Vec::new(),
+	}
+}
+
+fn children_mut<'a, 'src>(node: &'a mut ast::Node<'src>) -> Vec<&'a mut Vec<ast::Node<'src>>> {
+	match node {
+		ast::Node::Element(node) => vec![&mut node.children],
+		ast::Node::FlowControl(ast::FlowControl::When(node)) => {
+			let mut lists = vec![&mut node.children];
+			lists.extend(node.chain.iter_mut().map(|chained| &mut chained.children));
+			lists
+		}
+		ast::Node::FlowControl(ast::FlowControl::Each(node)) => {
+			let mut lists = vec![&mut node.children];
+			lists.extend(
+				node.fallback
+					.iter_mut()
+					.map(|fallback| &mut fallback.children),
+			);
+			lists
+		}
+		ast::Node::FlowControl(ast::FlowControl::Let(node)) => vec![&mut node.children],
+		ast::Node::FlowControl(ast::FlowControl::Match(node)) => node
+			.cases
+			.iter_mut()
+			.map(|case| &mut case.children)
+			.collect(),
+		ast::Node::FlowControl(ast::FlowControl::Await(node)) => {
+			let mut lists = vec![&mut node.children];
+			lists.extend(node.then.iter_mut().map(|then| &mut then.children));
+			lists.extend(node.catch.iter_mut().map(|catch| &mut catch.children));
+			lists
+		}
+		ast::Node::FlowControl(ast::FlowControl::Try(node)) => {
+			vec![&mut node.children, &mut node.catch.children]
+		}
+		_ => Vec::new(),
+	}
+}
+
+fn get<'a, 'src>(
+	document: &'a mut ast::Document<'src>,
+	path: &[(usize, usize)],
+) -> &'a mut ast::Node<'src> {
+	let (_, first) = path[0];
+	let mut node = &mut document.children[first];
+
+	for &(i, j) in &path[1..] {
+		node = &mut children_mut(node).swap_remove(i)[j];
+	}
+
+	node
+}
+
+/// Moves the offsets after the edit.
+struct Shift<'a> {
+	edit: &'a Edit,
+}
+
+impl Shift<'_> {
+	fn shift(&self, offset: &mut usize) {
+		if *offset >= self.edit.end {
+			*offset = (*offset as isize + self.edit.delta()) as usize;
+		}
+	}
+}
+
+fn leaf<V: VisitMut + ?Sized, N>(_visitor: &mut V, _node: &mut N) {}
+
+macro_rules! shift {
+	($($method:ident($ty:ty) => $walk:ident;)*) => {
+		$(
+			fn $method(&mut self, node: &mut $ty) {
+				self.shift(&mut node.start);
+				self.shift(&mut node.end);
+				$walk(self, node);
+			}
+		)*
+	};
+}
+
+impl VisitMut for Shift<'_> {
+	fn visit_dependency_statement(&mut self, node: &mut ast::DependencyStatement) {
+		self.shift(&mut node.start);
+		self.shift(&mut node.end);
+
+		if let Some(named) = &mut node.named {
+			self.shift(&mut named.start);
+			self.shift(&mut named.end);
+		}
+
+		walk_dependency_statement(self, node);
+	}
+
+	fn visit_element(&mut self, node: &mut ast::Element) {
+		self.shift(&mut node.start);
+		self.shift(&mut node.end);
+		self.shift(&mut node.start_tag.start);
+		self.shift(&mut node.start_tag.end);
+
+		if let Some(end_tag) = &mut node.end_tag {
+			self.shift(&mut end_tag.start);
+			self.shift(&mut end_tag.end);
+		}
+
+		walk_element(self, node);
+	}
+
+	shift! {
+		visit_dependency_default_specifier(ast::DependencyDefaultSpecifier) => walk_dependency_default_specifier;
+		visit_dependency_named_specifier(ast::DependencyNamedSpecifier) => walk_dependency_named_specifier;
+		visit_comment(ast::Comment) => leaf;
+		visit_static_attribute(ast::StaticAttribute) => walk_static_attribute;
+		visit_binding_attribute(ast::BindingAttribute) => walk_binding_attribute;
+		visit_spread_attribute(ast::SpreadAttribute) => walk_spread_attribute;
+		visit_short_binding_attribute(ast::ShortBindingAttribute) => walk_short_binding_attribute;
+		visit_text(ast::Text) => leaf;
+		visit_text_binding(ast::TextBinding) => walk_text_binding;
+		visit_flow_control_when(ast::FlowControlWhen) => walk_flow_control_when;
+		visit_flow_control_else(ast::FlowControlElse) => walk_flow_control_else;
+		visit_flow_control_each(ast::FlowControlEach) => walk_flow_control_each;
+		visit_flow_control_let(ast::FlowControlLet) => walk_flow_control_let;
+		visit_flow_control_match(ast::FlowControlMatch) => walk_flow_control_match;
+		visit_flow_control_case(ast::FlowControlCase) => walk_flow_control_case;
+		visit_flow_control_await(ast::FlowControlAwait) => walk_flow_control_await;
+		visit_flow_control_await_branch(ast::FlowControlAwaitBranch) => walk_flow_control_await_branch;
+		visit_flow_control_try(ast::FlowControlTry) => walk_flow_control_try;
+		visit_invalid(ast::Invalid) => leaf;
+		visit_identifier(ast::Identifier) => leaf;
+		visit_string_literal(ast::StringLiteral) => leaf;
+		visit_number_literal(ast::NumberLiteral) => leaf;
+		visit_boolean_literal(ast::BooleanLiteral) => leaf;
+		visit_null_literal(ast::NullLiteral) => leaf;
+		visit_identifier_expression(javascript::IdentifierExpression) => leaf;
+		visit_javascript_string_literal(javascript::StringLiteral) => leaf;
+		visit_javascript_number_literal(javascript::NumberLiteral) => leaf;
+		visit_javascript_bigint_literal(javascript::BigIntLiteral) => leaf;
+		visit_javascript_boolean_literal(javascript::BooleanLiteral) => leaf;
+		visit_javascript_null_literal(javascript::NullLiteral) => leaf;
+		visit_javascript_regexp_literal(javascript::RegExpLiteral) => leaf;
+		visit_this_expression(javascript::ThisExpression) => leaf;
+		visit_unary_expression(javascript::UnaryExpression) => walk_unary_expression;
+		visit_binary_expression(javascript::BinaryExpression) => walk_binary_expression;
+		visit_conditional_expression(javascript::ConditionalExpression) => walk_conditional_expression;
+		visit_call_expression(javascript::CallExpression) => walk_call_expression;
+		visit_new_expression(javascript::NewExpression) => walk_new_expression;
+		visit_member_expression(javascript::MemberExpression) => walk_member_expression;
+		visit_sequence_expression(javascript::SequenceExpression) => walk_sequence_expression;
+		visit_await_expression(javascript::AwaitExpression) => walk_await_expression;
+		visit_function_expression(javascript::FunctionExpression) => walk_function_expression;
+		visit_assignment_expression(javascript::AssignmentExpression) => walk_assignment_expression;
+		visit_spread_expression(javascript::SpreadExpression) => walk_spread_expression;
+		visit_template_literal(javascript::TemplateLiteral) => walk_template_literal;
+		visit_template_element(javascript::TemplateElement) => leaf;
+		visit_tagged_template_expression(javascript::TaggedTemplateExpression) => walk_tagged_template_expression;
+		visit_object_expression(javascript::ObjectExpression) => walk_object_expression;
+		visit_object_keyed_property(javascript::ObjectKeyedProperty) => walk_object_keyed_property;
+		visit_object_computed_property(javascript::ObjectComputedProperty) => walk_object_computed_property;
+		visit_array_expression(javascript::ArrayExpression) => walk_array_expression;
+		visit_parenthesized_expression(javascript::ParenthesizedExpression) => walk_parenthesized_expression;
+		visit_empty_expression(javascript::EmptyExpression) => leaf;
+		visit_ts_as_expression(javascript::TsAsExpression) => walk_ts_as_expression;
+		visit_ts_satisfies_expression(javascript::TsSatisfiesExpression) => walk_ts_satisfies_expression;
+		visit_ts_non_null_expression(javascript::TsNonNullExpression) => walk_ts_non_null_expression;
+		visit_ts_annotated_expression(javascript::TsAnnotatedExpression) => walk_ts_annotated_expression;
+		visit_ts_type(javascript::TsType) => leaf;
+		visit_block_statement(javascript::BlockStatement) => walk_block_statement;
+		visit_expression_statement(javascript::ExpressionStatement) => walk_expression_statement;
+		visit_variable_declaration(javascript::VariableDeclaration) => walk_variable_declaration;
+		visit_variable_declarator(javascript::VariableDeclarator) => walk_variable_declarator;
+		visit_return_statement(javascript::ReturnStatement) => walk_return_statement;
+		visit_if_statement(javascript::IfStatement) => walk_if_statement;
+		visit_throw_statement(javascript::ThrowStatement) => walk_throw_statement;
+		visit_try_statement(javascript::TryStatement) => walk_try_statement;
+		visit_catch_clause(javascript::CatchClause) => walk_catch_clause;
+		visit_empty_statement(javascript::EmptyStatement) => leaf;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Asserts that reparsing after the edit gives the same tree as parsing from scratch.
+	fn assert_reparse(input: &str, edit: Edit) -> Vec<Vec<(usize, usize)>> {
+		let document = crate::parse_document(input).unwrap();
+		let paths = enclosing(&document, &edit);
+		let output = edit.apply(input);
+
+		let expected = crate::parse_document(&output).unwrap();
+		let actual = reparse_document(document, &output, &edit).unwrap();
+		assert_eq!(format!("{:?}", actual), format!("{:?}", expected));
+
+		paths
+	}
+
+	#[test]
+	fn test_edit_apply() {
+		assert_eq!(Edit::new(1, 2, "öö").apply("aäc"), "aööc");
+		assert_eq!(Edit::new(3, 3, "d").apply("abc"), "abcd");
+	}
+
+	#[test]
+	fn test_reparse_text() {
+		let input = "<div>\n\t<p>foo</p>\n\t<p>bar</p>\n</div>\n<span>baz</span>";
+		let paths = assert_reparse(input, Edit::new(11, 12, "ooooo"));
+
+		// The first paragraph is the innermost node.
+		assert_eq!(paths, [vec![(0, 0)], vec![(0, 0), (0, 1)]]);
+	}
+
+	#[test]
+	fn test_reparse_deletion() {
+		let input = "<div>\n\t<p>foo {bar}</p>\n\t<p class='a'>{baz(1, [2])}</p>\n</div>\n<span>{qux}</span>";
+		assert_reparse(input, Edit::new(10, 14, ""));
+	}
+
+	#[test]
+	fn test_reparse_attribute() {
+		let input = "<div><p class='a'>foo</p><p title={b}>bar</p></div>";
+		assert_reparse(input, Edit::new(15, 16, "abc"));
+		assert_reparse(input, Edit::new(35, 36, "c + d"));
+	}
+
+	#[test]
+	fn test_reparse_flow_control() {
+		let input = "<div><#when {a}><p>b</p><#else when {c}><p>d</p><#else>e</when><#each {f in g}><i>{f}</i></each></div>";
+		assert_reparse(input, Edit::new(19, 20, "bb"));
+		assert_reparse(input, Edit::new(43, 43, "dd"));
+		assert_reparse(input, Edit::new(55, 56, "<b>e</b>"));
+		assert_reparse(input, Edit::new(83, 84, "f.h"));
+
+		let input = "<div><#each {f in g}><i>{f}</i><#else><p>h</p></each></div>";
+		assert_reparse(input, Edit::new(41, 42, "hh"));
+
+		let input = "<div><#match {a}><#case {1}><p>b</p><#default>c</match></div>";
+		assert_reparse(input, Edit::new(31, 32, "bb"));
+
+		let input = "<div><#await {a}>b<#then {c}><p>{c}</p><#catch>d</await></div>";
+		assert_reparse(input, Edit::new(33, 34, "c.e"));
+
+		let input = "<div><#try><p>{a}</p><#catch {e}>{e}</try></div>";
+		assert_reparse(input, Edit::new(15, 16, "a.b"));
+	}
+
+	#[test]
+	fn test_reparse_structure() {
+		let input = "<div><p>foo</p><p>bar</p></div>";
+
+		// Closes the paragraph early, so the parent is parsed again.
+		let paths = assert_reparse(input, Edit::new(10, 10, "</p><p>"));
+		assert_eq!(paths.len(), 2);
+
+		// Outside any element.
+		let paths = assert_reparse("<p>foo</p>  <p>bar</p>", Edit::new(11, 11, "<i></i>"));
+		assert!(paths.is_empty());
+	}
+
+	#[test]
+	fn test_reparse_error() {
+		let input = "<div><p>foo</p></div>";
+		let document = crate::parse_document(input).unwrap();
+		let edit = Edit::new(11, 15, "");
+
+		assert!(reparse_document(document, &edit.apply(input), &edit).is_err());
+	}
+}
@@ -0,0 +1,243 @@
+//! JSON representation of the syntax tree, enabled by the `serde` feature.
+//!
+//! The document is wrapped in an object with the schema [`VERSION`], which is bumped on every
+//! breaking change to the shape:
+//!
+//! ```json
+//! { "version": 13, "document": { "type": "Document", "children": [] } }
+//! ```
+//!
+//! Every node has `start` and `end` offsets, counted in unicode characters. Fields are
+//! camelCased. Nodes which can be one of several kinds have a `type` field naming the kind:
+//!
+//! - Template nodes are `DependencyStatement`, `Comment`, `Element`, `Text`, `TextBinding`,
+//!   `FlowControl` and `Invalid`. Flow control has a `kind` of `When`, `Each`, `Let`, `Match`,
+//!   `Await` or `Try`. Each has an `iterator`, which is an identifier or a pattern like the `params`
+//!   of arrow functions, and an `index`, a `key` and a `fallback` branch, or `null` when they
+//!   are left out. Let binds a `name` to a `value`. Match has `cases` with `tests`, which are
+//!   empty for the default case. Await has a `then` and a `catch` branch, or `null`, with an
+//!   optional `binding` pattern. Try always has a `catch` branch.
+//! - Attributes are `StaticAttribute`, `BindingAttribute`, `SpreadAttribute` and
+//!   `ShortBindingAttribute`.
+//! - Expressions follow ESTree: `Identifier`, `Literal`, `ThisExpression`, `UnaryExpression`,
+//!   `BinaryExpression`, `ConditionalExpression`, `CallExpression`, `NewExpression`,
+//!   `MemberExpression`, `SequenceExpression`, `AwaitExpression`, `ArrowFunctionExpression`,
+//!   `AssignmentExpression`, `SpreadElement`, `TemplateLiteral`, `TaggedTemplateExpression`,
+//!   `ObjectExpression`, `ArrayExpression`, with the debrix specific `ParenthesizedExpression`
+//!   and `EmptyExpression`. Literals have a `kind` of `String`, `Number`, `BigInt`, `Boolean`,
+//!   `Null` or `RegExp`, next to their `raw` source. Strings have the `value` with escapes
+//!   replaced, BigInts the digits as `value`, and regular expressions a `pattern` and `flags`.
+//!   Operators are their source text, like `"??"` or `"typeof"`, and `prefix` is false for
+//!   `a++`. Calls and member accesses in an optional chain are `optional`. Object properties are
+//!   `Property`, `ComputedProperty` and `SpreadElement`. Template literals have `quasis`, with
+//!   their `raw` and `cooked` text, between their `expressions`.
+//! - Arrow functions are `async` or not, and destructure their `params` with object and array
+//!   expressions. Their `body` is an expression, or a block of statements without a `type`.
+//!   Statements are `BlockStatement`, `ExpressionStatement`, `VariableDeclaration`,
+//!   `ReturnStatement`, `IfStatement`, `ThrowStatement`, `TryStatement` and `EmptyStatement`.
+//!   Other statements, like loops, `switch`, `break` and `continue`, and `function` or `class`
+//!   expressions are not part of this subset and fail to parse as unsupported.
+//! - TypeScript, when enabled, adds `TSAsExpression`, `TSSatisfiesExpression`,
+//!   `TSNonNullExpression`, and `TSAnnotatedExpression` for typed parameters. Types are a `raw`
+//!   `typeAnnotation`, and arrow functions have `typeParameters` and a `returnType`, or `null`.
+//!
+//! Nodes in fields of a single kind, like the `tagName` of an element, have no `type`.
+
+use crate::ast;
+use serde::{Deserialize, Serialize};
+
+pub const VERSION: u32 = 13;
+
+#[derive(Serialize)]
+struct Versioned<'a> {
+	version: u32,
+	document: &'a ast::Document<'a>,
+}
+
+#[derive(Deserialize)]
+struct Version {
+	version: u32,
+}
+
+#[derive(Deserialize)]
+struct Owned {
+	document: ast::Document<'static>,
+}
+
+pub fn to_string(document: &ast::Document) -> String {
+	serde_json::to_string(&Versioned {
+		version: VERSION,
+		document,
+	})
+	// The syntax tree has no maps with non-string keys, so serializing never fails.
+	.unwrap()
+}
+
+/// Reads a document from JSON, which owns its names and text. Fails if the JSON was written with
+/// another schema version.
+pub fn from_str(json: &str) -> Result<ast::Document<'static>, serde_json::Error> {
+	let Version { version } = serde_json::from_str(json)?;
+
+	if version != VERSION {
+		return Err(serde::de::Error::custom(format!(
+			"unsupported schema version {}, expected {}",
+			version, VERSION
+		)));
+	}
+
+	Ok(serde_json::from_str::<Owned>(json)?.document)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn to_value(input: &str) -> serde_json::Value {
+		let document = crate::parse_document(input).unwrap();
+		serde_json::from_str(&to_string(&document)).unwrap()
+	}
+
+	#[test]
+	fn test_json_shape() {
+		let value = to_value("<p class='a' title={b + 1}>{c ? d : e}</p>");
+
+		assert_eq!(value["version"], VERSION);
+		assert_eq!(value["document"]["type"], "Document");
+
+		let element = &value["document"]["children"][0];
+		assert_eq!(element["type"], "Element");
+		assert_eq!(element["tagName"]["name"], "p");
+		assert_eq!(element["startTag"]["end"], 27);
+		assert_eq!(element["attributes"][0]["type"], "StaticAttribute");
+		assert_eq!(element["attributes"][0]["value"]["value"], "a");
+
+		let binary = &element["attributes"][1]["value"];
+		assert_eq!(binary["type"], "BinaryExpression");
+		assert_eq!(binary["operator"], "+");
+		assert_eq!(binary["left"]["type"], "Identifier");
+
+		let conditional = &element["children"][0]["expression"];
+		assert_eq!(element["children"][0]["type"], "TextBinding");
+		assert_eq!(conditional["type"], "ConditionalExpression");
+		assert_eq!(conditional["test"]["name"], "c");
+	}
+
+	#[test]
+	fn test_json_literals() {
+		let value = to_value("<p>{f('a', 1, true, null)}</p>");
+		let arguments = &value["document"]["children"][0]["children"][0]["expression"]["arguments"];
+
+		assert_eq!(arguments[0]["type"], "Literal");
+		assert_eq!(arguments[0]["kind"], "String");
+		assert_eq!(arguments[0]["value"], "a");
+		assert_eq!(arguments[0]["raw"], "'a'");
+		assert_eq!(arguments[1]["kind"], "Number");
+		assert_eq!(arguments[1]["value"], 1.0);
+		assert_eq!(arguments[2]["kind"], "Boolean");
+		assert_eq!(arguments[3]["kind"], "Null");
+	}
+
+	#[test]
+	fn test_json_literal_forms() {
+		let value = to_value("<p title='a\\tb'>{f('\\x41', 0x10, 1_0n, /b/g)}</p>");
+		let element = &value["document"]["children"][0];
+		let arguments = &element["children"][0]["expression"]["arguments"];
+
+		assert_eq!(element["attributes"][0]["value"]["value"], "a\tb");
+		assert_eq!(arguments[0]["value"], "A");
+		assert_eq!(arguments[1]["value"], 16.0);
+		assert_eq!(arguments[2]["kind"], "BigInt");
+		assert_eq!(arguments[2]["value"], "10");
+		assert_eq!(arguments[3]["kind"], "RegExp");
+		assert_eq!(arguments[3]["pattern"], "b");
+		assert_eq!(arguments[3]["flags"], "g");
+	}
+
+	#[test]
+	fn test_json_template() {
+		let value = to_value("<p>{`a${b}\\u0063`}</p>");
+		let template = &value["document"]["children"][0]["children"][0]["expression"];
+
+		assert_eq!(template["type"], "TemplateLiteral");
+		assert_eq!(template["quasis"][0]["raw"], "a");
+		assert_eq!(template["quasis"][1]["raw"], "\\u0063");
+		assert_eq!(template["quasis"][1]["cooked"], "c");
+		assert_eq!(template["expressions"][0]["name"], "b");
+	}
+
+	#[test]
+	fn test_json_operators() {
+		let value = to_value("<p>{a++, this?.b() ?? c}</p>");
+		let sequence = &value["document"]["children"][0]["children"][0]["expression"];
+
+		assert_eq!(sequence["type"], "SequenceExpression");
+		assert_eq!(sequence["expressions"][0]["type"], "UnaryExpression");
+		assert_eq!(sequence["expressions"][0]["prefix"], false);
+
+		let nullish = &sequence["expressions"][1];
+		assert_eq!(nullish["operator"], "??");
+		assert_eq!(nullish["left"]["optional"], false);
+		assert_eq!(nullish["left"]["callee"]["optional"], true);
+		assert_eq!(
+			nullish["left"]["callee"]["object"]["type"],
+			"ThisExpression"
+		);
+	}
+
+	#[test]
+	fn test_json_functions() {
+		let value = to_value("<p>{async ({a, ...b} = {}) => { const c = await a; return c; }}</p>");
+		let function = &value["document"]["children"][0]["children"][0]["expression"];
+
+		assert_eq!(function["type"], "ArrowFunctionExpression");
+		assert_eq!(function["async"], true);
+		assert_eq!(function["params"][0]["type"], "AssignmentExpression");
+		assert_eq!(function["params"][0]["left"]["type"], "ObjectExpression");
+
+		let body = &function["body"]["body"];
+		assert_eq!(body[0]["type"], "VariableDeclaration");
+		assert_eq!(body[0]["kind"], "const");
+		assert_eq!(
+			body[0]["declarations"][0]["init"]["type"],
+			"AwaitExpression"
+		);
+		assert_eq!(body[1]["type"], "ReturnStatement");
+		assert_eq!(body[1]["argument"]["name"], "c");
+	}
+
+	#[test]
+	fn test_json_typescript() {
+		let document = crate::parse_document_typescript("<p>{(a?: T): U => a! as U}</p>").unwrap();
+		let value: serde_json::Value = serde_json::from_str(&to_string(&document)).unwrap();
+		let function = &value["document"]["children"][0]["children"][0]["expression"];
+
+		assert_eq!(function["typeParameters"], serde_json::Value::Null);
+		assert_eq!(function["returnType"]["raw"], "U");
+		assert_eq!(function["params"][0]["type"], "TSAnnotatedExpression");
+		assert_eq!(function["params"][0]["optional"], true);
+		assert_eq!(function["params"][0]["typeAnnotation"]["raw"], "T");
+		assert_eq!(function["body"]["type"], "TSAsExpression");
+		assert_eq!(
+			function["body"]["expression"]["type"],
+			"TSNonNullExpression"
+		);
+
+		let json = to_string(&document);
+		assert_eq!(to_string(&from_str(&json).unwrap()), json);
+	}
+
+	#[test]
+	fn test_json_roundtrip() {
+		let input = "using a from 'a'\n<div {...b}><#when {c}>{d.e[f]}<#else>g</when><#each {{h, s = [t]}, q in i; key h.r}>{(j) => k ??= [l, this]}{async x => { try { await x; } catch { y(); } }}<#else>u</each><#let {v = w.z}>{v}</let><#match {v}><#case {1, 2}>a<#default>b</match><#await {x}>y<#then {{z}}>{z}<#catch>e</await><#try>{x()}<#catch {e}>{e}</try>{m`n${o}`, p--}</div>";
+		let document = crate::parse_document(input).unwrap();
+		let json = to_string(&document);
+
+		assert_eq!(to_string(&from_str(&json).unwrap()), json);
+	}
+
+	#[test]
+	fn test_json_version() {
+		let json = r#"{ "version": 0, "document": { "type": "Document", "children": [] } }"#;
+		assert!(from_str(json).is_err());
+	}
+}
@@ -1,23 +1,80 @@
 pub mod ast;
+pub mod cst;
 mod error;
+pub mod incremental;
+#[cfg(feature = "serde")]
+pub mod json;
 mod location;
 mod parser;
 mod scanner;
+pub mod visit;
+pub mod visit_mut;
 
 pub(crate) use {self::parser::*, self::scanner::*, std::fmt};
 
-pub use self::{error::ParserError, parser::Parser};
+pub use self::{
+	error::ParserError,
+	location::{Encoding, LineIndex, Location, OffsetIndex, Position},
+	parser::Parser,
+	visit::Visit,
+	visit_mut::VisitMut,
+};
 
 #[cfg(test)]
 mod tests;
 
-pub fn parse_document(input: String) -> Result<ast::Document, ParserError> {
+/// Parses a document. The syntax tree borrows its names and text from the input.
+pub fn parse_document(input: &str) -> Result<ast::Document<'_>, ParserError> {
 	Ok(ast::Document {
 		children: parse(input)?,
 	})
 }
 
-pub fn parse(input: String) -> Result<Vec<ast::Node>, ParserError> {
+/// Parses a document like [`parse_document`], allowing TypeScript in its expressions, see
+/// [`Parser::set_typescript`].
+pub fn parse_document_typescript(input: &str) -> Result<ast::Document<'_>, ParserError> {
+	let mut parser = Parser::new(input);
+	let mut children = Vec::new();
+
+	parser.set_typescript(true);
+
+	while let Some(node) = parser.next()? {
+		children.push(node);
+	}
+
+	Ok(ast::Document { children })
+}
+
+/// Parses a document, recovering from errors. Returns the partial document, where unparsable
+/// input is replaced by `ast::Node::Invalid`, along with every error found.
+pub fn parse_document_recovering(input: &str) -> (ast::Document<'_>, Vec<ParserError>) {
+	let mut parser = Parser::new(input);
+	let mut children = Vec::new();
+
+	parser.set_recover(true);
+
+	// Errors are never returned when recovering.
+	while let Ok(Some(node)) = parser.next() {
+		children.push(node);
+	}
+
+	(ast::Document { children }, parser.take_errors())
+}
+
+/// Parses a document into a lossless concrete syntax tree, see [`cst`].
+pub fn parse_cst(input: &str) -> Result<cst::SyntaxNode, ParserError> {
+	let document = parse_document(input)?;
+	Ok(cst::build(input, &document))
+}
+
+/// Parses a document into a lossless concrete syntax tree, recovering from errors like
+/// [`parse_document_recovering`].
+pub fn parse_cst_recovering(input: &str) -> (cst::SyntaxNode, Vec<ParserError>) {
+	let (document, errors) = parse_document_recovering(input);
+	(cst::build(input, &document), errors)
+}
+
+pub fn parse(input: &str) -> Result<Vec<ast::Node<'_>>, ParserError> {
 	let mut parser = Parser::new(input);
 	let mut nodes = Vec::new();
 
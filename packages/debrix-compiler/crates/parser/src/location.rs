@@ -47,3 +47,138 @@ impl fmt::Debug for Location {
 		write!(f, "{:?} -> {:?}", self.start, self.end)
 	}
 }
+
+/// Unit of offsets. The parser and compiler count offsets in chars, which can be converted to
+/// UTF-8 bytes or UTF-16 code units (used by javascript strings) using `OffsetIndex`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Encoding {
+	Char,
+	Utf8,
+	Utf16,
+}
+
+impl Encoding {
+	fn len(&self, char: char) -> usize {
+		match self {
+			Encoding::Char => 1,
+			Encoding::Utf8 => char.len_utf8(),
+			Encoding::Utf16 => char.len_utf16(),
+		}
+	}
+}
+
+/// Converts char offsets in the input to offsets in another encoding.
+#[derive(Debug, Clone)]
+pub struct OffsetIndex {
+	/// Converted offsets of every char, followed by the length of the input. Empty for
+	/// `Encoding::Char`, where offsets are not converted.
+	offsets: Vec<usize>,
+}
+
+impl OffsetIndex {
+	pub fn new(input: &str, encoding: Encoding) -> Self {
+		let mut offsets = Vec::new();
+
+		if encoding != Encoding::Char {
+			let mut offset = 0;
+
+			// There are at most as many chars as bytes.
+			offsets.reserve(input.len() + 1);
+
+			for char in input.chars() {
+				offsets.push(offset);
+				offset += encoding.len(char);
+			}
+
+			offsets.push(offset);
+		}
+
+		Self { offsets }
+	}
+
+	pub fn offset(&self, offset: usize) -> usize {
+		if self.offsets.is_empty() {
+			offset
+		} else {
+			self.offsets[offset.min(self.offsets.len() - 1)]
+		}
+	}
+}
+
+/// Maps offsets in the input to positions, where lines and columns start at 1.
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+	/// Offsets of the first character of every line.
+	lines: Vec<usize>,
+}
+
+impl LineIndex {
+	pub fn new(input: &str) -> Self {
+		Self::with_encoding(input, Encoding::Char)
+	}
+
+	/// Creates a line index for offsets, and columns, in `encoding`.
+	pub fn with_encoding(input: &str, encoding: Encoding) -> Self {
+		let mut lines = vec![0];
+		let mut offset = 0;
+
+		for char in input.chars() {
+			offset += encoding.len(char);
+
+			if char == '\n' {
+				lines.push(offset);
+			}
+		}
+
+		Self { lines }
+	}
+
+	pub fn position(&self, offset: usize) -> Position {
+		let line = match self.lines.binary_search(&offset) {
+			Ok(line) => line,
+			Err(line) => line - 1,
+		};
+
+		Position::new(line + 1, offset - self.lines[line] + 1)
+	}
+
+	pub fn location(&self, start: usize, end: usize) -> Location {
+		Location::new(self.position(start), self.position(end))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_line_index() {
+		let index = LineIndex::new("foo\nbar\n\nbaz");
+
+		assert_eq!(index.position(0), Position::new(1, 1));
+		assert_eq!(index.position(2), Position::new(1, 3));
+		assert_eq!(index.position(3), Position::new(1, 4));
+		assert_eq!(index.position(4), Position::new(2, 1));
+		assert_eq!(index.position(8), Position::new(3, 1));
+		assert_eq!(index.position(10), Position::new(4, 2));
+	}
+
+	#[test]
+	fn test_offset_index() {
+		let input = "é👋\na";
+
+		let utf8 = OffsetIndex::new(input, Encoding::Utf8);
+		assert_eq!(utf8.offset(1), 2);
+		assert_eq!(utf8.offset(2), 6);
+		assert_eq!(utf8.offset(4), 8);
+
+		let utf16 = OffsetIndex::new(input, Encoding::Utf16);
+		assert_eq!(utf16.offset(1), 1);
+		assert_eq!(utf16.offset(2), 3);
+		assert_eq!(utf16.offset(4), 5);
+
+		let lines = LineIndex::with_encoding(input, Encoding::Utf16);
+		assert_eq!(lines.position(utf16.offset(2)), Position::new(1, 4));
+		assert_eq!(lines.position(utf16.offset(4)), Position::new(2, 2));
+	}
+}
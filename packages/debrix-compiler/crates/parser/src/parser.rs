@@ -7,22 +7,36 @@ mod javascript;
 mod literal;
 mod text;
 
-use std::ops::Deref;
+use std::cell::OnceCell;
 
 pub(crate) use identifier::*;
 
 use crate::*;
 
-pub struct Parser {
-	scanner: Scanner,
+pub struct Parser<'src> {
+	input: &'src str,
+	scanner: Scanner<'src>,
+	/// Built on the first error, as valid input never needs it.
+	lines: OnceCell<LineIndex>,
 	debug: bool,
+	recover: bool,
+	typescript: bool,
+	errors: Vec<ParserError>,
+	/// Tag names of the currently open elements and flow controls.
+	open: Vec<&'src str>,
 }
 
-impl Parser {
-	pub fn new(input: String) -> Self {
+impl<'src> Parser<'src> {
+	pub fn new(input: &'src str) -> Self {
 		Self {
-			scanner: Scanner::new(&input),
+			input,
+			scanner: Scanner::new(input),
+			lines: OnceCell::new(),
 			debug: false,
+			recover: false,
+			typescript: false,
+			errors: Vec::new(),
+			open: Vec::new(),
 		}
 	}
 
@@ -31,6 +45,153 @@ impl Parser {
 		self.debug = value;
 	}
 
+	/// When recovering, errors are collected instead of returned and the unparsable input is
+	/// replaced by `ast::Node::Invalid`. Collected errors are returned by `take_errors`.
+	pub fn set_recover(&mut self, value: bool) {
+		self.recover = value;
+	}
+
+	/// Allows TypeScript in expressions, like `a as T` or `(a: T) => a`. Types are kept in the
+	/// syntax tree as written.
+	pub fn set_typescript(&mut self, value: bool) {
+		self.typescript = value;
+	}
+
+	pub fn take_errors(&mut self) -> Vec<ParserError> {
+		std::mem::take(&mut self.errors)
+	}
+
+	/// Collects the error if recovering, otherwise returns it.
+	fn recover(&mut self, error: ParserError) -> Result<(), ParserError> {
+		if !self.recover {
+			return Err(error);
+		}
+
+		// Errors from unclosed elements often cascade at the same position, such as at the end
+		// of input. Only the first is useful.
+		if self.errors.last().map(|last| last.position) != Some(error.position) {
+			self.errors.push(error);
+		}
+
+		Ok(())
+	}
+
+	/// Skips input until the next element, closing tag or flow control, or past the next `}`.
+	/// Returns a placeholder node for the skipped input, which starts at `start`.
+	fn resync(&mut self, start: usize) -> ast::Node<'src> {
+		if self.scanner.cursor() <= start {
+			self.scanner.set_cursor(start);
+			self.scanner.next();
+		}
+
+		while let Some(char) = self.scanner.peek() {
+			if char == '<' {
+				break;
+			}
+
+			self.scanner.next();
+
+			if char == '}' {
+				break;
+			}
+		}
+
+		ast::Invalid {
+			start,
+			end: self.scanner.cursor(),
+		}
+		.into()
+	}
+
+	/// Skips the tag at the cursor, such as a stray closing tag, reporting it as unexpected.
+	/// Returns a placeholder node for the tag, or the error if not recovering.
+	fn skip_tag(&mut self) -> Result<ast::Node<'src>, ParserError> {
+		let start = self.scanner.cursor();
+		let error = self.unexpected();
+		self.recover(error)?;
+
+		// A `>` within a binding, such as in `<#hen {a > b}>`, does not end the tag.
+		let mut depth = 0usize;
+		while let Some(char) = self.scanner.peek() {
+			self.scanner.next();
+
+			match char {
+				'{' => depth += 1,
+				'}' => depth = depth.saturating_sub(1),
+				'>' if depth == 0 => break,
+				_ => {}
+			}
+		}
+
+		Ok(ast::Invalid {
+			start,
+			end: self.scanner.cursor(),
+		}
+		.into())
+	}
+
+	/// Returns whether the cursor is at the start of a flow control block.
+	fn test_flow_control(&self) -> bool {
+		["<#when", "<#each", "<#let", "<#match", "<#await", "<#try"]
+			.iter()
+			.any(|keyword| self.scanner.test(keyword))
+	}
+
+	/// Returns the keyword of the flow control branch at the cursor, such as `else` for `<#else>`.
+	fn test_flow_control_branch(&self) -> Option<&'static str> {
+		if !self.scanner.test("<#") {
+			return None;
+		}
+
+		["else", "case", "default", "then", "catch"]
+			.into_iter()
+			.find(|keyword| self.scanner.test(&format!("<#{keyword}")))
+	}
+
+	/// Returns the name of the closing tag at the cursor, without moving the cursor.
+	fn peek_closing_tag(&mut self) -> &'src str {
+		let cursor = self.scanner.cursor();
+		let mut name = "";
+
+		if self.scanner.take("</") {
+			let start = self.scanner.cursor();
+
+			while let Some(char) = self.scanner.peek() {
+				if char.is_whitespace() || char == '>' {
+					break;
+				}
+
+				self.scanner.next();
+			}
+
+			name = self.scanner.slice(start, self.scanner.cursor());
+		}
+
+		self.scanner.set_cursor(cursor);
+		name
+	}
+
+	/// Parses the closing tag of `name`. When recovering, a missing or mismatched closing tag is
+	/// reported and the element is implicitly closed, leaving the cursor before the closing tag.
+	fn parse_closing_tag(&mut self, name: &str) -> Result<Option<ast::Range>, ParserError> {
+		let start = self.scanner.cursor();
+
+		let error = if !self.scanner.take("</") {
+			self.expected(&["</"])
+		} else if !self.scanner.take(name) {
+			self.expected(&[name])
+		} else if !self.scanner.take(">") {
+			self.expected(&[">"])
+		} else {
+			return Ok(Some(ast::Range::new(start, self.scanner.cursor())));
+		};
+
+		self.recover(error)?;
+		self.scanner.set_cursor(start);
+
+		Ok(None)
+	}
+
 	fn skip_whitespace(&mut self) -> bool {
 		let mut has_whitespace = false;
 
@@ -42,14 +203,14 @@ impl Parser {
 					continue;
 				}
 
-				if char == &'/' {
+				if char == '/' {
 					if let Some(char) = self.scanner.next() {
 						match char {
-							&'/' => {
+							'/' => {
 								has_whitespace = true;
 
 								while let Some(char) = self.scanner.next() {
-									if char == &'\n' {
+									if char == '\n' {
 										break;
 									}
 								}
@@ -57,11 +218,11 @@ impl Parser {
 								continue;
 							}
 
-							&'*' => {
+							'*' => {
 								has_whitespace = true;
 
 								while let Some(char) = self.scanner.next() {
-									if char == &'*' {
+									if char == '*' {
 										self.scanner.next();
 
 										if self.scanner.take("/") {
@@ -92,11 +253,20 @@ impl Parser {
 		has_whitespace
 	}
 
+	fn error(&self, position: usize, positives: &[&str]) -> ParserError {
+		ParserError {
+			position,
+			location: self
+				.lines
+				.get_or_init(|| LineIndex::new(self.input))
+				.location(position, position),
+			positives: positives.iter().map(|x| x.to_string()).collect(),
+			message: None,
+		}
+	}
+
 	fn unexpected(&self) -> ParserError {
-		let error = ParserError {
-			position: self.scanner.cursor(),
-			positives: Vec::new(),
-		};
+		let error = self.error(self.scanner.cursor(), &[]);
 
 		if self.debug {
 			panic!("{:?}", error);
@@ -106,13 +276,7 @@ impl Parser {
 	}
 
 	fn expected(&self, positives: &[&str]) -> ParserError {
-		let error = ParserError {
-			position: self.scanner.cursor(),
-			positives: positives
-				.into_iter()
-				.map(|x| x.deref().to_owned())
-				.collect(),
-		};
+		let error = self.error(self.scanner.cursor(), positives);
 
 		if self.debug {
 			panic!("{:?}", error);
@@ -121,64 +285,144 @@ impl Parser {
 		error
 	}
 
-	pub fn next(&mut self) -> Result<Option<ast::Node>, ParserError> {
+	/// Parses the element or flow control at `start`. Returns it with the position after it.
+	pub(crate) fn parse_node_at(
+		&mut self,
+		start: usize,
+	) -> Result<(ast::Node<'src>, usize), ParserError> {
+		self.scanner.set_cursor(start);
+
+		let node = if self.scanner.test("<#") {
+			self.parse_flow_control()?.into()
+		} else {
+			self.parse_element()?.into()
+		};
+
+		Ok((node, self.scanner.cursor()))
+	}
+
+	pub fn next(&mut self) -> Result<Option<ast::Node<'src>>, ParserError> {
 		self.skip_whitespace();
 
 		if self.scanner.is_done() {
 			return Ok(None);
 		}
 
-		if self.scanner.test("using") {
-			return Ok(Some(self.parse_dependency_statement()?.into()));
-		}
+		let start = self.scanner.cursor();
 
-		if self.scanner.test("<") {
-			return Ok(Some(self.parse_element()?.into()));
-		}
+		let result = if self.scanner.test("using") {
+			self.parse_dependency_statement().map(|node| node.into())
+		} else if self.test_flow_control() {
+			// Flow control is only valid within an element. When recovering, the block is parsed
+			// and skipped as a whole.
+			let error = self.unexpected();
+			self.recover(error)?;
+
+			self.parse_flow_control().map(|_| {
+				ast::Invalid {
+					start,
+					end: self.scanner.cursor(),
+				}
+				.into()
+			})
+		} else if self.scanner.test("<#") {
+			return self.skip_tag().map(Some);
+		} else if self.scanner.test("<") && !self.scanner.test("</") {
+			self.parse_element().map(|node| node.into())
+		} else {
+			Err(self.unexpected())
+		};
 
-		Err(self.unexpected())
+		match result {
+			Ok(node) => Ok(Some(node)),
+			Err(error) => {
+				self.recover(error)?;
+				Ok(Some(self.resync(start)))
+			}
+		}
 	}
 
-	pub fn parse_children(&mut self) -> Result<Vec<ast::Node>, ParserError> {
+	pub fn parse_children(&mut self) -> Result<Vec<ast::Node<'src>>, ParserError> {
 		let mut children = Vec::new();
 
 		loop {
-			if let Some(char) = self.scanner.peek().cloned() {
-				if self.scanner.test("</") || self.scanner.test("<#") || char == '}' {
+			if let Some(char) = self.scanner.peek() {
+				if self.scanner.test("</") {
+					let name = self.peek_closing_tag();
+
+					// Closing tags without a matching open element are skipped when recovering.
+					if self.recover && !self.open.contains(&name) {
+						children.push(self.skip_tag()?);
+						continue;
+					}
+
 					break;
 				}
 
-				if char == '<' {
-					self.scanner.next();
-					if let Some(char) = self.scanner.peek() {
-						children.push(if char == &'!' {
-							self.scanner.back();
-							self.parse_comment()?.into()
-						} else {
-							self.scanner.back();
-							self.parse_element()?.into()
-						});
+				// Branches end the children of their block. Elsewhere, such as in `<div><#else>`,
+				// they are unexpected, as are unknown flow controls like `<#hen>`.
+				if let Some(keyword) = self.test_flow_control_branch() {
+					let block = self.open.last().copied().unwrap_or_default();
+
+					if matches!(
+						(block, keyword),
+						("when" | "each", "else")
+							| ("match", "case" | "default")
+							| ("await", "then" | "catch")
+							| ("try", "catch")
+					) {
+						break;
 					}
+
+					children.push(self.skip_tag()?);
 					continue;
 				}
 
-				if char == '{' {
-					children.push(self.parse_text_binding()?.into());
+				if self.scanner.test("<#") && !self.test_flow_control() {
+					children.push(self.skip_tag()?);
 					continue;
 				}
 
-				if char == '#' {
-					children.push(self.parse_flow_control()?.into());
+				if char == '}' {
+					if !self.recover {
+						break;
+					}
+
+					let start = self.scanner.cursor();
+					let error = self.unexpected();
+					self.recover(error)?;
+					children.push(self.resync(start));
 					continue;
 				}
 
-				let text = self.parse_text()?;
+				let start = self.scanner.cursor();
+
+				let result = if self.scanner.test("<#") || char == '#' {
+					self.parse_flow_control().map(|node| node.into())
+				} else if self.scanner.test("<!") {
+					self.parse_comment().map(|node| node.into())
+				} else if char == '<' {
+					self.parse_element().map(|node| node.into())
+				} else if char == '{' {
+					self.parse_text_binding().map(|node| node.into())
+				} else {
+					match self.parse_text() {
+						Ok(text) if text.content.is_empty() => continue,
+						result => result.map(|node| node.into()),
+					}
+				};
 
-				if &text.content != "" {
-					children.push(ast::Node::Text(text));
+				match result {
+					Ok(node) => children.push(node),
+					Err(error) => {
+						self.recover(error)?;
+						children.push(self.resync(start));
+					}
 				}
 			} else {
-				return Err(self.unexpected());
+				let error = self.unexpected();
+				self.recover(error)?;
+				break;
 			}
 		}
 
@@ -195,25 +439,25 @@ mod tests {
 
 	#[test]
 	fn test_skip_whitespace() {
-		let mut parser = Parser::new("foo bar".to_owned());
+		let mut parser = Parser::new("foo bar");
 		parser.scanner.set_cursor(3);
 		parser.skip_whitespace();
-		assert_eq!(parser.scanner.peek(), Some(&'b'));
+		assert_eq!(parser.scanner.peek(), Some('b'));
 	}
 
 	#[test]
 	fn test_skip_comment() {
-		let mut parser = Parser::new("foo//bar\nbaz".to_owned());
+		let mut parser = Parser::new("foo//bar\nbaz");
 		parser.scanner.set_cursor(3);
 		parser.skip_whitespace();
-		assert_eq!(parser.scanner.peek(), Some(&'b'));
+		assert_eq!(parser.scanner.peek(), Some('b'));
 	}
 
 	#[test]
 	fn test_skip_multiline_comment() {
-		let mut parser = Parser::new("foo/*\nbar\n*/baz".to_owned());
+		let mut parser = Parser::new("foo/*\nbar\n*/baz");
 		parser.scanner.set_cursor(3);
 		parser.skip_whitespace();
-		assert_eq!(parser.scanner.peek(), Some(&'b'));
+		assert_eq!(parser.scanner.peek(), Some('b'));
 	}
 }
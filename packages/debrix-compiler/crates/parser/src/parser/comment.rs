@@ -1,43 +1,25 @@
 use crate::*;
 
-impl Parser {
-	pub fn parse_comment(&mut self) -> Result<ast::Comment, ParserError> {
+impl<'src> Parser<'src> {
+	pub fn parse_comment(&mut self) -> Result<ast::Comment<'src>, ParserError> {
 		let start = self.scanner.cursor();
 
 		if !self.scanner.take("<!--") {
 			return Err(self.expected(&["<!--"]));
 		}
 
-		let mut comment = String::new();
-
-		while let Some(char) = self.scanner.peek().cloned() {
-			if char == '-' {
-				if let Some(char) = self.scanner.next() {
-					if char == &'-' {
-						if let Some(char) = self.scanner.next() {
-							if char == &'>' {
-								break;
-							} else {
-								comment.push('-');
-								comment.push('-');
-								comment.push(char.to_owned());
-							}
-						}
-					} else {
-						comment.push('-');
-						comment.push(char.to_owned());
-					}
-				}
-			}
-
-			comment.push(char.to_owned());
+		let content_start = self.scanner.cursor();
+		let mut content_end = content_start;
+
+		while !self.scanner.take("-->") && !self.scanner.is_done() {
 			self.scanner.next();
+			content_end = self.scanner.cursor();
 		}
 
 		Ok(ast::Comment {
 			start,
 			end: self.scanner.cursor(),
-			comment,
+			comment: self.scanner.slice(content_start, content_end).into(),
 		})
 	}
 }
@@ -46,8 +28,8 @@ impl Parser {
 mod tests {
 	use super::*;
 
-	fn parse(input: &str) -> ast::Comment {
-		let mut parser = Parser::new(input.to_owned());
+	fn parse(input: &str) -> ast::Comment<'_> {
+		let mut parser = Parser::new(input);
 		parser.set_debug(true);
 		parser.parse_comment().unwrap()
 	}
@@ -57,4 +39,10 @@ mod tests {
 		let comment = parse("<!-- hello world -->");
 		assert_eq!(comment.comment, " hello world ");
 	}
+
+	#[test]
+	fn test_comment_with_dashes() {
+		let comment = parse("<!-- a - b -- c -->");
+		assert_eq!(comment.comment, " a - b -- c ");
+	}
 }
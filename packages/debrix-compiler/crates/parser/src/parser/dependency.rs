@@ -1,10 +1,12 @@
 use crate::*;
 
-impl Parser {
-	pub fn parse_dependency_statement(&mut self) -> Result<ast::DependencyStatement, ParserError> {
+impl<'src> Parser<'src> {
+	pub fn parse_dependency_statement(
+		&mut self,
+	) -> Result<ast::DependencyStatement<'src>, ParserError> {
 		let start = self.scanner.cursor();
-		let mut default: Option<ast::DependencyDefaultSpecifier> = None;
-		let mut named: Vec<ast::DependencyNamedSpecifier> = Vec::new();
+		let mut default: Option<ast::DependencyDefaultSpecifier<'src>> = None;
+		let mut named: Vec<ast::DependencyNamedSpecifier<'src>> = Vec::new();
 		let mut named_start = 0_usize;
 		let mut named_end = 0_usize;
 
@@ -14,16 +16,16 @@ impl Parser {
 
 		self.skip_whitespace();
 
-		if let Some(char) = self.scanner.peek().cloned() {
+		if let Some(char) = self.scanner.peek() {
 			let start = self.scanner.cursor();
 
-			if is_identifier(&char) {
+			if is_identifier(char) {
 				let usage = self.parse_identifier()?;
 				let mut local = None;
 
 				self.skip_whitespace();
 
-				if let Some(char) = self.scanner.peek().cloned() {
+				if let Some(char) = self.scanner.peek() {
 					let cursor = self.scanner.cursor();
 
 					if self.scanner.take("from") {
@@ -33,12 +35,12 @@ impl Parser {
 							local = Some(ast::Identifier {
 								start: cursor,
 								end: cursor + 4,
-								name: "from".to_owned(),
+								name: "from".into(),
 							});
 						} else {
 							self.scanner.set_cursor(cursor);
 						}
-					} else if is_identifier(&char) {
+					} else if is_identifier(char) {
 						local = Some(self.parse_identifier()?);
 					}
 				} else {
@@ -84,14 +86,15 @@ impl Parser {
 			loop {
 				self.skip_whitespace();
 
-				if let Some(char) = self.scanner.peek().cloned() {
+				if let Some(char) = self.scanner.peek() {
 					if char == '}' {
 						self.scanner.next();
 						self.skip_whitespace();
 
 						named_end = self.scanner.cursor();
 						break;
-					} else if is_identifier(&char) {
+					} else if is_identifier(char) {
+						let start = self.scanner.cursor();
 						let usage = self.parse_identifier()?;
 						self.skip_whitespace();
 						let imported = self.parse_identifier()?;
@@ -151,8 +154,8 @@ impl Parser {
 mod tests {
 	use super::*;
 
-	fn parse(input: &str) -> ast::DependencyStatement {
-		let mut parser = Parser::new(input.to_owned());
+	fn parse(input: &str) -> ast::DependencyStatement<'_> {
+		let mut parser = Parser::new(input);
 		parser.set_debug(true);
 		parser.parse_dependency_statement().unwrap()
 	}
@@ -210,6 +213,7 @@ mod tests {
 
 		assert_eq!(specifier.as_ref().unwrap().usage.name, "foo");
 		assert_eq!(specifier.as_ref().unwrap().imported.name, "bar");
+		assert_eq!(specifier.as_ref().unwrap().start, 8);
 		assert_eq!(statement.source.value, "baz");
 	}
 
@@ -1,7 +1,7 @@
 use crate::*;
 
-impl Parser {
-	pub fn parse_element(&mut self) -> Result<ast::Element, ParserError> {
+impl<'src> Parser<'src> {
+	pub fn parse_element(&mut self) -> Result<ast::Element<'src>, ParserError> {
 		let start = self.scanner.cursor();
 		let mut self_closing = false;
 		let mut attributes = Vec::new();
@@ -29,9 +29,16 @@ impl Parser {
 					}
 					'\0' | '"' | '\'' | '=' => {
 						self.scanner.next();
-						return Err(self.unexpected());
+						let error = self.unexpected();
+						self.recover(error)?;
 					}
-					_ => attributes.push(self.parse_attribute()?),
+					_ => match self.parse_attribute() {
+						Ok(attribute) => attributes.push(attribute),
+						Err(error) => {
+							self.recover(error)?;
+							self.resync_attribute();
+						}
+					},
 				}
 			} else {
 				return Err(self.unexpected());
@@ -49,24 +56,12 @@ impl Parser {
 		start_tag.end = self.scanner.cursor();
 
 		if !self_closing {
-			children = self.parse_children()?;
-
-			let start = self.scanner.cursor();
-
-			if !self.scanner.take("</") {
-				return Err(self.expected(&["</"]));
-			}
-
-			if !self.scanner.take(&tag_name.name) {
-				return Err(self.expected(&[&tag_name.name]));
-			}
+			self.open.push(self.scanner.slice(tag_name.start, tag_name.end));
+			let result = self.parse_children();
+			self.open.pop();
 
-			if !self.scanner.take(">") {
-				return Err(self.expected(&[">"]));
-			}
-
-			let end = self.scanner.cursor();
-			end_tag = Some(ast::Range { start, end });
+			children = result?;
+			end_tag = self.parse_closing_tag(&tag_name.name)?;
 		}
 
 		Ok(ast::Element {
@@ -80,35 +75,56 @@ impl Parser {
 		})
 	}
 
-	fn parse_tag_name(&mut self) -> Result<ast::Identifier, ParserError> {
-		let start = self.scanner.cursor();
-		let mut name = String::new();
+	/// Skips input until the end of the current attribute.
+	fn resync_attribute(&mut self) {
+		let cursor = self.scanner.cursor();
+
+		// The attribute has already ended if its value is missing, like in `<div foo bar="baz">`.
+		if self.scanner.back() {
+			let ended = self.scanner.peek().is_some_and(|char| char.is_whitespace());
+			self.scanner.set_cursor(cursor);
+
+			if ended {
+				return;
+			}
+		}
+
+		while let Some(char) = self.scanner.peek() {
+			if char.is_whitespace() || char == '>' || char == '/' {
+				break;
+			}
 
-		if let Some(char) = self.scanner.peek().cloned() {
 			self.scanner.next();
-			assert!(char != '#');
-			name.push(char);
-		} else {
-			return Err(self.unexpected());
 		}
+	}
+
+	fn parse_tag_name(&mut self) -> Result<ast::Identifier<'src>, ParserError> {
+		let start = self.scanner.cursor();
+
+		// Flow control is parsed separately, and is never a valid tag name.
+		match self.scanner.peek() {
+			Some(char) if char != '#' => self.scanner.next(),
+			_ => return Err(self.unexpected()),
+		};
 
-		while let Some(char) = self.scanner.peek().cloned() {
+		while let Some(char) = self.scanner.peek() {
 			if char.is_whitespace() || char == '>' {
 				break;
 			}
 
 			self.scanner.next();
-			name.push(char);
 		}
 
+		let end = self.scanner.cursor();
+
 		Ok(ast::Identifier {
 			start,
-			end: self.scanner.cursor(),
-			name,
+			end,
+			name: self.scanner.slice(start, end).into(),
 		})
 	}
 
-	fn parse_attribute(&mut self) -> Result<ast::Attribute, ParserError> {
+	fn parse_attribute(&mut self) -> Result<ast::Attribute<'src>, ParserError> {
 		let start = self.scanner.cursor();
 
 		if self.scanner.take("{") {
@@ -148,7 +164,7 @@ impl Parser {
 	
 			if let Some(char) = self.scanner.peek() {
 				match char {
-					&'"' | &'\'' => {
+					'"' | '\'' => {
 						let value = self.parse_string()?;
 	
 						Ok(ast::Attribute::Static(ast::StaticAttribute {
@@ -159,7 +175,7 @@ impl Parser {
 						}))
 					}
 	
-					&'{' => {
+					'{' => {
 						self.scanner.next();
 						self.skip_whitespace();
 	
@@ -188,27 +204,27 @@ impl Parser {
 		}
 	}
 
-	fn parse_attribute_name(&mut self) -> Result<ast::Identifier, ParserError> {
+	fn parse_attribute_name(&mut self) -> Result<ast::Identifier<'src>, ParserError> {
 		let start = self.scanner.cursor();
-		let mut name = String::new();
 
 		while let Some(char) = self.scanner.peek() {
-			if char == &'\0' || char == &'"' || char == &'\'' || char == &'/' || char == &'>' {
+			if char == '\0' || char == '"' || char == '\'' || char == '/' || char == '>' {
 				return Err(self.unexpected());
 			}
 
-			if char.is_whitespace() || char == &'=' {
+			if char.is_whitespace() || char == '=' {
 				break;
 			}
 
-			name.push(*char);
 			self.scanner.next();
 		}
 
+		let end = self.scanner.cursor();
+
 		Ok(ast::Identifier {
 			start,
-			end: self.scanner.cursor(),
-			name,
+			end,
+			name: self.scanner.slice(start, end).into(),
 		})
 	}
 }
@@ -217,8 +233,8 @@ impl Parser {
 mod tests {
 	use super::*;
 
-	fn parse(input: &str) -> ast::Element {
-		let mut parser = Parser::new(input.to_owned());
+	fn parse(input: &str) -> ast::Element<'_> {
+		let mut parser = Parser::new(input);
 		parser.set_debug(true);
 		parser.parse_element().unwrap()
 	}
@@ -1,7 +1,14 @@
 use crate::*;
 
-impl Parser {
-	pub fn parse_flow_control(&mut self) -> Result<ast::FlowControl, ParserError> {
+/// The pending children, and the then and catch branches of an await block.
+type AwaitBody<'src> = (
+	Vec<ast::Node<'src>>,
+	Option<Box<ast::FlowControlAwaitBranch<'src>>>,
+	Option<Box<ast::FlowControlAwaitBranch<'src>>>,
+);
+
+impl<'src> Parser<'src> {
+	pub fn parse_flow_control(&mut self) -> Result<ast::FlowControl<'src>, ParserError> {
 		let start = self.scanner.cursor();
 
 		if !self.scanner.take("<#") {
@@ -28,43 +35,93 @@ impl Parser {
 			}
 
 			self.skip_whitespace();
-			let children = self.parse_children()?;
 
-			let mut chain = Vec::new();
-			loop {
-				let cursor = self.scanner.cursor();
+			self.open.push("when");
+			let result = self.parse_flow_control_when_body();
+			self.open.pop();
+
+			let (children, chain) = result?;
+			self.parse_closing_tag("when")?;
+
+			return Ok(ast::FlowControl::When(ast::FlowControlWhen {
+				start,
+				end: self.scanner.cursor(),
+				condition: Box::new(condition),
+				children,
+				chain,
+			}));
+		}
+
+		if self.scanner.take("each") {
+			self.skip_whitespace();
+
+			if !self.scanner.take("{") {
+				return Err(self.expected(&["{"]));
+			}
+
+			self.skip_whitespace();
+			let iterator = self.parse_javascript_pattern()?;
+			self.skip_whitespace();
+
+			let mut index = None;
+			if self.scanner.take(",") {
 				self.skip_whitespace();
+				index = Some(Box::new(self.parse_javascript_identifier()?));
+				self.skip_whitespace();
+			}
 
-				if self.scanner.test("<#") {
-					chain.push(self.parse_flow_control_else()?);
-				} else {
-					self.scanner.set_cursor(cursor);
-					break;
-				}
+			if !self.scanner.take("in") || self.scanner.peek().is_some_and(is_identifier) {
+				return Err(self.expected(&["in"]));
 			}
 
-			if !self.scanner.take("</") {
-				return Err(self.expected(&["</"]));
+			self.skip_whitespace();
+
+			let iterable = self.parse_javascript()?;
+			self.skip_whitespace();
+
+			let mut key = None;
+			if self.scanner.take(";") {
+				self.skip_whitespace();
+
+				if !self.scanner.take("key") || self.scanner.peek().is_some_and(is_identifier) {
+					return Err(self.expected(&["key"]));
+				}
+
+				self.skip_whitespace();
+				key = Some(Box::new(self.parse_javascript()?));
+				self.skip_whitespace();
 			}
 
-			if !self.scanner.take("when") {
-				return Err(self.expected(&["when"]));
+			if !self.scanner.take("}") {
+				return Err(self.expected(&["}"]));
 			}
 
 			if !self.scanner.take(">") {
 				return Err(self.expected(&[">"]));
 			}
 
-			return Ok(ast::FlowControl::When(ast::FlowControlWhen {
+			self.skip_whitespace();
+
+			self.open.push("each");
+			let result = self.parse_flow_control_each_body();
+			self.open.pop();
+
+			let (children, fallback) = result?;
+			self.parse_closing_tag("each")?;
+
+			return Ok(ast::FlowControl::Each(ast::FlowControlEach {
 				start,
 				end: self.scanner.cursor(),
-				condition: Box::new(condition),
+				iterable: Box::new(iterable),
+				iterator: Box::new(iterator),
+				index,
+				key,
 				children,
-				chain,
+				fallback,
 			}));
 		}
 
-		if self.scanner.take("each") {
+		if self.scanner.take("let") {
 			self.skip_whitespace();
 
 			if !self.scanner.take("{") {
@@ -72,16 +129,52 @@ impl Parser {
 			}
 
 			self.skip_whitespace();
-			let iterator = self.parse_javascript_identifier()?;
+			let name = self.parse_javascript_identifier()?;
 			self.skip_whitespace();
 
-			if !self.scanner.take("in") {
-				return Err(self.expected(&["in"]));
+			if !self.scanner.take("=") {
+				return Err(self.expected(&["="]));
 			}
 
+			self.skip_whitespace();
+			let value = self.parse_javascript()?;
 			self.skip_whitespace();
 
-			let iterable = self.parse_javascript()?;
+			if !self.scanner.take("}") {
+				return Err(self.expected(&["}"]));
+			}
+
+			if !self.scanner.take(">") {
+				return Err(self.expected(&[">"]));
+			}
+
+			self.skip_whitespace();
+
+			self.open.push("let");
+			let result = self.parse_children();
+			self.open.pop();
+
+			let children = result?;
+			self.parse_closing_tag("let")?;
+
+			return Ok(ast::FlowControl::Let(ast::FlowControlLet {
+				start,
+				end: self.scanner.cursor(),
+				name: Box::new(name),
+				value: Box::new(value),
+				children,
+			}));
+		}
+
+		if self.scanner.take("match") {
+			self.skip_whitespace();
+
+			if !self.scanner.take("{") {
+				return Err(self.expected(&["{"]));
+			}
+
+			self.skip_whitespace();
+			let discriminant = self.parse_javascript()?;
 			self.skip_whitespace();
 
 			if !self.scanner.take("}") {
@@ -93,33 +186,288 @@ impl Parser {
 			}
 
 			self.skip_whitespace();
-			let children = self.parse_children()?;
 
-			if !self.scanner.take("</") {
-				return Err(self.expected(&["</"]));
+			self.open.push("match");
+			let result = self.parse_flow_control_match_body();
+			self.open.pop();
+
+			let cases = result?;
+			self.parse_closing_tag("match")?;
+
+			return Ok(ast::FlowControl::Match(ast::FlowControlMatch {
+				start,
+				end: self.scanner.cursor(),
+				discriminant: Box::new(discriminant),
+				cases,
+			}));
+		}
+
+		if self.scanner.take("await") {
+			self.skip_whitespace();
+
+			if !self.scanner.take("{") {
+				return Err(self.expected(&["{"]));
 			}
 
-			if !self.scanner.take("each") {
-				return Err(self.expected(&["each"]));
+			self.skip_whitespace();
+			let promise = self.parse_javascript()?;
+			self.skip_whitespace();
+
+			if !self.scanner.take("}") {
+				return Err(self.expected(&["}"]));
 			}
 
 			if !self.scanner.take(">") {
 				return Err(self.expected(&[">"]));
 			}
 
-			return Ok(ast::FlowControl::Each(ast::FlowControlEach {
+			self.skip_whitespace();
+
+			self.open.push("await");
+			let result = self.parse_flow_control_await_body();
+			self.open.pop();
+
+			let (children, then, catch) = result?;
+			self.parse_closing_tag("await")?;
+
+			return Ok(ast::FlowControl::Await(ast::FlowControlAwait {
 				start,
 				end: self.scanner.cursor(),
-				iterable: Box::new(iterable),
-				iterator: Box::new(iterator),
+				promise: Box::new(promise),
 				children,
+				then,
+				catch,
+			}));
+		}
+
+		if self.scanner.take("try") {
+			if !self.scanner.take(">") {
+				return Err(self.expected(&[">"]));
+			}
+
+			self.skip_whitespace();
+
+			self.open.push("try");
+			let result = self.parse_flow_control_try_body();
+			self.open.pop();
+
+			let (children, catch) = result?;
+			self.parse_closing_tag("try")?;
+
+			return Ok(ast::FlowControl::Try(ast::FlowControlTry {
+				start,
+				end: self.scanner.cursor(),
+				children,
+				catch: Box::new(catch),
 			}));
 		}
 
 		Err(self.unexpected())
 	}
 
-	fn parse_flow_control_else(&mut self) -> Result<ast::FlowControlElse, ParserError> {
+	fn parse_flow_control_try_body(
+		&mut self,
+	) -> Result<(Vec<ast::Node<'src>>, ast::FlowControlAwaitBranch<'src>), ParserError> {
+		let children = self.parse_children()?;
+		self.skip_whitespace();
+
+		// Unlike in await blocks, the catch branch is required.
+		if !self.scanner.test("<#catch") {
+			return Err(self.expected(&["<#catch"]));
+		}
+
+		let catch = self.parse_flow_control_await_branch("catch")?;
+		Ok((children, catch))
+	}
+
+	fn parse_flow_control_await_body(&mut self) -> Result<AwaitBody<'src>, ParserError> {
+		let children = self.parse_children()?;
+		let mut then = None;
+		let mut catch = None;
+
+		// The then branch comes before the catch branch, and both are optional.
+		loop {
+			let cursor = self.scanner.cursor();
+			self.skip_whitespace();
+
+			if then.is_none() && catch.is_none() && self.scanner.test("<#then") {
+				then = Some(Box::new(self.parse_flow_control_await_branch("then")?));
+			} else if catch.is_none() && self.scanner.test("<#catch") {
+				catch = Some(Box::new(self.parse_flow_control_await_branch("catch")?));
+			} else {
+				self.scanner.set_cursor(cursor);
+				break;
+			}
+		}
+
+		Ok((children, then, catch))
+	}
+
+	fn parse_flow_control_await_branch(
+		&mut self,
+		keyword: &str,
+	) -> Result<ast::FlowControlAwaitBranch<'src>, ParserError> {
+		let start = self.scanner.cursor();
+		let mut binding = None;
+
+		if !self.scanner.take("<#") {
+			return Err(self.expected(&["<#"]));
+		}
+
+		if !self.scanner.take(keyword) {
+			return Err(self.expected(&[keyword]));
+		}
+
+		self.skip_whitespace();
+
+		if self.scanner.take("{") {
+			self.skip_whitespace();
+			binding = Some(Box::new(self.parse_javascript_pattern()?));
+			self.skip_whitespace();
+
+			if !self.scanner.take("}") {
+				return Err(self.expected(&["}"]));
+			}
+		}
+
+		if !self.scanner.take(">") {
+			return Err(self.expected(&[">"]));
+		}
+
+		let children = self.parse_children()?;
+
+		Ok(ast::FlowControlAwaitBranch {
+			start,
+			end: self.scanner.cursor(),
+			binding,
+			children,
+		})
+	}
+
+	fn parse_flow_control_match_body(
+		&mut self,
+	) -> Result<Vec<ast::FlowControlCase<'src>>, ParserError> {
+		let mut cases: Vec<ast::FlowControlCase> = Vec::new();
+
+		loop {
+			let cursor = self.scanner.cursor();
+			self.skip_whitespace();
+
+			if !self.scanner.test("<#") {
+				self.scanner.set_cursor(cursor);
+				break;
+			}
+
+			// Nothing can follow the default case.
+			if cases.last().is_some_and(|case| case.tests.is_empty()) {
+				return Err(self.expected(&["</"]));
+			}
+
+			cases.push(self.parse_flow_control_case()?);
+		}
+
+		Ok(cases)
+	}
+
+	fn parse_flow_control_case(&mut self) -> Result<ast::FlowControlCase<'src>, ParserError> {
+		let start = self.scanner.cursor();
+		let mut tests = Vec::new();
+
+		if !self.scanner.take("<#") {
+			return Err(self.expected(&["<#"]));
+		}
+
+		if self.scanner.take("case") {
+			self.skip_whitespace();
+
+			if !self.scanner.take("{") {
+				return Err(self.expected(&["{"]));
+			}
+
+			loop {
+				self.skip_whitespace();
+				tests.push(self.parse_javascript_assignment()?);
+				self.skip_whitespace();
+
+				if !self.scanner.take(",") {
+					break;
+				}
+			}
+
+			if !self.scanner.take("}") {
+				return Err(self.expected(&["}"]));
+			}
+		} else if self.scanner.take("default") {
+			self.skip_whitespace();
+		} else {
+			return Err(self.expected(&["case", "default"]));
+		}
+
+		if !self.scanner.take(">") {
+			return Err(self.expected(&[">"]));
+		}
+
+		let children = self.parse_children()?;
+
+		Ok(ast::FlowControlCase {
+			start,
+			end: self.scanner.cursor(),
+			tests,
+			children,
+		})
+	}
+
+	fn parse_flow_control_when_body(
+		&mut self,
+	) -> Result<(Vec<ast::Node<'src>>, Vec<ast::FlowControlElse<'src>>), ParserError> {
+		let children = self.parse_children()?;
+
+		let mut chain = Vec::new();
+		loop {
+			let cursor = self.scanner.cursor();
+			self.skip_whitespace();
+
+			if self.scanner.test("<#") {
+				chain.push(self.parse_flow_control_else()?);
+			} else {
+				self.scanner.set_cursor(cursor);
+				break;
+			}
+		}
+
+		Ok((children, chain))
+	}
+
+	fn parse_flow_control_each_body(
+		&mut self,
+	) -> Result<
+		(
+			Vec<ast::Node<'src>>,
+			Option<Box<ast::FlowControlElse<'src>>>,
+		),
+		ParserError,
+	> {
+		let children = self.parse_children()?;
+
+		let cursor = self.scanner.cursor();
+		self.skip_whitespace();
+
+		if !self.scanner.test("<#") {
+			self.scanner.set_cursor(cursor);
+			return Ok((children, None));
+		}
+
+		let fallback = self.parse_flow_control_else()?;
+
+		// An each block has a single fallback, which is not conditional.
+		if let Some(condition) = &fallback.condition {
+			return Err(self.error(condition.start(), &[">"]));
+		}
+
+		Ok((children, Some(Box::new(fallback))))
+	}
+
+	fn parse_flow_control_else(&mut self) -> Result<ast::FlowControlElse<'src>, ParserError> {
 		let start = self.scanner.cursor();
 		let mut condition = None;
 
@@ -170,8 +518,8 @@ impl Parser {
 mod tests {
 	use super::*;
 
-	fn parse(input: &str) -> ast::FlowControl {
-		let mut parser = Parser::new(input.to_owned());
+	fn parse(input: &str) -> ast::FlowControl<'_> {
+		let mut parser = Parser::new(input);
 		parser.set_debug(true);
 		parser.parse_flow_control().unwrap()
 	}
@@ -260,7 +608,13 @@ mod tests {
 		match node {
 			ast::FlowControl::Each(node) => {
 				assert!(node.children.len() == 1);
-				assert!(&*node.iterator.name == "foo");
+
+				match *node.iterator {
+					ast::javascript::Expression::Identifier(expr) => {
+						assert!(&expr.name == "foo");
+					}
+					_ => panic!("expected identifier"),
+				}
 
 				match *node.iterable {
 					ast::javascript::Expression::Identifier(expr) => {
@@ -279,4 +633,218 @@ mod tests {
 			_ => panic!("expected each"),
 		}
 	}
+
+	#[test]
+	fn test_flow_control_each_keyed() {
+		let node = parse("<#each {foo, i in bar; key foo.id}>baz</each>");
+
+		match node {
+			ast::FlowControl::Each(node) => {
+				assert!(matches!(
+					*node.iterator,
+					ast::javascript::Expression::Identifier(_)
+				));
+				assert!(&*node.index.unwrap().name == "i");
+
+				match *node.key.unwrap() {
+					ast::javascript::Expression::Member(expr) => {
+						assert_eq!(expr.start, 27);
+						assert_eq!(expr.end, 33);
+					}
+					_ => panic!("expected member"),
+				}
+			}
+			_ => panic!("expected each"),
+		}
+	}
+
+	#[test]
+	fn test_flow_control_each_key_expected() {
+		let mut parser = Parser::new("<#each {foo in bar; keys}>baz</each>");
+		assert!(parser.parse_flow_control().is_err());
+	}
+
+	#[test]
+	fn test_flow_control_each_in_expected() {
+		let mut parser = Parser::new("<#each {foo inbar}>baz</each>");
+		assert!(parser.parse_flow_control().is_err());
+	}
+
+	#[test]
+	fn test_flow_control_each_destructuring() {
+		let node = parse("<#each {{name, price = 0}, i in products}>baz</each>");
+
+		match node {
+			ast::FlowControl::Each(node) => {
+				assert!(matches!(
+					*node.iterator,
+					ast::javascript::Expression::Object(_)
+				));
+				assert!(&*node.index.unwrap().name == "i");
+			}
+			_ => panic!("expected each"),
+		}
+
+		let node = parse("<#each {[key, value] in entries}>baz</each>");
+
+		match node {
+			ast::FlowControl::Each(node) => {
+				assert!(matches!(
+					*node.iterator,
+					ast::javascript::Expression::Array(_)
+				));
+			}
+			_ => panic!("expected each"),
+		}
+	}
+
+	#[test]
+	fn test_flow_control_each_else() {
+		let node = parse("<#each {foo in bar}>baz <#else>qux</each>");
+
+		match node {
+			ast::FlowControl::Each(node) => {
+				assert!(node.children.len() == 1);
+
+				let fallback = node.fallback.unwrap();
+				assert!(fallback.condition.is_none());
+
+				match fallback.children.get(0).unwrap() {
+					ast::Node::Text(node) => {
+						assert_eq!(&node.content, "qux");
+					}
+					_ => panic!("expected text"),
+				}
+			}
+			_ => panic!("expected each"),
+		}
+	}
+
+	#[test]
+	fn test_flow_control_let() {
+		let node = parse("<#let {city = user.address.city}>{city}</let>");
+
+		match node {
+			ast::FlowControl::Let(node) => {
+				assert!(&*node.name.name == "city");
+				assert!(node.children.len() == 1);
+
+				match *node.value {
+					ast::javascript::Expression::Member(expr) => {
+						assert_eq!(expr.start, 14);
+						assert_eq!(expr.end, 31);
+					}
+					_ => panic!("expected member"),
+				}
+			}
+			_ => panic!("expected let"),
+		}
+	}
+
+	#[test]
+	fn test_flow_control_let_expected() {
+		let mut parser = Parser::new("<#let {city}>{city}</let>");
+		assert!(parser.parse_flow_control().is_err());
+	}
+
+	#[test]
+	fn test_flow_control_match() {
+		let node = parse("<#match {status}> <#case {'a'}>foo <#case {'b', 'c'}>bar<#default>baz</match>");
+
+		match node {
+			ast::FlowControl::Match(node) => {
+				assert!(matches!(
+					*node.discriminant,
+					ast::javascript::Expression::Identifier(_)
+				));
+				assert!(node.cases.len() == 3);
+				assert!(node.cases[0].tests.len() == 1);
+				assert!(node.cases[1].tests.len() == 2);
+				assert!(node.cases[2].tests.is_empty());
+
+				match node.cases[2].children.first().unwrap() {
+					ast::Node::Text(node) => {
+						assert_eq!(&node.content, "baz");
+					}
+					_ => panic!("expected text"),
+				}
+			}
+			_ => panic!("expected match"),
+		}
+	}
+
+	#[test]
+	fn test_flow_control_match_default_last() {
+		let mut parser = Parser::new("<#match {a}><#default>b<#case {c}>d</match>");
+		assert!(parser.parse_flow_control().is_err());
+
+		let mut parser = Parser::new("<#match {a}>b<#case {c}>d</match>");
+		assert!(parser.parse_flow_control().is_err());
+	}
+
+	#[test]
+	fn test_flow_control_await() {
+		let node = parse("<#await {user}>loading <#then {{name}}>{name} <#catch {error}>{error}</await>");
+
+		match node {
+			ast::FlowControl::Await(node) => {
+				assert!(node.children.len() == 1);
+
+				let then = node.then.unwrap();
+				assert!(matches!(
+					*then.binding.unwrap(),
+					ast::javascript::Expression::Object(_)
+				));
+
+				let catch = node.catch.unwrap();
+				assert!(matches!(
+					*catch.binding.unwrap(),
+					ast::javascript::Expression::Identifier(_)
+				));
+			}
+			_ => panic!("expected await"),
+		}
+
+		let node = parse("<#await {user}><#catch>failed</await>");
+
+		match node {
+			ast::FlowControl::Await(node) => {
+				assert!(node.children.is_empty());
+				assert!(node.then.is_none());
+				assert!(node.catch.unwrap().binding.is_none());
+			}
+			_ => panic!("expected await"),
+		}
+	}
+
+	#[test]
+	fn test_flow_control_await_order() {
+		let mut parser = Parser::new("<#await {a}><#catch>b<#then>c</await>");
+		assert!(parser.parse_flow_control().is_err());
+	}
+
+	#[test]
+	fn test_flow_control_try() {
+		let node = parse("<#try><p>{a.b}</p> <#catch {error}>{error.message}</try>");
+
+		match node {
+			ast::FlowControl::Try(node) => {
+				assert!(node.children.len() == 2);
+				assert!(matches!(
+					*node.catch.binding.unwrap(),
+					ast::javascript::Expression::Identifier(_)
+				));
+			}
+			_ => panic!("expected try"),
+		}
+
+		let mut parser = Parser::new("<#try>a</try>");
+		assert!(parser.parse_flow_control().is_err());
+	}
+
+	#[test]
+	fn test_flow_control_each_else_when() {
+		let mut parser = Parser::new("<#each {foo in bar}>baz<#else when {qux}>quux</each>");
+		assert!(parser.parse_flow_control().is_err());
+	}
 }
@@ -1,36 +1,33 @@
 use crate::*;
 
-pub fn is_identifier(char: &char) -> bool {
-	char.is_alphanumeric() || char == &'_' || char == &'$' || char == &':'
+pub fn is_identifier(char: char) -> bool {
+	char.is_alphanumeric() || char == '_' || char == '$' || char == ':'
 }
 
-impl Parser {
-	pub fn parse_identifier(&mut self) -> Result<ast::Identifier, ParserError> {
+impl<'src> Parser<'src> {
+	pub fn parse_identifier(&mut self) -> Result<ast::Identifier<'src>, ParserError> {
 		let start = self.scanner.cursor();
-		let mut name = String::new();
 
 		if let Some(char) = self.scanner.peek() {
 			if !is_identifier(char) {
 				return Err(self.expected(&["identifier"]));
 			}
-
-			name.push(*char);
 		} else {
 			return Err(self.unexpected());
 		}
 
 		while let Some(char) = self.scanner.next() {
-			if is_identifier(char) {
-				name.push(*char);
-			} else {
+			if !is_identifier(char) {
 				break;
 			}
 		}
 
+		let end = self.scanner.cursor();
+
 		Ok(ast::Identifier {
 			start,
-			end: self.scanner.cursor(),
-			name,
+			end,
+			name: self.scanner.slice(start, end).into(),
 		})
 	}
 }
@@ -39,8 +36,8 @@ impl Parser {
 mod tests {
 	use super::*;
 
-	fn parse(input: &str) -> ast::Identifier {
-		let mut parser = Parser::new(input.to_owned());
+	fn parse(input: &str) -> ast::Identifier<'_> {
+		let mut parser = Parser::new(input);
 		parser.set_debug(true);
 		parser.parse_identifier().unwrap()
 	}
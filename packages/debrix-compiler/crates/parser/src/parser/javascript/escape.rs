@@ -0,0 +1,110 @@
+use std::{
+	borrow::Cow,
+	iter::{Enumerate, Peekable},
+	str,
+};
+
+type Chars<'a> = Peekable<Enumerate<str::Chars<'a>>>;
+
+/// Returns the value of the raw text of a string or template, with its escapes replaced. Text
+/// without escapes is borrowed. Fails with the char offset of the first invalid escape.
+///
+/// Escaped lone surrogates have no char, so they become the replacement character, like in
+/// lossy UTF-16 decoding.
+pub(crate) fn unescape(raw: &str) -> Result<Cow<'_, str>, usize> {
+	if !raw.contains('\\') {
+		return Ok(Cow::Borrowed(raw));
+	}
+
+	let mut value = String::with_capacity(raw.len());
+	let mut chars = raw.chars().enumerate().peekable();
+
+	while let Some((i, char)) = chars.next() {
+		if char != '\\' {
+			value.push(char);
+			continue;
+		}
+
+		let escaped = match chars.next() {
+			Some((_, escaped)) => escaped,
+			None => return Err(i),
+		};
+
+		match escaped {
+			'n' => value.push('\n'),
+			't' => value.push('\t'),
+			'r' => value.push('\r'),
+			'b' => value.push('\u{8}'),
+			'f' => value.push('\u{c}'),
+			'v' => value.push('\u{b}'),
+			'0' if !matches!(chars.peek(), Some((_, '0'..='9'))) => value.push('\0'),
+			// Octal escapes are not allowed in modules.
+			'0'..='9' => return Err(i),
+			'x' => {
+				let code = hex(&mut chars, 2).ok_or(i)?;
+				value.push(char::from_u32(code).unwrap());
+			}
+			'u' => {
+				let mut code = unicode(&mut chars).ok_or(i)?;
+
+				if (0xD800..0xDC00).contains(&code) {
+					let mut lookahead = chars.clone();
+
+					if let (Some((_, '\\')), Some((_, 'u'))) = (lookahead.next(), lookahead.next())
+					{
+						if let Some(low @ 0xDC00..=0xDFFF) = unicode(&mut lookahead) {
+							code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+							chars = lookahead;
+						}
+					}
+				}
+
+				value.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+			}
+			// Line continuations.
+			'\r' => {
+				chars.next_if(|(_, char)| *char == '\n');
+			}
+			'\n' | '\u{2028}' | '\u{2029}' => {}
+			_ => value.push(escaped),
+		}
+	}
+
+	Ok(Cow::Owned(value))
+}
+
+/// Reads the code point of `\uXXXX` or `\u{X...}`, after the `u`.
+fn unicode(chars: &mut Chars) -> Option<u32> {
+	if chars.next_if(|(_, char)| *char == '{').is_none() {
+		return hex(chars, 4);
+	}
+
+	let mut code = 0;
+	let mut digits = 0;
+
+	loop {
+		let (_, char) = chars.next()?;
+
+		if char == '}' && digits > 0 {
+			return Some(code);
+		}
+
+		code = code * 16 + char.to_digit(16)?;
+		digits += 1;
+
+		if code > 0x10FFFF {
+			return None;
+		}
+	}
+}
+
+fn hex(chars: &mut Chars, digits: usize) -> Option<u32> {
+	let mut code = 0;
+
+	for _ in 0..digits {
+		let (_, char) = chars.next()?;
+		code = code * 16 + char.to_digit(16)?;
+	}
+
+	Some(code)
+}
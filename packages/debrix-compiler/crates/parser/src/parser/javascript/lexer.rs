@@ -22,19 +22,31 @@ pub enum TokenKind {
 
 	Identifier,
 	Numeric,
+	BigInt,
 	String,
 	Template,
+	RegExp,
 
 	True,       // true
 	False,      // false
 	Null,       // null
+	Await,      // await
+	Catch,      // catch
+	Const,      // const
 	Delete,     // delete
+	Else,       // else
+	Finally,    // finally
+	If,         // if
 	In,         // in
 	Instanceof, // instanceof
+	Let,        // let
 	New,        // new
 	Return,     // return
 	This,       // this
+	Throw,      // throw
+	Try,        // try
 	Typeof,     // typeof
+	Var,        // var
 	Void,       // void
 
 	Plus,                     // +
@@ -63,7 +75,9 @@ pub enum TokenKind {
 	BitOr,                    // |
 	LogicalAnd,               // &&
 	LogicalOr,                // ||
+	NullishCoalescing,        // ??
 	QuestionMark,             // ?
+	OptionalChain,            // ?.
 	Colon,                    // :
 	Assign,                   // =
 	PlusAssign,               // +=
@@ -78,6 +92,9 @@ pub enum TokenKind {
 	BitAndAssign,             // &=
 	BitXorAssign,             // ^=
 	BitOrAssign,              // |=
+	LogicalAndAssign,         // &&=
+	LogicalOrAssign,          // ||=
+	NullishAssign,            // ??=
 	Comma,                    // ,
 	Arrow,                    // =>
 	OpenParen,                // (
@@ -91,24 +108,20 @@ pub enum TokenKind {
 	Semicolon,                // ;
 }
 
-pub struct Lexer<'a> {
-	scanner: &'a mut Scanner,
+pub struct Lexer<'a, 'src> {
+	scanner: &'a mut Scanner<'src>,
 }
 
-impl<'a> Lexer<'a> {
-	pub fn new(scanner: &'a mut Scanner) -> Self {
+impl<'a, 'src> Lexer<'a, 'src> {
+	pub fn new(scanner: &'a mut Scanner<'src>) -> Self {
 		Self { scanner }
 	}
 
-	pub fn is_done(&self) -> bool {
-		self.scanner.is_done()
-	}
-
-	pub fn scanner(&self) -> &Scanner {
+	pub fn scanner(&self) -> &Scanner<'src> {
 		self.scanner
 	}
 
-	pub fn scanner_mut(&mut self) -> &mut Scanner {
+	pub fn scanner_mut(&mut self) -> &mut Scanner<'src> {
 		self.scanner
 	}
 
@@ -124,12 +137,12 @@ impl<'a> Lexer<'a> {
 					continue;
 				}
 
-				if char == &'/' {
+				if char == '/' {
 					if let Some(char) = self.scanner.next() {
 						match char {
-							&'/' => {
+							'/' => {
 								while let Some(char) = self.scanner.next() {
-									if char == &'\n' {
+									if char == '\n' {
 										break;
 									}
 								}
@@ -137,9 +150,9 @@ impl<'a> Lexer<'a> {
 								continue;
 							}
 
-							&'*' => {
+							'*' => {
 								while let Some(char) = self.scanner.next() {
-									if char == &'*' {
+									if char == '*' {
 										self.scanner.next();
 
 										if self.scanner.take("/") {
@@ -152,7 +165,6 @@ impl<'a> Lexer<'a> {
 							}
 
 							_ => {
-								self.scanner.back();
 								self.scanner.back();
 								break;
 							}
@@ -162,9 +174,9 @@ impl<'a> Lexer<'a> {
 						break;
 					}
 				}
-
-				break;
 			}
+
+			break;
 		}
 
 		if self.scanner.is_done() {
@@ -172,14 +184,11 @@ impl<'a> Lexer<'a> {
 		}
 
 		let start = self.scanner.cursor();
-		let char = self.scanner.peek().unwrap().clone();
+		let char = self.scanner.peek().unwrap();
 
 		if char.is_alphabetic() || char == '_' || char == '$' {
-			let mut ident = String::new();
-
 			while let Some(char) = self.scanner.peek() {
-				if char.is_alphanumeric() || char == &'_' || char == &'$' {
-					ident.push(char.to_owned());
+				if char.is_alphanumeric() || char == '_' || char == '$' {
 					self.scanner.next();
 					continue;
 				} else {
@@ -187,20 +196,28 @@ impl<'a> Lexer<'a> {
 				}
 			}
 
-			assert_eq!(start + ident.len(), self.scanner.cursor());
-
 			return Ok(Token::new(
-				match ident.as_ref() {
+				match self.scanner.slice(start, self.scanner.cursor()) {
 					"true" => TokenKind::True,
 					"false" => TokenKind::False,
 					"null" => TokenKind::Null,
+					"await" => TokenKind::Await,
+					"catch" => TokenKind::Catch,
+					"const" => TokenKind::Const,
 					"delete" => TokenKind::Delete,
+					"else" => TokenKind::Else,
+					"finally" => TokenKind::Finally,
+					"if" => TokenKind::If,
 					"in" => TokenKind::In,
 					"instanceof" => TokenKind::Instanceof,
+					"let" => TokenKind::Let,
 					"new" => TokenKind::New,
 					"return" => TokenKind::Return,
 					"this" => TokenKind::This,
+					"throw" => TokenKind::Throw,
+					"try" => TokenKind::Try,
 					"typeof" => TokenKind::Typeof,
+					"var" => TokenKind::Var,
 					"void" => TokenKind::Void,
 					_ => TokenKind::Identifier,
 				},
@@ -209,35 +226,31 @@ impl<'a> Lexer<'a> {
 			));
 		}
 
-		if char.is_numeric() {
-			let mut has_dot = false;
-
-			while let Some(char) = self.scanner.next() {
-				if char.is_numeric() {
-					continue;
-				}
-
-				if !has_dot && char == &'.' {
-					has_dot = true;
-				} else {
-					break;
-				}
-			}
-
-			return Ok(Token::new(TokenKind::Numeric, start, self.scanner.cursor()));
+		if char.is_ascii_digit() || (char == '.' && self.lookahead().is_some_and(is_digit(10))) {
+			let kind = self.scan_number()?;
+			return Ok(Token::new(kind, start, self.scanner.cursor()));
 		}
 
 		if char == '"' || char == '\'' {
-			let quote = char.clone();
+			let quote = char;
 
-			while let Some(char) = self.scanner.next() {
-				if char == &quote {
-					self.scanner.next();
-					break;
-				} else if char == &'\\' {
-					if self.scanner.is_done() {
-						return Err(self.scanner.cursor());
+			loop {
+				match self.scanner.next() {
+					Some(char) if char == quote => {
+						self.scanner.next();
+						break;
+					}
+
+					Some('\\') => {
+						// Skip the escaped char, which may be a quote or a line continuation.
+						if self.scanner.next() == Some('\r') && self.lookahead() == Some('\n') {
+							self.scanner.next();
+						}
 					}
+
+					Some('\n' | '\r') | None => return Err(self.scanner.cursor()),
+
+					Some(_) => {}
 				}
 			}
 
@@ -245,31 +258,7 @@ impl<'a> Lexer<'a> {
 		}
 
 		if char == '`' {
-			while let Some(char) = self.scanner.next().cloned() {
-				if char == '`' {
-					self.scanner.next();
-					break;
-				} else if char == '\\' {
-					if self.scanner.is_done() {
-						return Err(self.scanner.cursor());
-					}
-				} else if char == '$' {
-					if self.scanner.is_done() {
-						return Err(self.scanner.cursor());
-					}
-
-					if char == '{' {
-						while let Some(char) = self.scanner.next() {
-							if char == &'}' {
-								break;
-							}
-						}
-					} else {
-						self.scanner.back();
-					}
-				}
-			}
-
+			self.scan_template()?;
 			return Ok(Token::new(
 				TokenKind::Template,
 				start,
@@ -315,6 +304,14 @@ impl<'a> Lexer<'a> {
 
 			'&' => {
 				if self.scanner.take("&") {
+					if self.scanner.take("=") {
+						return Ok(Token::new(
+							TokenKind::LogicalAndAssign,
+							start,
+							self.scanner.cursor(),
+						));
+					}
+
 					return Ok(Token::new(
 						TokenKind::LogicalAnd,
 						start,
@@ -533,6 +530,14 @@ impl<'a> Lexer<'a> {
 
 			'|' => {
 				if self.scanner.take("|") {
+					if self.scanner.take("=") {
+						return Ok(Token::new(
+							TokenKind::LogicalOrAssign,
+							start,
+							self.scanner.cursor(),
+						));
+					}
+
 					return Ok(Token::new(
 						TokenKind::LogicalOr,
 						start,
@@ -605,14 +610,250 @@ impl<'a> Lexer<'a> {
 				self.scanner.cursor(),
 			)),
 			':' => Ok(Token::new(TokenKind::Colon, start, self.scanner.cursor())),
-			'?' => Ok(Token::new(
-				TokenKind::QuestionMark,
-				start,
-				self.scanner.cursor(),
-			)),
+			'?' => {
+				if self.scanner.take("?") {
+					if self.scanner.take("=") {
+						return Ok(Token::new(
+							TokenKind::NullishAssign,
+							start,
+							self.scanner.cursor(),
+						));
+					}
+
+					return Ok(Token::new(
+						TokenKind::NullishCoalescing,
+						start,
+						self.scanner.cursor(),
+					));
+				}
+
+				// `a?.5:b` is a conditional with a number.
+				if self.scanner.test(".") {
+					self.scanner.next();
+
+					if !matches!(self.scanner.peek(), Some('0'..='9')) {
+						return Ok(Token::new(
+							TokenKind::OptionalChain,
+							start,
+							self.scanner.cursor(),
+						));
+					}
+
+					self.scanner.back();
+				}
+
+				Ok(Token::new(
+					TokenKind::QuestionMark,
+					start,
+					self.scanner.cursor(),
+				))
+			}
 			'~' => Ok(Token::new(TokenKind::BitNot, start, self.scanner.cursor())),
 
 			_ => Err(self.scanner.cursor()),
 		}
 	}
+
+	/// Returns the char after the next char.
+	fn lookahead(&self) -> Option<char> {
+		self.scanner.clone().next()
+	}
+
+	/// Skips a numeric literal, which is a number or a BigInt.
+	fn scan_number(&mut self) -> Result<TokenKind, usize> {
+		let radix = match (self.scanner.peek(), self.lookahead()) {
+			(Some('0'), Some('x' | 'X')) => 16,
+			(Some('0'), Some('o' | 'O')) => 8,
+			(Some('0'), Some('b' | 'B')) => 2,
+			_ => 10,
+		};
+
+		let mut integer = true;
+
+		if radix != 10 {
+			self.scanner.next();
+			self.scanner.next();
+
+			if self.scan_digits(radix) == 0 {
+				return Err(self.scanner.cursor());
+			}
+		} else {
+			// Legacy octal literals like `017` are not allowed in modules.
+			if self.scanner.peek() == Some('0') && self.lookahead().is_some_and(is_digit(10)) {
+				return Err(self.scanner.cursor());
+			}
+
+			let digits = self.scan_digits(10);
+
+			if self.scanner.peek() == Some('.') {
+				integer = false;
+				self.scanner.next();
+				self.scan_digits(10);
+			}
+
+			if digits == 0 && integer {
+				return Err(self.scanner.cursor());
+			}
+
+			if matches!(self.scanner.peek(), Some('e' | 'E')) {
+				integer = false;
+				self.scanner.next();
+
+				if matches!(self.scanner.peek(), Some('+' | '-')) {
+					self.scanner.next();
+				}
+
+				if self.scan_digits(10) == 0 {
+					return Err(self.scanner.cursor());
+				}
+			}
+		}
+
+		let kind = if integer && self.scanner.peek() == Some('n') {
+			self.scanner.next();
+			TokenKind::BigInt
+		} else {
+			TokenKind::Numeric
+		};
+
+		// An identifier or a digit cannot follow a number, like in `3in` or `1_`.
+		if let Some(char) = self.scanner.peek() {
+			if char.is_alphanumeric() || char == '_' || char == '$' {
+				return Err(self.scanner.cursor());
+			}
+		}
+
+		Ok(kind)
+	}
+
+	/// Skips digits of the radix, with `_` separators between them. Returns the number of
+	/// digits.
+	fn scan_digits(&mut self, radix: u32) -> usize {
+		let mut digits = 0;
+
+		loop {
+			match self.scanner.peek() {
+				Some(char) if char.is_digit(radix) => {
+					self.scanner.next();
+					digits += 1;
+				}
+
+				Some('_') if digits > 0 && self.lookahead().is_some_and(is_digit(radix)) => {
+					self.scanner.next();
+				}
+
+				_ => return digits,
+			}
+		}
+	}
+
+	/// Scans a regular expression literal, which starts with `/` like division. Only the
+	/// parser knows which one is expected, so it asks for a regular expression where an
+	/// expression starts.
+	pub fn scan_regex(&mut self) -> Result<Token, usize> {
+		let start = self.scanner.cursor();
+		let mut class = false;
+
+		if self.scanner.peek() != Some('/') {
+			return Err(start);
+		}
+
+		loop {
+			match self.scanner.next() {
+				Some('/') if !class => break,
+				Some('[') => class = true,
+				Some(']') => class = false,
+
+				Some('\\') => {
+					if matches!(self.scanner.next(), Some('\n' | '\r') | None) {
+						return Err(self.scanner.cursor());
+					}
+				}
+
+				Some('\n' | '\r') | None => return Err(self.scanner.cursor()),
+
+				Some(_) => {}
+			}
+		}
+
+		self.scanner.next();
+		let flags_start = self.scanner.cursor();
+
+		while let Some(char) = self.scanner.peek() {
+			if !(char.is_alphanumeric() || char == '_' || char == '$') {
+				break;
+			}
+
+			let flags = self.scanner.slice(flags_start, self.scanner.cursor());
+
+			if !"dgimsuyv".contains(char) || flags.contains(char) {
+				return Err(self.scanner.cursor());
+			}
+
+			self.scanner.next();
+		}
+
+		Ok(Token::new(TokenKind::RegExp, start, self.scanner.cursor()))
+	}
+
+	/// Scans a single `>`, where the lexer would read `>>`, `>=` or the like. TypeScript type
+	/// arguments can end with several brackets at once, as in `Array<Array<T>>`.
+	pub fn scan_greater_than(&mut self) -> Result<Token, usize> {
+		let start = self.scanner.cursor();
+
+		if !self.scanner.take(">") {
+			return Err(start);
+		}
+
+		Ok(Token::new(
+			TokenKind::GreaterThan,
+			start,
+			self.scanner.cursor(),
+		))
+	}
+
+	/// Skips a template literal, including the expressions in its substitutions. The parser
+	/// scans the parts of the template again.
+	fn scan_template(&mut self) -> Result<(), usize> {
+		// Skip the opening backtick.
+		self.scanner.next();
+
+		loop {
+			match self.scanner.peek() {
+				Some('`') => {
+					self.scanner.next();
+					return Ok(());
+				}
+
+				Some('\\') => {
+					self.scanner.next();
+					self.scanner.next();
+				}
+
+				Some('$') if self.scanner.take("${") => {
+					let mut depth = 0;
+
+					loop {
+						match self.scan()?.kind {
+							TokenKind::OpenBrace => depth += 1,
+							TokenKind::CloseBrace if depth == 0 => break,
+							TokenKind::CloseBrace => depth -= 1,
+							TokenKind::EOF => return Err(self.scanner.cursor()),
+							_ => {}
+						}
+					}
+				}
+
+				Some(_) => {
+					self.scanner.next();
+				}
+
+				None => return Err(self.scanner.cursor()),
+			}
+		}
+	}
+}
+
+fn is_digit(radix: u32) -> impl Fn(char) -> bool {
+	move |char| char.is_digit(radix)
 }
@@ -1,9 +1,11 @@
 use crate::*;
 
+mod escape;
 mod lexer;
 mod parser;
 mod token_buffer;
 
+pub(crate) use self::escape::*;
 pub(crate) use self::lexer::*;
 pub(crate) use self::parser::*;
 pub(crate) use self::token_buffer::*;
@@ -11,16 +13,54 @@ pub(crate) use self::token_buffer::*;
 #[cfg(test)]
 mod tests;
 
-pub(crate) fn is_eof(kind: &TokenKind) -> bool {
-	matches!(kind, TokenKind::EOF)
-}
+impl<'src> Parser<'src> {
+	pub fn parse_javascript(&mut self) -> Result<ast::javascript::Expression<'src>, ParserError> {
+		let mut parser = JavascriptParser::new(&mut self.scanner);
+		parser.set_typescript(self.typescript);
+
+		let result = parser.parse_expression();
+		let unsupported = parser.take_unsupported();
+		result.map_err(|position| self.javascript_error(position, unsupported))
+	}
+
+	/// Parses an expression which is not a sequence, so that it can be followed by a comma, like
+	/// the elements of an array.
+	pub fn parse_javascript_assignment(
+		&mut self,
+	) -> Result<ast::javascript::Expression<'src>, ParserError> {
+		let mut parser = JavascriptParser::new(&mut self.scanner);
+		parser.set_typescript(self.typescript);
+
+		let result = parser.parse_assignment();
+		let unsupported = parser.take_unsupported();
+		result.map_err(|position| self.javascript_error(position, unsupported))
+	}
+
+	/// Parses the target of a binding, which is an identifier, or an object or array destructuring
+	/// the value.
+	pub fn parse_javascript_pattern(
+		&mut self,
+	) -> Result<ast::javascript::Expression<'src>, ParserError> {
+		let mut parser = JavascriptParser::new(&mut self.scanner);
+		parser.set_typescript(self.typescript);
+
+		let result = parser.parse_binding_target();
+		let unsupported = parser.take_unsupported();
+		result.map_err(|position| self.javascript_error(position, unsupported))
+	}
 
-impl Parser {
-	pub fn parse_javascript(&mut self) -> Result<ast::javascript::Expression, ParserError> {
-		Ok(JavascriptParser::new(&mut self.scanner).parse_expression()?)
+	pub fn parse_javascript_identifier(
+		&mut self,
+	) -> Result<ast::javascript::IdentifierExpression<'src>, ParserError> {
+		let result = JavascriptParser::new(&mut self.scanner).parse_identifier();
+		result.map_err(|position| self.error(position, &[]))
 	}
 
-	pub fn parse_javascript_identifier(&mut self) -> Result<ast::javascript::IdentifierExpression, ParserError> {
-		Ok(JavascriptParser::new(&mut self.scanner).parse_identifier()?)
+	/// Converts an error of the JavaScript parser, with the reason why the input is unsupported.
+	fn javascript_error(&self, position: usize, unsupported: Option<String>) -> ParserError {
+		ParserError {
+			message: unsupported,
+			..self.error(position, &[])
+		}
 	}
 }
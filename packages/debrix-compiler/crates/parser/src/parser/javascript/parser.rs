@@ -1,156 +1,512 @@
 use super::*;
 
-pub struct JavascriptParser<'a> {
-	pub tokens: TokenBuffer<'a>,
+pub struct JavascriptParser<'a, 'src> {
+	pub tokens: TokenBuffer<'a, 'src>,
+	/// Whether the parser is in the body of an async function, where `await` is allowed.
+	in_async: bool,
+	/// Whether TypeScript is allowed, see [`JavascriptParser::set_typescript`].
+	typescript: bool,
+	/// Why the last error was returned, when the input is JavaScript outside of the supported
+	/// subset, like a `for` loop.
+	unsupported: Option<String>,
 }
 
-impl<'a> JavascriptParser<'a> {
-	pub fn new(scanner: &'a mut Scanner) -> Self {
+impl<'a, 'src> JavascriptParser<'a, 'src> {
+	pub fn new(scanner: &'a mut Scanner<'src>) -> Self {
 		Self {
 			tokens: TokenBuffer::new(Lexer::new(scanner)),
+			in_async: false,
+			typescript: false,
+			unsupported: None,
 		}
 	}
 
-	pub fn parse_expression_lazy(&mut self) -> Result<ast::javascript::Expression, usize> {
+	/// When parsing TypeScript, the parser accepts `a as T`, `a satisfies T`, `a!`, and types on
+	/// the parameters, return value and type parameters of arrow functions.
+	pub fn set_typescript(&mut self, value: bool) {
+		self.typescript = value;
+	}
+
+	/// Takes the reason of the last error, if the input is not in the supported subset.
+	pub fn take_unsupported(&mut self) -> Option<String> {
+		self.unsupported.take()
+	}
+
+	/// Rejects the next token as the start of an unsupported `kind`, like a statement.
+	fn unsupported(&mut self, name: &str, kind: &str) -> Result<usize, usize> {
+		self.unsupported = Some(format!("Unsupported `{}` {}", name, kind));
+		Ok(self.peek()?.1)
+	}
+
+	/// Returns the source of the next token if it is an identifier, without consuming it. Used for
+	/// contextual keywords like `as`.
+	fn peek_identifier(&mut self) -> Result<Option<&'src str>, usize> {
 		let token = self.tokens.scan()?;
-		if is_eof(&token.kind) {
+		let (kind, start, end) = (token.kind.clone(), token.start, token.end);
+		self.tokens.unscan();
+
+		Ok(match kind {
+			TokenKind::Identifier => Some(self.tokens.scanner().slice(start, end)),
+			_ => None,
+		})
+	}
+
+	/// Returns the kind and start of the next token, without consuming it.
+	fn peek(&mut self) -> Result<(TokenKind, usize), usize> {
+		let token = self.tokens.scan()?;
+		let next = (token.kind.clone(), token.start);
+		self.tokens.unscan();
+		Ok(next)
+	}
+
+	/// Parses an expression, including sequences like `a, b`.
+	pub fn parse_expression(&mut self) -> Result<ast::javascript::Expression<'src>, usize> {
+		let first = self.parse_assignment()?;
+
+		if self.peek()?.0 != TokenKind::Comma {
+			return Ok(first);
+		}
+
+		let mut expressions = vec![first];
+
+		while self.peek()?.0 == TokenKind::Comma {
+			self.tokens.scan()?;
+			expressions.push(self.parse_assignment()?);
+		}
+
+		Ok(ast::javascript::SequenceExpression {
+			start: expressions.first().unwrap().start(),
+			end: expressions.last().unwrap().end(),
+			expressions,
+		}
+		.into())
+	}
+
+	/// Parses an expression without sequences, as in arguments and elements.
+	pub fn parse_assignment(&mut self) -> Result<ast::javascript::Expression<'src>, usize> {
+		let left = self.parse_conditional()?;
+
+		let operator = match assignment_operator(&self.peek()?.0) {
+			Some(operator) => operator,
+			None => return Ok(left),
+		};
+
+		// Only `=` destructures objects and arrays.
+		let destructuring = matches!(operator, ast::javascript::AssignmentOperator::Equal);
+
+		if !is_assignment_target(&left, destructuring) {
+			return Err(left.start());
+		}
+
+		self.tokens.scan()?;
+
+		// Assignment is right-associative, `a = b = c` is `a = (b = c)`.
+		let right = self.parse_assignment()?;
+
+		Ok(ast::javascript::AssignmentExpression {
+			start: left.start(),
+			end: right.end(),
+			operator,
+			left: Box::new(left),
+			right: Box::new(right),
+		}
+		.into())
+	}
+
+	pub fn parse_conditional(&mut self) -> Result<ast::javascript::Expression<'src>, usize> {
+		let condition = self.parse_binary(0)?;
+
+		if self.peek()?.0 != TokenKind::QuestionMark {
+			return Ok(condition);
+		}
+
+		self.tokens.scan()?;
+		let consequent = self.parse_assignment()?;
+
+		if self.tokens.scan()?.kind != TokenKind::Colon {
 			return Err(self.tokens.cursor());
 		}
 
-		let token_kind = token.kind.clone();
-		self.tokens.unscan();
+		let alternate = self.parse_assignment()?;
+
+		Ok(ast::javascript::ConditionalExpression {
+			start: condition.start(),
+			end: alternate.end(),
+			condition: Box::new(condition),
+			consequent: Box::new(consequent),
+			alternate: Box::new(alternate),
+		}
+		.into())
+	}
+
+	/// Parses binary operators which bind at least as tight as `min_precedence`, by precedence
+	/// climbing.
+	pub fn parse_binary(
+		&mut self,
+		min_precedence: u8,
+	) -> Result<ast::javascript::Expression<'src>, usize> {
+		let mut left = self.parse_unary()?;
+
+		loop {
+			let (kind, start) = self.peek()?;
+
+			// `as` and `satisfies` bind like relational operators, on the same line.
+			if self.typescript && min_precedence <= 7 {
+				let name = self.peek_identifier()?;
+
+				if matches!(name, Some("as" | "satisfies"))
+					&& !self
+						.tokens
+						.scanner()
+						.slice(left.end(), start)
+						.contains('\n')
+				{
+					left = self.parse_type_assertion(left)?;
+					continue;
+				}
+			}
+
+			let (operator, precedence) = match binary_operator(&kind) {
+				Some((operator, precedence)) if precedence >= min_precedence => {
+					(operator, precedence)
+				}
+				_ => break,
+			};
+
+			let exponent = matches!(operator, ast::javascript::BinaryOperator::Exponent);
+
+			// `-a ** b` is ambiguous, so the operand must be parenthesized.
+			if exponent && is_unary(&left) {
+				return Err(start);
+			}
+
+			self.tokens.scan()?;
+
+			// `**` is right-associative, the other operators are left-associative.
+			let right = if exponent {
+				self.parse_binary(precedence)?
+			} else {
+				self.parse_binary(precedence + 1)?
+			};
+
+			// `??` cannot be mixed with `&&` or `||` without parentheses.
+			if mixes_nullish(&operator, &left) || mixes_nullish(&operator, &right) {
+				return Err(start);
+			}
+
+			left = ast::javascript::BinaryExpression {
+				start: left.start(),
+				end: right.end(),
+				operator,
+				left: Box::new(left),
+				right: Box::new(right),
+			}
+			.into();
+		}
+
+		Ok(left)
+	}
+
+	pub fn parse_unary(&mut self) -> Result<ast::javascript::Expression<'src>, usize> {
+		let (kind, start) = self.peek()?;
+
+		let operator = match kind {
+			TokenKind::Minus => ast::javascript::UnaryOperator::Minus,
+			TokenKind::Plus => ast::javascript::UnaryOperator::Plus,
+			TokenKind::Increment => ast::javascript::UnaryOperator::Increment,
+			TokenKind::Decrement => ast::javascript::UnaryOperator::Decrement,
+			TokenKind::Not => ast::javascript::UnaryOperator::Not,
+			TokenKind::BitNot => ast::javascript::UnaryOperator::BitwiseNot,
+			TokenKind::Typeof => ast::javascript::UnaryOperator::Typeof,
+			TokenKind::Void => ast::javascript::UnaryOperator::Void,
+			TokenKind::Delete => ast::javascript::UnaryOperator::Delete,
+			TokenKind::Await => return Ok(self.parse_await()?.into()),
+
+			_ => return self.parse_postfix(),
+		};
+
+		self.tokens.scan()?;
+		let operand = self.parse_unary()?;
+
+		if is_update(&operator) && !is_assignment_target(&operand, false) {
+			return Err(operand.start());
+		}
+
+		Ok(ast::javascript::UnaryExpression {
+			start,
+			end: operand.end(),
+			operator,
+			operand: Box::new(operand),
+			prefix: true,
+		}
+		.into())
+	}
+
+	pub fn parse_await(&mut self) -> Result<ast::javascript::AwaitExpression<'src>, usize> {
+		let start = self.tokens.scan()?.start;
+
+		if !self.in_async {
+			return Err(start);
+		}
+
+		let argument = self.parse_unary()?;
+
+		Ok(ast::javascript::AwaitExpression {
+			start,
+			end: argument.end(),
+			argument: Box::new(argument),
+		})
+	}
+
+	pub fn parse_postfix(&mut self) -> Result<ast::javascript::Expression<'src>, usize> {
+		let operand = self.parse_left_hand_side()?;
+		let (kind, start) = self.peek()?;
+
+		let operator = match kind {
+			TokenKind::Increment => ast::javascript::UnaryOperator::Increment,
+			TokenKind::Decrement => ast::javascript::UnaryOperator::Decrement,
+			_ => return Ok(operand),
+		};
+
+		// A line break before `++` or `--` ends the expression.
+		if self
+			.tokens
+			.scanner()
+			.slice(operand.end(), start)
+			.contains('\n')
+		{
+			return Ok(operand);
+		}
+
+		if !is_assignment_target(&operand, false) {
+			return Err(start);
+		}
+
+		let end = self.tokens.scan()?.end;
 
-		Ok(match token_kind {
+		Ok(ast::javascript::UnaryExpression {
+			start: operand.start(),
+			end,
+			operator,
+			operand: Box::new(operand),
+			prefix: false,
+		}
+		.into())
+	}
+
+	/// Parses a primary expression followed by member accesses, calls and tagged templates.
+	pub fn parse_left_hand_side(&mut self) -> Result<ast::javascript::Expression<'src>, usize> {
+		let mut left = self.parse_primary()?;
+		let mut optional = false;
+
+		loop {
+			let (kind, start) = self.peek()?;
+
+			left = match kind {
+				TokenKind::Dot | TokenKind::OpenBracket => self.parse_member(left)?.into(),
+				TokenKind::OpenParen => self.parse_call(left)?.into(),
+
+				TokenKind::OptionalChain => {
+					optional = true;
+
+					self.tokens.scan()?;
+					let next = self.peek()?.0;
+					self.tokens.unscan();
+
+					if next == TokenKind::OpenParen {
+						self.parse_call(left)?.into()
+					} else {
+						self.parse_member(left)?.into()
+					}
+				}
+
+				// A non-null assertion, on the same line as its operand.
+				TokenKind::Not
+					if self.typescript
+						&& !self
+							.tokens
+							.scanner()
+							.slice(left.end(), start)
+							.contains('\n') =>
+				{
+					let end = self.tokens.scan()?.end;
+
+					ast::javascript::TsNonNullExpression {
+						start: left.start(),
+						end,
+						expression: Box::new(left),
+					}
+					.into()
+				}
+
+				// Optional chains cannot be tagged.
+				TokenKind::Template if optional => return Err(start),
+				TokenKind::Template => self.parse_tagged_template(left)?.into(),
+
+				_ => break,
+			}
+		}
+
+		Ok(left)
+	}
+
+	pub fn parse_primary(&mut self) -> Result<ast::javascript::Expression<'src>, usize> {
+		if let Some(name @ ("function" | "class")) = self.peek_identifier()? {
+			return Err(self.unsupported(name, "expression")?);
+		}
+
+		Ok(match self.peek()?.0 {
+			TokenKind::Identifier | TokenKind::OpenParen | TokenKind::LessThan
+				if self.is_arrow_function()? =>
+			{
+				self.parse_arrow_function()?.into()
+			}
 			TokenKind::Identifier => self.parse_identifier()?.into(),
+			TokenKind::This => self.parse_this()?.into(),
 			TokenKind::String => self.parse_string()?.into(),
 			TokenKind::Numeric => self.parse_number()?.into(),
-			TokenKind::Template => self.parse_template()?.into(),
+			TokenKind::BigInt => self.parse_bigint()?.into(),
+			TokenKind::Template => self.parse_template(false)?.into(),
 			TokenKind::True | TokenKind::False => self.parse_boolean()?.into(),
 			TokenKind::Null => self.parse_null()?.into(),
-			TokenKind::Minus
-			| TokenKind::Plus
-			| TokenKind::Increment
-			| TokenKind::Decrement
-			| TokenKind::Not
-			| TokenKind::BitNot
-			| TokenKind::Typeof
-			| TokenKind::Void
-			| TokenKind::Delete => self.parse_unary()?.into(),
-			TokenKind::OpenParen => self.parse_arrow_function_or_parenthesized()?,
+			TokenKind::OpenParen => self.parse_parenthesized()?.into(),
 			TokenKind::OpenBracket => self.parse_array()?.into(),
 			TokenKind::OpenBrace => self.parse_object()?.into(),
 			TokenKind::New => self.parse_new()?.into(),
 
+			// Where an expression starts, `/` is a regular expression rather than division.
+			TokenKind::Divide | TokenKind::DivideAssign => self.parse_regex()?.into(),
+
 			_ => return Err(self.tokens.cursor()),
 		})
 	}
 
-	pub fn parse_expression(&mut self) -> Result<ast::javascript::Expression, usize> {
-		let mut left = self.parse_expression_lazy()?;
-
-		while !self.tokens.is_done() {
-			let token = self.tokens.scan()?;
-			if is_eof(&token.kind) {
-				return Err(self.tokens.cursor());
-			}
-	
-			let token_kind = token.kind.clone();
-			self.tokens.unscan();
-	
-			left = match token_kind {
-				TokenKind::Plus
-				| TokenKind::Minus
-				| TokenKind::Multiply
-				| TokenKind::Divide
-				| TokenKind::Modulo
-				| TokenKind::Exponentiate
-				| TokenKind::LeftShift
-				| TokenKind::RightShift
-				| TokenKind::UnsignedRightShift
-				| TokenKind::LessThan
-				| TokenKind::GreaterThan
-				| TokenKind::LessThanEqual
-				| TokenKind::GreaterThanEqual
-				| TokenKind::Equal
-				| TokenKind::NotEqual
-				| TokenKind::StrictEqual
-				| TokenKind::StrictNotEqual
-				| TokenKind::BitAnd
-				| TokenKind::BitOr
-				| TokenKind::BitXor
-				| TokenKind::LogicalAnd
-				| TokenKind::LogicalOr
-				| TokenKind::Instanceof
-				| TokenKind::In => self.parse_binary(left)?.into(),
-				TokenKind::Assign
-				| TokenKind::PlusAssign
-				| TokenKind::MinusAssign
-				| TokenKind::MultiplyAssign
-				| TokenKind::ExponentiateAssign
-				| TokenKind::DivideAssign
-				| TokenKind::ModuloAssign
-				| TokenKind::LeftShiftAssign
-				| TokenKind::RightShiftAssign
-				| TokenKind::UnsignedRightShiftAssign
-				| TokenKind::BitAndAssign
-				| TokenKind::BitXorAssign
-				| TokenKind::BitOrAssign => self.parse_assignment(left)?.into(),
-				TokenKind::QuestionMark => self.parse_member_or_conditional(left)?.into(),
-				TokenKind::OpenParen => self.parse_call(left)?.into(),
-				TokenKind::Dot | TokenKind::OpenBracket => self.parse_member(left)?.into(),
-				TokenKind::Template => self.parse_tagged_template(left)?.into(),
-	
-				_ => break,
-			}
+	pub fn parse_identifier(
+		&mut self,
+	) -> Result<ast::javascript::IdentifierExpression<'src>, usize> {
+		let token = self.tokens.scan()?;
+		if token.kind != TokenKind::Identifier {
+			return Err(token.start);
 		}
-		
-		return Ok(left);
+
+		let start = token.start;
+		let end = token.end;
+		Ok(ast::javascript::IdentifierExpression {
+			start,
+			end,
+			name: self.tokens.scanner().slice(start, end).into(),
+		})
 	}
 
-	pub fn parse_identifier(&mut self) -> Result<ast::javascript::IdentifierExpression, usize> {
+	/// Parses the name of a property, which unlike an identifier can be a keyword.
+	pub fn parse_property_name(
+		&mut self,
+	) -> Result<ast::javascript::IdentifierExpression<'src>, usize> {
 		let token = self.tokens.scan()?;
+		if !is_identifier_name(&token.kind) {
+			return Err(token.start);
+		}
+
 		let start = token.start;
 		let end = token.end;
 		Ok(ast::javascript::IdentifierExpression {
 			start,
 			end,
-			name: self.tokens.scanner().slice(start, end).to_owned(),
+			name: self.tokens.scanner().slice(start, end).into(),
+		})
+	}
+
+	pub fn parse_this(&mut self) -> Result<ast::javascript::ThisExpression, usize> {
+		let token = self.tokens.scan()?;
+		Ok(ast::javascript::ThisExpression {
+			start: token.start,
+			end: token.end,
 		})
 	}
 
-	pub fn parse_string(&mut self) -> Result<ast::javascript::StringLiteral, usize> {
+	pub fn parse_string(&mut self) -> Result<ast::javascript::StringLiteral<'src>, usize> {
 		let token = self.tokens.scan()?;
 		let start = token.start;
 		let end = token.end;
+		let raw = self.tokens.scanner().slice(start, end);
 		Ok(ast::javascript::StringLiteral {
 			start,
 			end,
-			value: self.tokens.scanner().slice(start + 1, end - 1).to_owned(),
-			raw: self.tokens.scanner().slice(start, end).to_owned(),
-			quote: self
-				.tokens
-				.scanner()
-				.slice(start, start + 1)
-				.chars()
-				.next()
-				.unwrap(),
+			value: unescape(&raw[1..raw.len() - 1]).map_err(|offset| start + 1 + offset)?,
+			raw: raw.into(),
+			quote: raw.chars().next().unwrap(),
 		})
 	}
 
-	pub fn parse_number(&mut self) -> Result<ast::javascript::NumberLiteral, usize> {
+	pub fn parse_number(&mut self) -> Result<ast::javascript::NumberLiteral<'src>, usize> {
 		let token = self.tokens.scan()?;
 		let start = token.start;
 		let end = token.end;
 		let raw = self.tokens.scanner().slice(start, end);
+		let digits = raw.replace('_', "");
+
+		let radix = match digits.get(..2) {
+			Some("0x" | "0X") => 16,
+			Some("0o" | "0O") => 8,
+			Some("0b" | "0B") => 2,
+			_ => 10,
+		};
+
+		// Integers with a prefix may be too large for any integer type, so they are summed as
+		// floats like in JavaScript.
+		let value = if radix == 10 {
+			digits.parse().unwrap()
+		} else {
+			digits[2..].chars().fold(0_f64, |value, digit| {
+				value * radix as f64 + digit.to_digit(radix).unwrap() as f64
+			})
+		};
+
 		Ok(ast::javascript::NumberLiteral {
 			start,
 			end,
-			value: raw.parse().unwrap(),
-			raw: raw.to_owned(),
+			value,
+			raw: raw.into(),
+		})
+	}
+
+	pub fn parse_bigint(&mut self) -> Result<ast::javascript::BigIntLiteral<'src>, usize> {
+		let token = self.tokens.scan()?;
+		let start = token.start;
+		let end = token.end;
+		let raw = self.tokens.scanner().slice(start, end);
+		let digits = &raw[..raw.len() - 1];
+
+		Ok(ast::javascript::BigIntLiteral {
+			start,
+			end,
+			value: if digits.contains('_') {
+				digits.replace('_', "").into()
+			} else {
+				digits.into()
+			},
+			raw: raw.into(),
+		})
+	}
+
+	pub fn parse_regex(&mut self) -> Result<ast::javascript::RegExpLiteral<'src>, usize> {
+		self.tokens.scan()?;
+		let token = self.tokens.rescan_regex()?;
+		let start = token.start;
+		let end = token.end;
+		let raw = self.tokens.scanner().slice(start, end);
+		let (pattern, flags) = raw[1..].rsplit_once('/').unwrap();
+
+		Ok(ast::javascript::RegExpLiteral {
+			start,
+			end,
+			pattern: pattern.into(),
+			flags: flags.into(),
+			raw: raw.into(),
 		})
 	}
 
-	pub fn parse_boolean(&mut self) -> Result<ast::javascript::BooleanLiteral, usize> {
+	pub fn parse_boolean(&mut self) -> Result<ast::javascript::BooleanLiteral<'src>, usize> {
 		let token = self.tokens.scan()?;
 		let start = token.start;
 		let end = token.end;
@@ -159,165 +515,716 @@ impl<'a> JavascriptParser<'a> {
 			start,
 			end,
 			value: raw.parse().unwrap(),
-			raw: raw.to_owned(),
+			raw: raw.into(),
+		})
+	}
+
+	pub fn parse_null(&mut self) -> Result<ast::javascript::NullLiteral<'src>, usize> {
+		let token = self.tokens.scan()?;
+		let start = token.start;
+		let end = token.end;
+		Ok(ast::javascript::NullLiteral {
+			start,
+			end,
+			raw: "null".into(),
 		})
 	}
 
-	pub fn parse_null(&mut self) -> Result<ast::javascript::NullLiteral, usize> {
-		let token = self.tokens.scan()?;
-		let start = token.start;
-		let end = token.end;
-		Ok(ast::javascript::NullLiteral {
-			start,
-			end,
-			raw: "null".to_owned(),
-		})
-	}
+	pub fn parse_template(
+		&mut self,
+		tagged: bool,
+	) -> Result<ast::javascript::TemplateLiteral<'src>, usize> {
+		let token = self.tokens.scan()?;
+		let start = token.start;
+		let end = token.end;
+
+		// The lexer skipped the whole template, so it is scanned again for its parts, with a
+		// parser of its own for every substitution.
+		let mut scanner = self.tokens.scanner().clone();
+		scanner.set_cursor(start + 1);
+
+		let mut quasis = Vec::new();
+		let mut expressions = Vec::new();
+
+		loop {
+			let quasi_start = scanner.cursor();
+
+			while !scanner.test("`") && !scanner.test("${") && !scanner.is_done() {
+				if scanner.peek() == Some('\\') {
+					scanner.next();
+				}
+
+				scanner.next();
+			}
+
+			let raw = scanner.slice(quasi_start, scanner.cursor());
+			let cooked = match unescape(raw) {
+				Ok(cooked) => Some(cooked),
+				// Invalid escapes are only allowed in tagged templates.
+				Err(_) if tagged => None,
+				Err(offset) => return Err(quasi_start + offset),
+			};
+
+			quasis.push(ast::javascript::TemplateElement {
+				start: quasi_start,
+				end: scanner.cursor(),
+				raw: raw.into(),
+				cooked,
+			});
+
+			if scanner.take("`") {
+				break;
+			}
+
+			scanner.take("${");
+
+			let mut parser = JavascriptParser::new(&mut scanner);
+			parser.in_async = self.in_async;
+			parser.typescript = self.typescript;
+			let expression = parser.parse_expression();
+			self.unsupported = parser.take_unsupported();
+			expressions.push(expression?);
+
+			if parser.tokens.scan()?.kind != TokenKind::CloseBrace {
+				return Err(parser.tokens.cursor());
+			}
+		}
+
+		Ok(ast::javascript::TemplateLiteral {
+			start,
+			end,
+			quasis,
+			expressions,
+		})
+	}
+
+	/// Whether an arrow function starts at the next token, by scanning ahead to its `=>`.
+	fn is_arrow_function(&mut self) -> Result<bool, usize> {
+		let mut token_count = 0;
+		let result = self.scan_arrow_parameters(&mut token_count);
+
+		for _ in 0..token_count {
+			self.tokens.unscan();
+		}
+
+		result
+	}
+
+	/// Scans the parameters of an arrow function and the `=>` after them, counting the tokens.
+	fn scan_arrow_parameters(&mut self, token_count: &mut usize) -> Result<bool, usize> {
+		let token = self.tokens.scan()?;
+		let (mut kind, start, end) = (token.kind.clone(), token.start, token.end);
+		*token_count += 1;
+
+		// `async` before the parameters, on the same line.
+		if kind == TokenKind::Identifier && self.tokens.scanner().slice(start, end) == "async" {
+			let token = self.tokens.scan()?;
+			let (next, start) = (token.kind.clone(), token.start);
+			*token_count += 1;
+
+			if matches!(
+				next,
+				TokenKind::Identifier | TokenKind::OpenParen | TokenKind::LessThan
+			) && !self.tokens.scanner().slice(end, start).contains('\n')
+			{
+				kind = next;
+			} else {
+				self.tokens.unscan();
+				*token_count -= 1;
+			}
+		}
+
+		// Type parameters, as in `<T>(a: T) => a`.
+		if kind == TokenKind::LessThan {
+			if !self.typescript {
+				return Ok(false);
+			}
+
+			let mut depth = 1;
+
+			while depth > 0 {
+				let kind = self.tokens.scan()?.kind.clone();
+				*token_count += 1;
+
+				match kind {
+					TokenKind::LessThan => depth += 1,
+					TokenKind::GreaterThan => depth -= 1,
+					TokenKind::RightShift => depth -= 2,
+					TokenKind::UnsignedRightShift => depth -= 3,
+					TokenKind::EOF => return Ok(false),
+					_ => continue,
+				}
+			}
+
+			kind = self.tokens.scan()?.kind.clone();
+			*token_count += 1;
+
+			if depth < 0 || kind != TokenKind::OpenParen {
+				return Ok(false);
+			}
+		}
+
+		if kind == TokenKind::OpenParen {
+			let mut depth = 0;
+
+			loop {
+				let kind = self.tokens.scan()?.kind.clone();
+				*token_count += 1;
+
+				match kind {
+					TokenKind::OpenParen => depth += 1,
+					TokenKind::CloseParen if depth == 0 => break,
+					TokenKind::CloseParen => depth -= 1,
+					TokenKind::EOF => return Ok(false),
+					_ => continue,
+				}
+			}
+		} else if kind != TokenKind::Identifier {
+			return Ok(false);
+		}
+
+		let mut next = self.tokens.scan()?.kind.clone();
+		*token_count += 1;
+
+		// The return type, as in `(a): T => a`, ends at the first `=>` outside of brackets.
+		if next == TokenKind::Colon && kind == TokenKind::OpenParen && self.typescript {
+			let mut depth = 0;
+
+			loop {
+				next = self.tokens.scan()?.kind.clone();
+				*token_count += 1;
+
+				match next {
+					TokenKind::OpenParen | TokenKind::OpenBracket | TokenKind::OpenBrace => {
+						depth += 1
+					}
+					TokenKind::CloseParen | TokenKind::CloseBracket | TokenKind::CloseBrace
+						if depth == 0 =>
+					{
+						return Ok(false)
+					}
+					TokenKind::CloseParen | TokenKind::CloseBracket | TokenKind::CloseBrace => {
+						depth -= 1
+					}
+					TokenKind::Arrow if depth == 0 => break,
+					TokenKind::EOF => return Ok(false),
+					_ => continue,
+				}
+			}
+		}
+
+		Ok(next == TokenKind::Arrow)
+	}
+
+	pub fn parse_arrow_function(
+		&mut self,
+	) -> Result<ast::javascript::FunctionExpression<'src>, usize> {
+		let mut parameters = Vec::new();
+
+		let token = self.tokens.scan()?;
+		let (kind, start, end) = (token.kind.clone(), token.start, token.end);
+
+		// In `async => a`, `async` is the parameter.
+		let is_async = kind == TokenKind::Identifier
+			&& self.tokens.scanner().slice(start, end) == "async"
+			&& self.peek()?.0 != TokenKind::Arrow;
+
+		if !is_async {
+			self.tokens.unscan();
+		}
+
+		let type_parameters = if self.typescript && self.peek()?.0 == TokenKind::LessThan {
+			Some(self.parse_type_parameters()?)
+		} else {
+			None
+		};
+
+		// A single parameter without parentheses cannot have types.
+		let parenthesized = type_parameters.is_some() || self.peek()?.0 != TokenKind::Identifier;
+
+		if !parenthesized {
+			parameters.push(self.parse_identifier()?.into());
+		} else {
+			if self.tokens.scan()?.kind != TokenKind::OpenParen {
+				return Err(self.tokens.cursor());
+			}
+
+			loop {
+				match self.peek()?.0 {
+					TokenKind::CloseParen => {
+						self.tokens.scan()?;
+						break;
+					}
+
+					TokenKind::Ellipsis => {
+						let start = self.tokens.scan()?.start;
+						let argument = self.parse_binding_target()?;
+						let argument = self.parse_type_annotation(argument, false)?;
+
+						parameters.push(
+							ast::javascript::SpreadExpression {
+								start,
+								end: argument.end(),
+								argument: Box::new(argument),
+							}
+							.into(),
+						);
+
+						// The rest parameter is the last one.
+						if self.tokens.scan()?.kind != TokenKind::CloseParen {
+							return Err(self.tokens.cursor());
+						}
+
+						break;
+					}
+
+					_ => {
+						let target = self.parse_binding_target()?;
+						let target = self.parse_type_annotation(target, true)?;
+						parameters.push(self.parse_default(target)?);
+					}
+				}
+
+				match self.tokens.scan()?.kind {
+					TokenKind::Comma => continue,
+					TokenKind::CloseParen => break,
+					_ => return Err(self.tokens.cursor()),
+				}
+			}
+		}
+
+		let return_type = if parenthesized && self.typescript && self.peek()?.0 == TokenKind::Colon
+		{
+			self.tokens.scan()?;
+			Some(self.parse_type()?)
+		} else {
+			None
+		};
+
+		let token = self.tokens.scan()?;
+		if token.kind != TokenKind::Arrow {
+			return Err(self.tokens.cursor());
+		}
+
+		let in_async = std::mem::replace(&mut self.in_async, is_async);
+
+		let body = if self.peek()?.0 == TokenKind::OpenBrace {
+			self.parse_block().map(ast::javascript::FunctionBody::Block)
+		} else {
+			self.parse_assignment()
+				.map(|body| ast::javascript::FunctionBody::Expression(Box::new(body)))
+		};
+
+		self.in_async = in_async;
+		let body = body?;
+
+		Ok(ast::javascript::FunctionExpression {
+			start,
+			end: body.end(),
+			is_async,
+			parameters,
+			type_parameters,
+			return_type,
+			body,
+		})
+	}
+
+	/// Parses `as T` or `satisfies T` after an expression.
+	fn parse_type_assertion(
+		&mut self,
+		expression: ast::javascript::Expression<'src>,
+	) -> Result<ast::javascript::Expression<'src>, usize> {
+		let satisfies = self.peek_identifier()? == Some("satisfies");
+		self.tokens.scan()?;
+
+		let type_annotation = self.parse_type()?;
+		let start = expression.start();
+		let end = type_annotation.end;
+		let expression = Box::new(expression);
+
+		Ok(if satisfies {
+			ast::javascript::TsSatisfiesExpression {
+				start,
+				end,
+				expression,
+				type_annotation,
+			}
+			.into()
+		} else {
+			ast::javascript::TsAsExpression {
+				start,
+				end,
+				expression,
+				type_annotation,
+			}
+			.into()
+		})
+	}
+
+	/// Parses the type of a parameter or declared variable after its target, like `: T` or `?: T`
+	/// where `optional` is allowed. Returns the target as is if it has neither.
+	fn parse_type_annotation(
+		&mut self,
+		target: ast::javascript::Expression<'src>,
+		optional: bool,
+	) -> Result<ast::javascript::Expression<'src>, usize> {
+		if !self.typescript {
+			return Ok(target);
+		}
+
+		let optional = optional && self.peek()?.0 == TokenKind::QuestionMark;
+		if optional {
+			self.tokens.scan()?;
+		}
+
+		let type_annotation = if self.peek()?.0 == TokenKind::Colon {
+			self.tokens.scan()?;
+			Some(self.parse_type()?)
+		} else {
+			None
+		};
+
+		if !optional && type_annotation.is_none() {
+			return Ok(target);
+		}
+
+		Ok(ast::javascript::TsAnnotatedExpression {
+			start: target.start(),
+			end: self.tokens.cursor(),
+			expression: Box::new(target),
+			optional,
+			type_annotation,
+		}
+		.into())
+	}
+
+	/// Parses type parameters like `<T, U extends T = T>`.
+	pub fn parse_type_parameters(&mut self) -> Result<ast::javascript::TsType<'src>, usize> {
+		let start = self.peek()?.1;
+		self.skip_type_parameters()?;
+		Ok(self.type_from(start))
+	}
+
+	pub fn parse_type(&mut self) -> Result<ast::javascript::TsType<'src>, usize> {
+		let start = self.peek()?.1;
+		self.skip_type()?;
+		Ok(self.type_from(start))
+	}
+
+	fn type_from(&self, start: usize) -> ast::javascript::TsType<'src> {
+		let end = self.tokens.cursor();
+
+		ast::javascript::TsType {
+			start,
+			end,
+			raw: self.tokens.scanner().slice(start, end).into(),
+		}
+	}
+
+	fn skip_type_parameters(&mut self) -> Result<(), usize> {
+		if self.tokens.scan()?.kind != TokenKind::LessThan {
+			return Err(self.tokens.cursor());
+		}
+
+		loop {
+			// `const` type parameters.
+			if self.peek()?.0 == TokenKind::Const {
+				self.tokens.scan()?;
+			}
+
+			self.parse_identifier()?;
+
+			if self.peek_identifier()? == Some("extends") {
+				self.tokens.scan()?;
+				self.skip_type()?;
+			}
+
+			if self.peek()?.0 == TokenKind::Assign {
+				self.tokens.scan()?;
+				self.skip_type()?;
+			}
+
+			if self.peek()?.0 != TokenKind::Comma {
+				break;
+			}
+
+			self.tokens.scan()?;
+
+			// A trailing comma, as in `<T,>`.
+			if is_greater_than(&self.peek()?.0) {
+				break;
+			}
+		}
+
+		self.skip_greater_than()
+	}
+
+	/// Skips a type. Types are only erased, so they are parsed no further than needed to find
+	/// where they end. Object types, tuples and the parameters of function types are skipped as
+	/// balanced brackets.
+	fn skip_type(&mut self) -> Result<(), usize> {
+		self.skip_union_type()?;
+
+		// Conditional types, like `T extends U ? X : Y`.
+		if self.peek_identifier()? == Some("extends") {
+			self.tokens.scan()?;
+			self.skip_union_type()?;
+
+			if self.tokens.scan()?.kind != TokenKind::QuestionMark {
+				return Err(self.tokens.cursor());
+			}
+
+			self.skip_type()?;
+
+			if self.tokens.scan()?.kind != TokenKind::Colon {
+				return Err(self.tokens.cursor());
+			}
+
+			self.skip_type()?;
+		}
+
+		Ok(())
+	}
+
+	/// Skips unions and intersections, which may start with a `|` or `&`.
+	fn skip_union_type(&mut self) -> Result<(), usize> {
+		if matches!(self.peek()?.0, TokenKind::BitOr | TokenKind::BitAnd) {
+			self.tokens.scan()?;
+		}
+
+		loop {
+			self.skip_postfix_type()?;
+
+			if !matches!(self.peek()?.0, TokenKind::BitOr | TokenKind::BitAnd) {
+				return Ok(());
+			}
+
+			self.tokens.scan()?;
+		}
+	}
+
+	/// Skips a type followed by array types and indexed access types, like `T[]` and `T["a"]`.
+	fn skip_postfix_type(&mut self) -> Result<(), usize> {
+		self.skip_primary_type()?;
+
+		loop {
+			let end = self.tokens.cursor();
+			let (kind, start) = self.peek()?;
+
+			if kind != TokenKind::OpenBracket
+				|| self.tokens.scanner().slice(end, start).contains('\n')
+			{
+				return Ok(());
+			}
+
+			self.tokens.scan()?;
+
+			if self.peek()?.0 != TokenKind::CloseBracket {
+				self.skip_type()?;
+			}
+
+			if self.tokens.scan()?.kind != TokenKind::CloseBracket {
+				return Err(self.tokens.cursor());
+			}
+		}
+	}
+
+	fn skip_primary_type(&mut self) -> Result<(), usize> {
+		let (kind, start) = self.peek()?;
+
+		match kind {
+			TokenKind::OpenParen if self.is_function_type()? => self.skip_function_type(),
+			TokenKind::LessThan => self.skip_function_type(),
+
+			TokenKind::OpenParen => {
+				self.tokens.scan()?;
+				self.skip_type()?;
+
+				if self.tokens.scan()?.kind != TokenKind::CloseParen {
+					return Err(self.tokens.cursor());
+				}
+
+				Ok(())
+			}
+
+			// Constructor types, like `new () => T`.
+			TokenKind::New => {
+				self.tokens.scan()?;
+				self.skip_function_type()
+			}
+
+			TokenKind::OpenBrace | TokenKind::OpenBracket => self.skip_brackets(),
+
+			TokenKind::Typeof => {
+				self.tokens.scan()?;
+				self.skip_type_reference()
+			}
+
+			TokenKind::String
+			| TokenKind::Numeric
+			| TokenKind::BigInt
+			| TokenKind::Template
+			| TokenKind::True
+			| TokenKind::False => {
+				self.tokens.scan()?;
+				Ok(())
+			}
+
+			TokenKind::Minus => {
+				self.tokens.scan()?;
+
+				if !matches!(
+					self.tokens.scan()?.kind,
+					TokenKind::Numeric | TokenKind::BigInt
+				) {
+					return Err(self.tokens.cursor());
+				}
+
+				Ok(())
+			}
+
+			TokenKind::Identifier => match self.peek_identifier()? {
+				Some("keyof" | "readonly" | "unique") => {
+					self.tokens.scan()?;
+					self.skip_postfix_type()
+				}
+
+				Some("infer") => {
+					self.tokens.scan()?;
+					self.parse_identifier()?;
+					Ok(())
+				}
+
+				_ => self.skip_type_reference(),
+			},
+
+			// Keywords which are types, like `null`, `void` and `this`, or `const` in `as const`.
+			_ if is_identifier_name(&kind) => self.skip_type_reference(),
+
+			_ => Err(start),
+		}
+	}
+
+	/// Skips a possibly qualified name with type arguments, like `A.B<T>`.
+	fn skip_type_reference(&mut self) -> Result<(), usize> {
+		self.parse_property_name()?;
 
-	pub fn parse_template(&mut self) -> Result<ast::javascript::TemplateLiteral, usize> {
-		let token = self.tokens.scan()?;
-		let start = token.start;
-		let end = token.end;
-		Ok(ast::javascript::TemplateLiteral {
-			start,
-			end,
-			raw: self.tokens.scanner().slice(start + 1, end - 1).to_owned(),
-		})
-	}
+		while self.peek()?.0 == TokenKind::Dot {
+			self.tokens.scan()?;
+			self.parse_property_name()?;
+		}
 
-	pub fn parse_unary(&mut self) -> Result<ast::javascript::UnaryExpression, usize> {
-		let operator = self.tokens.scan()?;
-		let start = operator.start;
-		let operator = match operator.kind {
-			TokenKind::Minus => ast::javascript::UnaryOperator::Minus,
-			TokenKind::Plus => ast::javascript::UnaryOperator::Plus,
-			TokenKind::Increment => ast::javascript::UnaryOperator::Increment,
-			TokenKind::Decrement => ast::javascript::UnaryOperator::Decrement,
-			TokenKind::Not => ast::javascript::UnaryOperator::Not,
-			TokenKind::BitNot => ast::javascript::UnaryOperator::BitwiseNot,
-			TokenKind::Typeof => ast::javascript::UnaryOperator::Typeof,
-			TokenKind::Void => ast::javascript::UnaryOperator::Void,
-			TokenKind::Delete => ast::javascript::UnaryOperator::Delete,
+		if self.peek()?.0 != TokenKind::LessThan {
+			return Ok(());
+		}
 
-			_ => unreachable!("{}:{}", operator.start, operator.end),
-		};
+		self.tokens.scan()?;
 
-		let token = self.tokens.scan()?;
-		if is_eof(&token.kind) {
+		loop {
+			self.skip_type()?;
+
+			if self.peek()?.0 != TokenKind::Comma {
+				break;
+			}
+
+			self.tokens.scan()?;
+		}
+
+		self.skip_greater_than()
+	}
+
+	/// Skips a function type like `<T>(a: T) => T`.
+	fn skip_function_type(&mut self) -> Result<(), usize> {
+		if self.peek()?.0 == TokenKind::LessThan {
+			self.skip_type_parameters()?;
+		}
+
+		if self.peek()?.0 != TokenKind::OpenParen {
 			return Err(self.tokens.cursor());
 		}
 
-		let operand = self.tokens.peek();
-		let expression = ast::javascript::UnaryExpression {
-			start,
-			end: operand.end,
-			operator,
-			operand: Box::new(self.parse_expression()?),
-		};
+		self.skip_brackets()?;
+
+		if self.tokens.scan()?.kind != TokenKind::Arrow {
+			return Err(self.tokens.cursor());
+		}
 
-		Ok(expression)
+		self.skip_type()
 	}
 
-	pub fn parse_arrow_function_or_parenthesized(
-		&mut self,
-	) -> Result<ast::javascript::Expression, usize> {
+	/// Whether a function type starts at the next `(`, by scanning ahead to the `=>` after it.
+	fn is_function_type(&mut self) -> Result<bool, usize> {
 		let mut token_count = 0;
+		let mut depth = 0;
 
-		loop {
-			let token = self.tokens.scan()?;
+		let result = loop {
+			let kind = self.tokens.scan()?.kind.clone();
 			token_count += 1;
 
-			match token.kind {
-				TokenKind::CloseParen => break,
-				TokenKind::EOF => return Err(self.tokens.cursor()),
+			match kind {
+				TokenKind::OpenParen => depth += 1,
+				TokenKind::CloseParen if depth == 1 => {
+					let kind = self.tokens.scan()?.kind.clone();
+					token_count += 1;
+					break Ok(kind == TokenKind::Arrow);
+				}
+				TokenKind::CloseParen => depth -= 1,
+				TokenKind::EOF => break Ok(false),
 				_ => continue,
 			}
-		}
-
-		let has_next = !self.tokens.is_done();
-		let next = self.tokens.scan()?.kind.clone();
-
-		if has_next {
-			self.tokens.unscan();
-		}
+		};
 
 		for _ in 0..token_count {
 			self.tokens.unscan();
 		}
 
-		if next == TokenKind::Arrow {
-			Ok(self.parse_arrow_function()?.into())
-		} else {
-			Ok(self.parse_parenthesized()?.into())
-		}
+		result
 	}
 
-	pub fn parse_arrow_function(&mut self) -> Result<ast::javascript::FunctionExpression, usize> {
-		let start = self.tokens.cursor();
-		let mut parameters: Vec<ast::javascript::Expression> = Vec::new();
-
-		let token = self.tokens.scan()?;
-		if token.kind != TokenKind::OpenParen {
-			return Err(token.start);
-		}
+	/// Skips from an opening bracket to the one closing it.
+	fn skip_brackets(&mut self) -> Result<(), usize> {
+		let mut depth = 0;
 
 		loop {
 			let token = self.tokens.scan()?;
-			match token.kind {
-				TokenKind::CloseParen => break,
-
-				TokenKind::Identifier => {
-					self.tokens.unscan();
-					parameters.push(self.parse_identifier()?.into());
-				}
 
-				TokenKind::Ellipsis => {
-					let start = self.tokens.cursor();
-					let argument = self.parse_identifier()?.into();
-
-					parameters.push(ast::javascript::Expression::Spread(
-						ast::javascript::SpreadExpression {
-							start,
-							end: self.tokens.cursor(),
-							argument: Box::new(argument),
-						},
-					));
+			match token.kind {
+				TokenKind::OpenParen | TokenKind::OpenBracket | TokenKind::OpenBrace => depth += 1,
+				TokenKind::CloseParen | TokenKind::CloseBracket | TokenKind::CloseBrace => {
+					depth -= 1
 				}
-
-				_ => return Err(token.start),
-			}
-
-			let token = self.tokens.scan()?.kind.clone();
-			if is_eof(&token) {
-				return Err(self.tokens.cursor());
+				TokenKind::EOF => return Err(token.start),
+				_ => {}
 			}
 
-			if token == TokenKind::Comma {
-				continue;
-			} else {
-				break;
+			if depth <= 0 {
+				return if depth == 0 { Ok(()) } else { Err(token.start) };
 			}
 		}
+	}
 
+	/// Skips the `>` which closes type arguments or parameters. Tokens like `>>` are split, so
+	/// each `>` closes one list.
+	fn skip_greater_than(&mut self) -> Result<(), usize> {
 		let token = self.tokens.scan()?;
-		if token.kind != TokenKind::Arrow {
-			return Err(self.tokens.cursor());
+		let (kind, start) = (token.kind.clone(), token.start);
+
+		if !is_greater_than(&kind) {
+			return Err(start);
 		}
 
-		let body = self.parse_expression()?;
+		if kind != TokenKind::GreaterThan {
+			self.tokens.rescan_greater_than()?;
+		}
 
-		Ok(ast::javascript::FunctionExpression {
-			start,
-			end: self.tokens.cursor(),
-			parameters,
-			body: Box::new(body),
-		})
+		Ok(())
 	}
 
-	pub fn parse_parenthesized(&mut self) -> Result<ast::javascript::ParenthesizedExpression, usize> {
-		let start = self.tokens.cursor();
-
+	pub fn parse_parenthesized(
+		&mut self,
+	) -> Result<ast::javascript::ParenthesizedExpression<'src>, usize> {
 		let token = self.tokens.scan()?;
+		let start = token.start;
 		if token.kind != TokenKind::OpenParen {
 			return Err(token.start);
 		}
@@ -336,11 +1243,11 @@ impl<'a> JavascriptParser<'a> {
 		})
 	}
 
-	pub fn parse_array(&mut self) -> Result<ast::javascript::ArrayExpression, usize> {
-		let start = self.tokens.cursor();
+	pub fn parse_array(&mut self) -> Result<ast::javascript::ArrayExpression<'src>, usize> {
 		let mut elements = Vec::new();
 
 		let token = self.tokens.scan()?;
+		let start = token.start;
 		if token.kind != TokenKind::OpenBracket {
 			return Err(token.start);
 		}
@@ -355,11 +1262,25 @@ impl<'a> JavascriptParser<'a> {
 					elements.push(ast::javascript::Expression::Empty(
 						ast::javascript::EmptyExpression { start, end: start },
 					));
+					continue;
+				}
+
+				TokenKind::Ellipsis => {
+					let start = self.tokens.peek().start;
+					let argument = self.parse_assignment()?;
+
+					elements.push(ast::javascript::Expression::Spread(
+						ast::javascript::SpreadExpression {
+							start,
+							end: argument.end(),
+							argument: Box::new(argument),
+						},
+					));
 				}
 
 				_ => {
 					self.tokens.unscan();
-					let expression = self.parse_expression()?;
+					let expression = self.parse_assignment()?;
 					elements.push(expression);
 				}
 			}
@@ -378,19 +1299,20 @@ impl<'a> JavascriptParser<'a> {
 		})
 	}
 
-	pub fn parse_object(&mut self) -> Result<ast::javascript::ObjectExpression, usize> {
-		let start = self.tokens.cursor();
+	pub fn parse_object(&mut self) -> Result<ast::javascript::ObjectExpression<'src>, usize> {
 		let mut properties = Vec::new();
 
 		let token = self.tokens.scan()?;
+		let start = token.start;
 		if token.kind != TokenKind::OpenBrace {
 			return Err(token.start);
 		}
 
 		loop {
-			let start = self.tokens.cursor();
+			let token = self.tokens.scan()?;
+			let start = token.start;
 
-			match self.tokens.scan()?.kind {
+			match token.kind {
 				TokenKind::CloseBrace => break,
 
 				TokenKind::Identifier => {
@@ -400,7 +1322,7 @@ impl<'a> JavascriptParser<'a> {
 
 					let token = self.tokens.scan()?;
 					if token.kind == TokenKind::Colon {
-						value = Some(self.parse_expression()?);
+						value = Some(self.parse_assignment()?);
 					} else {
 						self.tokens.unscan();
 					}
@@ -416,7 +1338,7 @@ impl<'a> JavascriptParser<'a> {
 				}
 
 				TokenKind::OpenBracket => {
-					let key = self.parse_expression()?;
+					let key = self.parse_assignment()?;
 
 					let token = self.tokens.scan()?;
 					if token.kind != TokenKind::CloseBracket {
@@ -428,7 +1350,7 @@ impl<'a> JavascriptParser<'a> {
 						return Err(self.tokens.cursor());
 					}
 
-					let value = self.parse_expression()?;
+					let value = self.parse_assignment()?;
 
 					properties.push(ast::javascript::ObjectProperty::Computed(
 						ast::javascript::ObjectComputedProperty {
@@ -441,187 +1363,318 @@ impl<'a> JavascriptParser<'a> {
 				}
 
 				TokenKind::Ellipsis => {
-					// Scan away the ellipsis
-					self.tokens.scan()?;
+					let argument = self.parse_assignment()?;
+
+					properties.push(ast::javascript::ObjectProperty::Spread(
+						ast::javascript::SpreadExpression {
+							start,
+							end: self.tokens.cursor(),
+							argument: Box::new(argument),
+						},
+					))
+				}
+
+				_ => return Err(self.tokens.cursor()),
+			}
+
+			match self.tokens.scan()?.kind {
+				TokenKind::Comma => continue,
+				TokenKind::CloseBrace => break,
+				_ => return Err(self.tokens.cursor()),
+			}
+		}
+
+		Ok(ast::javascript::ObjectExpression {
+			start,
+			end: self.tokens.cursor(),
+			properties,
+		})
+	}
+
+	/// Parses the target of a parameter or declaration, which is an identifier, or an object or
+	/// array destructuring the value.
+	pub fn parse_binding_target(&mut self) -> Result<ast::javascript::Expression<'src>, usize> {
+		let (kind, start) = self.peek()?;
+
+		Ok(match kind {
+			TokenKind::Identifier => self.parse_identifier()?.into(),
+			TokenKind::OpenBrace => self.parse_object_pattern()?.into(),
+			TokenKind::OpenBracket => self.parse_array_pattern()?.into(),
+			_ => return Err(start),
+		})
+	}
+
+	/// Parses a binding target with an optional default value, like `a = 1`.
+	pub fn parse_binding_element(&mut self) -> Result<ast::javascript::Expression<'src>, usize> {
+		let target = self.parse_binding_target()?;
+		self.parse_default(target)
+	}
+
+	/// Parses the default value of a binding target, if any.
+	fn parse_default(
+		&mut self,
+		target: ast::javascript::Expression<'src>,
+	) -> Result<ast::javascript::Expression<'src>, usize> {
+		if self.peek()?.0 != TokenKind::Assign {
+			return Ok(target);
+		}
+
+		self.tokens.scan()?;
+		let value = self.parse_assignment()?;
+
+		Ok(ast::javascript::AssignmentExpression {
+			start: target.start(),
+			end: value.end(),
+			operator: ast::javascript::AssignmentOperator::Equal,
+			left: Box::new(target),
+			right: Box::new(value),
+		}
+		.into())
+	}
+
+	/// Parses an object pattern like `{ a, b: [c], d = 1, ...e }`. A shorthand property with a
+	/// default value has an assignment to its key as the value.
+	pub fn parse_object_pattern(
+		&mut self,
+	) -> Result<ast::javascript::ObjectExpression<'src>, usize> {
+		let mut properties = Vec::new();
+
+		let token = self.tokens.scan()?;
+		let start = token.start;
+		if token.kind != TokenKind::OpenBrace {
+			return Err(token.start);
+		}
+
+		loop {
+			let (kind, start) = self.peek()?;
+
+			match kind {
+				TokenKind::CloseBrace => {
+					self.tokens.scan()?;
+					break;
+				}
+
+				TokenKind::Identifier => {
+					let key = self.parse_identifier()?;
+
+					let value = match self.peek()?.0 {
+						TokenKind::Colon => {
+							self.tokens.scan()?;
+							Some(self.parse_binding_element()?)
+						}
+
+						TokenKind::Assign => {
+							self.tokens.scan()?;
+							let value = self.parse_assignment()?;
+
+							Some(
+								ast::javascript::AssignmentExpression {
+									start: key.start,
+									end: value.end(),
+									operator: ast::javascript::AssignmentOperator::Equal,
+									left: Box::new(key.clone().into()),
+									right: Box::new(value),
+								}
+								.into(),
+							)
+						}
+
+						_ => None,
+					};
+
+					properties.push(ast::javascript::ObjectProperty::Keyed(
+						ast::javascript::ObjectKeyedProperty {
+							start,
+							end: self.tokens.cursor(),
+							key: Box::new(key),
+							value,
+						},
+					));
+				}
+
+				TokenKind::OpenBracket => {
+					self.tokens.scan()?;
+					let key = self.parse_assignment()?;
+
+					if self.tokens.scan()?.kind != TokenKind::CloseBracket {
+						return Err(self.tokens.cursor());
+					}
+
+					if self.tokens.scan()?.kind != TokenKind::Colon {
+						return Err(self.tokens.cursor());
+					}
+
+					let value = self.parse_binding_element()?;
+
+					properties.push(ast::javascript::ObjectProperty::Computed(
+						ast::javascript::ObjectComputedProperty {
+							start,
+							end: self.tokens.cursor(),
+							key: Box::new(key),
+							value: Box::new(value),
+						},
+					));
+				}
+
+				TokenKind::Ellipsis => {
+					self.tokens.scan()?;
+					let argument = self.parse_identifier()?;
+
+					properties.push(ast::javascript::ObjectProperty::Spread(
+						ast::javascript::SpreadExpression {
+							start,
+							end: self.tokens.cursor(),
+							argument: Box::new(argument.into()),
+						},
+					));
+
+					// The rest property is the last one.
+					if self.tokens.scan()?.kind != TokenKind::CloseBrace {
+						return Err(self.tokens.cursor());
+					}
+
+					break;
+				}
+
+				_ => return Err(start),
+			}
+
+			match self.tokens.scan()?.kind {
+				TokenKind::Comma => continue,
+				TokenKind::CloseBrace => break,
+				_ => return Err(self.tokens.cursor()),
+			}
+		}
+
+		Ok(ast::javascript::ObjectExpression {
+			start,
+			end: self.tokens.cursor(),
+			properties,
+		})
+	}
+
+	/// Parses an array pattern like `[a, , [b], c = 1, ...d]`.
+	pub fn parse_array_pattern(&mut self) -> Result<ast::javascript::ArrayExpression<'src>, usize> {
+		let mut elements = Vec::new();
+
+		let token = self.tokens.scan()?;
+		let start = token.start;
+		if token.kind != TokenKind::OpenBracket {
+			return Err(token.start);
+		}
+
+		loop {
+			let start = self.tokens.cursor();
+
+			match self.peek()?.0 {
+				TokenKind::CloseBracket => {
+					self.tokens.scan()?;
+					break;
+				}
+
+				TokenKind::Comma => {
+					self.tokens.scan()?;
+					elements.push(ast::javascript::Expression::Empty(
+						ast::javascript::EmptyExpression { start, end: start },
+					));
+					continue;
+				}
+
+				TokenKind::Ellipsis => {
+					let start = self.tokens.scan()?.start;
+					let argument = self.parse_binding_target()?;
 
-					let argument = self.parse_expression()?;
-
-					properties.push(ast::javascript::ObjectProperty::Spread(
+					elements.push(
 						ast::javascript::SpreadExpression {
 							start,
-							end: self.tokens.cursor(),
+							end: argument.end(),
 							argument: Box::new(argument),
-						},
-					))
+						}
+						.into(),
+					);
+
+					// The rest element is the last one.
+					if self.tokens.scan()?.kind != TokenKind::CloseBracket {
+						return Err(self.tokens.cursor());
+					}
+
+					break;
 				}
 
-				_ => return Err(self.tokens.cursor()),
+				_ => elements.push(self.parse_binding_element()?),
 			}
 
 			match self.tokens.scan()?.kind {
+				TokenKind::CloseBracket => break,
 				TokenKind::Comma => continue,
-				TokenKind::CloseBrace => break,
 				_ => return Err(self.tokens.cursor()),
 			}
 		}
 
-		Ok(ast::javascript::ObjectExpression {
+		Ok(ast::javascript::ArrayExpression {
 			start,
 			end: self.tokens.cursor(),
-			properties,
+			elements,
 		})
 	}
 
-	pub fn parse_new(&mut self) -> Result<ast::javascript::NewExpression, usize> {
-		let start = self.tokens.cursor();
-
+	pub fn parse_new(&mut self) -> Result<ast::javascript::NewExpression<'src>, usize> {
 		let token = self.tokens.scan()?;
+		let start = token.start;
 		if token.kind != TokenKind::New {
 			return Err(self.tokens.cursor());
 		}
 
-		let callee = self.parse_expression_lazy()?;
-		let call = self.parse_call(callee)?;
-
-		Ok(ast::javascript::NewExpression {
-			start,
-			end: call.end,
-			callee: call.callee,
-			arguments: call.arguments,
-		})
-	}
-
-	pub fn parse_binary(
-		&mut self,
-		left: ast::javascript::Expression,
-	) -> Result<ast::javascript::BinaryExpression, usize> {
-		let token = self.tokens.scan()?;
-
-		let operator: ast::javascript::BinaryOperator = match token.kind {
-			TokenKind::Plus => ast::javascript::BinaryOperator::Plus,
-			TokenKind::Minus => ast::javascript::BinaryOperator::Minus,
-			TokenKind::Multiply => ast::javascript::BinaryOperator::Multiply,
-			TokenKind::Divide => ast::javascript::BinaryOperator::Divide,
-			TokenKind::Modulo => ast::javascript::BinaryOperator::Modulo,
-			TokenKind::Exponentiate => ast::javascript::BinaryOperator::Exponent,
-			TokenKind::LeftShift => ast::javascript::BinaryOperator::LeftShift,
-			TokenKind::RightShift => ast::javascript::BinaryOperator::RightShift,
-			TokenKind::UnsignedRightShift => ast::javascript::BinaryOperator::UnsignedRightShift,
-			TokenKind::LessThan => ast::javascript::BinaryOperator::LessThan,
-			TokenKind::GreaterThan => ast::javascript::BinaryOperator::GreaterThan,
-			TokenKind::LessThanEqual => ast::javascript::BinaryOperator::LessThanOrEqual,
-			TokenKind::GreaterThanEqual => ast::javascript::BinaryOperator::GreaterThanOrEqual,
-			TokenKind::Equal => ast::javascript::BinaryOperator::Equal,
-			TokenKind::NotEqual => ast::javascript::BinaryOperator::NotEqual,
-			TokenKind::StrictEqual => ast::javascript::BinaryOperator::StrictEqual,
-			TokenKind::StrictNotEqual => ast::javascript::BinaryOperator::StrictNotEqual,
-			TokenKind::BitAnd => ast::javascript::BinaryOperator::BitwiseAnd,
-			TokenKind::BitOr => ast::javascript::BinaryOperator::BitwiseOr,
-			TokenKind::BitXor => ast::javascript::BinaryOperator::BitwiseXor,
-			TokenKind::LogicalAnd => ast::javascript::BinaryOperator::LogicalAnd,
-			TokenKind::LogicalOr => ast::javascript::BinaryOperator::LogicalOr,
-			TokenKind::Instanceof => ast::javascript::BinaryOperator::InstanceOf,
-			TokenKind::In => ast::javascript::BinaryOperator::In,
-
-			_ => unreachable!(),
-		};
-
-		let right = self.parse_expression()?;
-
-		Ok(ast::javascript::BinaryExpression {
-			start: left.start(),
-			end: self.tokens.cursor(),
-			left: Box::new(left),
-			operator,
-			right: Box::new(right),
-		})
-	}
-
-	pub fn parse_assignment(
-		&mut self,
-		left: ast::javascript::Expression,
-	) -> Result<ast::javascript::AssignmentExpression, usize> {
-		let token = self.tokens.scan()?;
-
-		let operator: ast::javascript::AssignmentOperator = match token.kind {
-			TokenKind::Assign => ast::javascript::AssignmentOperator::Equal,
-			TokenKind::PlusAssign => ast::javascript::AssignmentOperator::PlusEqual,
-			TokenKind::MinusAssign => ast::javascript::AssignmentOperator::MinusEqual,
-			TokenKind::MultiplyAssign => ast::javascript::AssignmentOperator::MultiplyEqual,
-			TokenKind::ExponentiateAssign => ast::javascript::AssignmentOperator::ExponentEqual,
-			TokenKind::DivideAssign => ast::javascript::AssignmentOperator::DivideEqual,
-			TokenKind::ModuloAssign => ast::javascript::AssignmentOperator::ModuloEqual,
-			TokenKind::LeftShiftAssign => ast::javascript::AssignmentOperator::LeftShiftEqual,
-			TokenKind::RightShiftAssign => ast::javascript::AssignmentOperator::RightShiftEqual,
-			TokenKind::BitAndAssign => ast::javascript::AssignmentOperator::BitwiseAndEqual,
-			TokenKind::BitXorAssign => ast::javascript::AssignmentOperator::BitwiseXorEqual,
-			TokenKind::BitOrAssign => ast::javascript::AssignmentOperator::BitwiseOrEqual,
-			TokenKind::UnsignedRightShiftAssign => {
-				ast::javascript::AssignmentOperator::UnsignedRightShiftEqual
-			}
-
-			_ => unreachable!(),
+		let mut callee = if self.peek()?.0 == TokenKind::New {
+			self.parse_new()?.into()
+		} else {
+			self.parse_primary()?
 		};
 
-		let right = self.parse_expression()?;
+		// The arguments belong to the `new`, so the callee has no calls.
+		loop {
+			let (kind, start) = self.peek()?;
 
-		Ok(ast::javascript::AssignmentExpression {
-			start: left.start(),
-			end: self.tokens.cursor(),
-			left: Box::new(left),
-			operator,
-			right: Box::new(right),
-		})
-	}
+			callee = match kind {
+				TokenKind::Dot | TokenKind::OpenBracket => self.parse_member(callee)?.into(),
+				TokenKind::Template => self.parse_tagged_template(callee)?.into(),
+				TokenKind::OptionalChain => return Err(start),
+				_ => break,
+			}
+		}
 
-	pub fn parse_member_or_conditional(
-		&mut self,
-		left: ast::javascript::Expression,
-	) -> Result<ast::javascript::Expression, usize> {
-		if self.tokens.scan()?.kind != TokenKind::QuestionMark {
-			return Err(self.tokens.cursor());
+		// `new Foo` is `new Foo()`.
+		if self.peek()?.0 != TokenKind::OpenParen {
+			return Ok(ast::javascript::NewExpression {
+				start,
+				end: callee.end(),
+				callee: Box::new(callee),
+				arguments: Vec::new(),
+			});
 		}
 
-		let token_kind = self.tokens.scan()?.kind.clone();
-		self.tokens.unscan();
-		self.tokens.unscan();
+		let call = self.parse_call(callee)?;
 
-		Ok(match token_kind {
-			TokenKind::Dot => self.parse_member(left)?.into(),
-			_ => self.parse_conditional(left)?.into(),
+		Ok(ast::javascript::NewExpression {
+			start,
+			end: call.end,
+			callee: call.callee,
+			arguments: call.arguments,
 		})
 	}
 
-	pub fn parse_conditional(
+	pub fn parse_call(
 		&mut self,
-		left: ast::javascript::Expression,
-	) -> Result<ast::javascript::ConditionalExpression, usize> {
-		if self.tokens.scan()?.kind != TokenKind::QuestionMark {
-			return Err(self.tokens.cursor());
-		}
-
-		let consequent = self.parse_expression()?;
+		left: ast::javascript::Expression<'src>,
+	) -> Result<ast::javascript::CallExpression<'src>, usize> {
+		let mut optional = false;
 
-		if self.tokens.scan()?.kind != TokenKind::Colon {
-			return Err(self.tokens.cursor());
+		if self.tokens.scan()?.kind == TokenKind::OptionalChain {
+			optional = true;
+			self.tokens.scan()?;
 		}
 
-		let alternate = self.parse_expression()?;
-
-		Ok(ast::javascript::ConditionalExpression {
-			start: left.start(),
-			end: self.tokens.cursor(),
-			condition: Box::new(left),
-			consequent: Box::new(consequent),
-			alternate: Box::new(alternate),
-		})
-	}
-
-	pub fn parse_call(
-		&mut self,
-		left: ast::javascript::Expression,
-	) -> Result<ast::javascript::CallExpression, usize> {
-		if self.tokens.scan()?.kind != TokenKind::OpenParen {
+		if self.tokens.peek().kind != TokenKind::OpenParen {
 			// Expected open paren
 			return Err(self.tokens.cursor());
 		}
@@ -629,12 +1682,13 @@ impl<'a> JavascriptParser<'a> {
 		let mut arguments = Vec::new();
 
 		loop {
-			let start = self.tokens.cursor();
+			let token = self.tokens.scan()?;
+			let start = token.start;
 			let argument;
 
-			match self.tokens.scan()?.kind {
+			match token.kind {
 				TokenKind::Ellipsis => {
-					let expression = self.parse_expression()?;
+					let expression = self.parse_assignment()?;
 
 					argument =
 						ast::javascript::Expression::Spread(ast::javascript::SpreadExpression {
@@ -648,7 +1702,7 @@ impl<'a> JavascriptParser<'a> {
 
 				_ => {
 					self.tokens.unscan();
-					argument = self.parse_expression()?
+					argument = self.parse_assignment()?
 				}
 			}
 
@@ -671,30 +1725,26 @@ impl<'a> JavascriptParser<'a> {
 			end: self.tokens.cursor(),
 			arguments,
 			callee: Box::new(left),
+			optional,
 		})
 	}
 
 	pub fn parse_member(
 		&mut self,
-		left: ast::javascript::Expression,
-	) -> Result<ast::javascript::MemberExpression, usize> {
+		left: ast::javascript::Expression<'src>,
+	) -> Result<ast::javascript::MemberExpression<'src>, usize> {
 		let mut computed = false;
 		let mut optional = false;
 		let property;
 
 		match self.tokens.scan()?.kind {
 			TokenKind::Dot => {
-				property = self.parse_identifier()?.into();
+				property = self.parse_property_name()?.into();
 			}
 
-			TokenKind::QuestionMark => {
+			TokenKind::OptionalChain => {
 				optional = true;
 
-				if self.tokens.scan()?.kind != TokenKind::Dot {
-					// Expected dot
-					return Err(self.tokens.cursor());
-				}
-
 				if self.tokens.scan()?.kind == TokenKind::OpenBracket {
 					computed = true;
 					property = self.parse_expression()?;
@@ -705,7 +1755,7 @@ impl<'a> JavascriptParser<'a> {
 					}
 				} else {
 					self.tokens.unscan();
-					property = self.parse_identifier()?.into();
+					property = self.parse_property_name()?.into();
 				}
 			}
 
@@ -733,11 +1783,279 @@ impl<'a> JavascriptParser<'a> {
 		})
 	}
 
+	/// Parses a block of statements, the body of an arrow function like `() => { a(); }`.
+	pub fn parse_block(&mut self) -> Result<ast::javascript::BlockStatement<'src>, usize> {
+		let token = self.tokens.scan()?;
+		let start = token.start;
+		if token.kind != TokenKind::OpenBrace {
+			return Err(token.start);
+		}
+
+		let mut body = Vec::new();
+
+		loop {
+			match self.peek()?.0 {
+				TokenKind::CloseBrace => break,
+				TokenKind::EOF => return Err(self.tokens.cursor()),
+				_ => body.push(self.parse_statement()?),
+			}
+		}
+
+		let end = self.tokens.scan()?.end;
+		Ok(ast::javascript::BlockStatement { start, end, body })
+	}
+
+	pub fn parse_statement(&mut self) -> Result<ast::javascript::Statement<'src>, usize> {
+		// Keywords of statements outside of the supported subset are lexed as identifiers, so they
+		// are rejected here instead of being parsed as expressions.
+		if let Some(
+			name @ ("for" | "while" | "do" | "switch" | "break" | "continue" | "function" | "class"
+			| "with" | "debugger"),
+		) = self.peek_identifier()?
+		{
+			return Err(self.unsupported(name, "statement")?);
+		}
+
+		let (kind, start) = self.peek()?;
+
+		Ok(match kind {
+			TokenKind::OpenBrace => self.parse_block()?.into(),
+			TokenKind::Let | TokenKind::Const | TokenKind::Var => {
+				self.parse_variable_declaration()?.into()
+			}
+			TokenKind::If => self.parse_if()?.into(),
+			TokenKind::Return => self.parse_return()?.into(),
+			TokenKind::Throw => self.parse_throw()?.into(),
+			TokenKind::Try => self.parse_try()?.into(),
+
+			TokenKind::Semicolon => {
+				let end = self.tokens.scan()?.end;
+				ast::javascript::EmptyStatement { start, end }.into()
+			}
+
+			_ => {
+				let expression = self.parse_expression()?;
+				self.parse_semicolon()?;
+
+				ast::javascript::ExpressionStatement {
+					start,
+					end: self.tokens.cursor(),
+					expression,
+				}
+				.into()
+			}
+		})
+	}
+
+	/// Ends a statement at a `;`, or where one is inserted automatically: before a `}` or after a
+	/// line break.
+	fn parse_semicolon(&mut self) -> Result<(), usize> {
+		let end = self.tokens.cursor();
+		let (kind, start) = self.peek()?;
+
+		match kind {
+			TokenKind::Semicolon => {
+				self.tokens.scan()?;
+			}
+			TokenKind::CloseBrace | TokenKind::EOF => {}
+			_ if self.tokens.scanner().slice(end, start).contains('\n') => {}
+			_ => return Err(start),
+		}
+
+		Ok(())
+	}
+
+	pub fn parse_variable_declaration(
+		&mut self,
+	) -> Result<ast::javascript::VariableDeclaration<'src>, usize> {
+		let token = self.tokens.scan()?;
+		let start = token.start;
+
+		let kind = match token.kind {
+			TokenKind::Let => ast::javascript::VariableKind::Let,
+			TokenKind::Const => ast::javascript::VariableKind::Const,
+			TokenKind::Var => ast::javascript::VariableKind::Var,
+			_ => return Err(start),
+		};
+
+		let mut declarations = Vec::new();
+
+		loop {
+			let id = self.parse_binding_target()?;
+			let id = self.parse_type_annotation(id, false)?;
+
+			let init = if self.peek()?.0 == TokenKind::Assign {
+				self.tokens.scan()?;
+				Some(self.parse_assignment()?)
+			} else {
+				None
+			};
+
+			// Constants and patterns must be initialized.
+			let identifier = match &id {
+				ast::javascript::Expression::TsAnnotated(id) => {
+					matches!(*id.expression, ast::javascript::Expression::Identifier(_))
+				}
+				id => matches!(id, ast::javascript::Expression::Identifier(_)),
+			};
+			if init.is_none() && (kind == ast::javascript::VariableKind::Const || !identifier) {
+				return Err(self.tokens.cursor());
+			}
+
+			declarations.push(ast::javascript::VariableDeclarator {
+				start: id.start(),
+				end: self.tokens.cursor(),
+				id,
+				init,
+			});
+
+			if self.peek()?.0 != TokenKind::Comma {
+				break;
+			}
+
+			self.tokens.scan()?;
+		}
+
+		self.parse_semicolon()?;
+
+		Ok(ast::javascript::VariableDeclaration {
+			start,
+			end: self.tokens.cursor(),
+			kind,
+			declarations,
+		})
+	}
+
+	pub fn parse_if(&mut self) -> Result<ast::javascript::IfStatement<'src>, usize> {
+		let start = self.tokens.scan()?.start;
+
+		if self.tokens.scan()?.kind != TokenKind::OpenParen {
+			return Err(self.tokens.cursor());
+		}
+
+		let condition = self.parse_expression()?;
+
+		if self.tokens.scan()?.kind != TokenKind::CloseParen {
+			return Err(self.tokens.cursor());
+		}
+
+		let consequent = self.parse_statement()?;
+
+		let alternate = if self.peek()?.0 == TokenKind::Else {
+			self.tokens.scan()?;
+			Some(Box::new(self.parse_statement()?))
+		} else {
+			None
+		};
+
+		Ok(ast::javascript::IfStatement {
+			start,
+			end: self.tokens.cursor(),
+			condition,
+			consequent: Box::new(consequent),
+			alternate,
+		})
+	}
+
+	pub fn parse_return(&mut self) -> Result<ast::javascript::ReturnStatement<'src>, usize> {
+		let token = self.tokens.scan()?;
+		let (start, end) = (token.start, token.end);
+		let (kind, next) = self.peek()?;
+
+		// A line break after `return` ends the statement.
+		let argument = match kind {
+			TokenKind::Semicolon | TokenKind::CloseBrace | TokenKind::EOF => None,
+			_ if self.tokens.scanner().slice(end, next).contains('\n') => None,
+			_ => Some(self.parse_expression()?),
+		};
+
+		self.parse_semicolon()?;
+
+		Ok(ast::javascript::ReturnStatement {
+			start,
+			end: self.tokens.cursor(),
+			argument,
+		})
+	}
+
+	pub fn parse_throw(&mut self) -> Result<ast::javascript::ThrowStatement<'src>, usize> {
+		let token = self.tokens.scan()?;
+		let (start, end) = (token.start, token.end);
+		let next = self.peek()?.1;
+
+		// Unlike in `return`, the value cannot be on the next line.
+		if self.tokens.scanner().slice(end, next).contains('\n') {
+			return Err(next);
+		}
+
+		let argument = self.parse_expression()?;
+		self.parse_semicolon()?;
+
+		Ok(ast::javascript::ThrowStatement {
+			start,
+			end: self.tokens.cursor(),
+			argument,
+		})
+	}
+
+	pub fn parse_try(&mut self) -> Result<ast::javascript::TryStatement<'src>, usize> {
+		let start = self.tokens.scan()?.start;
+		let block = self.parse_block()?;
+
+		let handler = if self.peek()?.0 == TokenKind::Catch {
+			let start = self.tokens.scan()?.start;
+
+			// The parameter is optional, as in `catch { ... }`.
+			let parameter = if self.peek()?.0 == TokenKind::OpenParen {
+				self.tokens.scan()?;
+				let parameter = self.parse_binding_target()?;
+
+				if self.tokens.scan()?.kind != TokenKind::CloseParen {
+					return Err(self.tokens.cursor());
+				}
+
+				Some(parameter)
+			} else {
+				None
+			};
+
+			let body = self.parse_block()?;
+
+			Some(ast::javascript::CatchClause {
+				start,
+				end: body.end,
+				parameter,
+				body,
+			})
+		} else {
+			None
+		};
+
+		let finalizer = if self.peek()?.0 == TokenKind::Finally {
+			self.tokens.scan()?;
+			Some(self.parse_block()?)
+		} else {
+			None
+		};
+
+		if handler.is_none() && finalizer.is_none() {
+			return Err(self.tokens.cursor());
+		}
+
+		Ok(ast::javascript::TryStatement {
+			start,
+			end: self.tokens.cursor(),
+			block,
+			handler,
+			finalizer,
+		})
+	}
+
 	pub fn parse_tagged_template(
 		&mut self,
-		left: ast::javascript::Expression,
-	) -> Result<ast::javascript::TaggedTemplateExpression, usize> {
-		let quasi = self.parse_template()?;
+		left: ast::javascript::Expression<'src>,
+	) -> Result<ast::javascript::TaggedTemplateExpression<'src>, usize> {
+		let quasi = self.parse_template(true)?;
 
 		Ok(ast::javascript::TaggedTemplateExpression {
 			start: left.start(),
@@ -747,3 +2065,183 @@ impl<'a> JavascriptParser<'a> {
 		})
 	}
 }
+
+/// Returns the operator of a binary token, with its precedence. Operators of a higher
+/// precedence bind tighter.
+fn binary_operator(kind: &TokenKind) -> Option<(ast::javascript::BinaryOperator, u8)> {
+	Some(match kind {
+		TokenKind::LogicalOr => (ast::javascript::BinaryOperator::LogicalOr, 1),
+		TokenKind::NullishCoalescing => (ast::javascript::BinaryOperator::NullishCoalescing, 1),
+		TokenKind::LogicalAnd => (ast::javascript::BinaryOperator::LogicalAnd, 2),
+		TokenKind::BitOr => (ast::javascript::BinaryOperator::BitwiseOr, 3),
+		TokenKind::BitXor => (ast::javascript::BinaryOperator::BitwiseXor, 4),
+		TokenKind::BitAnd => (ast::javascript::BinaryOperator::BitwiseAnd, 5),
+		TokenKind::Equal => (ast::javascript::BinaryOperator::Equal, 6),
+		TokenKind::NotEqual => (ast::javascript::BinaryOperator::NotEqual, 6),
+		TokenKind::StrictEqual => (ast::javascript::BinaryOperator::StrictEqual, 6),
+		TokenKind::StrictNotEqual => (ast::javascript::BinaryOperator::StrictNotEqual, 6),
+		TokenKind::LessThan => (ast::javascript::BinaryOperator::LessThan, 7),
+		TokenKind::GreaterThan => (ast::javascript::BinaryOperator::GreaterThan, 7),
+		TokenKind::LessThanEqual => (ast::javascript::BinaryOperator::LessThanOrEqual, 7),
+		TokenKind::GreaterThanEqual => (ast::javascript::BinaryOperator::GreaterThanOrEqual, 7),
+		TokenKind::Instanceof => (ast::javascript::BinaryOperator::InstanceOf, 7),
+		TokenKind::In => (ast::javascript::BinaryOperator::In, 7),
+		TokenKind::LeftShift => (ast::javascript::BinaryOperator::LeftShift, 8),
+		TokenKind::RightShift => (ast::javascript::BinaryOperator::RightShift, 8),
+		TokenKind::UnsignedRightShift => (ast::javascript::BinaryOperator::UnsignedRightShift, 8),
+		TokenKind::Plus => (ast::javascript::BinaryOperator::Plus, 9),
+		TokenKind::Minus => (ast::javascript::BinaryOperator::Minus, 9),
+		TokenKind::Multiply => (ast::javascript::BinaryOperator::Multiply, 10),
+		TokenKind::Divide => (ast::javascript::BinaryOperator::Divide, 10),
+		TokenKind::Modulo => (ast::javascript::BinaryOperator::Modulo, 10),
+		TokenKind::Exponentiate => (ast::javascript::BinaryOperator::Exponent, 11),
+
+		_ => return None,
+	})
+}
+
+fn assignment_operator(kind: &TokenKind) -> Option<ast::javascript::AssignmentOperator> {
+	Some(match kind {
+		TokenKind::Assign => ast::javascript::AssignmentOperator::Equal,
+		TokenKind::PlusAssign => ast::javascript::AssignmentOperator::PlusEqual,
+		TokenKind::MinusAssign => ast::javascript::AssignmentOperator::MinusEqual,
+		TokenKind::MultiplyAssign => ast::javascript::AssignmentOperator::MultiplyEqual,
+		TokenKind::ExponentiateAssign => ast::javascript::AssignmentOperator::ExponentEqual,
+		TokenKind::DivideAssign => ast::javascript::AssignmentOperator::DivideEqual,
+		TokenKind::ModuloAssign => ast::javascript::AssignmentOperator::ModuloEqual,
+		TokenKind::LeftShiftAssign => ast::javascript::AssignmentOperator::LeftShiftEqual,
+		TokenKind::RightShiftAssign => ast::javascript::AssignmentOperator::RightShiftEqual,
+		TokenKind::UnsignedRightShiftAssign => {
+			ast::javascript::AssignmentOperator::UnsignedRightShiftEqual
+		}
+		TokenKind::BitAndAssign => ast::javascript::AssignmentOperator::BitwiseAndEqual,
+		TokenKind::BitXorAssign => ast::javascript::AssignmentOperator::BitwiseXorEqual,
+		TokenKind::BitOrAssign => ast::javascript::AssignmentOperator::BitwiseOrEqual,
+		TokenKind::LogicalAndAssign => ast::javascript::AssignmentOperator::LogicalAndEqual,
+		TokenKind::LogicalOrAssign => ast::javascript::AssignmentOperator::LogicalOrEqual,
+		TokenKind::NullishAssign => ast::javascript::AssignmentOperator::NullishEqual,
+
+		_ => return None,
+	})
+}
+
+/// Whether the token can be the name of a property, like `a.new`.
+fn is_identifier_name(kind: &TokenKind) -> bool {
+	matches!(
+		kind,
+		TokenKind::Identifier
+			| TokenKind::True
+			| TokenKind::False
+			| TokenKind::Null
+			| TokenKind::Await
+			| TokenKind::Catch
+			| TokenKind::Const
+			| TokenKind::Delete
+			| TokenKind::Else
+			| TokenKind::Finally
+			| TokenKind::If
+			| TokenKind::In
+			| TokenKind::Instanceof
+			| TokenKind::Let
+			| TokenKind::New
+			| TokenKind::Return
+			| TokenKind::This
+			| TokenKind::Throw
+			| TokenKind::Try
+			| TokenKind::Typeof
+			| TokenKind::Var
+			| TokenKind::Void
+	)
+}
+
+fn is_update(operator: &ast::javascript::UnaryOperator) -> bool {
+	matches!(
+		operator,
+		ast::javascript::UnaryOperator::Increment | ast::javascript::UnaryOperator::Decrement
+	)
+}
+
+/// Whether the expression is a unary expression other than `++a` and `--a`, or `await`.
+fn is_unary(expression: &ast::javascript::Expression) -> bool {
+	match expression {
+		ast::javascript::Expression::Unary(expression) => {
+			expression.prefix && !is_update(&expression.operator)
+		}
+		ast::javascript::Expression::Await(_) => true,
+		_ => false,
+	}
+}
+
+/// Whether the expression can be assigned to. With `destructuring`, objects and arrays are
+/// patterns which can be assigned to as well.
+fn is_assignment_target(expression: &ast::javascript::Expression, destructuring: bool) -> bool {
+	match expression {
+		ast::javascript::Expression::Identifier(_) => true,
+		ast::javascript::Expression::Member(_) => !is_optional_chain(expression),
+		ast::javascript::Expression::Parenthesized(expression) => {
+			is_assignment_target(&expression.expression, false)
+		}
+		ast::javascript::Expression::Object(_) | ast::javascript::Expression::Array(_) => {
+			destructuring
+		}
+		ast::javascript::Expression::TsAs(expression) => {
+			is_assignment_target(&expression.expression, false)
+		}
+		ast::javascript::Expression::TsSatisfies(expression) => {
+			is_assignment_target(&expression.expression, false)
+		}
+		ast::javascript::Expression::TsNonNull(expression) => {
+			is_assignment_target(&expression.expression, false)
+		}
+		_ => false,
+	}
+}
+
+/// Whether the token starts with `>`, like `>>` or `>=`.
+fn is_greater_than(kind: &TokenKind) -> bool {
+	matches!(
+		kind,
+		TokenKind::GreaterThan
+			| TokenKind::GreaterThanEqual
+			| TokenKind::RightShift
+			| TokenKind::UnsignedRightShift
+			| TokenKind::RightShiftAssign
+			| TokenKind::UnsignedRightShiftAssign
+	)
+}
+
+fn is_optional_chain(expression: &ast::javascript::Expression) -> bool {
+	match expression {
+		ast::javascript::Expression::Member(expression) => {
+			expression.optional || is_optional_chain(&expression.object)
+		}
+		ast::javascript::Expression::Call(expression) => {
+			expression.optional || is_optional_chain(&expression.callee)
+		}
+		_ => false,
+	}
+}
+
+/// Whether `operator` is `??` and the operand is `&&` or `||`, or the other way around.
+fn mixes_nullish(
+	operator: &ast::javascript::BinaryOperator,
+	operand: &ast::javascript::Expression,
+) -> bool {
+	let operand = match operand {
+		ast::javascript::Expression::Binary(operand) => &operand.operator,
+		_ => return false,
+	};
+
+	let is_nullish = |operator: &ast::javascript::BinaryOperator| {
+		matches!(operator, ast::javascript::BinaryOperator::NullishCoalescing)
+	};
+	let is_logical = |operator: &ast::javascript::BinaryOperator| {
+		matches!(
+			operator,
+			ast::javascript::BinaryOperator::LogicalAnd
+				| ast::javascript::BinaryOperator::LogicalOr
+		)
+	};
+
+	(is_nullish(operator) && is_logical(operand)) || (is_logical(operator) && is_nullish(operand))
+}
@@ -0,0 +1,265 @@
+//! Runs the expressions in `conformance.txt`, and `conformance_typescript.txt` as TypeScript,
+//! which are printed with every operation parenthesized to show how they were grouped.
+
+use crate::*;
+use ast::javascript as ast;
+
+fn print(expr: &ast::Expression) -> String {
+	match expr {
+		ast::Expression::Identifier(expr) => expr.name.to_string(),
+		ast::Expression::Literal(expr) => expr.raw().to_owned(),
+		ast::Expression::This(_) => "this".to_owned(),
+		ast::Expression::Unary(expr) if expr.prefix => {
+			let operator = expr.operator.to_string();
+
+			if operator.chars().all(char::is_alphabetic) {
+				format!("({} {})", operator, print(&expr.operand))
+			} else {
+				format!("({}{})", operator, print(&expr.operand))
+			}
+		}
+		ast::Expression::Unary(expr) => {
+			format!("({}{})", print(&expr.operand), expr.operator.to_string())
+		}
+		ast::Expression::Binary(expr) => format!(
+			"({} {} {})",
+			print(&expr.left),
+			expr.operator.to_string(),
+			print(&expr.right)
+		),
+		ast::Expression::Assignment(expr) => format!(
+			"({} {} {})",
+			print(&expr.left),
+			expr.operator.to_string(),
+			print(&expr.right)
+		),
+		ast::Expression::Conditional(expr) => format!(
+			"({} ? {} : {})",
+			print(&expr.condition),
+			print(&expr.consequent),
+			print(&expr.alternate)
+		),
+		ast::Expression::Sequence(expr) => format!("({})", list(&expr.expressions)),
+		ast::Expression::Call(expr) => format!(
+			"{}{}({})",
+			print(&expr.callee),
+			if expr.optional { "?." } else { "" },
+			list(&expr.arguments)
+		),
+		ast::Expression::New(expr) => {
+			format!("(new {}({}))", print(&expr.callee), list(&expr.arguments))
+		}
+		ast::Expression::Member(expr) => {
+			let optional = if expr.optional { "?." } else { "" };
+
+			if expr.computed {
+				format!(
+					"{}{}[{}]",
+					print(&expr.object),
+					optional,
+					print(&expr.property)
+				)
+			} else if expr.optional {
+				format!("{}?.{}", print(&expr.object), print(&expr.property))
+			} else {
+				format!("{}.{}", print(&expr.object), print(&expr.property))
+			}
+		}
+		ast::Expression::Await(expr) => format!("(await {})", print(&expr.argument)),
+		ast::Expression::Function(expr) => {
+			let body = match &expr.body {
+				ast::FunctionBody::Expression(body) => print(body),
+				ast::FunctionBody::Block(body) => block(body),
+			};
+
+			let type_parameters = expr.type_parameters.as_ref().map_or("", |ty| &ty.raw);
+			let return_type = match &expr.return_type {
+				Some(ty) => format!(": {}", ty.raw),
+				None => String::new(),
+			};
+
+			format!(
+				"({}{}({}){} => {})",
+				if expr.is_async { "async " } else { "" },
+				type_parameters,
+				list(&expr.parameters),
+				return_type,
+				body
+			)
+		}
+		ast::Expression::Spread(expr) => format!("...{}", print(&expr.argument)),
+		ast::Expression::Template(expr) => template(expr),
+		ast::Expression::TaggedTemplate(expr) => {
+			format!("{}{}", print(&expr.tag), template(&expr.quasi))
+		}
+		ast::Expression::Object(expr) => {
+			let properties: Vec<_> = expr
+				.properties
+				.iter()
+				.map(|property| match property {
+					ast::ObjectProperty::Keyed(property) => match &property.value {
+						Some(value) => format!("{}: {}", property.key.name, print(value)),
+						None => property.key.name.to_string(),
+					},
+					ast::ObjectProperty::Computed(property) => {
+						format!("[{}]: {}", print(&property.key), print(&property.value))
+					}
+					ast::ObjectProperty::Spread(property) => {
+						format!("...{}", print(&property.argument))
+					}
+				})
+				.collect();
+
+			format!("{{{}}}", properties.join(", "))
+		}
+		ast::Expression::Array(expr) => format!("[{}]", list(&expr.elements)),
+		ast::Expression::Parenthesized(expr) => format!("({})", print(&expr.expression)),
+		ast::Expression::Empty(_) => String::new(),
+		ast::Expression::TsAs(expr) => {
+			format!(
+				"({} as {})",
+				print(&expr.expression),
+				expr.type_annotation.raw
+			)
+		}
+		ast::Expression::TsSatisfies(expr) => format!(
+			"({} satisfies {})",
+			print(&expr.expression),
+			expr.type_annotation.raw
+		),
+		ast::Expression::TsNonNull(expr) => format!("({}!)", print(&expr.expression)),
+		ast::Expression::TsAnnotated(expr) => format!(
+			"{}{}{}",
+			print(&expr.expression),
+			if expr.optional { "?" } else { "" },
+			match &expr.type_annotation {
+				Some(ty) => format!(": {}", ty.raw),
+				None => String::new(),
+			}
+		),
+	}
+}
+
+fn list(exprs: &[ast::Expression]) -> String {
+	exprs.iter().map(print).collect::<Vec<_>>().join(", ")
+}
+
+fn block(stmt: &ast::BlockStatement) -> String {
+	let body: Vec<_> = stmt.body.iter().map(statement).collect();
+	format!("{{ {} }}", body.join(" "))
+}
+
+fn statement(stmt: &ast::Statement) -> String {
+	match stmt {
+		ast::Statement::Block(stmt) => block(stmt),
+		ast::Statement::Expression(stmt) => format!("{};", print(&stmt.expression)),
+		ast::Statement::Variable(stmt) => {
+			let declarations: Vec<_> = stmt
+				.declarations
+				.iter()
+				.map(|declarator| match &declarator.init {
+					Some(init) => format!("{} = {}", print(&declarator.id), print(init)),
+					None => print(&declarator.id),
+				})
+				.collect();
+
+			format!("{} {};", stmt.kind, declarations.join(", "))
+		}
+		ast::Statement::Return(stmt) => match &stmt.argument {
+			Some(argument) => format!("return {};", print(argument)),
+			None => "return;".to_owned(),
+		},
+		ast::Statement::If(stmt) => match &stmt.alternate {
+			Some(alternate) => format!(
+				"if ({}) {} else {}",
+				print(&stmt.condition),
+				statement(&stmt.consequent),
+				statement(alternate)
+			),
+			None => format!(
+				"if ({}) {}",
+				print(&stmt.condition),
+				statement(&stmt.consequent)
+			),
+		},
+		ast::Statement::Throw(stmt) => format!("throw {};", print(&stmt.argument)),
+		ast::Statement::Try(stmt) => {
+			let mut out = format!("try {}", block(&stmt.block));
+
+			if let Some(handler) = &stmt.handler {
+				match &handler.parameter {
+					Some(parameter) => out.push_str(&format!(" catch ({})", print(parameter))),
+					None => out.push_str(" catch"),
+				}
+
+				out.push_str(&format!(" {}", block(&handler.body)));
+			}
+
+			if let Some(finalizer) = &stmt.finalizer {
+				out.push_str(&format!(" finally {}", block(finalizer)));
+			}
+
+			out
+		}
+		ast::Statement::Empty(_) => ";".to_owned(),
+	}
+}
+
+fn template(expr: &ast::TemplateLiteral) -> String {
+	let mut out = String::from("`");
+
+	for (i, quasi) in expr.quasis.iter().enumerate() {
+		out.push_str(&quasi.raw);
+
+		if let Some(expr) = expr.expressions.get(i) {
+			out.push_str(&format!("${{{}}}", print(expr)));
+		}
+	}
+
+	out.push('`');
+	out
+}
+
+/// Parses every line of the corpus, as TypeScript if `typescript` is set.
+fn run(corpus: &str, typescript: bool) {
+	let mut failures = Vec::new();
+
+	for (i, line) in corpus.lines().enumerate() {
+		let line = line.trim();
+		if line.is_empty() || line.starts_with('#') {
+			continue;
+		}
+
+		let (input, expected) = line.split_once(" ==> ").unwrap();
+
+		let mut parser = Parser::new(input);
+		parser.set_typescript(typescript);
+
+		let actual = match parser.parse_javascript() {
+			Ok(expr) if parser.scanner.is_done() => print(&expr),
+			_ => "!".to_owned(),
+		};
+
+		if actual != expected {
+			failures.push(format!(
+				"line {}: {}\n  expected: {}\n    actual: {}",
+				i + 1,
+				input,
+				expected,
+				actual
+			));
+		}
+	}
+
+	assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn test_conformance() {
+	run(include_str!("conformance.txt"), false);
+}
+
+#[test]
+fn test_conformance_typescript() {
+	run(include_str!("conformance_typescript.txt"), true);
+}
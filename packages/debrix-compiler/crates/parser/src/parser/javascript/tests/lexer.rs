@@ -16,6 +16,52 @@ fn test_scan_identifier() {
 	assert_eq!(token.start, 0);
 }
 
+#[test]
+fn test_scan_number_literal_forms() {
+	for input in [
+		"0", "1_000", "0.5", ".5", "1.", "1e3", "1E-3", "2.5e+10", "0xff", "0XF_F", "0o17",
+		"0b1010",
+	] {
+		assert_eq!(tokenize(input).kind, TokenKind::Numeric, "{}", input);
+	}
+}
+
+#[test]
+fn test_scan_invalid_number_literal() {
+	for input in [
+		"017", "1_", "1__0", "0x", "1e", "1.5n", "1e3n", "3in", "0b2",
+	] {
+		let mut scanner = Scanner::new(input);
+		let result = Lexer::new(&mut scanner).scan();
+		assert!(
+			result.is_err() || !scanner.is_done(),
+			"{} should not be a number",
+			input
+		);
+	}
+}
+
+#[test]
+fn test_scan_bigint_literal() {
+	assert_eq!(tokenize("10n").kind, TokenKind::BigInt);
+	assert_eq!(tokenize("0xffn").kind, TokenKind::BigInt);
+	assert_eq!(tokenize("1_000n").kind, TokenKind::BigInt);
+}
+
+#[test]
+fn test_scan_regex_literal() {
+	let mut scanner = Scanner::new("/[/]\\/+/gi");
+	let token = Lexer::new(&mut scanner).scan_regex().unwrap();
+	assert_eq!(token.kind, TokenKind::RegExp);
+	assert_eq!(token.end, 10);
+	assert!(scanner.is_done());
+
+	for input in ["/a", "/a\n/", "/a/gg", "/a/x"] {
+		let mut scanner = Scanner::new(input);
+		assert!(Lexer::new(&mut scanner).scan_regex().is_err(), "{}", input);
+	}
+}
+
 #[test]
 fn test_scan_string_literal() {
 	let token = tokenize("\"abc\"");
@@ -44,6 +90,19 @@ fn test_scan_template_literal() {
 	assert_eq!(token.start, 0);
 }
 
+#[test]
+fn test_scan_template_literal_with_substitutions() {
+	let token = tokenize("`a ${ { b: `}` } } ${'`'} \\${c}`");
+	assert_eq!(token.kind, TokenKind::Template);
+	assert_eq!(token.start, 0);
+}
+
+#[test]
+fn test_scan_unterminated_template_literal() {
+	let mut scanner = Scanner::new("`a ${b`");
+	assert!(Lexer::new(&mut scanner).scan().is_err());
+}
+
 #[test]
 fn test_scan_keyword_true() {
 	assert_eq!(tokenize("true").kind, TokenKind::True)
@@ -229,11 +288,40 @@ fn test_scan_logical_or() {
 	assert_eq!(tokenize("||").kind, TokenKind::LogicalOr);
 }
 
+#[test]
+fn test_scan_nullish_coalescing() {
+	assert_eq!(tokenize("??").kind, TokenKind::NullishCoalescing);
+}
+
 #[test]
 fn test_scan_question_mark() {
 	assert_eq!(tokenize("?").kind, TokenKind::QuestionMark);
 }
 
+#[test]
+fn test_scan_optional_chain() {
+	assert_eq!(tokenize("?.").kind, TokenKind::OptionalChain);
+
+	// A conditional with a number.
+	let mut scanner = Scanner::new("?.5");
+	assert_eq!(
+		Lexer::new(&mut scanner).scan().unwrap().kind,
+		TokenKind::QuestionMark
+	);
+}
+
+#[test]
+fn test_scan_string_literal_escapes() {
+	assert_eq!(tokenize("'it\\'s'").end, 7);
+	assert_eq!(tokenize("'a\\\r\nb'").end, 7);
+
+	let mut scanner = Scanner::new("'a\nb'");
+	assert!(Lexer::new(&mut scanner).scan().is_err());
+
+	let mut scanner = Scanner::new("'a");
+	assert!(Lexer::new(&mut scanner).scan().is_err());
+}
+
 #[test]
 fn test_scan_colon() {
 	assert_eq!(tokenize(":").kind, TokenKind::Colon);
@@ -304,6 +392,21 @@ fn test_scan_bit_or_assign() {
 	assert_eq!(tokenize("|=").kind, TokenKind::BitOrAssign);
 }
 
+#[test]
+fn test_scan_logical_and_assign() {
+	assert_eq!(tokenize("&&=").kind, TokenKind::LogicalAndAssign);
+}
+
+#[test]
+fn test_scan_logical_or_assign() {
+	assert_eq!(tokenize("||=").kind, TokenKind::LogicalOrAssign);
+}
+
+#[test]
+fn test_scan_nullish_assign() {
+	assert_eq!(tokenize("??=").kind, TokenKind::NullishAssign);
+}
+
 #[test]
 fn test_scan_comma() {
 	assert_eq!(tokenize(",").kind, TokenKind::Comma);
@@ -373,3 +476,15 @@ fn test_skip_comment() {
 fn test_skip_multiline_comment() {
 	assert_eq!(tokenize("/*\nfoo\n*/bar").kind, TokenKind::Identifier);
 }
+
+#[test]
+fn test_scan_divide_after_whitespace() {
+	assert_eq!(tokenize(" /").kind, TokenKind::Divide);
+	assert_eq!(tokenize(" /=").kind, TokenKind::DivideAssign);
+}
+
+#[test]
+fn test_scan_eof() {
+	assert_eq!(tokenize("").kind, TokenKind::EOF);
+	assert_eq!(tokenize(" // foo").kind, TokenKind::EOF);
+}
@@ -1,5 +1,7 @@
 //
 
+mod conformance;
+
 mod lexer;
 
 mod parser;
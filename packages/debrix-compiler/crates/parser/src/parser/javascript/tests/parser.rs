@@ -1,8 +1,16 @@
 use crate::*;
 use ast::javascript as ast;
 
-fn parse(input: &str) -> ast::Expression {
-	let mut parser = Parser::new(input.to_owned());
+fn parse(input: &str) -> ast::Expression<'_> {
+	let mut parser = Parser::new(input);
+	let expression = parser.parse_javascript().unwrap();
+	assert!(parser.scanner.is_done());
+	expression
+}
+
+fn parse_typescript(input: &str) -> ast::Expression<'_> {
+	let mut parser = Parser::new(input);
+	parser.set_typescript(true);
 	let expression = parser.parse_javascript().unwrap();
 	assert!(parser.scanner.is_done());
 	expression
@@ -347,15 +355,173 @@ fn test_parse_function() {
 				_ => panic!("Expected Identifier"),
 			}
 
-			match *expr.body {
-				ast::Expression::Identifier(expr) => {
-					assert_eq!(expr.name, "bar");
+			match expr.body {
+				ast::FunctionBody::Expression(body) => match *body {
+					ast::Expression::Identifier(expr) => {
+						assert_eq!(expr.name, "bar");
+					}
+					_ => panic!("Expected Identifier"),
+				},
+				_ => panic!("Expected expression body"),
+			}
+		}
+		_ => panic!("Expected FunctionExpression"),
+	}
+}
+
+#[test]
+fn test_parse_async_function() {
+	match parse("async (e) => { await save(e); close(); }") {
+		ast::Expression::Function(expr) => {
+			assert!(expr.is_async);
+			assert_eq!((expr.start, expr.end), (0, 40));
+
+			match expr.body {
+				ast::FunctionBody::Block(block) => {
+					assert_eq!((block.start, block.end), (13, 40));
+					assert_eq!(block.body.len(), 2);
+
+					match &block.body[0] {
+						ast::Statement::Expression(stmt) => {
+							assert_eq!((stmt.start, stmt.end), (15, 29));
+							assert!(matches!(stmt.expression, ast::Expression::Await(_)));
+						}
+						_ => panic!("Expected ExpressionStatement"),
+					}
 				}
-				_ => panic!("Expected Identifier"),
+				_ => panic!("Expected block body"),
 			}
 		}
 		_ => panic!("Expected FunctionExpression"),
 	}
+
+	// `async` is only a keyword before the parameters.
+	assert!(matches!(parse("async(a)"), ast::Expression::Call(_)));
+	assert!(matches!(parse("async => async"), ast::Expression::Function(expr) if !expr.is_async));
+	assert!(matches!(parse("async a => a"), ast::Expression::Function(expr) if expr.is_async));
+}
+
+#[test]
+fn test_parse_await_outside_async() {
+	let mut parser = Parser::new("(a) => await a");
+	assert_eq!(parser.parse_javascript().unwrap_err().position, 7);
+
+	// Nested functions do not inherit `async`.
+	let mut parser = Parser::new("async () => () => await a");
+	assert_eq!(parser.parse_javascript().unwrap_err().position, 18);
+}
+
+#[test]
+fn test_parse_function_parameters() {
+	match parse("({ id, a: [b, , c = 1], ...rest } = {}, d = 2, ...e) => id") {
+		ast::Expression::Function(expr) => {
+			assert_eq!(expr.parameters.len(), 3);
+
+			match &expr.parameters[0] {
+				ast::Expression::Assignment(param) => match &*param.left {
+					ast::Expression::Object(pattern) => {
+						assert_eq!(pattern.properties.len(), 3);
+						assert!(matches!(
+							pattern.properties[2],
+							ast::ObjectProperty::Spread(_)
+						));
+					}
+					_ => panic!("Expected ObjectExpression"),
+				},
+				_ => panic!("Expected AssignmentExpression"),
+			}
+
+			assert!(matches!(expr.parameters[1], ast::Expression::Assignment(_)));
+			assert!(matches!(expr.parameters[2], ast::Expression::Spread(_)));
+		}
+		_ => panic!("Expected FunctionExpression"),
+	}
+
+	// The rest parameter is the last one, and cannot have a default.
+	assert!(Parser::new("(...a, b) => a").parse_javascript().is_err());
+	assert!(Parser::new("(...a = 1) => a").parse_javascript().is_err());
+	assert!(Parser::new("({ a: 1 }) => a").parse_javascript().is_err());
+}
+
+#[test]
+fn test_parse_statements() {
+	let input = "() => {\n\tlet a = 1, [b] = c\n\tif (a) return\n\telse { throw b }\n\ttry { d() } catch ({ e }) {} finally {}\n\t;\n}";
+
+	match parse(input) {
+		ast::Expression::Function(expr) => match expr.body {
+			ast::FunctionBody::Block(block) => {
+				assert!(matches!(
+					&block.body[..],
+					[
+						ast::Statement::Variable(_),
+						ast::Statement::If(_),
+						ast::Statement::Try(_),
+						ast::Statement::Empty(_),
+					]
+				));
+
+				match &block.body[1] {
+					ast::Statement::If(stmt) => {
+						// The line break ends the `return`.
+						match &*stmt.consequent {
+							ast::Statement::Return(stmt) => assert!(stmt.argument.is_none()),
+							_ => panic!("Expected ReturnStatement"),
+						}
+
+						assert!(matches!(
+							stmt.alternate.as_deref(),
+							Some(ast::Statement::Block(_))
+						));
+					}
+					_ => panic!("Expected IfStatement"),
+				}
+			}
+			_ => panic!("Expected block body"),
+		},
+		_ => panic!("Expected FunctionExpression"),
+	}
+
+	// Statements on the same line need a semicolon.
+	assert!(Parser::new("() => { a() b() }").parse_javascript().is_err());
+	assert!(Parser::new("() => { const a }").parse_javascript().is_err());
+	assert!(Parser::new("() => { try {} }").parse_javascript().is_err());
+}
+
+#[test]
+fn test_parse_unsupported() {
+	// Statements outside of the supported subset are rejected rather than parsed as identifiers.
+	let message = |input: &str| Parser::new(input).parse_javascript().unwrap_err().message;
+
+	assert_eq!(
+		message("() => {\n\tfor (const a of b) {}\n}").as_deref(),
+		Some("Unsupported `for` statement")
+	);
+	assert_eq!(
+		message("() => { while (a)\n{} }").as_deref(),
+		Some("Unsupported `while` statement")
+	);
+	assert_eq!(
+		message("() => { break; }").as_deref(),
+		Some("Unsupported `break` statement")
+	);
+	assert_eq!(
+		message("a.map(function (b) { return b })").as_deref(),
+		Some("Unsupported `function` expression")
+	);
+	assert_eq!(
+		message("`${function () {}}`").as_deref(),
+		Some("Unsupported `function` expression")
+	);
+
+	let error = Parser::new("() => {\n\tswitch (a) {}\n}")
+		.parse_javascript()
+		.unwrap_err();
+	assert_eq!(error.position, 9);
+	assert_eq!(
+		format!("{:?}", error),
+		"Unsupported `switch` statement at 2:2."
+	);
+	assert!(Parser::new("() => { a.for; }").parse_javascript().is_ok());
 }
 
 #[test]
@@ -416,17 +582,62 @@ fn test_parse_spread() {
 fn test_parse_template() {
 	match parse("`foo`") {
 		ast::Expression::Template(expr) => {
-			assert_eq!(expr.raw, "foo");
+			assert_eq!(expr.quasis.len(), 1);
+			assert_eq!(expr.quasis[0].raw, "foo");
+			assert!(expr.expressions.is_empty());
 		}
 		_ => panic!("Expected TemplateExpression"),
 	}
 }
 
+#[test]
+fn test_parse_template_substitutions() {
+	match parse("`a${b}c${ `d${e}` }`") {
+		ast::Expression::Template(expr) => {
+			let quasis: Vec<_> = expr.quasis.iter().map(|quasi| &quasi.raw).collect();
+			assert_eq!(quasis, ["a", "c", ""]);
+			assert_eq!((expr.quasis[1].start, expr.quasis[1].end), (6, 7));
+
+			match &expr.expressions[0] {
+				ast::Expression::Identifier(expr) => {
+					assert_eq!(expr.name, "b");
+					assert_eq!((expr.start, expr.end), (4, 5));
+				}
+				_ => panic!("Expected Identifier"),
+			}
+
+			match &expr.expressions[1] {
+				ast::Expression::Template(expr) => {
+					assert_eq!(expr.quasis[0].raw, "d");
+					assert_eq!(expr.expressions.len(), 1);
+				}
+				_ => panic!("Expected TemplateExpression"),
+			}
+		}
+		_ => panic!("Expected TemplateExpression"),
+	}
+}
+
+#[test]
+fn test_parse_template_escapes() {
+	match parse(r"`a\n\${b}\u{1F600}\x41\\`") {
+		ast::Expression::Template(expr) => {
+			assert_eq!(expr.quasis.len(), 1);
+			assert_eq!(expr.quasis[0].raw, r"a\n\${b}\u{1F600}\x41\\");
+			assert_eq!(expr.quasis[0].cooked.as_deref(), Some("a\n${b}😀A\\"));
+		}
+		_ => panic!("Expected TemplateExpression"),
+	}
+
+	let mut parser = Parser::new(r"`\unicode`");
+	assert!(parser.parse_javascript().is_err());
+}
+
 #[test]
 fn test_parse_tagged_template() {
 	match parse("foo`bar`") {
 		ast::Expression::TaggedTemplate(expr) => {
-			assert_eq!(expr.quasi.raw, "bar");
+			assert_eq!(expr.quasi.quasis[0].raw, "bar");
 			match *expr.tag {
 				ast::Expression::Identifier(expr) => {
 					assert_eq!(expr.name, "foo");
@@ -436,6 +647,15 @@ fn test_parse_tagged_template() {
 		}
 		_ => panic!("Expected TaggedTemplateExpression"),
 	}
+
+	// Tagged templates allow invalid escapes.
+	match parse(r"foo`\unicode`") {
+		ast::Expression::TaggedTemplate(expr) => {
+			assert_eq!(expr.quasi.quasis[0].raw, r"\unicode");
+			assert_eq!(expr.quasi.quasis[0].cooked, None);
+		}
+		_ => panic!("Expected TaggedTemplateExpression"),
+	}
 }
 
 #[test]
@@ -548,3 +768,184 @@ fn test_parse_array_trailing_comma() {
 		_ => panic!("Expected ArrayExpression"),
 	}
 }
+
+#[test]
+fn test_parse_this() {
+	match parse("this") {
+		ast::Expression::This(expr) => {
+			assert_eq!((expr.start, expr.end), (0, 4));
+		}
+		_ => panic!("Expected ThisExpression"),
+	}
+}
+
+#[test]
+fn test_parse_sequence() {
+	match parse("foo, bar") {
+		ast::Expression::Sequence(expr) => {
+			assert_eq!((expr.start, expr.end), (0, 8));
+			assert_eq!(expr.expressions.len(), 2);
+		}
+		_ => panic!("Expected SequenceExpression"),
+	}
+}
+
+#[test]
+fn test_parse_postfix_update() {
+	match parse("foo++") {
+		ast::Expression::Unary(expr) => {
+			assert!(matches!(expr.operator, ast::UnaryOperator::Increment));
+			assert!(!expr.prefix);
+			assert_eq!((expr.start, expr.end), (0, 5));
+		}
+		_ => panic!("Expected UnaryExpression"),
+	}
+}
+
+#[test]
+fn test_parse_update_after_line_break() {
+	// The line break ends the expression before `++`.
+	let mut parser = Parser::new("foo\n++bar");
+	let expression = parser.parse_javascript().unwrap();
+	assert!(matches!(expression, ast::Expression::Identifier(_)));
+	assert!(!parser.scanner.is_done());
+}
+
+#[test]
+fn test_parse_number_literal_forms() {
+	for (input, value) in [
+		("1_000", 1000.0),
+		(".5", 0.5),
+		("1e3", 1000.0),
+		("2.5E-1", 0.25),
+		("0xff", 255.0),
+		("0o17", 15.0),
+		("0b1010", 10.0),
+		("0xffff_ffff_ffff_ffff", 18446744073709551615.0),
+	] {
+		match parse(input) {
+			ast::Expression::Literal(ast::Literal::Number(literal)) => {
+				assert_eq!(literal.value, value, "{}", input);
+				assert_eq!(literal.raw, input);
+			}
+			_ => panic!("Expected NumberLiteral"),
+		}
+	}
+}
+
+#[test]
+fn test_parse_bigint_literal() {
+	match parse("0xf_fn") {
+		ast::Expression::Literal(ast::Literal::BigInt(literal)) => {
+			assert_eq!(literal.value, "0xff");
+			assert_eq!(literal.raw, "0xf_fn");
+		}
+		_ => panic!("Expected BigIntLiteral"),
+	}
+}
+
+#[test]
+fn test_parse_regex_literal() {
+	match parse("/\\d+\\//gu") {
+		ast::Expression::Literal(ast::Literal::RegExp(literal)) => {
+			assert_eq!(literal.pattern, "\\d+\\/");
+			assert_eq!(literal.flags, "gu");
+			assert_eq!((literal.start, literal.end), (0, 9));
+		}
+		_ => panic!("Expected RegExpLiteral"),
+	}
+}
+
+#[test]
+fn test_parse_regex_or_division() {
+	match parse("a / b / c") {
+		ast::Expression::Binary(expr) => {
+			assert!(matches!(expr.operator, ast::BinaryOperator::Divide));
+		}
+		_ => panic!("Expected BinaryExpression"),
+	}
+
+	match parse("/=/.test(a) / b") {
+		ast::Expression::Binary(expr) => match *expr.left {
+			ast::Expression::Call(call) => match *call.callee {
+				ast::Expression::Member(member) => {
+					assert!(matches!(
+						*member.object,
+						ast::Expression::Literal(ast::Literal::RegExp(_))
+					));
+				}
+				_ => panic!("Expected MemberExpression"),
+			},
+			_ => panic!("Expected CallExpression"),
+		},
+		_ => panic!("Expected BinaryExpression"),
+	}
+}
+
+#[test]
+fn test_parse_string_literal_escapes() {
+	match parse("'it\\'s \\x41\\u{1F44B}\\n'") {
+		ast::Expression::Literal(ast::Literal::String(literal)) => {
+			assert_eq!(literal.value, "it's A👋\n");
+			assert_eq!(literal.raw, "'it\\'s \\x41\\u{1F44B}\\n'");
+		}
+		_ => panic!("Expected StringLiteral"),
+	}
+
+	let mut parser = Parser::new("'\\01'");
+	assert_eq!(parser.parse_javascript().unwrap_err().position, 1);
+}
+
+#[test]
+fn test_parse_typescript() {
+	match parse_typescript("a! as Map<K, V>") {
+		ast::Expression::TsAs(expr) => {
+			assert!(matches!(*expr.expression, ast::Expression::TsNonNull(_)));
+			assert_eq!(expr.type_annotation.raw, "Map<K, V>");
+			assert_eq!(
+				(expr.type_annotation.start, expr.type_annotation.end),
+				(6, 15)
+			);
+			assert_eq!(expr.end, 15);
+		}
+		_ => panic!("Expected TSAsExpression"),
+	}
+
+	match parse_typescript("<T>(a?: T, b: T[] = []): T => a ?? b[0]") {
+		ast::Expression::Function(expr) => {
+			assert_eq!(expr.type_parameters.unwrap().raw, "<T>");
+			assert_eq!(expr.return_type.unwrap().raw, "T");
+
+			match &expr.parameters[0] {
+				ast::Expression::TsAnnotated(param) => {
+					assert!(param.optional);
+					assert_eq!(param.type_annotation.as_ref().unwrap().raw, "T");
+				}
+				_ => panic!("Expected TSAnnotatedExpression"),
+			}
+
+			match &expr.parameters[1] {
+				ast::Expression::Assignment(param) => {
+					assert!(matches!(*param.left, ast::Expression::TsAnnotated(_)));
+				}
+				_ => panic!("Expected AssignmentExpression"),
+			}
+		}
+		_ => panic!("Expected FunctionExpression"),
+	}
+
+	// A line break ends the expression before `!` and `as`.
+	let mut parser = Parser::new("a\n!b");
+	parser.set_typescript(true);
+	assert!(matches!(
+		parser.parse_javascript(),
+		Ok(ast::Expression::Identifier(_))
+	));
+
+	let mut parser = Parser::new("a\nas T");
+	parser.set_typescript(true);
+	assert!(matches!(
+		parser.parse_javascript(),
+		Ok(ast::Expression::Identifier(_))
+	));
+}
@@ -1,13 +1,13 @@
 use super::*;
 
-pub struct TokenBuffer<'a> {
+pub struct TokenBuffer<'a, 'src> {
 	buffer: Vec<Token>,
 	length: usize,
-	lexer: Lexer<'a>,
+	lexer: Lexer<'a, 'src>,
 }
 
-impl<'a> TokenBuffer<'a> {
-	pub fn new(lexer: Lexer<'a>) -> Self {
+impl<'a, 'src> TokenBuffer<'a, 'src> {
+	pub fn new(lexer: Lexer<'a, 'src>) -> Self {
 		Self {
 			buffer: Vec::new(),
 			length: 0,
@@ -19,7 +19,7 @@ impl<'a> TokenBuffer<'a> {
 		self.lexer.cursor()
 	}
 
-	pub fn scanner(&self) -> &Scanner {
+	pub fn scanner(&self) -> &Scanner<'src> {
 		self.lexer.scanner()
 	}
 
@@ -39,10 +39,6 @@ impl<'a> TokenBuffer<'a> {
 			return Ok(token);
 		}
 
-		if self.is_done() {
-			return Ok(self.buffer.last().unwrap());
-		}
-
 		let token = self.lexer.scan()?;
 		self.buffer.push(token);
 		self.length += 1;
@@ -50,6 +46,37 @@ impl<'a> TokenBuffer<'a> {
 		Ok(self.buffer.last().unwrap())
 	}
 
+	/// Scans the last token again as a regular expression. The lexer reads `/` as division,
+	/// since only the parser knows where a regular expression can start.
+	pub fn rescan_regex(&mut self) -> Result<&Token, usize> {
+		self.unscan();
+
+		let start = self.buffer[self.length].start;
+		self.buffer.truncate(self.length);
+		self.lexer.scanner_mut().set_cursor(start);
+
+		let token = self.lexer.scan_regex()?;
+		self.buffer.push(token);
+		self.length += 1;
+
+		Ok(self.buffer.last().unwrap())
+	}
+
+	/// Scans the last token again as a single `>`, see [`Lexer::scan_greater_than`].
+	pub fn rescan_greater_than(&mut self) -> Result<&Token, usize> {
+		self.unscan();
+
+		let start = self.buffer[self.length].start;
+		self.buffer.truncate(self.length);
+		self.lexer.scanner_mut().set_cursor(start);
+
+		let token = self.lexer.scan_greater_than()?;
+		self.buffer.push(token);
+		self.length += 1;
+
+		Ok(self.buffer.last().unwrap())
+	}
+
 	pub fn unscan(&mut self) {
 		if self.length == 0 {
 			panic!("cannot unscan");
@@ -65,8 +92,4 @@ impl<'a> TokenBuffer<'a> {
 			self.lexer.scanner_mut().set_cursor(token.end);
 		}
 	}
-
-	pub fn is_done(&mut self) -> bool {
-		self.lexer.is_done()
-	}
 }
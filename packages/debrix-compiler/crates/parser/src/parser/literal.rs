@@ -1,22 +1,31 @@
+use crate::parser::javascript::unescape;
 use crate::*;
 
-impl Parser {
-	pub fn parse_string(&mut self) -> Result<ast::StringLiteral, ParserError> {
+impl<'src> Parser<'src> {
+	pub fn parse_string(&mut self) -> Result<ast::StringLiteral<'src>, ParserError> {
 		let start = self.scanner.cursor();
-		let mut value = String::new();
 
-		if let Some(char) = self.scanner.peek().cloned() {
+		if let Some(char) = self.scanner.peek() {
 			if char == '\'' || char == '"' {
-				let quote = char.to_owned();
+				let quote = char;
 
 				while let Some(char) = self.scanner.next() {
-					if char == &quote {
+					if char == quote {
 						break;
-					} else {
-						value.push(char.to_owned());
+					}
+
+					// Skip the escaped char, which may be a quote.
+					if char == '\\' {
+						self.scanner.next();
 					}
 				}
 
+				let raw = self.scanner.slice(start + 1, self.scanner.cursor());
+				let value = match unescape(raw) {
+					Ok(value) => value,
+					Err(offset) => return Err(self.error(start + 1 + offset, &[])),
+				};
+
 				// skip the quote
 				self.scanner.next();
 
@@ -39,8 +48,8 @@ impl Parser {
 mod tests {
 	use super::*;
 
-	fn new_parser(input: &str) -> Parser {
-		let mut parser = Parser::new(input.to_owned());
+	fn new_parser(input: &str) -> Parser<'_> {
+		let mut parser = Parser::new(input);
 		parser.set_debug(true);
 		parser
 	}
@@ -53,4 +62,19 @@ mod tests {
 		assert_eq!(string.value, "foo");
 		assert_eq!(string.quote, '\'');
 	}
+
+	#[test]
+	fn test_parse_string_escapes() {
+		let mut parser = new_parser("'it\\'s\\n\\u{1F44B}'");
+		let string = parser.parse_string().unwrap();
+
+		assert_eq!(string.value, "it's\n👋");
+		assert_eq!(string.end, 18);
+	}
+
+	#[test]
+	fn test_parse_string_invalid_escape() {
+		let mut parser = Parser::new("'a\\u{110000}'");
+		assert_eq!(parser.parse_string().unwrap_err().position, 2);
+	}
 }
@@ -1,40 +1,31 @@
 use crate::*;
 
-impl Parser {
-	pub fn parse_text(&mut self) -> Result<ast::Text, ParserError> {
+impl<'src> Parser<'src> {
+	pub fn parse_text(&mut self) -> Result<ast::Text<'src>, ParserError> {
 		let start = self.scanner.cursor();
-		let mut content = String::new();
-
-		loop {
-			if let Some(char) = self.scanner.peek() {
-				if char == &'{' || char == &'<' || char == &'}' || char == &'#' {
-					break;
-				}
-
-				content.push(char.to_owned());
-
-				if char == &'\\' {
-					if let Some(char) = self.scanner.next() {
-						content.push(*char);
-					} else {
-						return Err(self.unexpected());
-					}
-				}
-				
-				self.scanner.next();
-			} else {
+
+		while let Some(char) = self.scanner.peek() {
+			if char == '{' || char == '<' || char == '}' || char == '#' {
 				break;
 			}
+
+			if char == '\\' && self.scanner.next().is_none() {
+				return Err(self.unexpected());
+			}
+
+			self.scanner.next();
 		}
 
+		let end = self.scanner.cursor();
+
 		Ok(ast::Text {
 			start,
-			end: self.scanner.cursor(),
-			content,
+			end,
+			content: self.scanner.slice(start, end).into(),
 		})
 	}
 
-	pub fn parse_text_binding(&mut self) -> Result<ast::TextBinding, ParserError> {
+	pub fn parse_text_binding(&mut self) -> Result<ast::TextBinding<'src>, ParserError> {
 		let start = self.scanner.cursor();
 
 		if !self.scanner.take("{") {
@@ -59,8 +50,8 @@ impl Parser {
 mod tests {
 	use super::*;
 
-	fn new_parser(input: &str) -> Parser {
-		let mut parser = Parser::new(input.to_owned());
+	fn new_parser(input: &str) -> Parser<'_> {
+		let mut parser = Parser::new(input);
 		parser.set_debug(true);
 		parser
 	}
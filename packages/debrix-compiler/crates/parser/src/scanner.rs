@@ -1,16 +1,27 @@
+use crate::{Encoding, OffsetIndex};
+use std::sync::Arc;
+
+/// Scans the input by byte, without copying it. All offsets, like the cursor, are char offsets.
+///
+/// The byte offset of the cursor is kept next to it. Other offsets are looked up in an index of
+/// the input, built once, unless the input is ASCII, where both offsets are the same.
 #[derive(Clone)]
-pub struct Scanner {
+pub struct Scanner<'src> {
+	input: &'src str,
 	cursor: usize,
-	chars: Vec<char>,
-	string: String,
+	/// Byte offset of the cursor.
+	byte: usize,
+	/// Byte offsets of the chars of non-ASCII input, shared with clones of the scanner.
+	bytes: Option<Arc<OffsetIndex>>,
 }
 
-impl Scanner {
-	pub fn new(string: &str) -> Self {
+impl<'src> Scanner<'src> {
+	pub fn new(input: &'src str) -> Self {
 		Self {
+			input,
 			cursor: 0,
-			chars: string.chars().collect(),
-			string: string.to_owned(),
+			byte: 0,
+			bytes: (!input.is_ascii()).then(|| Arc::new(OffsetIndex::new(input, Encoding::Utf8))),
 		}
 	}
 
@@ -18,61 +29,104 @@ impl Scanner {
 		self.cursor
 	}
 
-	#[allow(dead_code)]
 	pub fn set_cursor(&mut self, cursor: usize) {
+		self.byte = self.byte_offset(cursor);
 		self.cursor = cursor;
 	}
 
-	pub fn peek(&self) -> Option<&char> {
-		self.chars.get(self.cursor)
+	pub fn peek(&self) -> Option<char> {
+		let byte = *self.input.as_bytes().get(self.byte)?;
+
+		if byte.is_ascii() {
+			Some(byte as char)
+		} else {
+			self.input[self.byte..].chars().next()
+		}
 	}
 
 	pub fn is_done(&self) -> bool {
-		self.cursor == self.chars.len()
+		self.byte == self.input.len()
 	}
 
-	pub fn next(&mut self) -> Option<&char> {
-		if self.cursor == self.chars.len() {
-			return None;
-		}
+	pub fn next(&mut self) -> Option<char> {
+		let char = self.peek()?;
 
+		self.byte += char.len_utf8();
 		self.cursor += 1;
-		self.chars.get(self.cursor)
+		self.peek()
 	}
 
 	pub fn back(&mut self) -> bool {
 		if self.cursor == 0 {
-			false
-		} else {
-			self.cursor -= 1;
-			true
+			return false;
+		}
+
+		self.byte -= 1;
+
+		while !self.input.is_char_boundary(self.byte) {
+			self.byte -= 1;
 		}
+
+		self.cursor -= 1;
+		true
 	}
 
 	pub fn take(&mut self, target: &str) -> bool {
 		if self.test(target) {
-			self.cursor += target.len();
+			self.byte += target.len();
+			self.cursor += target.chars().count();
 			true
 		} else {
 			false
 		}
 	}
 
-	pub fn test(&mut self, target: &str) -> bool {
-		for (i, charx) in target.chars().enumerate() {
-			if let Some(chary) = self.chars.get(self.cursor + i) {
-				if &charx != chary {
-					return false;
-				}
-			} else {
-				return false;
-			}
+	pub fn test(&self, target: &str) -> bool {
+		self.input.as_bytes()[self.byte..].starts_with(target.as_bytes())
+	}
+
+	/// Returns the input between the char offsets, borrowed from the input.
+	pub fn slice(&self, start: usize, end: usize) -> &'src str {
+		&self.input[self.byte_offset(start)..self.byte_offset(end)]
+	}
+
+	/// Returns the byte offset of a char offset, which is at most the length of the input.
+	fn byte_offset(&self, offset: usize) -> usize {
+		match &self.bytes {
+			Some(bytes) => bytes.offset(offset),
+			None => offset.min(self.input.len()),
 		}
+	}
+}
 
-		true
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_slice_non_ascii() {
+		let mut scanner = Scanner::new("café 👋 bar");
+
+		assert!(scanner.take("café 👋"));
+		assert_eq!(scanner.cursor(), 6);
+		assert_eq!(scanner.slice(5, 10), "👋 bar");
+		assert_eq!(scanner.slice(0, 4), "café");
 	}
 
-	pub fn slice(&self, start: usize, end: usize) -> &str {
-		self.string.get(start..end).unwrap()
+	#[test]
+	fn test_move_non_ascii() {
+		let mut scanner = Scanner::new("aé👋b");
+
+		assert_eq!(scanner.next(), Some('é'));
+		assert_eq!(scanner.next(), Some('👋'));
+		assert_eq!(scanner.next(), Some('b'));
+		assert!(scanner.back());
+		assert_eq!(scanner.peek(), Some('👋'));
+
+		scanner.set_cursor(1);
+		assert_eq!(scanner.peek(), Some('é'));
+		scanner.set_cursor(4);
+		assert!(scanner.is_done());
+		assert_eq!(scanner.next(), None);
 	}
 }
@@ -5,8 +5,8 @@
 
 use super::*;
 
-fn parse(document: &str) -> Vec<ast::Node> {
-	let mut parser = Parser::new(document.to_owned());
+fn parse(document: &str) -> Vec<ast::Node<'_>> {
+	let mut parser = Parser::new(document);
 	let mut nodes = Vec::new();
 
 	parser.set_debug(true);
@@ -25,9 +25,296 @@ pub fn test_parse_empty_document() {
 
 #[test]
 pub fn test_parse_simple_document() {
-	parse(r#"
+	parse(
+		r#"
 		using model from 'self.model.js'
 
 		<p>Hello {$props.name}!</p>
-	"#);
+	"#,
+	);
+}
+
+#[test]
+pub fn test_parse_flow_control_document() {
+	parse(
+		r#"
+		<ul>
+			<#when {items.length}>
+				<#each {item in items}>
+					<li>{item}</li>
+				</each>
+			<#else>
+				<li>Empty</li>
+			</when>
+		</ul>
+	"#,
+	);
+}
+
+fn parse_recovering(document: &str) -> (ast::Document<'_>, Vec<ParserError>) {
+	parse_document_recovering(document)
+}
+
+#[test]
+pub fn test_parse_recovering_valid_document() {
+	let (document, errors) = parse_recovering("<p>Hello {name}!</p>");
+
+	assert!(errors.is_empty());
+	assert_eq!(document.children.len(), 1);
+}
+
+#[test]
+pub fn test_parse_recovering_multiple_errors_document() {
+	let (document, errors) = parse_recovering(
+		r#"
+		<div>
+			<p>{a +}</p>
+			<p>{b +}</p>
+		</div>
+		<span></span>
+	"#,
+	);
+
+	assert_eq!(errors.len(), 2);
+	assert_eq!(document.children.len(), 2);
+
+	match &document.children[0] {
+		ast::Node::Element(div) => {
+			let paragraphs: Vec<&ast::Node> = div
+				.children
+				.iter()
+				.filter(|node| matches!(node, ast::Node::Element(_)))
+				.collect();
+
+			assert_eq!(paragraphs.len(), 2);
+
+			for paragraph in paragraphs {
+				match paragraph {
+					ast::Node::Element(p) => {
+						assert!(matches!(p.children[0], ast::Node::Invalid(_)));
+						assert!(p.end_tag.is_some());
+					}
+					_ => unreachable!(),
+				}
+			}
+		}
+		_ => panic!("expected element"),
+	}
+}
+
+#[test]
+pub fn test_parse_recovering_mismatched_closing_tag_document() {
+	let (document, errors) = parse_recovering("<div><p>foo</div><span></span>");
+
+	assert_eq!(errors.len(), 1);
+	assert_eq!(document.children.len(), 2);
+
+	match &document.children[0] {
+		ast::Node::Element(div) => {
+			assert!(div.end_tag.is_some());
+
+			match &div.children[0] {
+				ast::Node::Element(p) => assert!(p.end_tag.is_none()),
+				_ => panic!("expected element"),
+			}
+		}
+		_ => panic!("expected element"),
+	}
+}
+
+#[test]
+pub fn test_parse_recovering_stray_closing_tag_document() {
+	let (document, errors) = parse_recovering("<div></span>foo</div>");
+
+	assert_eq!(errors.len(), 1);
+	assert_eq!(document.children.len(), 1);
+
+	match &document.children[0] {
+		ast::Node::Element(div) => {
+			assert!(matches!(div.children[0], ast::Node::Invalid(_)));
+			assert!(matches!(div.children[1], ast::Node::Text(_)));
+			assert!(div.end_tag.is_some());
+		}
+		_ => panic!("expected element"),
+	}
+}
+
+#[test]
+pub fn test_parse_recovering_invalid_attribute_document() {
+	let (document, errors) = parse_recovering(r#"<div foo bar="baz">qux</div>"#);
+
+	assert_eq!(errors.len(), 1);
+
+	match &document.children[0] {
+		ast::Node::Element(div) => {
+			assert_eq!(div.attributes.len(), 1);
+			assert_eq!(div.children.len(), 1);
+		}
+		_ => panic!("expected element"),
+	}
+}
+
+#[test]
+pub fn test_parse_recovering_unclosed_document() {
+	let (document, errors) = parse_recovering("<div><p>foo");
+
+	assert_eq!(errors.len(), 1);
+	assert_eq!(document.children.len(), 1);
+}
+
+#[test]
+pub fn test_parse_recovering_stray_flow_control_branch_document() {
+	let (document, errors) = parse_recovering("<div><#else>foo</div>");
+
+	assert_eq!(errors.len(), 1);
+	assert_eq!(errors[0].position, 5);
+	assert_eq!(document.children.len(), 1);
+
+	match &document.children[0] {
+		ast::Node::Element(div) => {
+			assert!(matches!(div.children[0], ast::Node::Invalid(_)));
+			assert!(matches!(div.children[1], ast::Node::Text(_)));
+			assert!(div.end_tag.is_some());
+		}
+		_ => panic!("expected element"),
+	}
+}
+
+#[test]
+pub fn test_parse_recovering_unknown_flow_control_document() {
+	let (document, errors) = parse_recovering("<div><#hen {a > b}>x<#else>y</when></div>");
+
+	assert_eq!(errors.len(), 3);
+	assert_eq!(document.children.len(), 1);
+
+	match &document.children[0] {
+		ast::Node::Element(div) => {
+			assert!(matches!(
+				div.children[..],
+				[
+					ast::Node::Invalid(ast::Invalid { start: 5, end: 19 }),
+					ast::Node::Text(_),
+					ast::Node::Invalid(_),
+					ast::Node::Text(_),
+					ast::Node::Invalid(_),
+				]
+			));
+			assert!(div.end_tag.is_some());
+		}
+		_ => panic!("expected element"),
+	}
+}
+
+#[test]
+pub fn test_parse_recovering_top_level_flow_control_document() {
+	let input = "<div>x</div><#when {a}>y</when><#else><p></p>";
+	let (document, errors) = parse_recovering(input);
+
+	assert_eq!(errors.len(), 2);
+	assert_eq!(errors[0].position, 12);
+	assert_eq!(errors[1].position, 31);
+
+	assert!(matches!(
+		document.children[..],
+		[
+			ast::Node::Element(_),
+			ast::Node::Invalid(ast::Invalid { start: 12, end: 31 }),
+			ast::Node::Invalid(ast::Invalid { start: 31, end: 38 }),
+			ast::Node::Element(_),
+		]
+	));
+
+	let (node, _) = parse_cst_recovering(input);
+	assert_eq!(node.to_string(), input);
+}
+
+#[test]
+pub fn test_parse_stray_flow_control_branch_document() {
+	assert!(parse_document("<div><#else></div>").is_err());
+	assert!(parse_document("<div><#hen {a}>x</when></div>").is_err());
+	assert!(parse_document("<div>x</div><#when {a}>y</when>").is_err());
+}
+
+#[test]
+pub fn test_parse_invalid_document_location() {
+	let error = parse_document("<div>\n\t<p>{a +}</p>\n</div>").unwrap_err();
+
+	assert_eq!(error.location.start, Position::new(2, 9));
+}
+
+#[test]
+pub fn test_parse_unicode_document() {
+	let nodes = parse(r#"<p title="👋">Café {"😀" + naïve}</p>"#);
+
+	match &nodes[0] {
+		ast::Node::Element(element) => match &element.children[1] {
+			ast::Node::TextBinding(binding) => {
+				assert_eq!(binding.start, 18);
+				assert_eq!(binding.end, 31);
+			}
+			_ => panic!("expected text binding"),
+		},
+		_ => panic!("expected element"),
+	}
+}
+
+fn assert_lossless(document: &str) -> cst::SyntaxNode {
+	let node = parse_cst(document).unwrap();
+	assert_eq!(node.to_string(), document);
+
+	let mut cursor = 0;
+	for token in node.tokens() {
+		assert_eq!(token.start, cursor);
+		cursor = token.end;
+	}
+	assert_eq!(cursor, document.chars().count());
+
+	node
+}
+
+#[test]
+pub fn test_parse_lossless_document() {
+	assert_lossless("");
+	assert_lossless("  \n\t ");
+	assert_lossless(
+		r#"
+		// Dependencies
+		using model from 'self.model.js'
+		using Button button, { Icon icon as i  Label label } from "./components"
+
+		<div /* bar */ class = "foo"  {...props}   {title}  on:click={() => count += 1}>
+			<!-- comment -->
+			Hello   {$props.name}!
+			<#when {a  ?  b :c}>
+				{foo( 1, 'two' , [3,  4] , { five: 5, [six]: 6 })}
+			<#else when {  d  }>
+				<input value={ new  Date(  ) . getTime ( ) } />
+			<#else>
+				{tag`template`}{`a ${ b + `c ${d}` /* } */ } \u{1F600} e`}
+			</when>
+			<#each { item in ( items ) }>
+				<p title={ item . title // trailing
+				}>{item.name}&nbsp;{item [ 'value' ]}</p>
+			</each>
+		</div>
+	"#,
+	);
+	assert_lossless("<p>Café 👋 {\"😀\" /* 👋 */ + naïve}</p>");
+	assert_lossless("<ul><#each { item ,i in items ; key  item.id }><li>{i}</li></each></ul>");
+	assert_lossless(
+		"<ul><#each {item in items}><li>{item}</li> <#else > <li>none</li></each></ul>",
+	);
+	assert_lossless("<p><#let { city = user.address.city }>{city}</let></p>");
+	assert_lossless("<p><#match { a }> <#case { 'b' ,c }>d <#default >e</match></p>");
+	assert_lossless("<p><#await { a }>b <#then { [c] }>{c} <#catch >d</await></p>");
+	assert_lossless("<p><#try>{a()} <#catch { e }>{e}</try></p>");
+}
+
+#[test]
+pub fn test_parse_lossless_invalid_document() {
+	let input = "<div>\n\t<p>{a +}</p>\n\t<span foo=>bar</div>";
+	let (node, errors) = parse_cst_recovering(input);
+
+	assert!(!errors.is_empty());
+	assert_eq!(node.to_string(), input);
 }
@@ -0,0 +1,1001 @@
+//! Traversal of the syntax tree by reference, see [`Visit`].
+
+use crate::ast::{self, javascript};
+
+/// Walks the syntax tree by reference.
+///
+/// Every `visit_*` method walks the children of its node by default, with the `walk_*` function
+/// of the same name. Implementors override the methods of the nodes they care about, and call
+/// the `walk_*` function to keep walking into the children.
+pub trait Visit<'ast> {
+	fn visit_document(&mut self, node: &'ast ast::Document) {
+		walk_document(self, node);
+	}
+
+	fn visit_node(&mut self, node: &'ast ast::Node) {
+		walk_node(self, node);
+	}
+
+	fn visit_dependency_statement(&mut self, node: &'ast ast::DependencyStatement) {
+		walk_dependency_statement(self, node);
+	}
+
+	fn visit_dependency_default_specifier(&mut self, node: &'ast ast::DependencyDefaultSpecifier) {
+		walk_dependency_default_specifier(self, node);
+	}
+
+	fn visit_dependency_named_specifier(&mut self, node: &'ast ast::DependencyNamedSpecifier) {
+		walk_dependency_named_specifier(self, node);
+	}
+
+	fn visit_comment(&mut self, _node: &'ast ast::Comment) {}
+
+	fn visit_element(&mut self, node: &'ast ast::Element) {
+		walk_element(self, node);
+	}
+
+	fn visit_attribute(&mut self, node: &'ast ast::Attribute) {
+		walk_attribute(self, node);
+	}
+
+	fn visit_static_attribute(&mut self, node: &'ast ast::StaticAttribute) {
+		walk_static_attribute(self, node);
+	}
+
+	fn visit_binding_attribute(&mut self, node: &'ast ast::BindingAttribute) {
+		walk_binding_attribute(self, node);
+	}
+
+	fn visit_spread_attribute(&mut self, node: &'ast ast::SpreadAttribute) {
+		walk_spread_attribute(self, node);
+	}
+
+	fn visit_short_binding_attribute(&mut self, node: &'ast ast::ShortBindingAttribute) {
+		walk_short_binding_attribute(self, node);
+	}
+
+	fn visit_text(&mut self, _node: &'ast ast::Text) {}
+
+	fn visit_text_binding(&mut self, node: &'ast ast::TextBinding) {
+		walk_text_binding(self, node);
+	}
+
+	fn visit_flow_control(&mut self, node: &'ast ast::FlowControl) {
+		walk_flow_control(self, node);
+	}
+
+	fn visit_flow_control_when(&mut self, node: &'ast ast::FlowControlWhen) {
+		walk_flow_control_when(self, node);
+	}
+
+	fn visit_flow_control_else(&mut self, node: &'ast ast::FlowControlElse) {
+		walk_flow_control_else(self, node);
+	}
+
+	fn visit_flow_control_each(&mut self, node: &'ast ast::FlowControlEach) {
+		walk_flow_control_each(self, node);
+	}
+
+	fn visit_flow_control_let(&mut self, node: &'ast ast::FlowControlLet) {
+		walk_flow_control_let(self, node);
+	}
+
+	fn visit_flow_control_match(&mut self, node: &'ast ast::FlowControlMatch) {
+		walk_flow_control_match(self, node);
+	}
+
+	fn visit_flow_control_case(&mut self, node: &'ast ast::FlowControlCase) {
+		walk_flow_control_case(self, node);
+	}
+
+	fn visit_flow_control_await(&mut self, node: &'ast ast::FlowControlAwait) {
+		walk_flow_control_await(self, node);
+	}
+
+	fn visit_flow_control_await_branch(&mut self, node: &'ast ast::FlowControlAwaitBranch) {
+		walk_flow_control_await_branch(self, node);
+	}
+
+	fn visit_flow_control_try(&mut self, node: &'ast ast::FlowControlTry) {
+		walk_flow_control_try(self, node);
+	}
+
+	fn visit_invalid(&mut self, _node: &'ast ast::Invalid) {}
+
+	fn visit_identifier(&mut self, _node: &'ast ast::Identifier) {}
+
+	fn visit_literal(&mut self, node: &'ast ast::Literal) {
+		walk_literal(self, node);
+	}
+
+	fn visit_string_literal(&mut self, _node: &'ast ast::StringLiteral) {}
+
+	fn visit_number_literal(&mut self, _node: &'ast ast::NumberLiteral) {}
+
+	fn visit_boolean_literal(&mut self, _node: &'ast ast::BooleanLiteral) {}
+
+	fn visit_null_literal(&mut self, _node: &'ast ast::NullLiteral) {}
+
+	fn visit_expression(&mut self, node: &'ast javascript::Expression) {
+		walk_expression(self, node);
+	}
+
+	fn visit_identifier_expression(&mut self, _node: &'ast javascript::IdentifierExpression) {}
+
+	fn visit_javascript_literal(&mut self, node: &'ast javascript::Literal) {
+		walk_javascript_literal(self, node);
+	}
+
+	fn visit_javascript_string_literal(&mut self, _node: &'ast javascript::StringLiteral) {}
+
+	fn visit_javascript_number_literal(&mut self, _node: &'ast javascript::NumberLiteral) {}
+
+	fn visit_javascript_bigint_literal(&mut self, _node: &'ast javascript::BigIntLiteral) {}
+
+	fn visit_javascript_boolean_literal(&mut self, _node: &'ast javascript::BooleanLiteral) {}
+
+	fn visit_javascript_null_literal(&mut self, _node: &'ast javascript::NullLiteral) {}
+
+	fn visit_javascript_regexp_literal(&mut self, _node: &'ast javascript::RegExpLiteral) {}
+
+	fn visit_this_expression(&mut self, _node: &'ast javascript::ThisExpression) {}
+
+	fn visit_unary_expression(&mut self, node: &'ast javascript::UnaryExpression) {
+		walk_unary_expression(self, node);
+	}
+
+	fn visit_binary_expression(&mut self, node: &'ast javascript::BinaryExpression) {
+		walk_binary_expression(self, node);
+	}
+
+	fn visit_conditional_expression(&mut self, node: &'ast javascript::ConditionalExpression) {
+		walk_conditional_expression(self, node);
+	}
+
+	fn visit_call_expression(&mut self, node: &'ast javascript::CallExpression) {
+		walk_call_expression(self, node);
+	}
+
+	fn visit_new_expression(&mut self, node: &'ast javascript::NewExpression) {
+		walk_new_expression(self, node);
+	}
+
+	fn visit_member_expression(&mut self, node: &'ast javascript::MemberExpression) {
+		walk_member_expression(self, node);
+	}
+
+	fn visit_await_expression(&mut self, node: &'ast javascript::AwaitExpression) {
+		walk_await_expression(self, node);
+	}
+
+	fn visit_sequence_expression(&mut self, node: &'ast javascript::SequenceExpression) {
+		walk_sequence_expression(self, node);
+	}
+
+	fn visit_function_expression(&mut self, node: &'ast javascript::FunctionExpression) {
+		walk_function_expression(self, node);
+	}
+
+	fn visit_function_body(&mut self, node: &'ast javascript::FunctionBody) {
+		walk_function_body(self, node);
+	}
+
+	fn visit_assignment_expression(&mut self, node: &'ast javascript::AssignmentExpression) {
+		walk_assignment_expression(self, node);
+	}
+
+	fn visit_spread_expression(&mut self, node: &'ast javascript::SpreadExpression) {
+		walk_spread_expression(self, node);
+	}
+
+	fn visit_template_literal(&mut self, node: &'ast javascript::TemplateLiteral) {
+		walk_template_literal(self, node);
+	}
+
+	fn visit_template_element(&mut self, _node: &'ast javascript::TemplateElement) {}
+
+	fn visit_tagged_template_expression(
+		&mut self,
+		node: &'ast javascript::TaggedTemplateExpression,
+	) {
+		walk_tagged_template_expression(self, node);
+	}
+
+	fn visit_object_expression(&mut self, node: &'ast javascript::ObjectExpression) {
+		walk_object_expression(self, node);
+	}
+
+	fn visit_object_property(&mut self, node: &'ast javascript::ObjectProperty) {
+		walk_object_property(self, node);
+	}
+
+	fn visit_object_keyed_property(&mut self, node: &'ast javascript::ObjectKeyedProperty) {
+		walk_object_keyed_property(self, node);
+	}
+
+	fn visit_object_computed_property(&mut self, node: &'ast javascript::ObjectComputedProperty) {
+		walk_object_computed_property(self, node);
+	}
+
+	fn visit_array_expression(&mut self, node: &'ast javascript::ArrayExpression) {
+		walk_array_expression(self, node);
+	}
+
+	fn visit_parenthesized_expression(&mut self, node: &'ast javascript::ParenthesizedExpression) {
+		walk_parenthesized_expression(self, node);
+	}
+
+	fn visit_empty_expression(&mut self, _node: &'ast javascript::EmptyExpression) {}
+
+	fn visit_ts_as_expression(&mut self, node: &'ast javascript::TsAsExpression) {
+		walk_ts_as_expression(self, node);
+	}
+
+	fn visit_ts_satisfies_expression(&mut self, node: &'ast javascript::TsSatisfiesExpression) {
+		walk_ts_satisfies_expression(self, node);
+	}
+
+	fn visit_ts_non_null_expression(&mut self, node: &'ast javascript::TsNonNullExpression) {
+		walk_ts_non_null_expression(self, node);
+	}
+
+	fn visit_ts_annotated_expression(&mut self, node: &'ast javascript::TsAnnotatedExpression) {
+		walk_ts_annotated_expression(self, node);
+	}
+
+	fn visit_ts_type(&mut self, _node: &'ast javascript::TsType) {}
+
+	fn visit_statement(&mut self, node: &'ast javascript::Statement) {
+		walk_statement(self, node);
+	}
+
+	fn visit_block_statement(&mut self, node: &'ast javascript::BlockStatement) {
+		walk_block_statement(self, node);
+	}
+
+	fn visit_expression_statement(&mut self, node: &'ast javascript::ExpressionStatement) {
+		walk_expression_statement(self, node);
+	}
+
+	fn visit_variable_declaration(&mut self, node: &'ast javascript::VariableDeclaration) {
+		walk_variable_declaration(self, node);
+	}
+
+	fn visit_variable_declarator(&mut self, node: &'ast javascript::VariableDeclarator) {
+		walk_variable_declarator(self, node);
+	}
+
+	fn visit_return_statement(&mut self, node: &'ast javascript::ReturnStatement) {
+		walk_return_statement(self, node);
+	}
+
+	fn visit_if_statement(&mut self, node: &'ast javascript::IfStatement) {
+		walk_if_statement(self, node);
+	}
+
+	fn visit_throw_statement(&mut self, node: &'ast javascript::ThrowStatement) {
+		walk_throw_statement(self, node);
+	}
+
+	fn visit_try_statement(&mut self, node: &'ast javascript::TryStatement) {
+		walk_try_statement(self, node);
+	}
+
+	fn visit_catch_clause(&mut self, node: &'ast javascript::CatchClause) {
+		walk_catch_clause(self, node);
+	}
+
+	fn visit_empty_statement(&mut self, _node: &'ast javascript::EmptyStatement) {}
+}
+
+pub fn walk_document<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, node: &'ast ast::Document) {
+	for child in &node.children {
+		visitor.visit_node(child);
+	}
+}
+
+pub fn walk_node<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, node: &'ast ast::Node) {
+	match node {
+		ast::Node::DependencyStatement(node) => visitor.visit_dependency_statement(node),
+		ast::Node::Comment(node) => visitor.visit_comment(node),
+		ast::Node::Element(node) => visitor.visit_element(node),
+		ast::Node::Text(node) => visitor.visit_text(node),
+		ast::Node::TextBinding(node) => visitor.visit_text_binding(node),
+		ast::Node::FlowControl(node) => visitor.visit_flow_control(node),
+		ast::Node::Invalid(node) => visitor.visit_invalid(node),
+	}
+}
+
+pub fn walk_dependency_statement<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::DependencyStatement,
+) {
+	if let Some(default) = &node.default {
+		visitor.visit_dependency_default_specifier(default);
+	}
+
+	if let Some(named) = &node.named {
+		for specifier in &named.nodes {
+			visitor.visit_dependency_named_specifier(specifier);
+		}
+	}
+
+	visitor.visit_string_literal(&node.source);
+}
+
+pub fn walk_dependency_default_specifier<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::DependencyDefaultSpecifier,
+) {
+	visitor.visit_identifier(&node.usage);
+
+	if let Some(local) = &node.local {
+		visitor.visit_identifier(local);
+	}
+}
+
+pub fn walk_dependency_named_specifier<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::DependencyNamedSpecifier,
+) {
+	visitor.visit_identifier(&node.usage);
+	visitor.visit_identifier(&node.imported);
+
+	if let Some(local) = &node.local {
+		visitor.visit_identifier(local);
+	}
+}
+
+pub fn walk_element<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, node: &'ast ast::Element) {
+	visitor.visit_identifier(&node.tag_name);
+
+	for attribute in &node.attributes {
+		visitor.visit_attribute(attribute);
+	}
+
+	for child in &node.children {
+		visitor.visit_node(child);
+	}
+}
+
+pub fn walk_attribute<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, node: &'ast ast::Attribute) {
+	match node {
+		ast::Attribute::Static(node) => visitor.visit_static_attribute(node),
+		ast::Attribute::Binding(node) => visitor.visit_binding_attribute(node),
+		ast::Attribute::Spread(node) => visitor.visit_spread_attribute(node),
+		ast::Attribute::ShortBinding(node) => visitor.visit_short_binding_attribute(node),
+	}
+}
+
+pub fn walk_static_attribute<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::StaticAttribute,
+) {
+	visitor.visit_identifier(&node.name);
+
+	if let Some(value) = &node.value {
+		visitor.visit_string_literal(value);
+	}
+}
+
+pub fn walk_binding_attribute<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::BindingAttribute,
+) {
+	visitor.visit_identifier(&node.name);
+	visitor.visit_expression(&node.value);
+}
+
+pub fn walk_spread_attribute<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::SpreadAttribute,
+) {
+	visitor.visit_expression(&node.value);
+}
+
+pub fn walk_short_binding_attribute<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::ShortBindingAttribute,
+) {
+	visitor.visit_identifier_expression(&node.name);
+}
+
+pub fn walk_text_binding<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::TextBinding,
+) {
+	visitor.visit_expression(&node.expression);
+}
+
+pub fn walk_flow_control<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::FlowControl,
+) {
+	match node {
+		ast::FlowControl::When(node) => visitor.visit_flow_control_when(node),
+		ast::FlowControl::Each(node) => visitor.visit_flow_control_each(node),
+		ast::FlowControl::Let(node) => visitor.visit_flow_control_let(node),
+		ast::FlowControl::Match(node) => visitor.visit_flow_control_match(node),
+		ast::FlowControl::Await(node) => visitor.visit_flow_control_await(node),
+		ast::FlowControl::Try(node) => visitor.visit_flow_control_try(node),
+	}
+}
+
+pub fn walk_flow_control_when<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::FlowControlWhen,
+) {
+	visitor.visit_expression(&node.condition);
+
+	for child in &node.children {
+		visitor.visit_node(child);
+	}
+
+	for chained in &node.chain {
+		visitor.visit_flow_control_else(chained);
+	}
+}
+
+pub fn walk_flow_control_else<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::FlowControlElse,
+) {
+	if let Some(condition) = &node.condition {
+		visitor.visit_expression(condition);
+	}
+
+	for child in &node.children {
+		visitor.visit_node(child);
+	}
+}
+
+pub fn walk_flow_control_each<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::FlowControlEach,
+) {
+	visitor.visit_expression(&node.iterator);
+
+	if let Some(index) = &node.index {
+		visitor.visit_identifier_expression(index);
+	}
+
+	visitor.visit_expression(&node.iterable);
+
+	if let Some(key) = &node.key {
+		visitor.visit_expression(key);
+	}
+
+	for child in &node.children {
+		visitor.visit_node(child);
+	}
+
+	if let Some(fallback) = &node.fallback {
+		visitor.visit_flow_control_else(fallback);
+	}
+}
+
+pub fn walk_flow_control_let<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::FlowControlLet,
+) {
+	visitor.visit_identifier_expression(&node.name);
+	visitor.visit_expression(&node.value);
+
+	for child in &node.children {
+		visitor.visit_node(child);
+	}
+}
+
+pub fn walk_flow_control_match<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::FlowControlMatch,
+) {
+	visitor.visit_expression(&node.discriminant);
+
+	for case in &node.cases {
+		visitor.visit_flow_control_case(case);
+	}
+}
+
+pub fn walk_flow_control_case<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::FlowControlCase,
+) {
+	for test in &node.tests {
+		visitor.visit_expression(test);
+	}
+
+	for child in &node.children {
+		visitor.visit_node(child);
+	}
+}
+
+pub fn walk_flow_control_await<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::FlowControlAwait,
+) {
+	visitor.visit_expression(&node.promise);
+
+	for child in &node.children {
+		visitor.visit_node(child);
+	}
+
+	if let Some(then) = &node.then {
+		visitor.visit_flow_control_await_branch(then);
+	}
+
+	if let Some(catch) = &node.catch {
+		visitor.visit_flow_control_await_branch(catch);
+	}
+}
+
+pub fn walk_flow_control_await_branch<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::FlowControlAwaitBranch,
+) {
+	if let Some(binding) = &node.binding {
+		visitor.visit_expression(binding);
+	}
+
+	for child in &node.children {
+		visitor.visit_node(child);
+	}
+}
+
+pub fn walk_flow_control_try<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast ast::FlowControlTry,
+) {
+	for child in &node.children {
+		visitor.visit_node(child);
+	}
+
+	visitor.visit_flow_control_await_branch(&node.catch);
+}
+
+pub fn walk_literal<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, node: &'ast ast::Literal) {
+	match node {
+		ast::Literal::Number(node) => visitor.visit_number_literal(node),
+		ast::Literal::String(node) => visitor.visit_string_literal(node),
+		ast::Literal::Boolean(node) => visitor.visit_boolean_literal(node),
+		ast::Literal::Null(node) => visitor.visit_null_literal(node),
+	}
+}
+
+pub fn walk_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::Expression,
+) {
+	match node {
+		javascript::Expression::Identifier(node) => visitor.visit_identifier_expression(node),
+		javascript::Expression::Literal(node) => visitor.visit_javascript_literal(node),
+		javascript::Expression::This(node) => visitor.visit_this_expression(node),
+		javascript::Expression::Unary(node) => visitor.visit_unary_expression(node),
+		javascript::Expression::Binary(node) => visitor.visit_binary_expression(node),
+		javascript::Expression::Conditional(node) => visitor.visit_conditional_expression(node),
+		javascript::Expression::Call(node) => visitor.visit_call_expression(node),
+		javascript::Expression::New(node) => visitor.visit_new_expression(node),
+		javascript::Expression::Member(node) => visitor.visit_member_expression(node),
+		javascript::Expression::Sequence(node) => visitor.visit_sequence_expression(node),
+		javascript::Expression::Await(node) => visitor.visit_await_expression(node),
+		javascript::Expression::Function(node) => visitor.visit_function_expression(node),
+		javascript::Expression::Assignment(node) => visitor.visit_assignment_expression(node),
+		javascript::Expression::Spread(node) => visitor.visit_spread_expression(node),
+		javascript::Expression::Template(node) => visitor.visit_template_literal(node),
+		javascript::Expression::TaggedTemplate(node) => {
+			visitor.visit_tagged_template_expression(node)
+		}
+		javascript::Expression::Object(node) => visitor.visit_object_expression(node),
+		javascript::Expression::Array(node) => visitor.visit_array_expression(node),
+		javascript::Expression::Parenthesized(node) => visitor.visit_parenthesized_expression(node),
+		javascript::Expression::Empty(node) => visitor.visit_empty_expression(node),
+		javascript::Expression::TsAs(node) => visitor.visit_ts_as_expression(node),
+		javascript::Expression::TsSatisfies(node) => visitor.visit_ts_satisfies_expression(node),
+		javascript::Expression::TsNonNull(node) => visitor.visit_ts_non_null_expression(node),
+		javascript::Expression::TsAnnotated(node) => visitor.visit_ts_annotated_expression(node),
+	}
+}
+
+pub fn walk_javascript_literal<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::Literal,
+) {
+	match node {
+		javascript::Literal::String(node) => visitor.visit_javascript_string_literal(node),
+		javascript::Literal::Number(node) => visitor.visit_javascript_number_literal(node),
+		javascript::Literal::BigInt(node) => visitor.visit_javascript_bigint_literal(node),
+		javascript::Literal::Boolean(node) => visitor.visit_javascript_boolean_literal(node),
+		javascript::Literal::Null(node) => visitor.visit_javascript_null_literal(node),
+		javascript::Literal::RegExp(node) => visitor.visit_javascript_regexp_literal(node),
+	}
+}
+
+pub fn walk_unary_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::UnaryExpression,
+) {
+	visitor.visit_expression(&node.operand);
+}
+
+pub fn walk_binary_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::BinaryExpression,
+) {
+	visitor.visit_expression(&node.left);
+	visitor.visit_expression(&node.right);
+}
+
+pub fn walk_conditional_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::ConditionalExpression,
+) {
+	visitor.visit_expression(&node.condition);
+	visitor.visit_expression(&node.consequent);
+	visitor.visit_expression(&node.alternate);
+}
+
+pub fn walk_call_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::CallExpression,
+) {
+	visitor.visit_expression(&node.callee);
+
+	for argument in &node.arguments {
+		visitor.visit_expression(argument);
+	}
+}
+
+pub fn walk_new_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::NewExpression,
+) {
+	visitor.visit_expression(&node.callee);
+
+	for argument in &node.arguments {
+		visitor.visit_expression(argument);
+	}
+}
+
+pub fn walk_member_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::MemberExpression,
+) {
+	visitor.visit_expression(&node.object);
+	visitor.visit_expression(&node.property);
+}
+
+pub fn walk_await_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::AwaitExpression,
+) {
+	visitor.visit_expression(&node.argument);
+}
+
+pub fn walk_sequence_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::SequenceExpression,
+) {
+	for expression in &node.expressions {
+		visitor.visit_expression(expression);
+	}
+}
+
+pub fn walk_function_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::FunctionExpression,
+) {
+	if let Some(type_parameters) = &node.type_parameters {
+		visitor.visit_ts_type(type_parameters);
+	}
+
+	for parameter in &node.parameters {
+		visitor.visit_expression(parameter);
+	}
+
+	if let Some(return_type) = &node.return_type {
+		visitor.visit_ts_type(return_type);
+	}
+
+	visitor.visit_function_body(&node.body);
+}
+
+pub fn walk_function_body<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::FunctionBody,
+) {
+	match node {
+		javascript::FunctionBody::Expression(node) => visitor.visit_expression(node),
+		javascript::FunctionBody::Block(node) => visitor.visit_block_statement(node),
+	}
+}
+
+pub fn walk_assignment_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::AssignmentExpression,
+) {
+	visitor.visit_expression(&node.left);
+	visitor.visit_expression(&node.right);
+}
+
+pub fn walk_spread_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::SpreadExpression,
+) {
+	visitor.visit_expression(&node.argument);
+}
+
+pub fn walk_template_literal<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::TemplateLiteral,
+) {
+	for (i, quasi) in node.quasis.iter().enumerate() {
+		visitor.visit_template_element(quasi);
+
+		if let Some(expression) = node.expressions.get(i) {
+			visitor.visit_expression(expression);
+		}
+	}
+}
+
+pub fn walk_tagged_template_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::TaggedTemplateExpression,
+) {
+	visitor.visit_expression(&node.tag);
+	visitor.visit_template_literal(&node.quasi);
+}
+
+pub fn walk_object_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::ObjectExpression,
+) {
+	for property in &node.properties {
+		visitor.visit_object_property(property);
+	}
+}
+
+pub fn walk_object_property<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::ObjectProperty,
+) {
+	match node {
+		javascript::ObjectProperty::Keyed(node) => visitor.visit_object_keyed_property(node),
+		javascript::ObjectProperty::Computed(node) => visitor.visit_object_computed_property(node),
+		javascript::ObjectProperty::Spread(node) => visitor.visit_spread_expression(node),
+	}
+}
+
+pub fn walk_object_keyed_property<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::ObjectKeyedProperty,
+) {
+	visitor.visit_identifier_expression(&node.key);
+
+	if let Some(value) = &node.value {
+		visitor.visit_expression(value);
+	}
+}
+
+pub fn walk_object_computed_property<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::ObjectComputedProperty,
+) {
+	visitor.visit_expression(&node.key);
+	visitor.visit_expression(&node.value);
+}
+
+pub fn walk_array_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::ArrayExpression,
+) {
+	for element in &node.elements {
+		visitor.visit_expression(element);
+	}
+}
+
+pub fn walk_parenthesized_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::ParenthesizedExpression,
+) {
+	visitor.visit_expression(&node.expression);
+}
+
+pub fn walk_ts_as_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::TsAsExpression,
+) {
+	visitor.visit_expression(&node.expression);
+	visitor.visit_ts_type(&node.type_annotation);
+}
+
+pub fn walk_ts_satisfies_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::TsSatisfiesExpression,
+) {
+	visitor.visit_expression(&node.expression);
+	visitor.visit_ts_type(&node.type_annotation);
+}
+
+pub fn walk_ts_non_null_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::TsNonNullExpression,
+) {
+	visitor.visit_expression(&node.expression);
+}
+
+pub fn walk_ts_annotated_expression<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::TsAnnotatedExpression,
+) {
+	visitor.visit_expression(&node.expression);
+
+	if let Some(type_annotation) = &node.type_annotation {
+		visitor.visit_ts_type(type_annotation);
+	}
+}
+
+pub fn walk_statement<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::Statement,
+) {
+	match node {
+		javascript::Statement::Block(node) => visitor.visit_block_statement(node),
+		javascript::Statement::Expression(node) => visitor.visit_expression_statement(node),
+		javascript::Statement::Variable(node) => visitor.visit_variable_declaration(node),
+		javascript::Statement::Return(node) => visitor.visit_return_statement(node),
+		javascript::Statement::If(node) => visitor.visit_if_statement(node),
+		javascript::Statement::Throw(node) => visitor.visit_throw_statement(node),
+		javascript::Statement::Try(node) => visitor.visit_try_statement(node),
+		javascript::Statement::Empty(node) => visitor.visit_empty_statement(node),
+	}
+}
+
+pub fn walk_block_statement<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::BlockStatement,
+) {
+	for statement in &node.body {
+		visitor.visit_statement(statement);
+	}
+}
+
+pub fn walk_expression_statement<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::ExpressionStatement,
+) {
+	visitor.visit_expression(&node.expression);
+}
+
+pub fn walk_variable_declaration<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::VariableDeclaration,
+) {
+	for declarator in &node.declarations {
+		visitor.visit_variable_declarator(declarator);
+	}
+}
+
+pub fn walk_variable_declarator<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::VariableDeclarator,
+) {
+	visitor.visit_expression(&node.id);
+
+	if let Some(init) = &node.init {
+		visitor.visit_expression(init);
+	}
+}
+
+pub fn walk_return_statement<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::ReturnStatement,
+) {
+	if let Some(argument) = &node.argument {
+		visitor.visit_expression(argument);
+	}
+}
+
+pub fn walk_if_statement<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::IfStatement,
+) {
+	visitor.visit_expression(&node.condition);
+	visitor.visit_statement(&node.consequent);
+
+	if let Some(alternate) = &node.alternate {
+		visitor.visit_statement(alternate);
+	}
+}
+
+pub fn walk_throw_statement<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::ThrowStatement,
+) {
+	visitor.visit_expression(&node.argument);
+}
+
+pub fn walk_try_statement<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::TryStatement,
+) {
+	visitor.visit_block_statement(&node.block);
+
+	if let Some(handler) = &node.handler {
+		visitor.visit_catch_clause(handler);
+	}
+
+	if let Some(finalizer) = &node.finalizer {
+		visitor.visit_block_statement(finalizer);
+	}
+}
+
+pub fn walk_catch_clause<'ast, V: Visit<'ast> + ?Sized>(
+	visitor: &mut V,
+	node: &'ast javascript::CatchClause,
+) {
+	if let Some(parameter) = &node.parameter {
+		visitor.visit_expression(parameter);
+	}
+
+	visitor.visit_block_statement(&node.body);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[derive(Default)]
+	struct Identifiers<'ast> {
+		names: Vec<&'ast str>,
+	}
+
+	impl<'ast> Visit<'ast> for Identifiers<'ast> {
+		fn visit_identifier_expression(&mut self, node: &'ast javascript::IdentifierExpression) {
+			self.names.push(&node.name);
+		}
+
+		fn visit_function_expression(&mut self, node: &'ast javascript::FunctionExpression) {
+			// Skip the parameters.
+			self.visit_function_body(&node.body);
+		}
+	}
+
+	#[test]
+	fn test_visit() {
+		let document = crate::parse_document(
+			"<div class={a} {b} {...c}>{d(e)}<#when {f}>{(x) => g}<#else when {h}></when><#each {i in j}>{{ k: l, [m]: n }}</each>{`${o}`}{p`${q}`}</div>",
+		)
+		.unwrap();
+
+		let mut visitor = Identifiers::default();
+		visitor.visit_document(&document);
+
+		assert_eq!(
+			visitor.names,
+			["a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p", "q"]
+		);
+	}
+
+	#[test]
+	fn test_visit_dependency_statement() {
+		#[derive(Default)]
+		struct Names(Vec<String>);
+
+		impl Visit<'_> for Names {
+			fn visit_identifier(&mut self, node: &ast::Identifier) {
+				self.0.push(node.name.to_string());
+			}
+
+			fn visit_string_literal(&mut self, node: &ast::StringLiteral) {
+				self.0.push(node.value.to_string());
+			}
+		}
+
+		let document = crate::parse_document("using a b, { c d as e } from 'f'\n<p />").unwrap();
+
+		let mut visitor = Names::default();
+		visitor.visit_document(&document);
+
+		assert_eq!(visitor.0, ["a", "b", "c", "d", "e", "f", "p"]);
+	}
+}
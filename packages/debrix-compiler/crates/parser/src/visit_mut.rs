@@ -0,0 +1,996 @@
+//! Traversal of the syntax tree by mutable reference, see [`VisitMut`].
+
+use crate::ast::{self, javascript};
+
+/// Walks the syntax tree by mutable reference, to transform it in place.
+///
+/// Every `visit_*` method walks the children of its node by default, with the `walk_*` function
+/// of the same name. Implementors override the methods of the nodes they care about, and call
+/// the `walk_*` function to keep walking into the children.
+pub trait VisitMut {
+	fn visit_document(&mut self, node: &mut ast::Document) {
+		walk_document(self, node);
+	}
+
+	fn visit_node(&mut self, node: &mut ast::Node) {
+		walk_node(self, node);
+	}
+
+	fn visit_dependency_statement(&mut self, node: &mut ast::DependencyStatement) {
+		walk_dependency_statement(self, node);
+	}
+
+	fn visit_dependency_default_specifier(&mut self, node: &mut ast::DependencyDefaultSpecifier) {
+		walk_dependency_default_specifier(self, node);
+	}
+
+	fn visit_dependency_named_specifier(&mut self, node: &mut ast::DependencyNamedSpecifier) {
+		walk_dependency_named_specifier(self, node);
+	}
+
+	fn visit_comment(&mut self, _node: &mut ast::Comment) {}
+
+	fn visit_element(&mut self, node: &mut ast::Element) {
+		walk_element(self, node);
+	}
+
+	fn visit_attribute(&mut self, node: &mut ast::Attribute) {
+		walk_attribute(self, node);
+	}
+
+	fn visit_static_attribute(&mut self, node: &mut ast::StaticAttribute) {
+		walk_static_attribute(self, node);
+	}
+
+	fn visit_binding_attribute(&mut self, node: &mut ast::BindingAttribute) {
+		walk_binding_attribute(self, node);
+	}
+
+	fn visit_spread_attribute(&mut self, node: &mut ast::SpreadAttribute) {
+		walk_spread_attribute(self, node);
+	}
+
+	fn visit_short_binding_attribute(&mut self, node: &mut ast::ShortBindingAttribute) {
+		walk_short_binding_attribute(self, node);
+	}
+
+	fn visit_text(&mut self, _node: &mut ast::Text) {}
+
+	fn visit_text_binding(&mut self, node: &mut ast::TextBinding) {
+		walk_text_binding(self, node);
+	}
+
+	fn visit_flow_control(&mut self, node: &mut ast::FlowControl) {
+		walk_flow_control(self, node);
+	}
+
+	fn visit_flow_control_when(&mut self, node: &mut ast::FlowControlWhen) {
+		walk_flow_control_when(self, node);
+	}
+
+	fn visit_flow_control_else(&mut self, node: &mut ast::FlowControlElse) {
+		walk_flow_control_else(self, node);
+	}
+
+	fn visit_flow_control_each(&mut self, node: &mut ast::FlowControlEach) {
+		walk_flow_control_each(self, node);
+	}
+
+	fn visit_flow_control_let(&mut self, node: &mut ast::FlowControlLet) {
+		walk_flow_control_let(self, node);
+	}
+
+	fn visit_flow_control_match(&mut self, node: &mut ast::FlowControlMatch) {
+		walk_flow_control_match(self, node);
+	}
+
+	fn visit_flow_control_case(&mut self, node: &mut ast::FlowControlCase) {
+		walk_flow_control_case(self, node);
+	}
+
+	fn visit_flow_control_await(&mut self, node: &mut ast::FlowControlAwait) {
+		walk_flow_control_await(self, node);
+	}
+
+	fn visit_flow_control_await_branch(&mut self, node: &mut ast::FlowControlAwaitBranch) {
+		walk_flow_control_await_branch(self, node);
+	}
+
+	fn visit_flow_control_try(&mut self, node: &mut ast::FlowControlTry) {
+		walk_flow_control_try(self, node);
+	}
+
+	fn visit_invalid(&mut self, _node: &mut ast::Invalid) {}
+
+	fn visit_identifier(&mut self, _node: &mut ast::Identifier) {}
+
+	fn visit_literal(&mut self, node: &mut ast::Literal) {
+		walk_literal(self, node);
+	}
+
+	fn visit_string_literal(&mut self, _node: &mut ast::StringLiteral) {}
+
+	fn visit_number_literal(&mut self, _node: &mut ast::NumberLiteral) {}
+
+	fn visit_boolean_literal(&mut self, _node: &mut ast::BooleanLiteral) {}
+
+	fn visit_null_literal(&mut self, _node: &mut ast::NullLiteral) {}
+
+	fn visit_expression(&mut self, node: &mut javascript::Expression) {
+		walk_expression(self, node);
+	}
+
+	fn visit_identifier_expression(&mut self, _node: &mut javascript::IdentifierExpression) {}
+
+	fn visit_javascript_literal(&mut self, node: &mut javascript::Literal) {
+		walk_javascript_literal(self, node);
+	}
+
+	fn visit_javascript_string_literal(&mut self, _node: &mut javascript::StringLiteral) {}
+
+	fn visit_javascript_number_literal(&mut self, _node: &mut javascript::NumberLiteral) {}
+
+	fn visit_javascript_bigint_literal(&mut self, _node: &mut javascript::BigIntLiteral) {}
+
+	fn visit_javascript_boolean_literal(&mut self, _node: &mut javascript::BooleanLiteral) {}
+
+	fn visit_javascript_null_literal(&mut self, _node: &mut javascript::NullLiteral) {}
+
+	fn visit_javascript_regexp_literal(&mut self, _node: &mut javascript::RegExpLiteral) {}
+
+	fn visit_this_expression(&mut self, _node: &mut javascript::ThisExpression) {}
+
+	fn visit_unary_expression(&mut self, node: &mut javascript::UnaryExpression) {
+		walk_unary_expression(self, node);
+	}
+
+	fn visit_binary_expression(&mut self, node: &mut javascript::BinaryExpression) {
+		walk_binary_expression(self, node);
+	}
+
+	fn visit_conditional_expression(&mut self, node: &mut javascript::ConditionalExpression) {
+		walk_conditional_expression(self, node);
+	}
+
+	fn visit_call_expression(&mut self, node: &mut javascript::CallExpression) {
+		walk_call_expression(self, node);
+	}
+
+	fn visit_new_expression(&mut self, node: &mut javascript::NewExpression) {
+		walk_new_expression(self, node);
+	}
+
+	fn visit_member_expression(&mut self, node: &mut javascript::MemberExpression) {
+		walk_member_expression(self, node);
+	}
+
+	fn visit_await_expression(&mut self, node: &mut javascript::AwaitExpression) {
+		walk_await_expression(self, node);
+	}
+
+	fn visit_sequence_expression(&mut self, node: &mut javascript::SequenceExpression) {
+		walk_sequence_expression(self, node);
+	}
+
+	fn visit_function_expression(&mut self, node: &mut javascript::FunctionExpression) {
+		walk_function_expression(self, node);
+	}
+
+	fn visit_function_body(&mut self, node: &mut javascript::FunctionBody) {
+		walk_function_body(self, node);
+	}
+
+	fn visit_assignment_expression(&mut self, node: &mut javascript::AssignmentExpression) {
+		walk_assignment_expression(self, node);
+	}
+
+	fn visit_spread_expression(&mut self, node: &mut javascript::SpreadExpression) {
+		walk_spread_expression(self, node);
+	}
+
+	fn visit_template_literal(&mut self, node: &mut javascript::TemplateLiteral) {
+		walk_template_literal(self, node);
+	}
+
+	fn visit_template_element(&mut self, _node: &mut javascript::TemplateElement) {}
+
+	fn visit_tagged_template_expression(
+		&mut self,
+		node: &mut javascript::TaggedTemplateExpression,
+	) {
+		walk_tagged_template_expression(self, node);
+	}
+
+	fn visit_object_expression(&mut self, node: &mut javascript::ObjectExpression) {
+		walk_object_expression(self, node);
+	}
+
+	fn visit_object_property(&mut self, node: &mut javascript::ObjectProperty) {
+		walk_object_property(self, node);
+	}
+
+	fn visit_object_keyed_property(&mut self, node: &mut javascript::ObjectKeyedProperty) {
+		walk_object_keyed_property(self, node);
+	}
+
+	fn visit_object_computed_property(&mut self, node: &mut javascript::ObjectComputedProperty) {
+		walk_object_computed_property(self, node);
+	}
+
+	fn visit_array_expression(&mut self, node: &mut javascript::ArrayExpression) {
+		walk_array_expression(self, node);
+	}
+
+	fn visit_parenthesized_expression(&mut self, node: &mut javascript::ParenthesizedExpression) {
+		walk_parenthesized_expression(self, node);
+	}
+
+	fn visit_empty_expression(&mut self, _node: &mut javascript::EmptyExpression) {}
+
+	fn visit_ts_as_expression(&mut self, node: &mut javascript::TsAsExpression) {
+		walk_ts_as_expression(self, node);
+	}
+
+	fn visit_ts_satisfies_expression(&mut self, node: &mut javascript::TsSatisfiesExpression) {
+		walk_ts_satisfies_expression(self, node);
+	}
+
+	fn visit_ts_non_null_expression(&mut self, node: &mut javascript::TsNonNullExpression) {
+		walk_ts_non_null_expression(self, node);
+	}
+
+	fn visit_ts_annotated_expression(&mut self, node: &mut javascript::TsAnnotatedExpression) {
+		walk_ts_annotated_expression(self, node);
+	}
+
+	fn visit_ts_type(&mut self, _node: &mut javascript::TsType) {}
+
+	fn visit_statement(&mut self, node: &mut javascript::Statement) {
+		walk_statement(self, node);
+	}
+
+	fn visit_block_statement(&mut self, node: &mut javascript::BlockStatement) {
+		walk_block_statement(self, node);
+	}
+
+	fn visit_expression_statement(&mut self, node: &mut javascript::ExpressionStatement) {
+		walk_expression_statement(self, node);
+	}
+
+	fn visit_variable_declaration(&mut self, node: &mut javascript::VariableDeclaration) {
+		walk_variable_declaration(self, node);
+	}
+
+	fn visit_variable_declarator(&mut self, node: &mut javascript::VariableDeclarator) {
+		walk_variable_declarator(self, node);
+	}
+
+	fn visit_return_statement(&mut self, node: &mut javascript::ReturnStatement) {
+		walk_return_statement(self, node);
+	}
+
+	fn visit_if_statement(&mut self, node: &mut javascript::IfStatement) {
+		walk_if_statement(self, node);
+	}
+
+	fn visit_throw_statement(&mut self, node: &mut javascript::ThrowStatement) {
+		walk_throw_statement(self, node);
+	}
+
+	fn visit_try_statement(&mut self, node: &mut javascript::TryStatement) {
+		walk_try_statement(self, node);
+	}
+
+	fn visit_catch_clause(&mut self, node: &mut javascript::CatchClause) {
+		walk_catch_clause(self, node);
+	}
+
+	fn visit_empty_statement(&mut self, _node: &mut javascript::EmptyStatement) {}
+}
+
+pub fn walk_document<V: VisitMut + ?Sized>(visitor: &mut V, node: &mut ast::Document) {
+	for child in &mut node.children {
+		visitor.visit_node(child);
+	}
+}
+
+pub fn walk_node<V: VisitMut + ?Sized>(visitor: &mut V, node: &mut ast::Node) {
+	match node {
+		ast::Node::DependencyStatement(node) => visitor.visit_dependency_statement(node),
+		ast::Node::Comment(node) => visitor.visit_comment(node),
+		ast::Node::Element(node) => visitor.visit_element(node),
+		ast::Node::Text(node) => visitor.visit_text(node),
+		ast::Node::TextBinding(node) => visitor.visit_text_binding(node),
+		ast::Node::FlowControl(node) => visitor.visit_flow_control(node),
+		ast::Node::Invalid(node) => visitor.visit_invalid(node),
+	}
+}
+
+pub fn walk_dependency_statement<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut ast::DependencyStatement,
+) {
+	if let Some(default) = &mut node.default {
+		visitor.visit_dependency_default_specifier(default);
+	}
+
+	if let Some(named) = &mut node.named {
+		for specifier in &mut named.nodes {
+			visitor.visit_dependency_named_specifier(specifier);
+		}
+	}
+
+	visitor.visit_string_literal(&mut node.source);
+}
+
+pub fn walk_dependency_default_specifier<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut ast::DependencyDefaultSpecifier,
+) {
+	visitor.visit_identifier(&mut node.usage);
+
+	if let Some(local) = &mut node.local {
+		visitor.visit_identifier(local);
+	}
+}
+
+pub fn walk_dependency_named_specifier<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut ast::DependencyNamedSpecifier,
+) {
+	visitor.visit_identifier(&mut node.usage);
+	visitor.visit_identifier(&mut node.imported);
+
+	if let Some(local) = &mut node.local {
+		visitor.visit_identifier(local);
+	}
+}
+
+pub fn walk_element<V: VisitMut + ?Sized>(visitor: &mut V, node: &mut ast::Element) {
+	visitor.visit_identifier(&mut node.tag_name);
+
+	for attribute in &mut node.attributes {
+		visitor.visit_attribute(attribute);
+	}
+
+	for child in &mut node.children {
+		visitor.visit_node(child);
+	}
+}
+
+pub fn walk_attribute<V: VisitMut + ?Sized>(visitor: &mut V, node: &mut ast::Attribute) {
+	match node {
+		ast::Attribute::Static(node) => visitor.visit_static_attribute(node),
+		ast::Attribute::Binding(node) => visitor.visit_binding_attribute(node),
+		ast::Attribute::Spread(node) => visitor.visit_spread_attribute(node),
+		ast::Attribute::ShortBinding(node) => visitor.visit_short_binding_attribute(node),
+	}
+}
+
+pub fn walk_static_attribute<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut ast::StaticAttribute,
+) {
+	visitor.visit_identifier(&mut node.name);
+
+	if let Some(value) = &mut node.value {
+		visitor.visit_string_literal(value);
+	}
+}
+
+pub fn walk_binding_attribute<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut ast::BindingAttribute,
+) {
+	visitor.visit_identifier(&mut node.name);
+	visitor.visit_expression(&mut node.value);
+}
+
+pub fn walk_spread_attribute<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut ast::SpreadAttribute,
+) {
+	visitor.visit_expression(&mut node.value);
+}
+
+pub fn walk_short_binding_attribute<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut ast::ShortBindingAttribute,
+) {
+	visitor.visit_identifier_expression(&mut node.name);
+}
+
+pub fn walk_text_binding<V: VisitMut + ?Sized>(visitor: &mut V, node: &mut ast::TextBinding) {
+	visitor.visit_expression(&mut node.expression);
+}
+
+pub fn walk_flow_control<V: VisitMut + ?Sized>(visitor: &mut V, node: &mut ast::FlowControl) {
+	match node {
+		ast::FlowControl::When(node) => visitor.visit_flow_control_when(node),
+		ast::FlowControl::Each(node) => visitor.visit_flow_control_each(node),
+		ast::FlowControl::Let(node) => visitor.visit_flow_control_let(node),
+		ast::FlowControl::Match(node) => visitor.visit_flow_control_match(node),
+		ast::FlowControl::Await(node) => visitor.visit_flow_control_await(node),
+		ast::FlowControl::Try(node) => visitor.visit_flow_control_try(node),
+	}
+}
+
+pub fn walk_flow_control_when<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut ast::FlowControlWhen,
+) {
+	visitor.visit_expression(&mut node.condition);
+
+	for child in &mut node.children {
+		visitor.visit_node(child);
+	}
+
+	for chained in &mut node.chain {
+		visitor.visit_flow_control_else(chained);
+	}
+}
+
+pub fn walk_flow_control_else<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut ast::FlowControlElse,
+) {
+	if let Some(condition) = &mut node.condition {
+		visitor.visit_expression(condition);
+	}
+
+	for child in &mut node.children {
+		visitor.visit_node(child);
+	}
+}
+
+pub fn walk_flow_control_each<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut ast::FlowControlEach,
+) {
+	visitor.visit_expression(&mut node.iterator);
+
+	if let Some(index) = &mut node.index {
+		visitor.visit_identifier_expression(index);
+	}
+
+	visitor.visit_expression(&mut node.iterable);
+
+	if let Some(key) = &mut node.key {
+		visitor.visit_expression(key);
+	}
+
+	for child in &mut node.children {
+		visitor.visit_node(child);
+	}
+
+	if let Some(fallback) = &mut node.fallback {
+		visitor.visit_flow_control_else(fallback);
+	}
+}
+
+pub fn walk_flow_control_let<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut ast::FlowControlLet,
+) {
+	visitor.visit_identifier_expression(&mut node.name);
+	visitor.visit_expression(&mut node.value);
+
+	for child in &mut node.children {
+		visitor.visit_node(child);
+	}
+}
+
+pub fn walk_flow_control_match<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut ast::FlowControlMatch,
+) {
+	visitor.visit_expression(&mut node.discriminant);
+
+	for case in &mut node.cases {
+		visitor.visit_flow_control_case(case);
+	}
+}
+
+pub fn walk_flow_control_case<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut ast::FlowControlCase,
+) {
+	for test in &mut node.tests {
+		visitor.visit_expression(test);
+	}
+
+	for child in &mut node.children {
+		visitor.visit_node(child);
+	}
+}
+
+pub fn walk_flow_control_await<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut ast::FlowControlAwait,
+) {
+	visitor.visit_expression(&mut node.promise);
+
+	for child in &mut node.children {
+		visitor.visit_node(child);
+	}
+
+	if let Some(then) = &mut node.then {
+		visitor.visit_flow_control_await_branch(then);
+	}
+
+	if let Some(catch) = &mut node.catch {
+		visitor.visit_flow_control_await_branch(catch);
+	}
+}
+
+pub fn walk_flow_control_await_branch<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut ast::FlowControlAwaitBranch,
+) {
+	if let Some(binding) = &mut node.binding {
+		visitor.visit_expression(binding);
+	}
+
+	for child in &mut node.children {
+		visitor.visit_node(child);
+	}
+}
+
+pub fn walk_flow_control_try<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut ast::FlowControlTry,
+) {
+	for child in &mut node.children {
+		visitor.visit_node(child);
+	}
+
+	visitor.visit_flow_control_await_branch(&mut node.catch);
+}
+
+pub fn walk_literal<V: VisitMut + ?Sized>(visitor: &mut V, node: &mut ast::Literal) {
+	match node {
+		ast::Literal::Number(node) => visitor.visit_number_literal(node),
+		ast::Literal::String(node) => visitor.visit_string_literal(node),
+		ast::Literal::Boolean(node) => visitor.visit_boolean_literal(node),
+		ast::Literal::Null(node) => visitor.visit_null_literal(node),
+	}
+}
+
+pub fn walk_expression<V: VisitMut + ?Sized>(visitor: &mut V, node: &mut javascript::Expression) {
+	match node {
+		javascript::Expression::Identifier(node) => visitor.visit_identifier_expression(node),
+		javascript::Expression::Literal(node) => visitor.visit_javascript_literal(node),
+		javascript::Expression::This(node) => visitor.visit_this_expression(node),
+		javascript::Expression::Unary(node) => visitor.visit_unary_expression(node),
+		javascript::Expression::Binary(node) => visitor.visit_binary_expression(node),
+		javascript::Expression::Conditional(node) => visitor.visit_conditional_expression(node),
+		javascript::Expression::Call(node) => visitor.visit_call_expression(node),
+		javascript::Expression::New(node) => visitor.visit_new_expression(node),
+		javascript::Expression::Member(node) => visitor.visit_member_expression(node),
+		javascript::Expression::Sequence(node) => visitor.visit_sequence_expression(node),
+		javascript::Expression::Await(node) => visitor.visit_await_expression(node),
+		javascript::Expression::Function(node) => visitor.visit_function_expression(node),
+		javascript::Expression::Assignment(node) => visitor.visit_assignment_expression(node),
+		javascript::Expression::Spread(node) => visitor.visit_spread_expression(node),
+		javascript::Expression::Template(node) => visitor.visit_template_literal(node),
+		javascript::Expression::TaggedTemplate(node) => {
+			visitor.visit_tagged_template_expression(node)
+		}
+		javascript::Expression::Object(node) => visitor.visit_object_expression(node),
+		javascript::Expression::Array(node) => visitor.visit_array_expression(node),
+		javascript::Expression::Parenthesized(node) => visitor.visit_parenthesized_expression(node),
+		javascript::Expression::Empty(node) => visitor.visit_empty_expression(node),
+		javascript::Expression::TsAs(node) => visitor.visit_ts_as_expression(node),
+		javascript::Expression::TsSatisfies(node) => visitor.visit_ts_satisfies_expression(node),
+		javascript::Expression::TsNonNull(node) => visitor.visit_ts_non_null_expression(node),
+		javascript::Expression::TsAnnotated(node) => visitor.visit_ts_annotated_expression(node),
+	}
+}
+
+pub fn walk_javascript_literal<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::Literal,
+) {
+	match node {
+		javascript::Literal::String(node) => visitor.visit_javascript_string_literal(node),
+		javascript::Literal::Number(node) => visitor.visit_javascript_number_literal(node),
+		javascript::Literal::BigInt(node) => visitor.visit_javascript_bigint_literal(node),
+		javascript::Literal::Boolean(node) => visitor.visit_javascript_boolean_literal(node),
+		javascript::Literal::Null(node) => visitor.visit_javascript_null_literal(node),
+		javascript::Literal::RegExp(node) => visitor.visit_javascript_regexp_literal(node),
+	}
+}
+
+pub fn walk_unary_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::UnaryExpression,
+) {
+	visitor.visit_expression(&mut node.operand);
+}
+
+pub fn walk_binary_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::BinaryExpression,
+) {
+	visitor.visit_expression(&mut node.left);
+	visitor.visit_expression(&mut node.right);
+}
+
+pub fn walk_conditional_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::ConditionalExpression,
+) {
+	visitor.visit_expression(&mut node.condition);
+	visitor.visit_expression(&mut node.consequent);
+	visitor.visit_expression(&mut node.alternate);
+}
+
+pub fn walk_call_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::CallExpression,
+) {
+	visitor.visit_expression(&mut node.callee);
+
+	for argument in &mut node.arguments {
+		visitor.visit_expression(argument);
+	}
+}
+
+pub fn walk_new_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::NewExpression,
+) {
+	visitor.visit_expression(&mut node.callee);
+
+	for argument in &mut node.arguments {
+		visitor.visit_expression(argument);
+	}
+}
+
+pub fn walk_member_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::MemberExpression,
+) {
+	visitor.visit_expression(&mut node.object);
+	visitor.visit_expression(&mut node.property);
+}
+
+pub fn walk_await_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::AwaitExpression,
+) {
+	visitor.visit_expression(&mut node.argument);
+}
+
+pub fn walk_sequence_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::SequenceExpression,
+) {
+	for expression in &mut node.expressions {
+		visitor.visit_expression(expression);
+	}
+}
+
+pub fn walk_function_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::FunctionExpression,
+) {
+	if let Some(type_parameters) = &mut node.type_parameters {
+		visitor.visit_ts_type(type_parameters);
+	}
+
+	for parameter in &mut node.parameters {
+		visitor.visit_expression(parameter);
+	}
+
+	if let Some(return_type) = &mut node.return_type {
+		visitor.visit_ts_type(return_type);
+	}
+
+	visitor.visit_function_body(&mut node.body);
+}
+
+pub fn walk_function_body<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::FunctionBody,
+) {
+	match node {
+		javascript::FunctionBody::Expression(node) => visitor.visit_expression(node),
+		javascript::FunctionBody::Block(node) => visitor.visit_block_statement(node),
+	}
+}
+
+pub fn walk_assignment_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::AssignmentExpression,
+) {
+	visitor.visit_expression(&mut node.left);
+	visitor.visit_expression(&mut node.right);
+}
+
+pub fn walk_spread_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::SpreadExpression,
+) {
+	visitor.visit_expression(&mut node.argument);
+}
+
+pub fn walk_template_literal<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::TemplateLiteral,
+) {
+	for (i, quasi) in node.quasis.iter_mut().enumerate() {
+		visitor.visit_template_element(quasi);
+
+		if let Some(expression) = node.expressions.get_mut(i) {
+			visitor.visit_expression(expression);
+		}
+	}
+}
+
+pub fn walk_tagged_template_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::TaggedTemplateExpression,
+) {
+	visitor.visit_expression(&mut node.tag);
+	visitor.visit_template_literal(&mut node.quasi);
+}
+
+pub fn walk_object_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::ObjectExpression,
+) {
+	for property in &mut node.properties {
+		visitor.visit_object_property(property);
+	}
+}
+
+pub fn walk_object_property<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::ObjectProperty,
+) {
+	match node {
+		javascript::ObjectProperty::Keyed(node) => visitor.visit_object_keyed_property(node),
+		javascript::ObjectProperty::Computed(node) => visitor.visit_object_computed_property(node),
+		javascript::ObjectProperty::Spread(node) => visitor.visit_spread_expression(node),
+	}
+}
+
+pub fn walk_object_keyed_property<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::ObjectKeyedProperty,
+) {
+	visitor.visit_identifier_expression(&mut node.key);
+
+	if let Some(value) = &mut node.value {
+		visitor.visit_expression(value);
+	}
+}
+
+pub fn walk_object_computed_property<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::ObjectComputedProperty,
+) {
+	visitor.visit_expression(&mut node.key);
+	visitor.visit_expression(&mut node.value);
+}
+
+pub fn walk_array_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::ArrayExpression,
+) {
+	for element in &mut node.elements {
+		visitor.visit_expression(element);
+	}
+}
+
+pub fn walk_parenthesized_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::ParenthesizedExpression,
+) {
+	visitor.visit_expression(&mut node.expression);
+}
+
+pub fn walk_ts_as_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::TsAsExpression,
+) {
+	visitor.visit_expression(&mut node.expression);
+	visitor.visit_ts_type(&mut node.type_annotation);
+}
+
+pub fn walk_ts_satisfies_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::TsSatisfiesExpression,
+) {
+	visitor.visit_expression(&mut node.expression);
+	visitor.visit_ts_type(&mut node.type_annotation);
+}
+
+pub fn walk_ts_non_null_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::TsNonNullExpression,
+) {
+	visitor.visit_expression(&mut node.expression);
+}
+
+pub fn walk_ts_annotated_expression<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::TsAnnotatedExpression,
+) {
+	visitor.visit_expression(&mut node.expression);
+
+	if let Some(type_annotation) = &mut node.type_annotation {
+		visitor.visit_ts_type(type_annotation);
+	}
+}
+
+pub fn walk_statement<V: VisitMut + ?Sized>(visitor: &mut V, node: &mut javascript::Statement) {
+	match node {
+		javascript::Statement::Block(node) => visitor.visit_block_statement(node),
+		javascript::Statement::Expression(node) => visitor.visit_expression_statement(node),
+		javascript::Statement::Variable(node) => visitor.visit_variable_declaration(node),
+		javascript::Statement::Return(node) => visitor.visit_return_statement(node),
+		javascript::Statement::If(node) => visitor.visit_if_statement(node),
+		javascript::Statement::Throw(node) => visitor.visit_throw_statement(node),
+		javascript::Statement::Try(node) => visitor.visit_try_statement(node),
+		javascript::Statement::Empty(node) => visitor.visit_empty_statement(node),
+	}
+}
+
+pub fn walk_block_statement<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::BlockStatement,
+) {
+	for statement in &mut node.body {
+		visitor.visit_statement(statement);
+	}
+}
+
+pub fn walk_expression_statement<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::ExpressionStatement,
+) {
+	visitor.visit_expression(&mut node.expression);
+}
+
+pub fn walk_variable_declaration<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::VariableDeclaration,
+) {
+	for declarator in &mut node.declarations {
+		visitor.visit_variable_declarator(declarator);
+	}
+}
+
+pub fn walk_variable_declarator<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::VariableDeclarator,
+) {
+	visitor.visit_expression(&mut node.id);
+
+	if let Some(init) = &mut node.init {
+		visitor.visit_expression(init);
+	}
+}
+
+pub fn walk_return_statement<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::ReturnStatement,
+) {
+	if let Some(argument) = &mut node.argument {
+		visitor.visit_expression(argument);
+	}
+}
+
+pub fn walk_if_statement<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::IfStatement,
+) {
+	visitor.visit_expression(&mut node.condition);
+	visitor.visit_statement(&mut node.consequent);
+
+	if let Some(alternate) = &mut node.alternate {
+		visitor.visit_statement(alternate);
+	}
+}
+
+pub fn walk_throw_statement<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::ThrowStatement,
+) {
+	visitor.visit_expression(&mut node.argument);
+}
+
+pub fn walk_try_statement<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::TryStatement,
+) {
+	visitor.visit_block_statement(&mut node.block);
+
+	if let Some(handler) = &mut node.handler {
+		visitor.visit_catch_clause(handler);
+	}
+
+	if let Some(finalizer) = &mut node.finalizer {
+		visitor.visit_block_statement(finalizer);
+	}
+}
+
+pub fn walk_catch_clause<V: VisitMut + ?Sized>(
+	visitor: &mut V,
+	node: &mut javascript::CatchClause,
+) {
+	if let Some(parameter) = &mut node.parameter {
+		visitor.visit_expression(parameter);
+	}
+
+	visitor.visit_block_statement(&mut node.body);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	struct Rename;
+
+	impl VisitMut for Rename {
+		fn visit_identifier_expression(&mut self, node: &mut javascript::IdentifierExpression) {
+			node.name = format!("self.{}", node.name).into();
+		}
+
+		fn visit_element(&mut self, node: &mut ast::Element) {
+			node.attributes.clear();
+			walk_element(self, node);
+		}
+	}
+
+	#[test]
+	fn test_visit_mut() {
+		let mut document =
+			crate::parse_document("<div class={a}><p title={b}>{c + d.e}</p></div>").unwrap();
+
+		Rename.visit_document(&mut document);
+
+		let div = match &document.children[0] {
+			ast::Node::Element(node) => node,
+			_ => panic!("expected element"),
+		};
+
+		assert!(div.attributes.is_empty());
+
+		let p = match &div.children[0] {
+			ast::Node::Element(node) => node,
+			_ => panic!("expected element"),
+		};
+
+		assert!(p.attributes.is_empty());
+
+		let binary = match &p.children[0] {
+			ast::Node::TextBinding(node) => match &node.expression {
+				javascript::Expression::Binary(expr) => expr,
+				_ => panic!("expected binary expression"),
+			},
+			_ => panic!("expected text binding"),
+		};
+
+		match (&*binary.left, &*binary.right) {
+			(javascript::Expression::Identifier(left), javascript::Expression::Member(right)) => {
+				assert_eq!(left.name, "self.c");
+
+				match (&*right.object, &*right.property) {
+					(
+						javascript::Expression::Identifier(object),
+						javascript::Expression::Identifier(property),
+					) => {
+						assert_eq!(object.name, "self.d");
+						// Properties are identifiers too, it is up to the visitor to skip them.
+						assert_eq!(property.name, "self.e");
+					}
+					_ => panic!("expected identifiers"),
+				}
+			}
+			_ => panic!("expected identifier and member expression"),
+		}
+	}
+}
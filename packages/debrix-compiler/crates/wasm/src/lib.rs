@@ -9,6 +9,15 @@ pub fn int_to_target(int: usize) -> debrix_compiler::Target {
 	}
 }
 
+pub fn int_to_encoding(int: usize) -> debrix_compiler::Encoding {
+	match int {
+		0 => debrix_compiler::Encoding::Char,
+		1 => debrix_compiler::Encoding::Utf8,
+		2 => debrix_compiler::Encoding::Utf16,
+		_ => unreachable!(),
+	}
+}
+
 fn serialize_chunk(chunk: &debrix_compiler::Chunk) -> js_sys::Object {
 	let serialized = js_sys::Object::new();
 
@@ -28,6 +37,24 @@ fn serialize_chunk(chunk: &debrix_compiler::Chunk) -> js_sys::Object {
 	serialized
 }
 
+#[allow(unused_must_use)]
+fn serialize_location(location: &debrix_compiler::Location) -> js_sys::Object {
+	let serialized = js_sys::Object::new();
+
+	for (name, position) in [("start", &location.start), ("end", &location.end)] {
+		let js_position = js_sys::Object::new();
+		js_sys::Reflect::set(&js_position, &"line".into(), &(position.line as f64).into());
+		js_sys::Reflect::set(
+			&js_position,
+			&"column".into(),
+			&(position.column as f64).into(),
+		);
+		js_sys::Reflect::set(&serialized, &name.into(), &js_position);
+	}
+
+	serialized
+}
+
 #[allow(unused_must_use)]
 fn serialize_error(error: &debrix_compiler::Error) -> js_sys::Object {
 	let serialized = js_sys::Object::new();
@@ -38,6 +65,11 @@ fn serialize_error(error: &debrix_compiler::Error) -> js_sys::Object {
 			js_sys::Reflect::set(&serialized, &"message".into(), &format!("{:?}", err).into());
 			js_sys::Reflect::set(&serialized, &"start".into(), &(err.start as f64).into());
 			js_sys::Reflect::set(&serialized, &"end".into(), &(err.end as f64).into());
+			js_sys::Reflect::set(
+				&serialized,
+				&"location".into(),
+				&serialize_location(&err.location),
+			);
 			js_sys::Reflect::set(&serialized, &"_message".into(), &err.message.clone().into());
 		}
 		debrix_compiler::Error::ParserError(err) => {
@@ -49,6 +81,11 @@ fn serialize_error(error: &debrix_compiler::Error) -> js_sys::Object {
 			js_sys::Reflect::set(&serialized, &"type".into(), &1_f64.into());
 			js_sys::Reflect::set(&serialized, &"message".into(), &format!("{:?}", err).into());
 			js_sys::Reflect::set(&serialized, &"start".into(), &(err.position as f64).into());
+			js_sys::Reflect::set(
+				&serialized,
+				&"location".into(),
+				&serialize_location(&err.location),
+			);
 			js_sys::Reflect::set(&serialized, &"positives".into(), &positives);
 		}
 	}
@@ -72,11 +109,32 @@ where
 }
 
 #[wasm_bindgen]
-pub fn build(input: &str, target: usize) -> js_sys::Object {
+pub fn build(input: &str, target: usize, encoding: usize, typescript: bool) -> js_sys::Object {
 	let target = int_to_target(target);
+	let encoding = int_to_encoding(encoding);
+
+	let result = if typescript {
+		debrix_compiler::build_typescript(input.to_owned(), target, encoding)
+	} else {
+		debrix_compiler::build_with_encoding(input.to_owned(), target, encoding)
+	};
 
-	serialize_result(match debrix_compiler::build(input.to_owned(), target) {
+	serialize_result(match result {
 		Ok(result) => Ok(serialize_chunk(&result)),
 		Err(err) => Err(serialize_error(&err)),
 	})
 }
+
+#[wasm_bindgen]
+pub fn parse(input: &str, typescript: bool) -> js_sys::Object {
+	let result = if typescript {
+		debrix_compiler::parse_typescript(input.to_owned())
+	} else {
+		debrix_compiler::parse(input.to_owned())
+	};
+
+	serialize_result(match result {
+		Ok(json) => Ok(js_sys::JsString::from(json)),
+		Err(err) => Err(serialize_error(&err)),
+	})
+}
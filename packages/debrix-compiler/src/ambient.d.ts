@@ -5,7 +5,13 @@ declare module 'debrix.wasm.lib' {
 	}
 
 	export function initSync(bytes: unknown): void;
-	export function build(input: string, target: number): WasmBuildResult;
+	export function build(
+		input: string,
+		target: number,
+		encoding: number,
+		typescript: boolean
+	): WasmBuildResult;
+	export function parse(input: string, typescript: boolean): WasmBuildResult;
 }
 
 declare module 'debrix.wasm' {
@@ -14,5 +20,11 @@ declare module 'debrix.wasm' {
 }
 
 declare module 'debrix.node' {
-	export function build(input: string, target: number): any;
+	export function build(
+		input: string,
+		target: number,
+		encoding?: number,
+		typescript?: boolean
+	): any;
+	export function parse(input: string, typescript?: boolean): string;
 }
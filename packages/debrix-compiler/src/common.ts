@@ -12,17 +12,55 @@ export interface Build {
 	mappings: Mapping[];
 }
 
+/** Version of the syntax tree returned by `parse`, bumped on breaking changes. */
+export const SCHEMA_VERSION = 13;
+
+export interface Parse {
+	version: number;
+	document: Document;
+}
+
+export interface Document {
+	type: 'Document';
+	children: Node[];
+}
+
+/**
+ * A node in the syntax tree. Offsets count unicode characters. See the `json`
+ * module of the parser crate for the shape of every node; expressions follow
+ * ESTree.
+ */
+export interface Node {
+	type?: string;
+	start: number;
+	end: number;
+	[key: string]: unknown;
+}
+
 export type Error = CompilerError | ParserError;
 
+/** Lines and columns start at 1. */
+export interface Position {
+	line: number;
+	column: number;
+}
+
+export interface Location {
+	start: Position;
+	end: Position;
+}
+
 export class CompilerError extends Error {
 	start!: number;
 	end!: number;
+	location!: Location;
 	_message!: string;
 }
 
 export class ParserError extends Error {
 	start!: number;
 	end!: never;
+	location!: Location;
 	positives!: string[];
 }
 
@@ -32,7 +70,25 @@ export enum Target {
 	Server,
 }
 
-export function _validate(input: string, target: Target) {
+/**
+ * Unit used for offsets in mappings and errors. Lines and columns in error
+ * locations are counted in the same unit.
+ */
+export enum Encoding {
+	/** Unicode code points. */
+	Char,
+	/** UTF-8 bytes. */
+	Utf8,
+	/** UTF-16 code units, as used by JavaScript strings. */
+	Utf16,
+}
+
+export function _validate(
+	input: string,
+	target: Target,
+	encoding: Encoding = Encoding.Char,
+	typescript = false
+) {
 	if (typeof input !== 'string') throw new Error('invalid input');
 
 	if (
@@ -42,6 +98,16 @@ export function _validate(input: string, target: Target) {
 		target % 1 !== 0
 	)
 		throw new Error('invalid target');
+
+	if (
+		typeof encoding !== 'number' ||
+		encoding < 0 ||
+		encoding > 2 ||
+		encoding % 1 !== 0
+	)
+		throw new Error('invalid encoding');
+
+	if (typeof typescript !== 'boolean') throw new Error('invalid typescript');
 }
 
 export function _createError(obj: InternalErrorObject) {
@@ -50,6 +116,7 @@ export function _createError(obj: InternalErrorObject) {
 		/* compiler error */ let err = new CompilerError(obj.message);
 		err.start = obj.start;
 		err.end = obj.end;
+		err.location = obj.location;
 		err._message = obj._message;
 		return err;
 	}
@@ -58,6 +125,7 @@ export function _createError(obj: InternalErrorObject) {
 		/* parser error */ let err = new ParserError(obj.message);
 		err.start = obj.start;
 		(err.end as number) = obj.end;
+		err.location = obj.location;
 		err.positives = obj.positives;
 		return err;
 	}
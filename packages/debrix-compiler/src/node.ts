@@ -1,7 +1,9 @@
 import {
 	Build,
+	Encoding,
 	InternalBuildObject,
 	InternalErrorObject,
+	Parse,
 	Target,
 	_createError,
 	_validate,
@@ -23,17 +25,12 @@ let worker_filename: string | URL;
 const worker = new Worker(worker_filename);
 worker.unref();
 
-export function build(
-	input: string,
-	target: Target = Target.Client
-): Promise<Build> {
-	return new Promise<Build>((resolve, reject) => {
-		_validate(input, target);
-
+function request<T>(method: string, args: unknown[]): Promise<T> {
+	return new Promise<T>((resolve, reject) => {
 		const handle = nextHandle();
-		const listener = ([_handle, build, error]: [
+		const listener = ([_handle, result, error]: [
 			number,
-			InternalBuildObject | undefined,
+			T | undefined,
 			InternalErrorObject | undefined
 		]) => {
 			if (_handle !== handle) return;
@@ -43,20 +40,50 @@ export function build(
 			if (error !== undefined) {
 				reject(_createError(error));
 			} else {
-				resolve(build!);
+				resolve(result!);
 			}
 		};
 
 		worker.on('message', listener);
-		worker.postMessage([handle, input, target]);
+		worker.postMessage([handle, method, args]);
 	});
 }
 
+export async function build(
+	input: string,
+	target: Target = Target.Client,
+	encoding: Encoding = Encoding.Char,
+	typescript = false
+): Promise<Build> {
+	_validate(input, target, encoding, typescript);
+	return request<InternalBuildObject>('build', [
+		input,
+		target,
+		encoding,
+		typescript,
+	]);
+}
+
+/** Parses the input into the syntax tree, without compiling it. */
+export async function parse(input: string, typescript = false): Promise<Parse> {
+	_validate(input, Target.Client, Encoding.Char, typescript);
+	return JSON.parse(
+		await request<string>('parse', [input, typescript])
+	) as Parse;
+}
+
 export {
 	type Build,
 	CompilerError,
+	type Document,
+	Encoding,
 	type Error,
+	type Location,
 	type Mapping,
+	type Node,
+	type Parse,
 	ParserError,
+	type Position,
+	SCHEMA_VERSION,
 	Target,
 } from './common';
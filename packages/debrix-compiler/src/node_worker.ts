@@ -14,20 +14,22 @@ declare const NATIVE_MODULE_PATH: string;
 const compiler = require(NATIVE_MODULE_PATH) as typeof import('debrix.node');
 
 import { parentPort } from 'node:worker_threads';
-import type { InternalBuildObject, InternalErrorObject } from './common';
+import type { InternalErrorObject } from './common';
 
 parentPort!.on(
 	'message',
-	([handle, input, target]: [number, string, number]) => {
-		let build: InternalBuildObject | undefined,
-			error: InternalErrorObject | undefined;
+	([handle, method, args]: [number, 'build' | 'parse', unknown[]]) => {
+		let result: unknown, error: InternalErrorObject | undefined;
 
 		try {
-			build = compiler.build(input, target);
+			result =
+				method === 'parse'
+					? compiler.parse(...(args as [string, boolean]))
+					: compiler.build(...(args as [string, number, number, boolean]));
 		} catch (err) {
 			error = err;
 		}
 
-		parentPort!.postMessage([handle, build, error]);
+		parentPort!.postMessage([handle, result, error]);
 	}
 );
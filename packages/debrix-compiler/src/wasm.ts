@@ -1,7 +1,9 @@
 import {
 	Build,
+	Encoding,
 	InternalBuildObject,
 	InternalErrorObject,
+	Parse,
 	Target,
 	_createError,
 	_validate,
@@ -33,11 +35,11 @@ function isNodeJs() {
 	return typeof process !== 'undefined';
 }
 
-let _service: ((input: string, target: Target) => Promise<Build>) | undefined;
+type Service = <T>(method: 'build' | 'parse', args: unknown[]) => Promise<T>;
 
-async function ensureService(): Promise<
-	(input: string, target: Target) => Promise<Build>
-> {
+let _service: Service | undefined;
+
+async function ensureService(): Promise<Service> {
 	if (_service !== undefined) return _service;
 
 	if (isNodeJs()) {
@@ -51,12 +53,12 @@ async function ensureService(): Promise<
 		const worker = new worker_threads.Worker(workerText, { eval: true });
 		worker.unref();
 
-		return (_service = (input, target) =>
-			new Promise((resolve, reject) => {
+		return (_service = <T>(method: 'build' | 'parse', args: unknown[]) =>
+			new Promise<T>((resolve, reject) => {
 				const handle = nextHandle();
-				const listener = ([_handle, build, error]: [
+				const listener = ([_handle, result, error]: [
 					number,
-					InternalBuildObject | undefined,
+					T | undefined,
 					InternalErrorObject | undefined
 				]) => {
 					if (_handle !== handle) return;
@@ -66,12 +68,12 @@ async function ensureService(): Promise<
 					if (error !== undefined) {
 						reject(error);
 					} else {
-						resolve(build!);
+						resolve(result!);
 					}
 				};
 
 				worker.on('message', listener);
-				worker.postMessage([handle, input, target]);
+				worker.postMessage([handle, method, args]);
 			}));
 	} else {
 		const workerText =
@@ -88,14 +90,14 @@ async function ensureService(): Promise<
 			for (const listener of listeners) listener(ev);
 		};
 
-		return (_service = (input, target) =>
-			new Promise((resolve, reject) => {
+		return (_service = <T>(method: 'build' | 'parse', args: unknown[]) =>
+			new Promise<T>((resolve, reject) => {
 				const handle = nextHandle();
 				const listener = (event: MessageEvent) => {
 					// eslint-disable-next-line @typescript-eslint/no-unsafe-assignment
-					const [_handle, build, error] = event.data as [
+					const [_handle, result, error] = event.data as [
 						number,
-						InternalBuildObject | undefined,
+						T | undefined,
 						InternalErrorObject | undefined
 					];
 
@@ -104,13 +106,13 @@ async function ensureService(): Promise<
 					if (error !== undefined) {
 						reject(_createError(error));
 					} else {
-						resolve(build!);
+						resolve(result!);
 					}
 					listeners.delete(listener);
 				};
 
 				listeners.add(listener);
-				worker.postMessage([handle, input, target]);
+				worker.postMessage([handle, method, args]);
 			}));
 	}
 }
@@ -122,19 +124,43 @@ export async function initialize(): Promise<void> {
 
 export async function build(
 	input: string,
-	target = Target.Client
+	target = Target.Client,
+	encoding = Encoding.Char,
+	typescript = false
 ): Promise<Build> {
-	_validate(input, target);
+	_validate(input, target, encoding, typescript);
+
+	const service = await ensureService();
+	return service<InternalBuildObject>('build', [
+		input,
+		target,
+		encoding,
+		typescript,
+	]);
+}
+
+/** Parses the input into the syntax tree, without compiling it. */
+export async function parse(input: string, typescript = false): Promise<Parse> {
+	_validate(input, Target.Client, Encoding.Char, typescript);
 
 	const service = await ensureService();
-	return service(input, target);
+	return JSON.parse(
+		await service<string>('parse', [input, typescript])
+	) as Parse;
 }
 
 export {
 	type Build,
 	CompilerError,
+	type Document,
+	Encoding,
 	type Error,
+	type Location,
 	type Mapping,
+	type Node,
+	type Parse,
 	ParserError,
+	type Position,
+	SCHEMA_VERSION,
 	Target,
 } from './common';
@@ -3,12 +3,12 @@
 /* eslint-disable @typescript-eslint/no-unsafe-call */
 /* eslint-disable @typescript-eslint/no-unsafe-member-access */
 
-import { initSync, build as _build } from 'debrix.wasm.lib';
+import { initSync, build as _build, parse as _parse } from 'debrix.wasm.lib';
 import bytes from 'debrix.wasm';
 
 initSync(bytes);
 
-module.exports.listener = function ([handle, input, target]: any) {
-	const result = _build(input, target);
+module.exports.listener = function ([handle, method, args]: any) {
+	const result = method === 'parse' ? _parse(...args) : _build(...args);
 	module.exports.postMessage([handle, result.result, result.error]);
 };
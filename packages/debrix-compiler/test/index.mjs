@@ -26,3 +26,18 @@ await test('WASM dist compiles document', async () => {
 await test('WASM and Node dist have the same result', () => {
 	assert.deepEqual(nodeResult, wasmResult);
 });
+
+await test('Errors have line and column locations', async () => {
+	await assert.rejects(node.build('<div>\n\t<p>{a +}</p>\n</div>'), (error) => {
+		assert.deepEqual(error.location.start, { line: 2, column: 9 });
+		return true;
+	});
+});
+
+await test('Node and WASM dist parse the same syntax tree', async () => {
+	const nodeTree = await node.parse(document);
+	const wasmTree = await wasm.parse(document);
+	assert.equal(nodeTree.version, node.SCHEMA_VERSION);
+	assert.equal(nodeTree.document.children[1].type, 'Element');
+	assert.deepEqual(nodeTree, wasmTree);
+});
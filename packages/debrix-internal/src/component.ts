@@ -115,6 +115,12 @@ class Self extends Model {
 export interface ComponentOptions<T extends ViewModel>
 	extends _ComponentOptions<T> {
 	__family?: T | false;
+
+	/**
+	 * Claims the server rendered element at the index within the parent,
+	 * instead of rendering a new element.
+	 */
+	hydrate?: readonly [parent: ParentNode, index: number];
 }
 
 export class Component<E extends Element, T extends ViewModel>
@@ -134,6 +140,11 @@ export class Component<E extends Element, T extends ViewModel>
 			new (): Component<E, T>;
 			prototype: Component<E, T>;
 			readonly render: (self: Self) => E;
+			readonly hydrate?: (
+				self: Self,
+				parent: ParentNode,
+				index: number
+			) => [E[], number];
 			readonly model?: {
 				new (): T;
 				prototype: T;
@@ -160,7 +171,17 @@ export class Component<E extends Element, T extends ViewModel>
 
 		if (constructor.__family) self[constructor.__family] = this._data;
 
-		this._element = constructor.render.call(this._data, self);
+		if (options?.hydrate && constructor.hydrate) {
+			const [parent, index] = options.hydrate;
+			[[this._element]] = constructor.hydrate.call(
+				this._data,
+				self,
+				parent,
+				index
+			);
+		} else {
+			this._element = constructor.render.call(this._data, self);
+		}
 	}
 
 	insert(target: ParentNode, previous?: ChildNode | null): void {
@@ -1,7 +1,580 @@
-export function claim(parent: Element, index: number): Node {
+import type { Computed, Subscription } from 'debrix';
+import { destroy, detach, insert } from './document';
+import {
+	createAwait,
+	createFallback,
+	Keyed,
+	observe,
+	observeBranches,
+	reconcile,
+	scoped,
+	selectBranch,
+	unique,
+	within,
+} from './reactivity';
+import {
+	createFragment,
+	Fragment,
+	isFragment,
+	isPromiseLike,
+	NodeLike,
+} from './utils';
+
+type Nodes = readonly (NodeLike<ChildNode> | readonly NodeLike<ChildNode>[])[];
+
+/**
+ * Claims the already rendered nodes of a fragment, starting at `index` in
+ * `parent`, and returns them along with the index of the next unclaimed node.
+ */
+export type Hydrate<A extends unknown[] = []> = (
+	parent: ParentNode,
+	index: number,
+	...args: A
+) => [Nodes, number];
+
+export function claim(parent: ParentNode, index: number): Node {
 	return parent.childNodes.item(index);
 }
 
+/**
+ * Claims a text node. As empty text is not rendered by the server, a new text
+ * node is inserted if there is none at `index`.
+ */
+export function claim_text(parent: ParentNode, index: number): Text {
+	const node = parent.childNodes.item(index);
+	if (node?.nodeType === Node.TEXT_NODE) return node as Text;
+
+	const text = document.createTextNode('');
+	parent.insertBefore(text, node);
+	return text;
+}
+
 export function split_text(node: Text, offset: number): Text {
 	return node.splitText(offset);
 }
+
+/**
+ * Returns the length of the text rendered for a text binding.
+ */
+export function text_length(value: unknown): number {
+	return value === undefined || value === null ? 0 : String(value).length;
+}
+
+type Slot = ((...args: never[]) => Nodes) & {
+	hydrate?: (...args: never[]) => [Nodes, number];
+};
+
+/**
+ * Attaches the hydrate function of a slot to its render function.
+ */
+export function hydratable<S extends (...args: never[]) => Nodes>(
+	render: S,
+	hydrate: (...args: never[]) => [Nodes, number]
+): S & Slot {
+	return Object.assign(render, { hydrate });
+}
+
+function block(parent: ParentNode, index: number) {
+	return claim(parent, index) as ChildNode;
+}
+
+/**
+ * Returns the comment ending the block which starts at `start`, past the
+ * blocks nested in it.
+ */
+function blockEnd(start: ChildNode): ChildNode {
+	let node = start;
+	let depth = 0;
+
+	for (;;) {
+		if (node.nodeType === Node.COMMENT_NODE) {
+			const data = (node as Comment).data;
+			if (data === '[') depth++;
+			else if (data === ']' && --depth === 0) return node;
+		}
+
+		node = node.nextSibling!;
+	}
+}
+
+export function claim_slot<S>(
+	parent: ParentNode,
+	index: number,
+	self: S,
+	slot?: Slot
+): [Nodes, number] {
+	const start = block(parent, index);
+	let nodes: Nodes = [];
+	let next = index + 1;
+
+	if (slot?.hydrate) {
+		[nodes, next] = (slot.hydrate as (...args: unknown[]) => [Nodes, number])(
+			self,
+			parent,
+			next
+		);
+	} else if (slot) {
+		// The slot was not rendered by the server, render it on the client instead.
+		nodes = (slot as (...args: unknown[]) => Nodes)(self);
+		insert(parent, parent.childNodes.item(next), ...nodes);
+	}
+
+	const end = block(parent, next);
+	return [[start, ...nodes, end], next + 1];
+}
+
+type Branch = readonly [
+	accessor: Computed<boolean> | null,
+	render: () => Nodes,
+	hydrate: Hydrate
+];
+
+/**
+ * Claims the selected branch of a chain, like `bind_when_chain`. Only the
+ * accessors up to the selected branch are observed.
+ */
+export function claim_when(
+	parent: ParentNode,
+	index: number,
+	branches: readonly Branch[]
+): [Fragment, number] {
+	const start = block(parent, index);
+	let current = selectBranch(branches);
+	let nodes: Nodes = [];
+	let next = index + 1;
+
+	if (current !== -1) [nodes, next] = branches[current]![2](parent, next);
+
+	const end = block(parent, next);
+	let subscriptions: Subscription[] = [];
+
+	const revoke = () => {
+		for (const subscription of subscriptions) subscription.revoke();
+		subscriptions = [];
+	};
+
+	const subscribe = () => {
+		revoke();
+		subscriptions = observeBranches(branches, current, rerender);
+	};
+
+	const rerender = () => {
+		const selected = selectBranch(branches);
+		if (selected === current) return;
+
+		destroy(...(nodes.flat() as NodeLike<ChildNode>[]));
+		current = selected;
+		nodes = current === -1 ? [] : branches[current]![1]();
+		insert(end.parentNode!, end, ...nodes);
+		subscribe();
+	};
+
+	subscribe();
+
+	return [
+		createFragment({
+			insert(target, previous) {
+				insert(target, previous, start, ...nodes, end);
+				rerender();
+				subscribe();
+			},
+
+			detach(target) {
+				revoke();
+				detach(target, start, ...(nodes.flat() as NodeLike<ChildNode>[]), end);
+			},
+
+			destroy() {
+				revoke();
+				destroy(start, ...(nodes.flat() as NodeLike<ChildNode>[]), end);
+			},
+		}),
+		next + 1,
+	];
+}
+
+/**
+ * Claims the case of a match selected by the accessor, which selects the index
+ * of a case, or -1 when none matches.
+ */
+export function claim_match(
+	parent: ParentNode,
+	index: number,
+	accessor: Computed<number>,
+	cases: readonly (readonly [render: () => Nodes, hydrate: Hydrate])[]
+): [Fragment, number] {
+	const start = block(parent, index);
+	let current = accessor.get();
+	let nodes: Nodes = [];
+	let next = index + 1;
+
+	if (current !== -1) [nodes, next] = cases[current]![1](parent, next);
+
+	const end = block(parent, next);
+
+	const subscription = observe(accessor, () => {
+		const selected = accessor.get();
+		if (selected === current) return;
+
+		destroy(...(nodes.flat() as NodeLike<ChildNode>[]));
+		current = selected;
+		nodes = current === -1 ? [] : cases[current]![0]();
+		insert(end.parentNode!, end, ...nodes);
+	});
+
+	return [
+		createFragment({
+			insert(target, previous) {
+				insert(target, previous, start, ...nodes, end);
+			},
+
+			detach(target) {
+				detach(target, start, ...(nodes.flat() as NodeLike<ChildNode>[]), end);
+			},
+
+			destroy() {
+				subscription.revoke();
+				destroy(start, ...(nodes.flat() as NodeLike<ChildNode>[]), end);
+			},
+		}),
+		next + 1,
+	];
+}
+
+/**
+ * Claims the nodes of a `<#let>` block, which are rendered again when the value
+ * changes. Like `bind_let`, the nodes are kept while detached, and rendered
+ * again when inserted only if the value changed meanwhile.
+ */
+export function claim_let<T = unknown>(
+	parent: ParentNode,
+	index: number,
+	accessor: Computed<T>,
+	render: (value: T) => Nodes,
+	hydrate: Hydrate<[value: T]>
+): [Fragment, number] {
+	const start = block(parent, index);
+	let value = accessor.get();
+	const [claimed, next] = hydrate(parent, index + 1, value);
+	let nodes = claimed;
+	const end = block(parent, next);
+
+	const rerender = () => {
+		if (accessor.get() === value) return;
+
+		destroy(...(nodes.flat() as NodeLike<ChildNode>[]));
+		nodes = render((value = accessor.get()));
+		insert(end.parentNode!, end, ...nodes);
+	};
+
+	let subscription = observe(accessor, rerender);
+
+	return [
+		createFragment({
+			insert(target, previous) {
+				insert(target, previous, start, ...nodes, end);
+				rerender();
+
+				subscription.revoke();
+				subscription = observe(accessor, rerender);
+			},
+
+			detach(target) {
+				subscription.revoke();
+				detach(target, start, ...(nodes.flat() as NodeLike<ChildNode>[]), end);
+			},
+
+			destroy() {
+				subscription.revoke();
+				destroy(start, ...(nodes.flat() as NodeLike<ChildNode>[]), end);
+			},
+		}),
+		next + 1,
+	];
+}
+
+/**
+ * Claims the items of a list. With a `key`, items are reconciled by key when
+ * the list changes, instead of rendered again. The `fallback` is claimed when
+ * the list is empty, and rendered whenever it becomes empty afterwards.
+ */
+export function claim_each<T = unknown>(
+	parent: ParentNode,
+	index: number,
+	accessor: Computed<ArrayLike<T>>,
+	render: (item: T, index: number) => Nodes,
+	hydrate: Hydrate<[item: T, index: number]>,
+	key?: (item: T, index: number) => unknown,
+	fallback?: readonly [render: () => Nodes, hydrate: Hydrate]
+): [Fragment, number] {
+	const start = block(parent, index);
+	const keys = new Set<unknown>();
+	let entries: Keyed[] = [];
+	let next = index + 1;
+
+	const array = accessor.get();
+	let claimed: NodeLike<ChildNode>[] | undefined;
+
+	if (fallback && array.length === 0) {
+		const [nodes, after] = fallback[1](parent, next);
+		claimed = nodes.flat() as NodeLike<ChildNode>[];
+		next = after;
+	}
+
+	const empty = createFallback(fallback?.[0], claimed);
+
+	for (let i = 0; i < array.length; i++) {
+		const [claimed, after] = hydrate(parent, next, array[i]!, i);
+		const nodes = claimed.flat() as NodeLike<ChildNode>[];
+
+		// Claimed nodes cannot be padded, so entries which are empty, or start or
+		// end with a fragment, are never reused.
+		const reusable =
+			nodes.length > 0 &&
+			!isFragment(nodes[0]) &&
+			!isFragment(nodes[nodes.length - 1]);
+
+		const itemKey = key?.(array[i]!, i);
+		if (key) unique(keys, itemKey);
+
+		entries.push({ key: key && reusable ? itemKey : Symbol(), nodes });
+		next = after;
+	}
+
+	const end = block(parent, next);
+	const all = () => entries.flatMap((entry) => entry.nodes);
+
+	const subscription = observe(accessor, () => {
+		const array = accessor.get();
+		empty.update(end.parentNode!, end, array.length === 0);
+
+		if (key) {
+			entries = reconcile(end.parentNode!, end, entries, array, render, key);
+			return;
+		}
+
+		destroy(...all());
+		entries = [];
+
+		for (let i = 0; i < array.length; i++)
+			entries.push({
+				key: i,
+				nodes: render(array[i]!, i).flat() as NodeLike<ChildNode>[],
+			});
+
+		insert(end.parentNode!, end, ...all());
+	});
+
+	return [
+		createFragment({
+			insert(target, previous) {
+				insert(target, previous, start, ...all(), end);
+				empty.update(target, end, accessor.get().length === 0);
+			},
+
+			detach(target) {
+				detach(target, start, ...all(), end);
+				empty.detach(target);
+			},
+
+			destroy() {
+				subscription.revoke();
+				destroy(start, ...all(), end);
+				empty.destroy();
+			},
+		}),
+		next + 1,
+	];
+}
+
+/**
+ * Claims an `<#await>` block. The server renders the pending branch for
+ * promises, and the then branch for other values.
+ */
+export function claim_await<T = unknown>(
+	parent: ParentNode,
+	index: number,
+	accessor: Computed<T | PromiseLike<T>>,
+	pending: readonly [render: () => Nodes, hydrate: Hydrate],
+	then?: readonly [
+		render: (value: T) => Nodes,
+		hydrate: Hydrate<[value: T]>
+	],
+	caught?: (error: unknown) => Nodes
+): [Fragment, number] {
+	const start = block(parent, index);
+	const value = accessor.get();
+	let nodes: Nodes = [];
+	let next = index + 1;
+
+	if (isPromiseLike(value)) [nodes, next] = pending[1](parent, next);
+	else if (then) [nodes, next] = then[1](parent, next, value as T);
+
+	const end = block(parent, next);
+
+	// Like in `bind_await`, promises settling while detached are rendered, and
+	// their nodes inserted along with the block.
+	const show = (next: Nodes) => {
+		destroy(...(nodes.flat() as NodeLike<ChildNode>[]));
+		nodes = next;
+		if (end.parentNode) insert(end.parentNode, end, ...nodes);
+	};
+
+	const awaiting = createAwait(show, pending[0], then?.[0], caught);
+	awaiting.update(value, true);
+
+	const update = () => awaiting.update(accessor.get());
+	let subscription = observe(accessor, update);
+
+	return [
+		createFragment({
+			insert(target, previous) {
+				insert(target, previous, start, ...nodes, end);
+				update();
+
+				subscription.revoke();
+				subscription = observe(accessor, update);
+			},
+
+			detach(target) {
+				subscription.revoke();
+				detach(target, start, ...(nodes.flat() as NodeLike<ChildNode>[]), end);
+			},
+
+			destroy() {
+				subscription.revoke();
+				awaiting.cancel();
+				destroy(start, ...(nodes.flat() as NodeLike<ChildNode>[]), end);
+			},
+		}),
+		next + 1,
+	];
+}
+
+/**
+ * Claims the nodes of a `<#try>` block, hydrated within its boundary. When the
+ * server caught an error, the nodes are rendered again instead. When the nodes
+ * throw, the nodes claimed or rendered so far are replaced by the nodes of the
+ * catch branch, which is rendered within the enclosing boundary, like with
+ * `bind_try`.
+ */
+export function claim_try(
+	parent: ParentNode,
+	index: number,
+	render: readonly [render: () => Nodes, hydrate: Hydrate],
+	caught: (error: unknown) => Nodes
+): [Fragment, number] {
+	const start = block(parent, index);
+	const end = blockEnd(start);
+	let nodes: Nodes = [];
+	let failed = false;
+
+	const clear = () => {
+		destroy(...(nodes.flat() as NodeLike<ChildNode>[]));
+		nodes = [];
+		while (start.nextSibling !== end) start.nextSibling!.remove();
+	};
+
+	const show = scoped((error: unknown) => {
+		clear();
+		nodes = caught(error);
+		insert(end.parentNode!, end, ...nodes);
+	});
+
+	const fail = (error: unknown) => {
+		if (failed) return;
+		failed = true;
+		show(error);
+	};
+
+	const marker = start.nextSibling!;
+
+	if (
+		marker.nodeType === Node.COMMENT_NODE &&
+		(marker as Comment).data === '!'
+	) {
+		// The server caught an error, which is likely thrown again on the client.
+		clear();
+		within(fail, () => {
+			const rendered = render[0]();
+			if (!failed) insert(end.parentNode!, end, ...(nodes = rendered));
+		});
+	} else {
+		within(fail, () => ([nodes] = render[1](parent, index + 1)));
+	}
+
+	return [
+		createFragment({
+			insert(target, previous) {
+				insert(target, previous, start, ...nodes, end);
+			},
+
+			detach(target) {
+				detach(target, start, ...(nodes.flat() as NodeLike<ChildNode>[]), end);
+			},
+
+			destroy() {
+				destroy(start, ...(nodes.flat() as NodeLike<ChildNode>[]), end);
+			},
+		}),
+		Array.prototype.indexOf.call(parent.childNodes, end) + 1,
+	];
+}
+
+/**
+ * Claims the instance of a `<component is={...}>` element, which the server
+ * rendered within a block. The instance is created again when the constructor
+ * changes. Like `bind_component`, changes are not observed while detached.
+ */
+export function claim_component<C>(
+	parent: ParentNode,
+	index: number,
+	accessor: Computed<C | null | undefined>,
+	create: (
+		constructor: C,
+		hydrate?: readonly [parent: ParentNode, index: number]
+	) => Fragment
+): [Fragment, number] {
+	const start = block(parent, index);
+	const end = blockEnd(start);
+	let constructor = accessor.get();
+	let instance: Fragment | undefined;
+
+	// The instance claims its nodes, however many, from within the block.
+	if (constructor) instance = create(constructor, [parent, index + 1]);
+
+	const rerender = () => {
+		if (accessor.get() === constructor) return;
+
+		instance?.destroy();
+		constructor = accessor.get();
+		instance = constructor ? create(constructor) : undefined;
+		if (instance) insert(end.parentNode!, end, instance);
+	};
+
+	let subscription = observe(accessor, rerender);
+
+	return [
+		createFragment({
+			insert(target, previous) {
+				insert(target, previous, start, ...(instance ? [instance] : []), end);
+				rerender();
+
+				subscription.revoke();
+				subscription = observe(accessor, rerender);
+			},
+
+			detach(target) {
+				subscription.revoke();
+				detach(target, start, ...(instance ? [instance] : []), end);
+			},
+
+			destroy() {
+				subscription.revoke();
+				instance?.destroy();
+				destroy(start, end);
+			},
+		}),
+		Array.prototype.indexOf.call(parent.childNodes, end) + 1,
+	];
+}
@@ -2,4 +2,5 @@ export * from './component';
 export * from './document';
 export * from './hydration';
 export * from './reactivity';
+export * from './server';
 export * from './utils';
@@ -1,17 +1,79 @@
-import type { Binder, Computed, Subscription } from 'debrix';
-import { insert, detach, destroy } from './document';
-import { createFragment, Fragment, NodeLike } from './utils';
+import type {
+	Binder,
+	Computed,
+	Subscription,
+	SubscriptionListener,
+} from 'debrix';
+import { insert, detach, destroy, text } from './document';
+import {
+	createFragment,
+	flat,
+	Fragment,
+	isFragment,
+	isPromiseLike,
+	NodeLike,
+} from './utils';
 
 interface Lifecycle {
 	destroy(): void;
 }
 
+type Boundary = (error: unknown) => void;
+
+let boundary: Boundary | undefined;
+
+/**
+ * Runs the callback within the boundary of a `<#try>` block, which is handed
+ * what the callback throws. Callbacks scoped meanwhile run within it as well.
+ * The boundary is handed the error outside of itself, within the enclosing one.
+ */
+export function within<T>(current: Boundary, callback: () => T): T | undefined {
+	const previous = boundary;
+	boundary = current;
+
+	try {
+		return callback();
+	} catch (error) {
+		boundary = previous;
+		current(error);
+		return undefined;
+	} finally {
+		boundary = previous;
+	}
+}
+
+/**
+ * Returns the callback bound to the current boundary, if any, so that it runs
+ * within the boundary when called later on, like when a binding is updated.
+ */
+export function scoped<A extends unknown[]>(
+	callback: (...args: A) => void
+): (...args: A) => void {
+	const current = boundary;
+	if (!current) return callback;
+	return (...args) => within(current, () => callback(...args));
+}
+
+/**
+ * Observes the accessor, with the listener scoped to the current boundary.
+ */
+export function observe<T>(
+	accessor: Computed<T>,
+	listener: SubscriptionListener<T>
+): Subscription {
+	return accessor.observe(scoped(listener));
+}
+
 export function bind<T, N extends ChildNode>(
 	node: N,
 	binder: Binder<T, N>,
 	accessor: Computed<T>
 ): Lifecycle {
-	const binding = binder(node, accessor);
+	const binding = binder(node, {
+		get: () => accessor.get(),
+		dispose: () => accessor.dispose(),
+		observe: (listener) => observe(accessor, listener),
+	});
 	return {
 		destroy() {
 			binding.destroy?.();
@@ -20,7 +82,8 @@ export function bind<T, N extends ChildNode>(
 }
 
 export function bind_text(node: Text, accessor: Computed<string>): Lifecycle {
-	const subscription = accessor.observe(
+	const subscription = observe(
+		accessor,
 		() => (node.textContent = accessor.get())
 	);
 	node.textContent = accessor.get();
@@ -42,7 +105,7 @@ export function bind_attr<N extends Element>(
 			? node.removeAttribute(attr)
 			: node.setAttribute(attr, value);
 	};
-	const subscription = accessor.observe(render);
+	const subscription = observe(accessor, render);
 	render();
 	return {
 		destroy() {
@@ -74,7 +137,7 @@ export function bind_attr_spread<N extends Element>(
 		}
 	};
 
-	const subscription = accessor.observe(render);
+	const subscription = observe(accessor, render);
 	render();
 
 	return {
@@ -84,59 +147,171 @@ export function bind_attr_spread<N extends Element>(
 	};
 }
 
+/**
+ * Binds a `<#when>` block, whose nodes are rendered when the accessor holds,
+ * and destroyed when it no longer does.
+ */
 export function bind_when(
-	nodes: NodeLike<ChildNode>[],
+	render: () => Nodes,
 	accessor: Computed<boolean>
+): Fragment {
+	return bind_when_chain([[accessor, render]]);
+}
+
+type Branch = readonly [
+	accessor: Computed<boolean> | null,
+	render: () => Nodes
+];
+
+/**
+ * Returns the index of the first branch whose accessor holds, or that has no
+ * accessor, or -1 when there is none. Accessors after it are not evaluated.
+ */
+export function selectBranch(
+	branches: readonly (readonly [Computed<boolean> | null, ...unknown[]])[]
+): number {
+	return branches.findIndex(([accessor]) => !accessor || accessor.get());
+}
+
+/**
+ * Observes the accessors of the branches up to the selected one, which are the
+ * only ones evaluated by `selectBranch`, or all of them when none is selected.
+ */
+export function observeBranches(
+	branches: readonly (readonly [Computed<boolean> | null, ...unknown[]])[],
+	selected: number,
+	listener: () => void
+): Subscription[] {
+	const observed = selected === -1 ? branches : branches.slice(0, selected + 1);
+	const subscriptions: Subscription[] = [];
+
+	for (const [accessor] of observed)
+		if (accessor) subscriptions.push(observe(accessor, listener));
+
+	return subscriptions;
+}
+
+/**
+ * Binds a chain of branches, where the first branch whose accessor is true, or
+ * that has no accessor, is rendered. Only the selected branch is rendered, and
+ * its nodes are destroyed when another one is selected. Like `bind_let`, the
+ * nodes are kept while detached, and rendered again when inserted only if
+ * another branch was selected meanwhile.
+ */
+export function bind_when_chain(branches: readonly Branch[]): Fragment {
+	let subscriptions: Subscription[] = [];
+	let nodes: NodeLike<ChildNode>[] = [];
+	let current = -1;
+
+	const revoke = () => {
+		for (const subscription of subscriptions) subscription.revoke();
+		subscriptions = [];
+	};
+
+	return createFragment({
+		insert(target, previous) {
+			const rerender = (selected: number) => {
+				destroy(...nodes);
+				current = selected;
+				nodes = current === -1 ? [] : flat(branches[current]![1]());
+				insert(target, previous, ...nodes);
+			};
+
+			const subscribe = () => {
+				revoke();
+				subscriptions = observeBranches(branches, current, () => {
+					const selected = selectBranch(branches);
+					if (selected === current) return;
+
+					rerender(selected);
+					subscribe();
+				});
+			};
+
+			const selected = selectBranch(branches);
+			if (selected === current) insert(target, previous, ...nodes);
+			else rerender(selected);
+
+			subscribe();
+		},
+
+		detach(target) {
+			revoke();
+			detach(target, ...nodes);
+		},
+
+		destroy() {
+			revoke();
+			destroy(...nodes);
+		},
+	});
+}
+
+/**
+ * Binds the cases of a match. The accessor selects the index of the rendered
+ * case, or -1 when none matches, so the matched value is evaluated once. Like
+ * in `bind_when_chain`, only the selected case is rendered, and its nodes are
+ * kept while detached.
+ */
+export function bind_match(
+	accessor: Computed<number>,
+	cases: readonly (() => Nodes)[]
 ): Fragment {
 	let subscription: Subscription | undefined;
-	let attached = false;
+	let nodes: NodeLike<ChildNode>[] = [];
+	let current = -1;
 
 	return createFragment({
 		insert(target, previous) {
-			const rerender = (value: boolean) => {
-				if (value) {
-					if (!attached) {
-						insert(target, previous, ...nodes);
-						attached = true;
-					}
-				} else {
-					if (attached) {
-						detach(target, ...nodes);
-						attached = false;
-					}
-				}
+			const rerender = (selected: number) => {
+				destroy(...nodes);
+				current = selected;
+				nodes = current === -1 ? [] : flat(cases[current]!());
+				insert(target, previous, ...nodes);
 			};
 
-			rerender(accessor.get());
-			subscription = accessor.observe(() => rerender(accessor.get()));
+			const selected = accessor.get();
+			if (selected === current) insert(target, previous, ...nodes);
+			else rerender(selected);
+
+			subscription?.revoke();
+			subscription = observe(accessor, () => {
+				const selected = accessor.get();
+				if (selected !== current) rerender(selected);
+			});
 		},
 
 		detach(target) {
+			subscription?.revoke();
 			detach(target, ...nodes);
-			attached = false;
 		},
 
 		destroy() {
-			subscription!.revoke();
+			subscription?.revoke();
 			destroy(...nodes);
 		},
 	});
 }
 
 export function bind_each<T = unknown>(
-	render: (item: T) => readonly ChildNode[],
-	accessor: Computed<ArrayLike<T>>
+	render: (item: T, index: number) => readonly ChildNode[],
+	accessor: Computed<ArrayLike<T>>,
+	fallback?: () => Nodes
 ): Lifecycle {
 	let subscription: Subscription | undefined;
 	let prevNodes: readonly ChildNode[] = [];
+	const empty = createFallback(fallback);
 
 	return createFragment({
 		insert(target, previous) {
 			const rerender = (array: ArrayLike<T>) => {
+				empty.update(target, previous, array.length === 0);
+
 				const newNodes: ChildNode[] = [];
 
 				const length = array.length;
-				for (let i = 0; i < length; i++) newNodes.push(...render(array[i]!));
+				for (let i = 0; i < length; i++)
+					newNodes.push(...render(array[i]!, i));
 
 				const newLength = newNodes.length;
 				const prevLength = prevNodes.length;
@@ -160,16 +335,440 @@ export function bind_each<T = unknown>(
 			};
 
 			rerender(accessor.get());
-			subscription = accessor.observe(() => rerender(accessor.get()));
+			subscription = observe(accessor, () => rerender(accessor.get()));
 		},
 
 		detach(target) {
 			detach(target, ...prevNodes);
+			empty.detach(target);
 		},
 
 		destroy() {
 			subscription!.revoke();
 			destroy(...prevNodes);
+			empty.destroy();
+		},
+	});
+}
+
+type Nodes = readonly (NodeLike<ChildNode> | readonly NodeLike<ChildNode>[])[];
+
+export interface Fallback {
+	update(target: ParentNode, previous: ChildNode | null, empty: boolean): void;
+	detach(target: ParentNode): void;
+	destroy(): void;
+}
+
+/**
+ * Creates the fallback of a list, the `<#else>` branch of an each block, which
+ * is rendered and attached while the list is empty. Nodes already claimed from
+ * the document are attached from the start.
+ */
+export function createFallback(
+	render?: () => Nodes,
+	claimed?: NodeLike<ChildNode>[]
+): Fallback {
+	let nodes = claimed;
+	let attached = !!claimed;
+
+	return {
+		update(target, previous, empty) {
+			if (!render || empty === attached) return;
+
+			nodes ??= flat(render());
+			if (empty) insert(target, previous, ...nodes);
+			else detach(target, ...nodes);
+
+			attached = empty;
+		},
+
+		detach(target) {
+			if (attached) detach(target, ...nodes!);
+			attached = false;
+		},
+
+		destroy() {
+			if (nodes) destroy(...nodes);
+		},
+	};
+}
+
+export interface Keyed {
+	key: unknown;
+	nodes: NodeLike<ChildNode>[];
+}
+
+/**
+ * Creates the entry of an item in a keyed list. Nodes are padded with empty
+ * text where they start or end with a fragment, so that the entry can be
+ * located in its parent.
+ */
+export function keyed(key: unknown, nodes: Nodes): Keyed {
+	const flattened = flat(nodes);
+
+	if (!flattened.length || isFragment(flattened[0]))
+		flattened.unshift(text(''));
+
+	if (isFragment(flattened[flattened.length - 1])) flattened.push(text(''));
+
+	return { key, nodes: flattened };
+}
+
+/**
+ * Throws when a key is used twice in a keyed list, which would show the nodes
+ * of both items as those of one.
+ */
+export function unique(keys: Set<unknown>, key: unknown): void {
+	if (keys.has(key))
+		throw new Error(`Duplicate key in keyed each block: ${String(key)}`);
+	keys.add(key);
+}
+
+/**
+ * Moves the nodes from `first` to `last` before `anchor`, along with the nodes
+ * of the fragments between them, without inserting the fragments again.
+ */
+function move(
+	target: ParentNode,
+	anchor: ChildNode | null,
+	first: ChildNode,
+	last: ChildNode
+) {
+	let node = first;
+
+	for (;;) {
+		const next = node.nextSibling!;
+		target.insertBefore(node, anchor);
+		if (node === last) return;
+		node = next;
+	}
+}
+
+/**
+ * Updates the entries of a keyed list to match `array`, placing their nodes
+ * before `anchor`. Entries are reused by key, and their nodes are moved rather
+ * than rendered again, so an entry shows the item and index it was rendered
+ * with. Keys must be unique within the list.
+ */
+export function reconcile<T>(
+	target: ParentNode,
+	anchor: ChildNode | null,
+	entries: readonly Keyed[],
+	array: ArrayLike<T>,
+	render: (item: T, index: number) => Nodes,
+	key: (item: T, index: number) => unknown
+): Keyed[] {
+	const previous = new Map<unknown, Keyed>();
+	for (const entry of entries) previous.set(entry.key, entry);
+
+	const keys = new Set<unknown>();
+	const next = new Array<Keyed>(array.length);
+
+	for (let i = 0; i < array.length; i++) {
+		const item = array[i]!;
+		const k = key(item, i);
+		unique(keys, k);
+
+		const entry = previous.get(k);
+
+		if (entry) {
+			previous.delete(k);
+			next[i] = entry;
+		} else {
+			next[i] = keyed(k, render(item, i));
+		}
+	}
+
+	for (const entry of previous.values()) destroy(...entry.nodes);
+
+	for (let i = next.length - 1; i >= 0; i--) {
+		const nodes = next[i]!.nodes;
+		const first = nodes[0] as ChildNode;
+		const last = nodes[nodes.length - 1] as ChildNode;
+
+		if (first.parentNode !== target) insert(target, anchor, ...nodes);
+		else if (last.nextSibling !== anchor) move(target, anchor, first, last);
+
+		anchor = first;
+	}
+
+	return next;
+}
+
+export function bind_each_keyed<T = unknown>(
+	render: (item: T, index: number) => Nodes,
+	accessor: Computed<ArrayLike<T>>,
+	key: (item: T, index: number) => unknown,
+	fallback?: () => Nodes
+): Fragment {
+	let subscription: Subscription | undefined;
+	let entries: Keyed[] = [];
+	const empty = createFallback(fallback);
+
+	return createFragment({
+		insert(target, previous) {
+			const rerender = () => {
+				const array = accessor.get();
+
+				empty.update(target, previous, array.length === 0);
+				entries = reconcile(target, previous, entries, array, render, key);
+			};
+
+			subscription?.revoke();
+			rerender();
+			subscription = observe(accessor, rerender);
+		},
+
+		detach(target) {
+			subscription?.revoke();
+			for (const entry of entries) detach(target, ...entry.nodes);
+			empty.detach(target);
+		},
+
+		destroy() {
+			subscription?.revoke();
+			for (const entry of entries) destroy(...entry.nodes);
+			empty.destroy();
+		},
+	});
+}
+
+/**
+ * Binds the value of a `<#let>` block to the nodes rendered with it. The value
+ * is computed once for all the nodes, which are rendered again when it changes.
+ * The nodes are kept while detached, and rendered again when inserted only if
+ * the value changed meanwhile.
+ */
+export function bind_let<T = unknown>(
+	render: (value: T) => Nodes,
+	accessor: Computed<T>
+): Fragment {
+	let subscription: Subscription | undefined;
+	let nodes: NodeLike<ChildNode>[] | undefined;
+	let value: T;
+
+	return createFragment({
+		insert(target, previous) {
+			const rerender = () => {
+				if (nodes) destroy(...nodes);
+				nodes = flat(render((value = accessor.get())));
+				insert(target, previous, ...nodes);
+			};
+
+			if (nodes && accessor.get() === value) insert(target, previous, ...nodes);
+			else rerender();
+
+			subscription?.revoke();
+			subscription = observe(accessor, () => {
+				if (accessor.get() !== value) rerender();
+			});
+		},
+
+		detach(target) {
+			subscription?.revoke();
+			if (nodes) detach(target, ...nodes);
+		},
+
+		destroy() {
+			subscription?.revoke();
+			if (nodes) destroy(...nodes);
+		},
+	});
+}
+
+export interface Await<T> {
+	update(value: T | PromiseLike<T>, claimed?: boolean): void;
+	cancel(): void;
+}
+
+/**
+ * Creates the state of an `<#await>` block, which shows the branch for the
+ * state of the awaited value. Results of promises which have been replaced, or
+ * cancelled, are ignored. Values which are not promises are shown as resolved.
+ * When `claimed`, the nodes for the value are already shown.
+ */
+export function createAwait<T>(
+	show: (nodes: Nodes) => void,
+	pending: () => Nodes,
+	then?: (value: T) => Nodes,
+	caught?: (error: unknown) => Nodes
+): Await<T> {
+	const none = {};
+	let current: unknown = none;
+
+	const resolve = (value: T) => show(then ? then(value) : []);
+
+	return {
+		update(value, claimed = false) {
+			if (value === current) return;
+			const promise = (current = value);
+
+			if (!isPromiseLike(promise)) {
+				if (!claimed) resolve(promise);
+				return;
+			}
+
+			if (!claimed) show(pending());
+
+			// Without a catch branch, rejections are left unhandled. The branches are
+			// rendered within the boundary the promise was awaited in.
+			promise.then(
+				scoped((value: T) => {
+					if (current === promise) resolve(value);
+				}),
+				caught &&
+					scoped((error: unknown) => {
+						if (current === promise) show(caught(error));
+					})
+			);
+		},
+
+		cancel() {
+			current = none;
+		},
+	};
+}
+
+/**
+ * Binds an `<#await>` block, which shows the pending nodes until the promise
+ * settles, and then the nodes of the then or catch branch. Changes are not
+ * observed while detached, but promises settling meanwhile are rendered, and
+ * their nodes inserted along with the block.
+ */
+export function bind_await<T = unknown>(
+	accessor: Computed<T | PromiseLike<T>>,
+	pending: () => Nodes,
+	then?: (value: T) => Nodes,
+	caught?: (error: unknown) => Nodes
+): Fragment {
+	let subscription: Subscription | undefined;
+	let position: [target: ParentNode, previous: ChildNode | null] | undefined;
+	let nodes: NodeLike<ChildNode>[] = [];
+
+	const show = (next: Nodes) => {
+		destroy(...nodes);
+		nodes = flat(next);
+		if (position) insert(...position, ...nodes);
+	};
+
+	const awaiting = createAwait(show, pending, then, caught);
+
+	return createFragment({
+		insert(target, previous) {
+			position = [target, previous];
+			insert(target, previous, ...nodes);
+			awaiting.update(accessor.get());
+
+			subscription?.revoke();
+			subscription = observe(accessor, () => awaiting.update(accessor.get()));
+		},
+
+		detach(target) {
+			position = undefined;
+			subscription?.revoke();
+			detach(target, ...nodes);
+		},
+
+		destroy() {
+			subscription?.revoke();
+			awaiting.cancel();
+			destroy(...nodes);
+		},
+	});
+}
+
+/**
+ * Binds a `<#try>` block, which renders and inserts the nodes within a
+ * boundary. When the nodes throw while rendered or inserted, or when their
+ * bindings are updated, they are replaced by the nodes of the catch branch.
+ * The catch branch is rendered within the enclosing boundary, so what it throws
+ * is caught by an enclosing block, or thrown when there is none.
+ */
+export function bind_try(
+	render: () => Nodes,
+	caught: (error: unknown) => Nodes
+): Fragment {
+	let nodes: NodeLike<ChildNode>[] = [];
+
+	return createFragment({
+		insert(target, previous) {
+			let failed = false;
+
+			const show = scoped((error: unknown) => {
+				destroy(...nodes);
+				nodes = flat(caught(error));
+				insert(target, previous, ...nodes);
+			});
+
+			const fail = (error: unknown) => {
+				// Only the first error is caught, the nodes are thrown away after it.
+				if (failed) return;
+				failed = true;
+				show(error);
+			};
+
+			destroy(...nodes);
+			nodes = [];
+
+			within(fail, () => {
+				const rendered = flat(render());
+				if (!failed) insert(target, previous, ...(nodes = rendered));
+			});
+		},
+
+		detach(target) {
+			detach(target, ...nodes);
+		},
+
+		destroy() {
+			destroy(...nodes);
+		},
+	});
+}
+
+/**
+ * Binds a `<component is={...}>` element, which shows an instance of the
+ * constructor from the accessor. When the constructor changes, the instance is
+ * destroyed and an instance of the new constructor is inserted in its place.
+ * Nothing is shown without a constructor. The first instance is created when
+ * inserted, within the boundary of the block it is inserted in. Like in
+ * `bind_let`, the instance is kept while detached, and created again when
+ * inserted only if the constructor changed meanwhile.
+ */
+export function bind_component<C>(
+	accessor: Computed<C | null | undefined>,
+	create: (constructor: C) => Fragment
+): Fragment {
+	let subscription: Subscription | undefined;
+	let constructor: C | null | undefined;
+	let instance: Fragment | undefined;
+
+	return createFragment({
+		insert(target, previous) {
+			const rerender = () => {
+				instance?.destroy();
+				constructor = accessor.get();
+				instance = constructor ? create(constructor) : undefined;
+				if (instance) insert(target, previous, instance);
+			};
+
+			if (accessor.get() !== constructor) rerender();
+			else if (instance) insert(target, previous, instance);
+
+			subscription?.revoke();
+			subscription = observe(accessor, () => {
+				if (accessor.get() !== constructor) rerender();
+			});
+		},
+
+		detach(target) {
+			subscription?.revoke();
+			if (instance) detach(target, instance);
+		},
+
+		destroy() {
+			subscription?.revoke();
+			instance?.destroy();
 		},
 	});
 }
@@ -0,0 +1,106 @@
+import type { ViewModel } from 'debrix';
+import type { AttributeMap } from './reactivity';
+import { entries, hasOwn } from './utils';
+
+const ESCAPED: Record<string, string> = {
+	'&': '&amp;',
+	'<': '&lt;',
+	'>': '&gt;',
+	'"': '&quot;',
+	"'": '&#39;',
+};
+
+/**
+ * Escapes a value to be rendered as html text or attribute value. Nullish
+ * values are rendered as empty strings, as they would be by `bind_text`.
+ */
+export function escape(value: unknown): string {
+	if (value === undefined || value === null) return '';
+	return String(value).replace(/[&<>"']/g, (char) => ESCAPED[char]!);
+}
+
+export function render_attr(name: string, value: string | undefined): string {
+	return value === undefined ? '' : ` ${name}="${escape(value)}"`;
+}
+
+export function render_attr_spread(attrs: AttributeMap): string {
+	let html = '';
+
+	for (const name in attrs) {
+		if (hasOwn(attrs, name)) {
+			const value = attrs[name];
+
+			// Matches bind_attr_spread, where '' and undefined removes the attribute.
+			// eslint-disable-next-line @typescript-eslint/strict-boolean-expressions
+			if (value) html += render_attr(name, value);
+		}
+	}
+
+	return html;
+}
+
+type ServerAttributes = Record<string, string | undefined>;
+type ServerSlots = Readonly<Record<string, (self: ServerSelf) => string>>;
+
+interface ServerSelf {
+	/** @internal */
+	[family: symbol]: unknown;
+	readonly attrs: ServerAttributes;
+	readonly slots: ServerSlots;
+}
+
+export interface ServerComponentOptions<T extends ViewModel> {
+	data?: T;
+	slots?: ServerSlots;
+	attrs?: Record<string, string | undefined> & {
+		_?: Record<string, string | undefined>;
+	};
+	__family?: T | false;
+}
+
+export class ServerComponent<T extends ViewModel> {
+	/** @internal */
+	protected _data: T | undefined;
+
+	/** @internal */
+	protected _html: string;
+
+	constructor(options?: ServerComponentOptions<T>) {
+		const constructor = this.constructor as {
+			readonly render: (self: ServerSelf) => string;
+			readonly model?: {
+				new (): T;
+				prototype: T;
+			};
+			readonly __family?: symbol;
+		};
+
+		if (options?.data) {
+			this._data = options.data;
+		} else if (constructor.model) {
+			this._data = new constructor.model();
+
+			// Should check if __family is false or undefined.
+			// eslint-disable-next-line @typescript-eslint/strict-boolean-expressions
+		} else if (options?.__family) {
+			this._data = options.__family;
+		}
+
+		const attrs: ServerAttributes = { ...options?.attrs?._ };
+
+		for (const [key, value] of entries(options?.attrs ?? {})) {
+			if (key === '_') continue;
+			attrs[key] = value as string | undefined;
+		}
+
+		const self: ServerSelf = { attrs, slots: options?.slots ?? {} };
+
+		if (constructor.__family) self[constructor.__family] = this._data;
+
+		this._html = constructor.render.call(this._data, self);
+	}
+
+	toString(): string {
+		return this._html;
+	}
+}
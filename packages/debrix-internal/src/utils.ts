@@ -42,6 +42,14 @@ export function isFragment(value: unknown): value is Fragment {
 	return value !== null && typeof value === 'object' && FRAGMENT in value;
 }
 
+export function isPromiseLike(value: unknown): value is PromiseLike<unknown> {
+	return (
+		value !== null &&
+		(typeof value === 'object' || typeof value === 'function') &&
+		typeof (value as PromiseLike<unknown>).then === 'function'
+	);
+}
+
 export type FlatArray<Arr, Depth extends number> = {
 	done: Arr;
 	recur: Arr extends readonly (infer InnerArr)[]
@@ -0,0 +1,175 @@
+// A minimal document, implementing the parts of the DOM used by the runtime.
+
+class Node {
+	static ELEMENT_NODE = 1;
+	static TEXT_NODE = 3;
+	static COMMENT_NODE = 8;
+
+	parentNode = null;
+
+	get nextSibling() {
+		const siblings = this.parentNode?._children ?? [];
+		return siblings[siblings.indexOf(this) + 1] ?? null;
+	}
+
+	before(...nodes) {
+		for (const node of nodes) this.parentNode?.insertBefore(node, this);
+	}
+
+	remove() {
+		this.parentNode?.removeChild(this);
+	}
+}
+
+class CharacterData extends Node {
+	constructor(data) {
+		super();
+		this.data = data;
+	}
+
+	get textContent() {
+		return this.data;
+	}
+
+	set textContent(data) {
+		this.data = String(data ?? '');
+	}
+}
+
+class Text extends CharacterData {
+	nodeType = Node.TEXT_NODE;
+
+	splitText(offset) {
+		const text = new Text(this.data.slice(offset));
+		this.data = this.data.slice(0, offset);
+		this.parentNode?.insertBefore(text, this.nextSibling);
+		return text;
+	}
+}
+
+class Comment extends CharacterData {
+	nodeType = Node.COMMENT_NODE;
+}
+
+class Element extends Node {
+	nodeType = Node.ELEMENT_NODE;
+	attributes = new Map();
+	_children = [];
+
+	constructor(tagName) {
+		super();
+		this.tagName = tagName;
+	}
+
+	get childNodes() {
+		const children = [...this._children];
+		children.item = (index) => children[index] ?? null;
+		return children;
+	}
+
+	get textContent() {
+		return this._children.map((child) => child.textContent).join('');
+	}
+
+	setAttribute(name, value) {
+		this.attributes.set(name, String(value));
+	}
+
+	removeAttribute(name) {
+		this.attributes.delete(name);
+	}
+
+	append(...nodes) {
+		for (const node of nodes) this.insertBefore(node, null);
+	}
+
+	insertBefore(node, child) {
+		if (child && child.parentNode !== this)
+			throw new Error('The reference node is not a child of this node.');
+
+		node.remove();
+		const index = child ? this._children.indexOf(child) : this._children.length;
+		this._children.splice(index, 0, node);
+		node.parentNode = this;
+		return node;
+	}
+
+	removeChild(node) {
+		if (node.parentNode !== this)
+			throw new Error('The node to be removed is not a child of this node.');
+
+		this._children.splice(this._children.indexOf(node), 1);
+		node.parentNode = null;
+		return node;
+	}
+
+	replaceChild(node, child) {
+		this.insertBefore(node, child);
+		return this.removeChild(child);
+	}
+}
+
+globalThis.Node = Node;
+globalThis.document = {
+	createElement: (tagName) => new Element(tagName),
+	createTextNode: (data) => new Text(data),
+	createComment: (data) => new Comment(data),
+};
+
+/**
+ * Serializes the children of the node, with comments as `<!--data-->`.
+ */
+export function html(node) {
+	return node._children
+		.map((child) => {
+			if (child.nodeType === Node.TEXT_NODE) return child.data;
+			if (child.nodeType === Node.COMMENT_NODE) return `<!--${child.data}-->`;
+			return `<${child.tagName}>${html(child)}</${child.tagName}>`;
+		})
+		.join('');
+}
+
+/**
+ * Creates an element with the children, as the server would have rendered
+ * them. Children starting with `<!--` are comments, others are text.
+ */
+export function rendered(...children) {
+	const parent = document.createElement('div');
+
+	for (const child of children) {
+		if (child.startsWith('<!--'))
+			parent.append(document.createComment(child.slice(4, -3)));
+		else parent.append(document.createTextNode(child));
+	}
+
+	return parent;
+}
+
+/**
+ * Creates a computed value, which notifies its observers when set. The number
+ * of observers is exposed as `observers`.
+ */
+export function signal(value) {
+	const listeners = new Set();
+
+	return {
+		get: () => value,
+
+		set(next) {
+			const previous = value;
+			value = next;
+			for (const listener of [...listeners]) listener(next, previous);
+		},
+
+		observe(listener) {
+			listeners.add(listener);
+			return { revoke: () => listeners.delete(listener) };
+		},
+
+		dispose() {},
+
+		get observers() {
+			return listeners.size;
+		},
+	};
+}
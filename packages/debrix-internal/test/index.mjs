@@ -0,0 +1,813 @@
+import test from 'node:test';
+import assert from 'node:assert/strict';
+import { html, rendered, signal } from './dom.mjs';
+import * as internal from '../index.mjs';
+
+/**
+ * Creates a fragment which records how many times it is inserted.
+ */
+function probe(data) {
+	const node = internal.text(data);
+	const fragment = internal.createFragment({
+		insert(target, previous) {
+			fragment.inserts++;
+			internal.insert(target, previous, node);
+		},
+		detach(target) {
+			internal.detach(target, node);
+		},
+		destroy() {
+			internal.destroy(node);
+		},
+	});
+
+	fragment.inserts = 0;
+	return fragment;
+}
+
+await test('Keyed lists move the nodes of reordered items', () => {
+	const parent = document.createElement('ul');
+	const items = signal([{ id: 1 }, { id: 2 }, { id: 3 }]);
+	let renders = 0;
+
+	const list = internal.bind_each_keyed(
+		(item) => (renders++, [internal.text(`${item.id}`)]),
+		items,
+		(item) => item.id
+	);
+
+	internal.insert(parent, null, list);
+	const [one, two, three] = parent.childNodes;
+
+	items.set([{ id: 3 }, { id: 1 }, { id: 2 }]);
+	assert.equal(html(parent), '312');
+	assert.deepEqual([...parent.childNodes], [three, one, two]);
+	assert.equal(renders, 3);
+
+	items.set([{ id: 2 }, { id: 4 }]);
+	assert.equal(html(parent), '24');
+	assert.equal(parent.childNodes[0], two);
+	assert.equal(renders, 4);
+});
+
+await test('Keyed lists move fragments without inserting them again', () => {
+	const parent = document.createElement('ul');
+	const items = signal(['a', 'b']);
+	const probes = new Map();
+
+	const list = internal.bind_each_keyed(
+		(item) => {
+			const fragment = probe(item);
+			probes.set(item, fragment);
+			return [fragment];
+		},
+		items,
+		(item) => item
+	);
+
+	internal.insert(parent, null, list);
+	items.set(['b', 'a']);
+
+	assert.equal(html(parent), 'ba');
+	assert.equal(probes.get('a').inserts, 1);
+	assert.equal(probes.get('b').inserts, 1);
+});
+
+await test('Keyed lists reconcile changes made while detached', () => {
+	const parent = document.createElement('ul');
+	const items = signal(['a', 'b']);
+	let renders = 0;
+
+	const list = internal.bind_each_keyed(
+		(item) => (renders++, [internal.text(item)]),
+		items,
+		(item) => item
+	);
+
+	internal.insert(parent, null, list);
+	internal.detach(parent, list);
+	assert.equal(items.observers, 0);
+
+	items.set(['b', 'c']);
+	assert.equal(html(parent), '');
+
+	internal.insert(parent, null, list);
+	assert.equal(html(parent), 'bc');
+	assert.equal(renders, 3);
+	assert.equal(items.observers, 1);
+});
+
+await test('Keyed lists throw on duplicate keys', () => {
+	const parent = document.createElement('ul');
+	const items = signal([1, 2]);
+
+	const list = internal.bind_each_keyed(
+		(item) => [internal.text(`${item}`)],
+		items,
+		(item) => item
+	);
+
+	internal.insert(parent, null, list);
+	assert.throws(
+		() => items.set([1, 1]),
+		/Duplicate key in keyed each block: 1/
+	);
+});
+
+await test('Claimed keyed lists reuse the claimed nodes', () => {
+	const parent = rendered('<!--[-->', 'a', 'b', '<!--]-->');
+	const items = signal(['a', 'b']);
+
+	const [list, next] = internal.claim_each(
+		parent,
+		0,
+		items,
+		(item) => [internal.text(item)],
+		(parent, index) => [[internal.claim(parent, index)], index + 1],
+		(item) => item
+	);
+
+	const [a, b] = parent.childNodes.slice(1, 3);
+	assert.equal(next, 4);
+
+	items.set(['b', 'c', 'a']);
+	assert.equal(html(parent), '<!--[-->bca<!--]-->');
+	assert.equal(parent.childNodes[1], b);
+	assert.equal(parent.childNodes[3], a);
+
+	list.destroy();
+	assert.equal(html(parent), '');
+});
+
+await test('Claimed keyed lists throw on duplicate keys', () => {
+	const parent = rendered('<!--[-->', 'a', 'a', '<!--]-->');
+
+	assert.throws(
+		() =>
+			internal.claim_each(
+				parent,
+				0,
+				signal(['a', 'a']),
+				(item) => [internal.text(item)],
+				(parent, index) => [[internal.claim(parent, index)], index + 1],
+				(item) => item
+			),
+		/Duplicate key/
+	);
+});
+
+await test('Let blocks keep their nodes when inserted again', () => {
+	const parent = document.createElement('p');
+	const value = signal('a');
+	let renders = 0;
+
+	const block = internal.bind_let(
+		(value) => (renders++, [internal.text(value)]),
+		value
+	);
+
+	internal.insert(parent, null, block);
+	const [node] = parent.childNodes;
+
+	block.detach(parent);
+	internal.insert(parent, null, block);
+	assert.equal(parent.childNodes[0], node);
+	assert.equal(renders, 1);
+
+	value.set('a');
+	assert.equal(renders, 1);
+
+	value.set('b');
+	assert.equal(html(parent), 'b');
+	assert.equal(renders, 2);
+
+	block.detach(parent);
+	value.set('c');
+	assert.equal(renders, 2);
+
+	internal.insert(parent, null, block);
+	assert.equal(html(parent), 'c');
+	assert.equal(renders, 3);
+});
+
+await test('Claimed let blocks render again on change only', () => {
+	const parent = rendered('<!--[-->', 'a', '<!--]-->');
+	const value = signal('a');
+	let renders = 0;
+
+	const [block] = internal.claim_let(
+		parent,
+		0,
+		value,
+		(value) => (renders++, [internal.text(value)]),
+		(parent, index) => [[internal.claim(parent, index)], index + 1]
+	);
+
+	value.set('a');
+	assert.equal(renders, 0);
+
+	value.set('b');
+	assert.equal(html(parent), '<!--[-->b<!--]-->');
+	assert.equal(renders, 1);
+
+	block.detach(parent);
+	value.set('c');
+	assert.equal(renders, 1);
+
+	internal.insert(parent, null, block);
+	assert.equal(html(parent), '<!--[-->c<!--]-->');
+	assert.equal(renders, 2);
+
+	value.set('d');
+	assert.equal(html(parent), '<!--[-->d<!--]-->');
+	assert.equal(renders, 3);
+});
+
+/**
+ * Creates a computed value, which throws when set to an error.
+ */
+function throwing(value) {
+	const source = signal(value);
+
+	return {
+		...source,
+		get() {
+			const value = source.get();
+			if (value instanceof Error) throw value;
+			return value;
+		},
+	};
+}
+
+/**
+ * Creates a fragment which throws when inserted.
+ */
+function broken(error) {
+	return internal.createFragment({
+		insert() {
+			throw error;
+		},
+		detach() {},
+		destroy() {},
+	});
+}
+
+await test('Try blocks catch errors thrown by their children', () => {
+	const parent = document.createElement('p');
+	const value = throwing('a');
+
+	const block = internal.bind_try(
+		() => {
+			const node = internal.text('');
+			internal.bind_text(node, value);
+			return [node];
+		},
+		(error) => [internal.text(error.message)]
+	);
+
+	internal.insert(parent, null, block);
+	assert.equal(html(parent), 'a');
+
+	value.set(new Error('updated'));
+	assert.equal(html(parent), 'updated');
+
+	const inserted = internal.bind_try(
+		() => [internal.text('a'), broken(new Error('inserted'))],
+		(error) => [internal.text(error.message)]
+	);
+
+	internal.insert(parent, null, inserted);
+	assert.equal(html(parent), 'updatedinserted');
+});
+
+await test('Errors of catch branches reach the enclosing try block', () => {
+	const parent = document.createElement('p');
+	const value = throwing('caught');
+
+	const block = internal.bind_try(
+		() => [
+			internal.bind_try(
+				() => [broken(new Error('inner'))],
+				() => {
+					const node = internal.text('');
+					internal.bind_text(node, value);
+					return [node];
+				}
+			),
+		],
+		(error) => [internal.text(error.message)]
+	);
+
+	internal.insert(parent, null, block);
+	assert.equal(html(parent), 'caught');
+
+	value.set(new Error('outer'));
+	assert.equal(html(parent), 'outer');
+
+	const unhandled = internal.bind_try(
+		() => [broken(new Error('inner'))],
+		() => [broken(new Error('unhandled'))]
+	);
+
+	assert.throws(() => internal.insert(parent, null, unhandled), /unhandled/);
+});
+
+await test('Claimed try blocks catch errors thrown while hydrated', () => {
+	const parent = rendered('<!--[-->', 'a', '<!--]-->');
+
+	internal.claim_try(
+		parent,
+		0,
+		[
+			() => [internal.text('a')],
+			() => {
+				throw new Error('hydrated');
+			},
+		],
+		(error) => [internal.text(error.message)]
+	);
+
+	assert.equal(html(parent), '<!--[-->hydrated<!--]-->');
+
+	const outer = rendered('<!--[-->', '<!--[-->', 'a', '<!--]-->', '<!--]-->');
+	const value = throwing('caught');
+
+	internal.claim_try(
+		outer,
+		0,
+		[
+			() => [],
+			(parent, index) => {
+				const [block, next] = internal.claim_try(
+					parent,
+					index,
+					[
+						() => [],
+						() => {
+							throw new Error('inner');
+						},
+					],
+					() => {
+						const node = internal.text('');
+						internal.bind_text(node, value);
+						return [node];
+					}
+				);
+
+				return [[block], next];
+			},
+		],
+		(error) => [internal.text(error.message)]
+	);
+
+	assert.equal(html(outer), '<!--[--><!--[-->caught<!--]--><!--]-->');
+
+	value.set(new Error('outer'));
+	assert.equal(html(outer), '<!--[-->outer<!--]-->');
+});
+
+await test('Dynamic components create their instance when inserted', () => {
+	const parent = document.createElement('div');
+	const constructor = signal('a');
+	const created = [];
+
+	const component = internal.bind_component(constructor, (name) => {
+		created.push(name);
+		return probe(name);
+	});
+
+	assert.deepEqual(created, []);
+
+	internal.insert(parent, null, component);
+	assert.equal(html(parent), 'a');
+
+	constructor.set('b');
+	assert.equal(html(parent), 'b');
+
+	constructor.set(null);
+	assert.equal(html(parent), '');
+	assert.deepEqual(created, ['a', 'b']);
+
+	// Changes are not observed while detached.
+	constructor.set('c');
+	internal.detach(parent, component);
+	assert.equal(constructor.observers, 0);
+
+	internal.insert(parent, null, component);
+	assert.deepEqual(created, ['a', 'b', 'c']);
+
+	internal.detach(parent, component);
+	constructor.set('d');
+	internal.insert(parent, null, component);
+	assert.equal(html(parent), 'd');
+	assert.deepEqual(created, ['a', 'b', 'c', 'd']);
+	internal.destroy(component);
+
+	const block = internal.bind_try(
+		() => [
+			internal.bind_component(signal('c'), () => {
+				throw new Error('created');
+			}),
+		],
+		(error) => [internal.text(error.message)]
+	);
+
+	internal.insert(parent, null, block);
+	assert.equal(html(parent), 'created');
+});
+
+await test('Match blocks render the selected case only', () => {
+	const parent = document.createElement('p');
+	const selected = signal(0);
+	const renders = [];
+
+	const render = (name) => () => {
+		renders.push(name);
+		return [internal.text(name)];
+	};
+
+	const block = internal.bind_match(selected, [render('a'), render('b')]);
+
+	internal.insert(parent, null, block);
+	assert.equal(html(parent), 'a');
+	assert.deepEqual(renders, ['a']);
+
+	selected.set(1);
+	assert.equal(html(parent), 'b');
+
+	selected.set(-1);
+	assert.equal(html(parent), '');
+
+	// Cases are kept while detached, and rendered again on change only.
+	selected.set(0);
+	internal.detach(parent, block);
+	assert.equal(selected.observers, 0);
+
+	internal.insert(parent, null, block);
+	assert.deepEqual(renders, ['a', 'b', 'a']);
+
+	internal.detach(parent, block);
+	selected.set(1);
+	internal.insert(parent, null, block);
+	assert.equal(html(parent), 'b');
+	assert.deepEqual(renders, ['a', 'b', 'a', 'b']);
+});
+
+await test('Claimed match blocks claim the selected case', () => {
+	const parent = rendered('<!--[-->', 'b', '<!--]-->');
+	const selected = signal(1);
+	const claim = (parent, index) => [[internal.claim(parent, index)], index + 1];
+
+	const [, next] = internal.claim_match(parent, 0, selected, [
+		[() => [internal.text('a')], claim],
+		[() => [internal.text('b')], claim],
+	]);
+
+	assert.equal(next, 3);
+	assert.equal(html(parent), '<!--[-->b<!--]-->');
+
+	selected.set(0);
+	assert.equal(html(parent), '<!--[-->a<!--]-->');
+
+	selected.set(-1);
+	assert.equal(html(parent), '<!--[--><!--]-->');
+});
+
+/**
+ * Creates a promise along with the functions settling it.
+ */
+function deferred() {
+	let resolve, reject;
+	const promise = new Promise((...settle) => ([resolve, reject] = settle));
+	return { promise, resolve, reject };
+}
+
+/**
+ * Waits for the callbacks of settled promises to run.
+ */
+const settled = () => new Promise((resolve) => setTimeout(resolve));
+
+await test('Await blocks ignore results of replaced promises', async () => {
+	const parent = document.createElement('p');
+	const first = deferred();
+	const second = deferred();
+	const value = signal(first.promise);
+
+	const block = internal.bind_await(
+		value,
+		() => [internal.text('pending')],
+		(value) => [internal.text(`then ${value}`)],
+		(error) => [internal.text(`catch ${error}`)]
+	);
+
+	internal.insert(parent, null, block);
+	assert.equal(html(parent), 'pending');
+
+	value.set(second.promise);
+	first.resolve('first');
+	await settled();
+	assert.equal(html(parent), 'pending');
+
+	second.reject('second');
+	await settled();
+	assert.equal(html(parent), 'catch second');
+
+	value.set('value');
+	assert.equal(html(parent), 'then value');
+});
+
+await test('Await blocks show promises settled while detached', async () => {
+	const parent = document.createElement('p');
+	const promise = deferred();
+	const value = signal(promise.promise);
+
+	const block = internal.bind_await(
+		value,
+		() => [internal.text('pending')],
+		(value) => [internal.text(`then ${value}`)]
+	);
+
+	internal.insert(parent, null, block);
+	internal.detach(parent, block);
+	assert.equal(value.observers, 0);
+
+	promise.resolve('a');
+	await settled();
+	assert.equal(html(parent), '');
+
+	internal.insert(parent, null, block);
+	assert.equal(html(parent), 'then a');
+
+	internal.detach(parent, block);
+	value.set('b');
+	internal.insert(parent, null, block);
+	assert.equal(html(parent), 'then b');
+});
+
+await test('Await blocks ignore results of cancelled promises', async () => {
+	const shown = [];
+	const promise = deferred();
+
+	const awaiting = internal.createAwait(
+		(nodes) => shown.push(...nodes.map((node) => node.data)),
+		() => [internal.text('pending')],
+		(value) => [internal.text(value)]
+	);
+
+	awaiting.update(promise.promise);
+	awaiting.cancel();
+	promise.resolve('resolved');
+	await settled();
+
+	assert.deepEqual(shown, ['pending']);
+});
+
+await test('Claimed await blocks show the state of new promises', async () => {
+	const parent = rendered('<!--[-->', 'then a', '<!--]-->');
+	const promise = deferred();
+	const value = signal('a');
+
+	internal.claim_await(
+		parent,
+		0,
+		value,
+		[() => [internal.text('pending')], () => [[], 1]],
+		[
+			(value) => [internal.text(`then ${value}`)],
+			(parent, index) => [[internal.claim(parent, index)], index + 1],
+		]
+	);
+
+	assert.equal(html(parent), '<!--[-->then a<!--]-->');
+
+	value.set(promise.promise);
+	assert.equal(html(parent), '<!--[-->pending<!--]-->');
+
+	promise.resolve('b');
+	await settled();
+	assert.equal(html(parent), '<!--[-->then b<!--]-->');
+});
+
+await test('Claimed await blocks stop observing while detached', async () => {
+	const parent = rendered('<!--[-->', 'then a', '<!--]-->');
+	const value = signal('a');
+
+	const [block] = internal.claim_await(
+		parent,
+		0,
+		value,
+		[() => [internal.text('pending')], () => [[], 1]],
+		[
+			(value) => [internal.text(`then ${value}`)],
+			(parent, index) => [[internal.claim(parent, index)], index + 1],
+		]
+	);
+
+	internal.detach(parent, block);
+	assert.equal(value.observers, 0);
+
+	value.set('b');
+	internal.insert(parent, null, block);
+	assert.equal(html(parent), '<!--[-->then b<!--]-->');
+	assert.equal(value.observers, 1);
+});
+
+await test('Slots in branches are claimed and rendered with $self', () => {
+	const parent = rendered(
+		'<!--[-->',
+		'<!--[-->',
+		'slot',
+		'<!--]-->',
+		'<!--]-->'
+	);
+	const visible = signal(true);
+	const calls = [];
+
+	const render = (self) => {
+		calls.push(self);
+		return [internal.text('slot')];
+	};
+
+	const hydrate = (self, parent, index) => {
+		calls.push(self);
+		return [[internal.claim(parent, index)], index + 1];
+	};
+
+	const $self = { slots: { main: internal.hydratable(render, hydrate) } };
+
+	const [, next] = internal.claim_when(parent, 0, [
+		[
+			visible,
+			() => {
+				const slot = internal.element('div');
+				internal.insert(slot, null, $self.slots.main($self));
+				return [slot];
+			},
+			(parent, index) =>
+				internal.claim_slot(parent, index, $self, $self.slots.main),
+		],
+	]);
+
+	const [claimed] = parent.childNodes.slice(2, 3);
+	assert.equal(next, 5);
+	assert.equal(claimed.data, 'slot');
+
+	visible.set(false);
+	assert.equal(html(parent), '<!--[--><!--]-->');
+
+	visible.set(true);
+	assert.equal(html(parent), '<!--[--><div>slot</div><!--]-->');
+	assert.deepEqual(calls, [$self, $self]);
+});
+
+await test('When chains render the first branch which holds only', () => {
+	const parent = document.createElement('p');
+	const user = signal(null);
+	const guest = signal(true);
+
+	const chain = internal.bind_when_chain([
+		[user, () => [internal.text(user.get().name)]],
+		[guest, () => [internal.text('guest')]],
+		[null, () => [internal.text('none')]],
+	]);
+
+	// Branches which are not selected are not rendered.
+	internal.insert(parent, null, chain);
+	assert.equal(html(parent), 'guest');
+
+	// Accessors after the selected branch are not observed.
+	user.set({ name: 'a' });
+	assert.equal(html(parent), 'a');
+	assert.equal(guest.observers, 0);
+
+	user.set(null);
+	guest.set(false);
+	assert.equal(html(parent), 'none');
+	assert.equal(guest.observers, 1);
+});
+
+await test('When blocks keep their nodes while detached', () => {
+	const parent = document.createElement('p');
+	const visible = signal(true);
+	let renders = 0;
+
+	const block = internal.bind_when(
+		() => [internal.text(`a${++renders}`)],
+		visible
+	);
+
+	internal.insert(parent, null, block);
+	internal.detach(parent, block);
+	assert.equal(visible.observers, 0);
+
+	visible.set(false);
+	visible.set(true);
+	internal.insert(parent, null, block);
+	assert.equal(html(parent), 'a1');
+
+	visible.set(false);
+	visible.set(true);
+	assert.equal(html(parent), 'a2');
+
+	internal.detach(parent, block);
+	visible.set(false);
+	internal.insert(parent, null, block);
+	assert.equal(html(parent), '');
+});
+
+await test('Claimed when chains observe accessors up to the selection', () => {
+	const parent = rendered('<!--[-->', 'a', '<!--]-->');
+	const a = signal(true);
+	const b = signal(false);
+	const claim = (parent, index) => [[internal.claim(parent, index)], index + 1];
+
+	internal.claim_when(parent, 0, [
+		[a, () => [internal.text('a')], claim],
+		[b, () => [internal.text('b')], claim],
+	]);
+
+	assert.equal(b.observers, 0);
+
+	a.set(false);
+	assert.equal(html(parent), '<!--[--><!--]-->');
+	assert.equal(b.observers, 1);
+
+	b.set(true);
+	assert.equal(html(parent), '<!--[-->b<!--]-->');
+});
+
+await test('Claimed dynamic components create new instances on change', () => {
+	const parent = rendered('<!--[-->', 'a', '<!--]-->');
+	const constructor = signal('a');
+	const created = [];
+
+	const [component, next] = internal.claim_component(
+		parent,
+		0,
+		constructor,
+		(name, hydrate) => {
+			created.push(name);
+			const node = hydrate ? internal.claim(...hydrate) : internal.text(name);
+
+			return internal.createFragment({
+				insert: (target, previous) => internal.insert(target, previous, node),
+				detach: (target) => internal.detach(target, node),
+				destroy: () => internal.destroy(node),
+			});
+		}
+	);
+
+	assert.equal(next, 3);
+	assert.deepEqual(created, ['a']);
+
+	constructor.set('b');
+	assert.deepEqual(created, ['a', 'b']);
+	assert.equal(html(parent), '<!--[-->b<!--]-->');
+
+	internal.detach(parent, component);
+	assert.equal(constructor.observers, 0);
+
+	constructor.set('c');
+	internal.insert(parent, null, component);
+	assert.deepEqual(created, ['a', 'b', 'c']);
+	assert.equal(html(parent), '<!--[-->c<!--]-->');
+});
+
+await test('Claimed dynamic components claim all of their nodes', () => {
+	const parent = rendered(
+		'<!--[-->',
+		'<!--[-->',
+		'a',
+		'<!--]-->',
+		'a',
+		'<!--]-->',
+		'after'
+	);
+	const constructor = signal('a');
+
+	const [, next] = internal.claim_component(
+		parent,
+		0,
+		constructor,
+		(name, hydrate) => {
+			// The component renders a block followed by a text node.
+			const nodes = hydrate
+				? [0, 1, 2, 3].map((offset) =>
+						internal.claim(hydrate[0], hydrate[1] + offset)
+					)
+				: [internal.text(name)];
+
+			return internal.createFragment({
+				insert: (target, previous) =>
+					internal.insert(target, previous, ...nodes),
+				detach: (target) => internal.detach(target, ...nodes),
+				destroy: () => internal.destroy(...nodes),
+			});
+		}
+	);
+
+	assert.equal(next, 6);
+
+	constructor.set('b');
+	assert.equal(html(parent), '<!--[-->b<!--]-->after');
+});